@@ -0,0 +1,60 @@
+//! 构建脚本：嵌入 git 提交、构建日期与 rustc 版本，供 `cc-switch version` 使用
+
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    watch_git_head();
+
+    let git_sha = command_output("git", &["rev-parse", "--short=12", "HEAD"]).unwrap_or_default();
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = command_output(&rustc, &["--version"]).unwrap_or_default();
+
+    println!("cargo:rustc-env=CC_SWITCH_GIT_SHA={}", git_sha);
+    println!("cargo:rustc-env=CC_SWITCH_BUILD_DATE={}", build_date());
+    println!("cargo:rustc-env=CC_SWITCH_RUSTC={}", rustc_version);
+}
+
+/// 提交变化时重新运行（不在 git 仓库中构建时跳过）
+fn watch_git_head() {
+    let git_dir = Path::new("../.git");
+    let head = git_dir.join("HEAD");
+    if !head.is_file() {
+        return;
+    }
+    println!("cargo:rerun-if-changed={}", head.display());
+    if let Ok(content) = std::fs::read_to_string(&head) {
+        if let Some(reference) = content.trim().strip_prefix("ref: ") {
+            let ref_path = git_dir.join(reference);
+            if ref_path.is_file() {
+                println!("cargo:rerun-if-changed={}", ref_path.display());
+            }
+        }
+    }
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// 构建日期（UTC，YYYY-MM-DD）
+///
+/// 依次使用 SOURCE_DATE_EPOCH（可重现构建）、HEAD 的提交日期，最后回退到当前日期。
+fn build_date() -> String {
+    let from_epoch = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+        .map(|dt| dt.format("%Y-%m-%d").to_string());
+
+    from_epoch
+        .or_else(|| command_output("git", &["log", "-1", "--format=%cs"]))
+        .unwrap_or_else(|| chrono::Utc::now().format("%Y-%m-%d").to_string())
+}
@@ -4,145 +4,363 @@
 
 use anyhow::{bail, Result};
 use colored::Colorize;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
-use ccswitch_core::{AppState, AppType, Provider};
+use ccswitch_core::{AppError, AppState, AppType, Provider};
+use indexmap::IndexMap;
 
-use crate::cli::AppTypeArg;
-use crate::output::OutputContext;
+use crate::cli::{AppTypeArg, OutputFormat};
+use crate::commands::provider::{apply_provider_edit, classify_send_error, http_status_error, ProviderEditFields};
+use crate::commands::retry::{retry_with_backoff, CheckFailure, RETRY_BACKOFF};
+use crate::output::{
+    confirm_destructive, glyphs, print_banner, print_error, print_header, print_import_summary, sym, ImportOutcome,
+    ImportSummary, OutputContext,
+};
 
 /// 批量切换所有应用到指定供应商
-pub fn batch_switch(_ctx: &OutputContext, name: &str) -> Result<()> {
+///
+/// `atomic` 时任一应用切换失败会回滚所有已切换的应用。
+pub fn batch_switch(_ctx: &OutputContext, name: &str, atomic: bool) -> Result<()> {
     let state = AppState::init()?;
 
+    print_banner("批量切换供应商");
+    println!("将切换所有应用到供应商: {}", name.green().bold());
     println!();
-    println!("{}", "═══ 批量切换供应商 ═══".cyan().bold());
+
+    // 按名称解析各应用中的供应商
+    let mut targets = Vec::new();
+    let mut skipped_apps = Vec::new();
+    for app_type in AppType::all() {
+        match ccswitch_core::ProviderService::find(&state, *app_type, name)? {
+            Some(provider) => targets.push((*app_type, provider.id)),
+            None => skipped_apps.push(app_type.display_name()),
+        }
+    }
+
+    if targets.is_empty() {
+        print_error(&format!("所有应用中都不存在供应商: {}", name));
+        return Err(AppError::ProviderNotFound(name.to_string()).into());
+    }
+
+    if atomic {
+        return batch_switch_atomic(&state, &targets, &skipped_apps);
+    }
+
+    let (success_count, failed_apps) = switch_each(&state, &targets);
+
+    print_switch_summary(success_count, &failed_apps);
+    print_skipped_apps(&skipped_apps);
     println!();
-    println!("将切换所有应用到供应商: {}", name.green().bold());
+
+    Ok(())
+}
+
+/// 按 `应用=供应商` 映射批量切换，每个应用切换到各自的供应商
+pub fn batch_switch_map(_ctx: &OutputContext, entries: &[String], atomic: bool) -> Result<()> {
+    let state = AppState::init()?;
+
+    let requests = match parse_switch_map(entries) {
+        Ok(requests) => requests,
+        Err(e) => {
+            print_error(&e.to_string());
+            bail!("无效的切换映射");
+        }
+    };
+
+    print_banner("批量切换供应商");
+    println!("按映射切换:");
+    for (app_type, name) in &requests {
+        println!("  {} {} {}", app_type.display_name(), sym("→"), name.green());
+    }
+    println!();
+
+    let (targets, missing) = resolve_switch_map(&state, &requests)?;
+
+    if atomic {
+        if !missing.is_empty() {
+            for (app_type, name) in &missing {
+                print_error(&format!("{}: 未找到供应商 {}", app_type.display_name(), name));
+            }
+            bail!("批量切换失败，所有应用保持原状");
+        }
+        return batch_switch_atomic(&state, &targets, &[]);
+    }
+
+    for (app_type, name) in &missing {
+        println!(
+            "  {} {} ... {} {}",
+            sym("→").blue(),
+            app_type.display_name(),
+            sym("✗").red(),
+            format!("(未找到供应商: {})", name).dimmed()
+        );
+    }
+
+    let (success_count, mut failed_apps) = switch_each(&state, &targets);
+    failed_apps.extend(missing.iter().map(|(app_type, _)| app_type.display_name()));
+
+    print_switch_summary(success_count, &failed_apps);
     println!();
 
+    if success_count == 0 {
+        bail!("批量切换失败");
+    }
+    Ok(())
+}
+
+/// 解析 `应用=供应商` 形式的映射，同一应用只能出现一次
+fn parse_switch_map(entries: &[String]) -> Result<Vec<(AppType, String)>> {
+    let mut requests: Vec<(AppType, String)> = Vec::new();
+    for entry in entries {
+        let Some((app, name)) = entry.split_once('=') else {
+            bail!("无效的映射格式（应为 应用=供应商）: {}", entry);
+        };
+        let app_type: AppType = app.parse()?;
+        let name = name.trim();
+        if name.is_empty() {
+            bail!("映射中的供应商名称不能为空: {}", entry);
+        }
+        if requests.iter().any(|(a, _)| *a == app_type) {
+            bail!("应用 {} 在映射中重复出现", app_type.as_str());
+        }
+        requests.push((app_type, name.to_string()));
+    }
+    Ok(requests)
+}
+
+/// 应用与供应商（ID 或名称）的对应列表
+type AppTargets = Vec<(AppType, String)>;
+
+/// 解析映射中各应用的供应商 ID，返回可切换的目标与找不到供应商的应用
+fn resolve_switch_map(
+    state: &AppState,
+    requests: &[(AppType, String)],
+) -> Result<(AppTargets, AppTargets)> {
+    let mut targets = Vec::new();
+    let mut missing = Vec::new();
+    for (app_type, name) in requests {
+        match ccswitch_core::ProviderService::find(state, *app_type, name)? {
+            Some(provider) => targets.push((*app_type, provider.id)),
+            None => missing.push((*app_type, name.clone())),
+        }
+    }
+    Ok((targets, missing))
+}
+
+/// 逐个切换应用，返回成功数量与失败的应用
+fn switch_each(state: &AppState, targets: &[(AppType, String)]) -> (usize, Vec<&'static str>) {
     let mut success_count = 0;
     let mut failed_apps = Vec::new();
 
-    for app_type in AppType::all() {
+    for (app_type, id) in targets {
         let display_name = app_type.display_name();
-        print!("  {} {} ... ", "→".blue(), display_name);
+        print!("  {} {} ... ", sym("→").blue(), display_name);
 
-        match ccswitch_core::ProviderService::switch(&state, *app_type, name) {
+        match ccswitch_core::ProviderService::switch(state, *app_type, id) {
             Ok(_) => {
-                println!("{}", "✓".green());
+                println!("{}", sym("✓").green());
                 success_count += 1;
             }
             Err(e) => {
-                println!("{} {}", "✗".red(), format!("({})", e).dimmed());
+                println!("{} {}", sym("✗").red(), format!("({})", e).dimmed());
                 failed_apps.push(display_name);
             }
         }
     }
 
+    (success_count, failed_apps)
+}
+
+/// 打印批量切换结果
+fn print_switch_summary(success_count: usize, failed_apps: &[&str]) {
     println!();
     if failed_apps.is_empty() {
-        println!("{}", format!("✓ 成功切换 {} 个应用", success_count).green().bold());
+        println!("{}", glyphs(&format!("✓ 成功切换 {} 个应用", success_count)).green().bold());
     } else {
-        println!("{}", format!("⚠ 成功切换 {} 个应用", success_count).yellow());
+        println!("{}", glyphs(&format!("⚠ 成功切换 {} 个应用", success_count)).yellow());
         println!("{}", format!("  失败的应用: {}", failed_apps.join(", ")).red());
     }
-    println!();
+}
 
-    Ok(())
+/// 原子批量切换：全部成功或全部回滚
+fn batch_switch_atomic(
+    state: &AppState,
+    targets: &[(AppType, String)],
+    skipped_apps: &[&str],
+) -> Result<()> {
+    let apps: Vec<&str> = targets.iter().map(|(app, _)| app.display_name()).collect();
+    println!("  {} {}", sym("→").blue(), apps.join(", "));
+
+    match ccswitch_core::ProviderService::switch_all_atomic(state, targets, false) {
+        Ok(()) => {
+            println!();
+            println!("{}", glyphs(&format!("✓ 成功切换 {} 个应用", targets.len())).green().bold());
+            print_skipped_apps(skipped_apps);
+            println!();
+            Ok(())
+        }
+        Err(e) => {
+            println!();
+            print_error(&e.to_string());
+            bail!("批量切换失败，所有应用保持原状");
+        }
+    }
+}
+
+/// 打印未找到供应商而跳过的应用
+fn print_skipped_apps(skipped_apps: &[&str]) {
+    if !skipped_apps.is_empty() {
+        println!(
+            "{}",
+            format!("  跳过的应用（无此供应商）: {}", skipped_apps.join(", ")).dimmed()
+        );
+    }
 }
 
 /// 批量测试所有供应商的 API
 pub async fn batch_test(
-    _ctx: &OutputContext,
+    ctx: &OutputContext,
     app_type: AppTypeArg,
     timeout: u64,
     verbose: bool,
+    retries: u32,
 ) -> Result<()> {
     let state = AppState::init()?;
+    let human = ctx.format == OutputFormat::Table;
 
-    println!();
-    println!("{}", "═══ 批量测试供应商 API ═══".cyan().bold());
-    println!();
+    if human {
+        print_banner("批量测试供应商 API");
+    }
 
-    let mut total_tested = 0;
-    let mut total_success = 0;
-    let mut results = Vec::new();
+    let mut results: Vec<BatchTestResult> = Vec::new();
 
     for app in app_type.to_app_types() {
         let providers = ccswitch_core::ProviderService::list(&state, app)?;
 
         if providers.is_empty() {
-            println!("{}: {}", app.display_name().yellow(), "无供应商".dimmed());
+            if human {
+                println!("{}: {}", app.display_name().yellow(), "无供应商".dimmed());
+            }
             continue;
         }
 
-        println!("{}: 测试 {} 个供应商", app.display_name().cyan().bold(), providers.len());
+        if human {
+            println!("{}: 测试 {} 个供应商", app.display_name().cyan().bold(), providers.len());
+        }
 
         for (id, provider) in providers.iter() {
-            print!("  {} {} ... ", "→".blue(), provider.name);
-            total_tested += 1;
-
-            let result = test_provider_api(id, &provider, app, timeout).await;
-
-            match result {
-                Ok(latency) => {
-                    let latency_str = format!("{}ms", latency);
-                    let latency_colored = if latency < 200 {
-                        latency_str.green()
-                    } else if latency < 500 {
-                        latency_str.yellow()
-                    } else {
-                        latency_str.red()
-                    };
+            if human {
+                print!("  {} {} ... ", sym("→").blue(), provider.name);
+            }
 
-                    println!("{} ({})", "✓".green(), latency_colored);
-                    total_success += 1;
-                    results.push((app.display_name(), provider.name.clone(), true, Some(latency)));
-                }
-                Err(e) => {
-                    println!("{} {}", "✗".red(), if verbose {
-                        format!("({})", e)
-                    } else {
-                        "(失败)".to_string()
-                    }.dimmed());
-                    results.push((app.display_name(), provider.name.clone(), false, None));
+            let result = retry_with_backoff(retries.saturating_add(1), RETRY_BACKOFF, || test_provider_api(id, provider, app, timeout)).await;
+
+            if human {
+                match &result {
+                    Ok(latency) => {
+                        let latency_str = format!("{}ms", latency);
+                        let latency_colored = if *latency < 200 {
+                            latency_str.green()
+                        } else if *latency < 500 {
+                            latency_str.yellow()
+                        } else {
+                            latency_str.red()
+                        };
+
+                        println!("{} ({})", sym("✓").green(), latency_colored);
+                    }
+                    Err(e) => {
+                        println!("{} {}", sym("✗").red(), if verbose {
+                            format!("({})", e)
+                        } else {
+                            "(失败)".to_string()
+                        }.dimmed());
+                    }
                 }
             }
+
+            results.push(BatchTestResult::new(app, &provider.name, result));
         }
 
-        println!();
+        if human {
+            println!();
+        }
     }
 
-    // 显示汇总
-    println!("{}", "═══ 测试汇总 ═══".cyan().bold());
-    println!();
-    println!("  总计测试: {}", total_tested);
-    println!("  成功: {}", format!("{}", total_success).green());
-    println!("  失败: {}", format!("{}", total_tested - total_success).red());
-    println!("  成功率: {}%", (total_success * 100 / total_tested.max(1)));
-    println!();
-
-    // 显示详细结果（仅失败的）
-    let failed: Vec<_> = results.iter().filter(|r| !r.2).collect();
-    if !failed.is_empty() {
-        println!("{}", "失败的供应商:".yellow().bold());
-        for (app, name, _, _) in failed {
-            println!("  {} - {}", app.dimmed(), name.red());
+    let total_tested = results.len();
+    let total_success = results.iter().filter(|r| r.ok).count();
+
+    match ctx.format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&results)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(&results)?),
+        OutputFormat::Table => {
+            // 显示汇总
+            print_header("测试汇总");
+            println!();
+            println!("  总计测试: {}", total_tested);
+            println!("  成功: {}", format!("{}", total_success).green());
+            println!("  失败: {}", format!("{}", total_tested - total_success).red());
+            println!("  成功率: {}%", (total_success * 100 / total_tested.max(1)));
+            println!();
+
+            // 显示详细结果（仅失败的）
+            let failed: Vec<_> = results.iter().filter(|r| !r.ok).collect();
+            if !failed.is_empty() {
+                println!("{}", "失败的供应商:".yellow().bold());
+                for r in failed {
+                    let app = r.app.parse::<AppType>().map(|a| a.display_name()).unwrap_or(&r.app);
+                    println!("  {} - {}", app.dimmed(), r.provider.red());
+                }
+                println!();
+            }
         }
-        println!();
+    }
+
+    // 存在失败时以非零状态退出（与输出格式无关），便于 CI 判断
+    if total_success < total_tested {
+        bail!("{} 个供应商测试失败", total_tested - total_success);
     }
 
     Ok(())
 }
 
+/// 批量测试的单条结果（JSON/YAML 输出）
+#[derive(Debug, Serialize)]
+pub struct BatchTestResult {
+    pub app: String,
+    pub provider: String,
+    pub ok: bool,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl BatchTestResult {
+    fn new(app: AppType, provider: &str, result: Result<u64>) -> Self {
+        let (latency_ms, error) = match result {
+            Ok(latency) => (Some(latency), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        Self {
+            app: app.as_str().to_string(),
+            provider: provider.to_string(),
+            ok: error.is_none(),
+            latency_ms,
+            error,
+        }
+    }
+}
+
 /// 测试单个供应商的 API
 async fn test_provider_api(
     _id: &str,
     provider: &Provider,
     app_type: AppType,
     timeout: u64,
-) -> Result<u64> {
+) -> std::result::Result<u64, CheckFailure> {
     let start = std::time::Instant::now();
 
     // 从 settings_config 中提取配置
@@ -168,7 +386,7 @@ async fn test_provider_api(
             test_gemini_api(api_key, url, timeout).await?;
         }
         AppType::OpenCode | AppType::OpenClaw => {
-            bail!("该应用不支持 API 测试");
+            return Err(CheckFailure::fatal(anyhow::anyhow!("该应用不支持 API 测试")));
         }
     }
 
@@ -177,7 +395,7 @@ async fn test_provider_api(
 }
 
 /// 测试 Anthropic API
-async fn test_anthropic_api(api_key: &str, base_url: &str, timeout: u64) -> Result<()> {
+async fn test_anthropic_api(api_key: &str, base_url: &str, timeout: u64) -> std::result::Result<(), CheckFailure> {
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(timeout))
         .build()?;
@@ -193,17 +411,20 @@ async fn test_anthropic_api(api_key: &str, base_url: &str, timeout: u64) -> Resu
             "messages": [{"role": "user", "content": "Hi"}]
         }))
         .send()
-        .await?;
+        .await
+        .map_err(classify_send_error)?;
 
-    if !response.status().is_success() {
-        bail!("API 返回错误: {}", response.status());
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(http_status_error(status, &body));
     }
 
     Ok(())
 }
 
 /// 测试 OpenAI API
-async fn test_openai_api(api_key: &str, base_url: &str, timeout: u64) -> Result<()> {
+async fn test_openai_api(api_key: &str, base_url: &str, timeout: u64) -> std::result::Result<(), CheckFailure> {
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(timeout))
         .build()?;
@@ -212,17 +433,20 @@ async fn test_openai_api(api_key: &str, base_url: &str, timeout: u64) -> Result<
         .get(format!("{}/models", base_url))
         .header("Authorization", format!("Bearer {}", api_key))
         .send()
-        .await?;
+        .await
+        .map_err(classify_send_error)?;
 
-    if !response.status().is_success() {
-        bail!("API 返回错误: {}", response.status());
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(http_status_error(status, &body));
     }
 
     Ok(())
 }
 
 /// 测试 Gemini API
-async fn test_gemini_api(api_key: &str, base_url: &str, timeout: u64) -> Result<()> {
+async fn test_gemini_api(api_key: &str, base_url: &str, timeout: u64) -> std::result::Result<(), CheckFailure> {
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(timeout))
         .build()?;
@@ -230,110 +454,189 @@ async fn test_gemini_api(api_key: &str, base_url: &str, timeout: u64) -> Result<
     let response = client
         .get(format!("{}/v1/models?key={}", base_url, api_key))
         .send()
-        .await?;
+        .await
+        .map_err(classify_send_error)?;
 
-    if !response.status().is_success() {
-        bail!("API 返回错误: {}", response.status());
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(http_status_error(status, &body));
     }
 
     Ok(())
 }
 
 /// 批量导出配置
-pub fn batch_export(_ctx: &OutputContext, output_file: &str, app_type: AppTypeArg) -> Result<()> {
+pub fn batch_export(
+    _ctx: &OutputContext,
+    output_file: &str,
+    app_type: AppTypeArg,
+    manifest: bool,
+) -> Result<()> {
     let state = AppState::init()?;
 
-    println!();
-    println!("{}", "═══ 批量导出配置 ═══".cyan().bold());
-    println!();
+    print_banner("批量导出配置");
 
     let mut all_configs = HashMap::new();
+    let mut counts = IndexMap::new();
 
     for app in app_type.to_app_types() {
         let providers = ccswitch_core::ProviderService::list(&state, app)?;
 
         if !providers.is_empty() {
             println!("  {} 导出 {} 个供应商", app.display_name().cyan(), providers.len());
+            counts.insert(app.to_string(), providers.len());
             all_configs.insert(app.to_string(), providers);
         }
     }
 
     // 序列化为 YAML
     let yaml = serde_yaml::to_string(&all_configs)?;
-    std::fs::write(output_file, yaml)?;
+    std::fs::write(output_file, &yaml)?;
 
     println!();
-    println!("{}", format!("✓ 配置已导出到: {}", output_file).green().bold());
+    println!("{}", glyphs(&format!("✓ 配置已导出到: {}", output_file)).green().bold());
+
+    if manifest {
+        let manifest_path = write_export_manifest(Path::new(output_file), yaml.as_bytes(), counts)?;
+        println!("{}", glyphs(&format!("✓ 清单已写入: {}", manifest_path.display())).green());
+    }
     println!();
 
     Ok(())
 }
 
-/// 批量导入配置
-pub fn batch_import(_ctx: &OutputContext, input_file: &str, overwrite: bool) -> Result<()> {
+/// 批量导出的清单文件，用于校验备份完整性
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportManifest {
+    /// 导出时间（RFC 3339）
+    pub exported_at: String,
+    /// 各应用导出的供应商数量
+    pub counts_per_app: IndexMap<String, usize>,
+    /// 导出文件名
+    pub file: String,
+    /// 导出文件内容的校验和，格式为 `sha256:<hex>`
+    pub checksum: String,
+}
+
+/// 计算导出内容的校验和
+fn export_checksum(bytes: &[u8]) -> String {
+    format!("sha256:{:x}", Sha256::digest(bytes))
+}
+
+/// 在导出文件旁写入 `<文件名>.manifest.json`，返回清单路径
+fn write_export_manifest(
+    output_file: &Path,
+    bytes: &[u8],
+    counts_per_app: IndexMap<String, usize>,
+) -> Result<PathBuf> {
+    let file_name = output_file
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let manifest = ExportManifest {
+        exported_at: Utc::now().to_rfc3339(),
+        counts_per_app,
+        checksum: export_checksum(bytes),
+        file: file_name.clone(),
+    };
+
+    let manifest_path = output_file.with_file_name(format!("{}.manifest.json", file_name));
+    std::fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+    Ok(manifest_path)
+}
+
+/// 从文件批量导入配置
+pub fn batch_import(ctx: &OutputContext, input_file: &str, overwrite: bool, force: bool) -> Result<()> {
     let state = AppState::init()?;
 
-    println!();
-    println!("{}", "═══ 批量导入配置 ═══".cyan().bold());
-    println!();
+    if ctx.format == OutputFormat::Table {
+        print_banner("批量导入配置");
+    }
 
     // 读取文件
     let content = std::fs::read_to_string(input_file)?;
-    let configs: HashMap<String, indexmap::IndexMap<String, Provider>> = serde_yaml::from_str(&content)?;
+    let configs: IndexMap<String, IndexMap<String, Provider>> = serde_yaml::from_str(&content)?;
 
-    let mut total_imported = 0;
-    let mut total_skipped = 0;
+    if overwrite {
+        let conflicts = count_import_conflicts(&state, &configs)?;
+        if conflicts > 0
+            && !confirm_destructive(&format!("将覆盖 {} 个已存在的供应商，确认继续?", conflicts), force)?
+        {
+            println!("{}", "已取消".yellow());
+            return Ok(());
+        }
+    }
+
+    let summary = import_bundle(&state, configs, overwrite)?;
+    print_import_summary(ctx, &summary);
+
+    Ok(())
+}
+
+/// 统计配置包中与已有供应商同名的数量
+fn count_import_conflicts(
+    state: &AppState,
+    configs: &IndexMap<String, IndexMap<String, Provider>>,
+) -> Result<usize> {
+    let mut conflicts = 0;
+    for (app_str, providers) in configs {
+        let Ok(app_type) = app_str.parse::<AppType>() else { continue };
+        for provider in providers.values() {
+            if ccswitch_core::ProviderService::find(state, app_type, &provider.name)?.is_some() {
+                conflicts += 1;
+            }
+        }
+    }
+    Ok(conflicts)
+}
+
+/// 导入多应用配置包，按应用汇总每个供应商的导入结果
+fn import_bundle(
+    state: &AppState,
+    configs: IndexMap<String, IndexMap<String, Provider>>,
+    overwrite: bool,
+) -> Result<ImportSummary> {
+    let mut summary = ImportSummary::default();
 
     for (app_str, providers) in configs.into_iter() {
-        let app_type: AppType = match app_str.as_str() {
-            "claude" => AppType::Claude,
-            "codex" => AppType::Codex,
-            "gemini" => AppType::Gemini,
-            "opencode" => AppType::OpenCode,
-            _ => {
-                println!("  {} 未知的应用类型: {}", "⚠".yellow(), app_str);
+        let app_type: AppType = match app_str.parse() {
+            Ok(app_type) => app_type,
+            Err(e) => {
+                print_error(&e.to_string());
                 continue;
             }
         };
 
-        println!("{}: 导入 {} 个供应商", app_type.display_name().cyan(), providers.len());
-
         for (_id, provider) in providers {
             // 检查是否已存在
-            let existing = ccswitch_core::ProviderService::find(&state, app_type, &provider.name)?;
+            let existing = ccswitch_core::ProviderService::find(state, app_type, &provider.name)?;
             let exists = existing.is_some();
 
             if exists && !overwrite {
-                println!("  {} {} (已跳过)", "→".dimmed(), provider.name.dimmed());
-                total_skipped += 1;
+                summary.record(app_type, ImportOutcome::Skipped, provider.name);
                 continue;
             }
 
-            match ccswitch_core::ProviderService::add(&state, app_type, provider.clone()) {
+            let name = provider.name.clone();
+            match ccswitch_core::ProviderService::add(state, app_type, provider) {
                 Ok(_) => {
-                    println!("  {} {} {}",
-                        "→".blue(),
-                        provider.name,
-                        if exists { "(已覆盖)".yellow() } else { "(新增)".green() }
-                    );
-                    total_imported += 1;
+                    let outcome = if exists {
+                        ImportOutcome::Overwritten
+                    } else {
+                        ImportOutcome::Added
+                    };
+                    summary.record(app_type, outcome, name);
                 }
                 Err(e) => {
-                    println!("  {} {} {}", "✗".red(), provider.name, format!("({})", e).dimmed());
+                    print_error(&format!("{} 导入失败: {}", name, e));
+                    summary.record(app_type, ImportOutcome::Failed, name);
                 }
             }
         }
-
-        println!();
     }
 
-    println!("{}", "═══ 导入汇总 ═══".cyan().bold());
-    println!();
-    println!("  导入成功: {}", format!("{}", total_imported).green());
-    println!("  已跳过: {}", format!("{}", total_skipped).yellow());
-    println!();
-
-    Ok(())
+    Ok(summary)
 }
 
 /// 批量删除供应商
@@ -345,9 +648,7 @@ pub fn batch_remove(
 ) -> Result<()> {
     let state = AppState::init()?;
 
-    println!();
-    println!("{}", "═══ 批量删除供应商 ═══".cyan().bold());
-    println!();
+    print_banner("批量删除供应商");
 
     if !force {
         println!("将删除以下供应商:");
@@ -356,13 +657,7 @@ pub fn batch_remove(
         }
         println!();
 
-        print!("确认删除? [y/N]: ");
-        std::io::Write::flush(&mut std::io::stdout())?;
-
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input)?;
-
-        if !input.trim().eq_ignore_ascii_case("y") {
+        if !confirm_destructive("确认删除?", force)? {
             println!("{}", "已取消".yellow());
             return Ok(());
         }
@@ -376,11 +671,11 @@ pub fn batch_remove(
         for name in names {
             match ccswitch_core::ProviderService::delete(&state, app, name) {
                 Ok(_) => {
-                    println!("  {} {} - {}", "✓".green(), app.display_name().dimmed(), name);
+                    println!("  {} {} - {}", sym("✓").green(), app.display_name().dimmed(), name);
                     success_count += 1;
                 }
                 Err(e) => {
-                    println!("  {} {} - {} {}", "✗".red(), app.display_name().dimmed(), name, format!("({})", e).dimmed());
+                    println!("  {} {} - {} {}", sym("✗").red(), app.display_name().dimmed(), name, format!("({})", e).dimmed());
                     failed.push((app.display_name(), name.clone()));
                 }
             }
@@ -389,9 +684,9 @@ pub fn batch_remove(
 
     println!();
     if failed.is_empty() {
-        println!("{}", format!("✓ 成功删除 {} 个供应商", success_count).green().bold());
+        println!("{}", glyphs(&format!("✓ 成功删除 {} 个供应商", success_count)).green().bold());
     } else {
-        println!("{}", format!("⚠ 成功删除 {} 个，失败 {} 个", success_count, failed.len()).yellow());
+        println!("{}", glyphs(&format!("⚠ 成功删除 {} 个，失败 {} 个", success_count, failed.len())).yellow());
     }
     println!();
 
@@ -404,57 +699,73 @@ pub fn batch_sync(
     source_app: AppType,
     target_apps: Vec<AppType>,
     overwrite: bool,
+    pattern: Option<&str>,
+    dry_run: bool,
+    force: bool,
 ) -> Result<()> {
     let state = AppState::init()?;
 
+    // 覆盖已有配置前先预览冲突并确认
+    if overwrite && !dry_run {
+        let preview = sync_providers(&state, source_app, &target_apps, pattern, overwrite, true)?;
+        let conflicts = preview.iter().filter(|o| o.action == SyncAction::Overwrite).count();
+        if conflicts > 0
+            && !confirm_destructive(&format!("将覆盖 {} 个已存在的供应商，确认继续?", conflicts), force)?
+        {
+            println!("{}", "已取消".yellow());
+            return Ok(());
+        }
+    }
+
     println!();
-    println!("{}", "═══ 批量同步配置 ═══".cyan().bold());
+    if dry_run {
+        print_header("批量同步配置（预览，不会写入）");
+    } else {
+        print_header("批量同步配置");
+    }
     println!();
 
-    // 获取源应用的所有供应商
-    let source_providers = ccswitch_core::ProviderService::list(&state, source_app)?;
-
-    if source_providers.is_empty() {
-        bail!("源应用 {} 没有供应商配置", source_app.display_name());
-    }
+    let outcomes = sync_providers(&state, source_app, &target_apps, pattern, overwrite, dry_run)?;
 
+    let source_count = outcomes.len() / target_apps.len().max(1);
     println!("从 {} 同步 {} 个供应商到:",
         source_app.display_name().cyan().bold(),
-        source_providers.len()
+        source_count
     );
+    if let Some(p) = pattern {
+        println!("匹配模式: {}", p.yellow());
+    }
 
     for target in &target_apps {
-        println!("  → {}", target.display_name());
+        println!("  {} {}", sym("→"), target.display_name());
     }
     println!();
 
     let mut total_synced = 0;
     let mut total_skipped = 0;
 
-    for target in target_apps {
+    for target in &target_apps {
         println!("{}", target.display_name().cyan().bold());
 
-        for (_id, provider) in &source_providers {
-            let existing = ccswitch_core::ProviderService::find(&state, target, &provider.name)?;
-            let exists = existing.is_some();
-
-            if exists && !overwrite {
-                println!("  {} {} (已跳过)", "→".dimmed(), provider.name.dimmed());
-                total_skipped += 1;
+        for outcome in outcomes.iter().filter(|o| o.target == *target) {
+            if let Some(e) = &outcome.error {
+                println!("  {} {} {}", sym("✗").red(), outcome.name, format!("({})", e).dimmed());
                 continue;
             }
-
-            match ccswitch_core::ProviderService::add(&state, target, provider.clone()) {
-                Ok(_) => {
-                    println!("  {} {} {}",
-                        "→".blue(),
-                        provider.name,
-                        if exists { "(已覆盖)".yellow() } else { "(新增)".green() }
-                    );
-                    total_synced += 1;
+            match outcome.action {
+                SyncAction::Skip => {
+                    println!("  {} {} (已跳过)", sym("→").dimmed(), outcome.name.dimmed());
+                    total_skipped += 1;
                 }
-                Err(e) => {
-                    println!("  {} {} {}", "✗".red(), provider.name, format!("({})", e).dimmed());
+                SyncAction::Create | SyncAction::Overwrite => {
+                    let label = match (outcome.action, dry_run) {
+                        (SyncAction::Create, false) => "(新增)".green(),
+                        (SyncAction::Create, true) => "(将新增)".green(),
+                        (_, false) => "(已覆盖)".yellow(),
+                        (_, true) => "(将覆盖)".yellow(),
+                    };
+                    println!("  {} {} {}", sym("→").blue(), outcome.name, label);
+                    total_synced += 1;
                 }
             }
         }
@@ -462,15 +773,92 @@ pub fn batch_sync(
         println!();
     }
 
-    println!("{}", "═══ 同步汇总 ═══".cyan().bold());
+    print_header("同步汇总");
     println!();
-    println!("  同步成功: {}", format!("{}", total_synced).green());
-    println!("  已跳过: {}", format!("{}", total_skipped).yellow());
+    if dry_run {
+        println!("  将同步: {}", format!("{}", total_synced).green());
+        println!("  将跳过: {}", format!("{}", total_skipped).yellow());
+        println!();
+        println!("{}", "预览模式，未写入任何配置".dimmed());
+    } else {
+        println!("  同步成功: {}", format!("{}", total_synced).green());
+        println!("  已跳过: {}", format!("{}", total_skipped).yellow());
+    }
     println!();
 
     Ok(())
 }
 
+/// 同步动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SyncAction {
+    Create,
+    Overwrite,
+    Skip,
+}
+
+/// 单个供应商的同步结果
+#[derive(Debug)]
+struct SyncOutcome {
+    target: AppType,
+    name: String,
+    action: SyncAction,
+    error: Option<String>,
+}
+
+/// 将源应用中名称匹配 `pattern` 的供应商同步到目标应用，`dry_run` 时只计算结果不写入
+fn sync_providers(
+    state: &AppState,
+    source_app: AppType,
+    target_apps: &[AppType],
+    pattern: Option<&str>,
+    overwrite: bool,
+    dry_run: bool,
+) -> Result<Vec<SyncOutcome>> {
+    let source_providers = ccswitch_core::ProviderService::list(state, source_app)?;
+
+    if source_providers.is_empty() {
+        bail!("源应用 {} 没有供应商配置", source_app.display_name());
+    }
+
+    let selected: Vec<&Provider> = source_providers
+        .values()
+        .filter(|p| pattern.map_or(true, |pat| p.name.contains(pat)))
+        .collect();
+    if selected.is_empty() {
+        bail!("源应用 {} 中没有名称匹配 '{}' 的供应商", source_app.display_name(), pattern.unwrap_or_default());
+    }
+
+    let mut outcomes = Vec::new();
+    for target in target_apps {
+        for provider in &selected {
+            let exists = ccswitch_core::ProviderService::find(state, *target, &provider.name)?.is_some();
+            let action = match (exists, overwrite) {
+                (true, false) => SyncAction::Skip,
+                (true, true) => SyncAction::Overwrite,
+                (false, _) => SyncAction::Create,
+            };
+
+            let error = if dry_run || action == SyncAction::Skip {
+                None
+            } else {
+                ccswitch_core::ProviderService::add(state, *target, (*provider).clone())
+                    .err()
+                    .map(|e| e.to_string())
+            };
+
+            outcomes.push(SyncOutcome {
+                target: *target,
+                name: provider.name.clone(),
+                action,
+                error,
+            });
+        }
+    }
+
+    Ok(outcomes)
+}
+
 /// 批量编辑供应商配置
 pub fn batch_edit(
     _ctx: &OutputContext,
@@ -479,12 +867,11 @@ pub fn batch_edit(
     value: &str,
     pattern: Option<&str>,
 ) -> Result<()> {
+    let field = BatchEditField::parse(field)?;
     let state = AppState::init()?;
 
-    println!();
-    println!("{}", "═══ 批量编辑配置 ═══".cyan().bold());
-    println!();
-    println!("修改字段: {}", field.cyan());
+    print_banner("批量编辑配置");
+    println!("修改字段: {}", field.name().cyan());
     println!("新值: {}", value.green());
     if let Some(p) = pattern {
         println!("匹配模式: {}", p.yellow());
@@ -510,32 +897,19 @@ pub fn batch_edit(
                 }
             }
 
-            // 根据字段名更新配置
-            let config = provider.settings_config.as_object_mut()
-                .ok_or_else(|| anyhow::anyhow!("配置格式错误"))?;
-
-            match field {
-                "base_url" | "base-url" | "baseUrl" => {
-                    config.insert("baseUrl".to_string(), serde_json::Value::String(value.to_string()));
-                }
-                "model" => {
-                    config.insert("model".to_string(), serde_json::Value::String(value.to_string()));
-                }
-                "small_model" | "small-model" | "smallModel" => {
-                    config.insert("smallModel".to_string(), serde_json::Value::String(value.to_string()));
-                }
-                _ => {
-                    bail!("不支持的字段: {}。支持的字段: base-url, model, small-model", field);
-                }
+            // 按应用类型写入对应的配置位置
+            if let Err(e) = apply_batch_edit(&mut provider, app, field, value) {
+                println!("  {} {} {}", sym("✗").red(), provider.name, format!("({})", e).dimmed());
+                continue;
             }
 
             match ccswitch_core::ProviderService::update(&state, app, provider.clone()) {
                 Ok(_) => {
-                    println!("  {} {}", "✓".green(), provider.name);
+                    println!("  {} {}", sym("✓").green(), provider.name);
                     total_updated += 1;
                 }
                 Err(e) => {
-                    println!("  {} {} {}", "✗".red(), provider.name, format!("({})", e).dimmed());
+                    println!("  {} {} {}", sym("✗").red(), provider.name, format!("({})", e).dimmed());
                 }
             }
         }
@@ -543,8 +917,276 @@ pub fn batch_edit(
         println!();
     }
 
-    println!("{}", format!("✓ 成功更新 {} 个供应商", total_updated).green().bold());
+    println!("{}", glyphs(&format!("✓ 成功更新 {} 个供应商", total_updated)).green().bold());
     println!();
 
     Ok(())
 }
+
+/// batch edit 支持的字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BatchEditField {
+    BaseUrl,
+    Model,
+    SmallModel,
+}
+
+impl BatchEditField {
+    fn parse(field: &str) -> Result<Self> {
+        match field {
+            "base_url" | "base-url" | "baseUrl" => Ok(Self::BaseUrl),
+            "model" => Ok(Self::Model),
+            "small_model" | "small-model" | "smallModel" => Ok(Self::SmallModel),
+            _ => bail!("不支持的字段: {}。支持的字段: base-url, model, small-model", field),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::BaseUrl => "base-url",
+            Self::Model => "model",
+            Self::SmallModel => "small-model",
+        }
+    }
+}
+
+/// 将单个字段写入供应商配置，与单个 `edit` 使用相同的按应用处理逻辑
+fn apply_batch_edit(provider: &mut Provider, app: AppType, field: BatchEditField, value: &str) -> Result<()> {
+    if field == BatchEditField::SmallModel && app != AppType::Claude {
+        bail!("{} 不支持 small-model", app.display_name());
+    }
+    let fields = match field {
+        BatchEditField::BaseUrl => ProviderEditFields { base_url: Some(value), ..Default::default() },
+        BatchEditField::Model => ProviderEditFields { model: Some(value), ..Default::default() },
+        BatchEditField::SmallModel => ProviderEditFields { small_model: Some(value), ..Default::default() },
+    };
+    apply_provider_edit(&mut provider.settings_config, &app, &fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn claude_provider(id: &str, name: &str) -> Provider {
+        Provider::new(id, name, json!({ "env": { "ANTHROPIC_AUTH_TOKEN": "sk-test" } }))
+    }
+
+    fn codex_provider(id: &str, name: &str) -> Provider {
+        Provider::new(
+            id,
+            name,
+            json!({ "config": "model = \"gpt-5\"\n", "auth": "[openai]\napi_key = \"sk-test\"\n" }),
+        )
+    }
+
+    #[test]
+    fn test_batch_edit_base_url_updates_claude_env() {
+        let mut provider = claude_provider("a", "A");
+        apply_batch_edit(&mut provider, AppType::Claude, BatchEditField::BaseUrl, "https://relay.example.com").unwrap();
+        apply_batch_edit(&mut provider, AppType::Claude, BatchEditField::Model, "claude-sonnet-4").unwrap();
+
+        let env = &provider.settings_config["env"];
+        assert_eq!(env["ANTHROPIC_BASE_URL"], "https://relay.example.com");
+        assert_eq!(env["ANTHROPIC_MODEL"], "claude-sonnet-4");
+        assert_eq!(env["ANTHROPIC_AUTH_TOKEN"], "sk-test");
+        assert!(provider.settings_config.get("baseUrl").is_none());
+    }
+
+    #[test]
+    fn test_batch_edit_base_url_updates_opencode_and_openclaw() {
+        let mut opencode = Provider::new("o", "O", json!({ "npm": "@ai-sdk/openai-compatible", "options": { "apiKey": "sk-test" } }));
+        apply_batch_edit(&mut opencode, AppType::OpenCode, BatchEditField::BaseUrl, "https://relay.example.com/v1").unwrap();
+        assert_eq!(opencode.settings_config["options"]["baseURL"], "https://relay.example.com/v1");
+        assert_eq!(opencode.settings_config["options"]["apiKey"], "sk-test");
+
+        let mut openclaw = Provider::new("c", "C", json!({ "apiKey": "sk-test", "models": [{ "id": "gpt-5" }] }));
+        apply_batch_edit(&mut openclaw, AppType::OpenClaw, BatchEditField::BaseUrl, "https://relay.example.com/v1").unwrap();
+        assert_eq!(openclaw.settings_config["baseUrl"], "https://relay.example.com/v1");
+
+        let err = apply_batch_edit(&mut openclaw, AppType::OpenClaw, BatchEditField::Model, "o3").unwrap_err();
+        assert!(err.to_string().contains("OpenClaw"));
+    }
+
+    #[test]
+    fn test_batch_edit_base_url_patches_codex_toml() {
+        let config = "model_provider = \"relay\"\nmodel = \"gpt-5\"\n\n[model_providers.relay]\nname = \"Relay\"\nbase_url = \"https://old.example.com/v1\"\nwire_api = \"responses\"\n\n[mcp_servers.fs]\ncommand = \"npx\"\n";
+        let mut provider = Provider::new("r", "Relay", json!({ "config": config, "auth": "[openai]\napi_key = \"sk-test\"\n" }));
+
+        apply_batch_edit(&mut provider, AppType::Codex, BatchEditField::BaseUrl, "https://new.example.com/v1").unwrap();
+
+        let text = provider.settings_config["config"].as_str().unwrap();
+        let table: toml::Table = toml::from_str(text).unwrap();
+        let relay = &table["model_providers"]["relay"];
+        assert_eq!(relay["base_url"].as_str(), Some("https://new.example.com/v1"));
+        assert_eq!(relay["wire_api"].as_str(), Some("responses"));
+        assert_eq!(table["model"].as_str(), Some("gpt-5"));
+        assert_eq!(table["mcp_servers"]["fs"]["command"].as_str(), Some("npx"));
+        assert!(provider.settings_config.get("baseUrl").is_none());
+
+        // Codex 没有小模型字段
+        assert!(apply_batch_edit(&mut provider, AppType::Codex, BatchEditField::SmallModel, "x").is_err());
+    }
+
+    #[test]
+    fn test_import_bundle_groups_by_app() {
+        let state = AppState::memory().unwrap();
+        // 预置当前供应商，避免导入时写入 live 配置
+        state.db.save_provider("claude", &claude_provider("existing", "Existing")).unwrap();
+        state.db.set_current_provider("claude", "existing").unwrap();
+        state.db.save_provider("codex", &codex_provider("current", "Current")).unwrap();
+        state.db.set_current_provider("codex", "current").unwrap();
+
+        let mut claude = IndexMap::new();
+        claude.insert("existing".to_string(), claude_provider("existing", "Existing"));
+        claude.insert("fresh".to_string(), claude_provider("fresh", "Fresh"));
+        let mut codex = IndexMap::new();
+        codex.insert("relay".to_string(), codex_provider("relay", "Relay"));
+
+        let mut bundle = IndexMap::new();
+        bundle.insert("claude".to_string(), claude);
+        bundle.insert("codex".to_string(), codex);
+
+        let summary = import_bundle(&state, bundle, false).unwrap();
+
+        let claude = &summary.apps["claude"];
+        assert_eq!(claude.added, vec!["Fresh".to_string()]);
+        assert_eq!(claude.skipped, vec!["Existing".to_string()]);
+        assert!(claude.overwritten.is_empty());
+
+        let codex = &summary.apps["codex"];
+        assert_eq!(codex.added, vec!["Relay".to_string()]);
+        assert!(codex.skipped.is_empty());
+
+        assert_eq!(summary.count(ImportOutcome::Added), 2);
+        assert_eq!(summary.count(ImportOutcome::Failed), 0);
+    }
+
+    #[test]
+    fn test_export_manifest_matches_exported_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("backup.yaml");
+
+        let mut claude = IndexMap::new();
+        claude.insert("a".to_string(), claude_provider("a", "A"));
+        claude.insert("b".to_string(), claude_provider("b", "B"));
+        let mut bundle = IndexMap::new();
+        bundle.insert("claude".to_string(), claude);
+        let yaml = serde_yaml::to_string(&bundle).unwrap();
+        std::fs::write(&output, &yaml).unwrap();
+
+        let mut counts = IndexMap::new();
+        counts.insert("claude".to_string(), 2);
+        let manifest_path = write_export_manifest(&output, yaml.as_bytes(), counts).unwrap();
+        assert_eq!(manifest_path, dir.path().join("backup.yaml.manifest.json"));
+
+        let manifest: ExportManifest =
+            serde_json::from_str(&std::fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        let exported = std::fs::read(&output).unwrap();
+        assert_eq!(manifest.file, "backup.yaml");
+        assert_eq!(manifest.checksum, export_checksum(&exported));
+        assert!(manifest.checksum.starts_with("sha256:"));
+        assert_eq!(manifest.checksum.len(), "sha256:".len() + 64);
+
+        let parsed: IndexMap<String, IndexMap<String, Provider>> =
+            serde_yaml::from_slice(&exported).unwrap();
+        assert_eq!(manifest.counts_per_app["claude"], parsed["claude"].len());
+    }
+
+    #[test]
+    fn test_batch_test_results_json_shape() {
+        let results = vec![
+            BatchTestResult::new(AppType::Claude, "Fast", Ok(120)),
+            BatchTestResult::new(AppType::Codex, "Broken", Err(anyhow::anyhow!("HTTP 401"))),
+        ];
+
+        let value = serde_json::to_value(&results).unwrap();
+        assert_eq!(
+            value,
+            json!([
+                { "app": "claude", "provider": "Fast", "ok": true, "latency_ms": 120, "error": null },
+                { "app": "codex", "provider": "Broken", "ok": false, "latency_ms": null, "error": "HTTP 401" }
+            ])
+        );
+    }
+
+    /// 预置源和目标应用，目标应用设置当前供应商以避免写入 live 配置
+    fn sync_fixture() -> AppState {
+        let state = AppState::memory().unwrap();
+        for (id, name) in [("a", "OpenAI Relay"), ("b", "OpenAI Official"), ("c", "Other")] {
+            // 同时满足 Claude 与 Gemini 的校验
+            let provider = Provider::new(
+                id,
+                name,
+                json!({ "apiKey": "sk-test", "env": { "ANTHROPIC_AUTH_TOKEN": "sk-test" } }),
+            );
+            state.db.save_provider("claude", &provider).unwrap();
+        }
+        state.db.save_provider("gemini", &claude_provider("g", "Gemini Current")).unwrap();
+        state.db.set_current_provider("gemini", "g").unwrap();
+        state
+    }
+
+    #[test]
+    fn test_sync_providers_filters_by_pattern() {
+        let state = sync_fixture();
+
+        let outcomes =
+            sync_providers(&state, AppType::Claude, &[AppType::Gemini], Some("OpenAI"), false, false)
+                .unwrap();
+        let names: Vec<&str> = outcomes.iter().map(|o| o.name.as_str()).collect();
+        assert_eq!(names, ["OpenAI Relay", "OpenAI Official"]);
+        assert!(outcomes.iter().all(|o| o.action == SyncAction::Create && o.error.is_none()));
+
+        let gemini = state.db.get_all_providers("gemini").unwrap();
+        assert_eq!(gemini.len(), 3);
+        assert!(!gemini.values().any(|p| p.name == "Other"));
+
+        assert!(sync_providers(&state, AppType::Claude, &[AppType::Gemini], Some("nope"), false, false).is_err());
+    }
+
+    #[test]
+    fn test_sync_providers_dry_run_writes_nothing() {
+        let state = sync_fixture();
+        state.db.save_provider("gemini", &claude_provider("x", "Other")).unwrap();
+
+        let outcomes =
+            sync_providers(&state, AppType::Claude, &[AppType::Gemini], None, false, true).unwrap();
+        let actions: Vec<SyncAction> = outcomes.iter().map(|o| o.action).collect();
+        assert_eq!(actions, [SyncAction::Create, SyncAction::Create, SyncAction::Skip]);
+
+        let gemini = state.db.get_all_providers("gemini").unwrap();
+        assert_eq!(gemini.len(), 2);
+    }
+
+    #[test]
+    fn test_switch_map_reports_missing_provider() {
+        let state = AppState::memory().unwrap();
+        state.db.save_provider("claude", &claude_provider("yw", "云雾")).unwrap();
+        state.db.save_provider("codex", &codex_provider("oa", "OpenAI")).unwrap();
+
+        let entries: Vec<String> = ["claude=云雾", "codex=OpenAI", "gemini=Google"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let requests = parse_switch_map(&entries).unwrap();
+        let (targets, missing) = resolve_switch_map(&state, &requests).unwrap();
+
+        assert_eq!(
+            targets,
+            vec![(AppType::Claude, "yw".to_string()), (AppType::Codex, "oa".to_string())]
+        );
+        assert_eq!(missing, vec![(AppType::Gemini, "Google".to_string())]);
+    }
+
+    #[test]
+    fn test_parse_switch_map_rejects_bad_entries() {
+        let parse = |entries: &[&str]| {
+            parse_switch_map(&entries.iter().map(|s| s.to_string()).collect::<Vec<_>>())
+        };
+        assert!(parse(&["claude"]).is_err());
+        assert!(parse(&["unknown=x"]).is_err());
+        assert!(parse(&["claude="]).is_err());
+        assert!(parse(&["claude=a", "claude=b"]).is_err());
+    }
+}
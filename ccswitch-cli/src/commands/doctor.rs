@@ -0,0 +1,226 @@
+//! doctor 命令实现
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use ccswitch_core::{config, AppState, AppType, EnvCheckerService, ProviderService};
+use colored::Colorize;
+
+use crate::output::{print_header, sym, OutputContext};
+
+/// 检查结果状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+/// 单项检查
+#[derive(Debug)]
+struct DoctorCheck {
+    label: String,
+    status: CheckStatus,
+    detail: Option<String>,
+}
+
+impl DoctorCheck {
+    fn new(label: &str, status: CheckStatus, detail: impl Into<Option<String>>) -> Self {
+        Self {
+            label: label.to_string(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// 一组检查（通用或某个应用）
+#[derive(Debug)]
+struct DoctorSection {
+    title: String,
+    checks: Vec<DoctorCheck>,
+}
+
+/// 执行 doctor 命令
+pub fn run(_ctx: &OutputContext) -> Result<()> {
+    println!();
+    print_header("cc-switch 健康检查");
+
+    let mut sections = Vec::new();
+    let state = match AppState::init() {
+        Ok(state) => {
+            sections.push(DoctorSection {
+                title: "通用".to_string(),
+                checks: vec![DoctorCheck::new(
+                    "数据库",
+                    CheckStatus::Ok,
+                    config::get_app_config_dir().display().to_string(),
+                )],
+            });
+            Some(state)
+        }
+        Err(e) => {
+            sections.push(DoctorSection {
+                title: "通用".to_string(),
+                checks: vec![DoctorCheck::new("数据库", CheckStatus::Fail, e.to_string())],
+            });
+            None
+        }
+    };
+    if let Some(state) = &state {
+        sections.extend(app_sections(state));
+    }
+
+    for section in &sections {
+        println!();
+        println!("{}", format!("【{}】", section.title).bold());
+        for check in &section.checks {
+            let mark = match check.status {
+                CheckStatus::Ok => sym("✓").green(),
+                CheckStatus::Warn => sym("⚠").yellow(),
+                CheckStatus::Fail => sym("✗").red(),
+            };
+            match &check.detail {
+                Some(detail) => println!("  {} {} {}", mark, check.label, detail.dimmed()),
+                None => println!("  {} {}", mark, check.label),
+            }
+        }
+    }
+
+    let count = |status: CheckStatus| {
+        sections
+            .iter()
+            .flat_map(|s| &s.checks)
+            .filter(|c| c.status == status)
+            .count()
+    };
+    let (ok, warn, fail) = (
+        count(CheckStatus::Ok),
+        count(CheckStatus::Warn),
+        count(CheckStatus::Fail),
+    );
+
+    println!();
+    let summary = format!("通过 {} 项，警告 {} 项，失败 {} 项", ok, warn, fail);
+    if fail > 0 {
+        println!("{}", summary.red().bold());
+        println!();
+        bail!("健康检查发现 {} 个问题", fail);
+    }
+    if warn > 0 {
+        println!("{}", summary.yellow());
+    } else {
+        println!("{}", summary.green().bold());
+    }
+    println!();
+
+    Ok(())
+}
+
+/// 为每个应用生成一组检查
+fn app_sections(state: &AppState) -> Vec<DoctorSection> {
+    let env_results = EnvCheckerService::check_all();
+
+    AppType::all()
+        .iter()
+        .map(|&app_type| {
+            let mut checks = Vec::new();
+
+            let dir = app_config_dir(app_type);
+            checks.push(if dir.exists() {
+                DoctorCheck::new("配置目录", CheckStatus::Ok, dir.display().to_string())
+            } else {
+                DoctorCheck::new(
+                    "配置目录",
+                    CheckStatus::Warn,
+                    format!("{} 不存在", dir.display()),
+                )
+            });
+
+            let cmd = EnvCheckerService::tool_command(app_type);
+            let tool = EnvCheckerService::detect_tool(cmd);
+            checks.push(if tool.installed {
+                DoctorCheck::new("CLI 工具", CheckStatus::Ok, tool.version.unwrap_or_else(|| cmd.to_string()))
+            } else {
+                DoctorCheck::new("CLI 工具", CheckStatus::Warn, format!("未在 PATH 中找到 {}", cmd))
+            });
+
+            checks.push(match &env_results {
+                Ok(results) => {
+                    let conflicts: Vec<&str> = results
+                        .iter()
+                        .filter(|r| r.app == app_type)
+                        .flat_map(|r| r.conflicts.iter().map(|c| c.name.as_str()))
+                        .collect();
+                    if conflicts.is_empty() {
+                        DoctorCheck::new("环境变量", CheckStatus::Ok, None)
+                    } else {
+                        DoctorCheck::new(
+                            "环境变量",
+                            CheckStatus::Warn,
+                            format!("存在冲突: {}", conflicts.join(", ")),
+                        )
+                    }
+                }
+                Err(e) => DoctorCheck::new("环境变量", CheckStatus::Fail, e.to_string()),
+            });
+
+            checks.push(match ProviderService::live_matches_current(state, app_type) {
+                Ok(Some(true)) => DoctorCheck::new("live 配置", CheckStatus::Ok, "与当前供应商一致".to_string()),
+                Ok(Some(false)) => {
+                    let name = ProviderService::current_provider(state, app_type)
+                        .ok()
+                        .flatten()
+                        .map(|p| p.name)
+                        .unwrap_or_else(|| "<名称>".to_string());
+                    DoctorCheck::new(
+                        "live 配置",
+                        CheckStatus::Fail,
+                        format!(
+                            "与当前供应商不一致，可运行 cc-switch use '{}' --app {} 重新同步",
+                            name,
+                            app_type.as_str()
+                        ),
+                    )
+                }
+                Ok(None) if app_type.is_additive_mode() => {
+                    DoctorCheck::new("live 配置", CheckStatus::Ok, "累加模式，无需检查".to_string())
+                }
+                Ok(None) => DoctorCheck::new("live 配置", CheckStatus::Ok, "未设置当前供应商".to_string()),
+                Err(e) => DoctorCheck::new("live 配置", CheckStatus::Fail, e.to_string()),
+            });
+
+            DoctorSection {
+                title: app_type.display_name().to_string(),
+                checks,
+            }
+        })
+        .collect()
+}
+
+/// 应用的配置目录
+fn app_config_dir(app_type: AppType) -> PathBuf {
+    match app_type {
+        AppType::Claude => config::get_claude_config_dir(),
+        AppType::Codex => config::get_codex_config_dir(),
+        AppType::Gemini => config::get_gemini_config_dir(),
+        AppType::OpenCode => config::get_opencode_config_dir(),
+        AppType::OpenClaw => config::get_openclaw_config_dir(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_doctor_has_section_per_app() {
+        let state = AppState::memory().unwrap();
+        let sections = app_sections(&state);
+
+        let titles: Vec<&str> = sections.iter().map(|s| s.title.as_str()).collect();
+        let expected: Vec<&str> = AppType::all().iter().map(|a| a.display_name()).collect();
+        assert_eq!(titles, expected);
+        assert!(sections.iter().all(|s| s.checks.len() == 4));
+    }
+}
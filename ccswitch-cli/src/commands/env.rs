@@ -2,22 +2,59 @@
 //!
 //! 实现环境变量冲突检测的 CLI 命令。
 
-use anyhow::Result;
-use ccswitch_core::EnvCheckerService;
+use anyhow::{bail, Result};
+use ccswitch_core::services::env_checker::EnvSource;
+use ccswitch_core::{AppType, EnvCheckerService};
+use serde::Serialize;
+use std::io::{self, Write};
+
+use crate::cli::{AppTypeArg, OutputFormat};
+use crate::output::{glyphs, print_error, print_info, print_success, print_warning, OutputContext};
+
+/// 环境变量条目（JSON/YAML 输出）
+#[derive(Debug, Serialize)]
+pub struct EnvVarEntry {
+    pub app: String,
+    pub name: String,
+    pub value_masked: Option<String>,
+    pub set: bool,
+}
 
-use crate::cli::AppTypeArg;
-use crate::output::{print_info, print_success, print_warning, OutputContext};
+/// 收集指定应用的相关环境变量
+fn collect_env_vars(app_types: &[AppType]) -> Vec<EnvVarEntry> {
+    app_types
+        .iter()
+        .flat_map(|app_type| {
+            EnvCheckerService::list_env_vars(*app_type)
+                .into_iter()
+                .map(|(name, value)| EnvVarEntry {
+                    app: app_type.as_str().to_string(),
+                    name,
+                    set: value.is_some(),
+                    value_masked: value,
+                })
+        })
+        .collect()
+}
 
 /// 检查环境变量冲突
-pub fn check(_ctx: &OutputContext, app: AppTypeArg) -> Result<()> {
+pub fn check(_ctx: &OutputContext, app: AppTypeArg, fix: bool, yes: bool) -> Result<()> {
     let app_types = app.to_app_types();
 
-    println!("\n🔍 环境变量冲突检测\n");
+    println!("{}", glyphs("\n🔍 环境变量冲突检测\n"));
 
     let mut total_conflicts = 0;
+    let mut shell_conflict_apps = Vec::new();
 
     for app_type in app_types {
         let result = EnvCheckerService::check(app_type)?;
+        if result
+            .conflicts
+            .iter()
+            .any(|c| matches!(c.source, EnvSource::ShellConfig(_)))
+        {
+            shell_conflict_apps.push(app_type);
+        }
 
         if result.conflicts.is_empty() {
             print_success(&format!("{}: 无冲突", app_type.display_name()));
@@ -30,8 +67,8 @@ pub fn check(_ctx: &OutputContext, app: AppTypeArg) -> Result<()> {
 
             for conflict in &result.conflicts {
                 let source = match &conflict.source {
-                    ccswitch_core::services::env_checker::EnvSource::Process => "进程环境".to_string(),
-                    ccswitch_core::services::env_checker::EnvSource::ShellConfig(file) => {
+                    EnvSource::Process => "进程环境".to_string(),
+                    EnvSource::ShellConfig(file) => {
                         format!("Shell 配置 ({})", file)
                     }
                 };
@@ -57,14 +94,112 @@ pub fn check(_ctx: &OutputContext, app: AppTypeArg) -> Result<()> {
         print_info("这些环境变量可能会覆盖配置文件中的设置");
     }
 
+    if fix {
+        fix_conflicts(&shell_conflict_apps, yes)?;
+    }
+
+    Ok(())
+}
+
+/// 从备份恢复 Shell 配置
+pub fn restore(_ctx: &OutputContext, backup: Option<&str>, list: bool, yes: bool) -> Result<()> {
+    if list {
+        let backups = EnvCheckerService::list_backups()?;
+        if backups.is_empty() {
+            print_info("没有可用的备份");
+            return Ok(());
+        }
+        println!("{}", glyphs("\n📦 可用备份（1 为最新）\n"));
+        for (i, path) in backups.iter().enumerate() {
+            let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+            println!("  {:>3}. {}", i + 1, name);
+        }
+        println!();
+        return Ok(());
+    }
+
+    let backup_path = match EnvCheckerService::resolve_backup(backup) {
+        Ok(path) => path,
+        Err(e) => {
+            print_error(&e.to_string());
+            bail!("无法选择备份");
+        }
+    };
+
+    if !yes {
+        print!("将使用备份 {} 覆盖当前 Shell 配置，是否继续? [y/N] ", backup_path.display());
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        if !input.trim().eq_ignore_ascii_case("y") {
+            print_info("已取消");
+            return Ok(());
+        }
+    }
+
+    let files = EnvCheckerService::restore_backup(&backup_path)?;
+    print_success(&format!("已从 {} 恢复", backup_path.display()));
+    for file in &files {
+        println!("  - {}", file);
+    }
+    print_warning("请重启终端以使更改生效");
+
+    Ok(())
+}
+
+/// 备份 Shell 配置并移除冲突的环境变量
+fn fix_conflicts(apps: &[AppType], yes: bool) -> Result<()> {
+    if apps.is_empty() {
+        print_info("Shell 配置文件中没有需要清除的环境变量");
+        return Ok(());
+    }
+
+    let names: Vec<&str> = apps.iter().map(|a| a.display_name()).collect();
+    if !yes {
+        print!(
+            "将备份 Shell 配置并移除 {} 的冲突环境变量，是否继续? [y/N] ",
+            names.join(", ")
+        );
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        if !input.trim().eq_ignore_ascii_case("y") {
+            print_info("已取消");
+            return Ok(());
+        }
+    }
+
+    let report = EnvCheckerService::fix_conflicts(apps)?;
+    println!();
+    print_success(&format!("已创建备份: {}", report.backup_dir.display()));
+    for (app_type, files) in &report.modified {
+        if files.is_empty() {
+            continue;
+        }
+        println!("  {}: 已修改 {}", app_type.display_name(), files.join(", "));
+    }
+    print_warning("请重启终端以使更改生效（当前进程中的环境变量需手动 unset）");
+
     Ok(())
 }
 
 /// 列出相关环境变量
-pub fn list(_ctx: &OutputContext, app: AppTypeArg) -> Result<()> {
+pub fn list(ctx: &OutputContext, app: AppTypeArg) -> Result<()> {
     let app_types = app.to_app_types();
 
-    println!("\n📋 相关环境变量\n");
+    match ctx.format {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&collect_env_vars(&app_types))?);
+            return Ok(());
+        }
+        OutputFormat::Yaml => {
+            println!("{}", serde_yaml::to_string(&collect_env_vars(&app_types))?);
+            return Ok(());
+        }
+        OutputFormat::Table => {}
+    }
+
+    println!("{}", glyphs("\n📋 相关环境变量\n"));
 
     for app_type in app_types {
         println!("{}", app_type.display_name());
@@ -81,4 +216,28 @@ pub fn list(_ctx: &OutputContext, app: AppTypeArg) -> Result<()> {
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ccswitch_core::test_support::isolated_home;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn test_collect_env_vars_marks_set_entries() {
+        let _home = isolated_home();
+        std::env::set_var("OPENAI_ORG_ID", "org-1234567890");
+        let entries = collect_env_vars(&[AppType::Codex]);
+        std::env::remove_var("OPENAI_ORG_ID");
+
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["OPENAI_API_KEY", "OPENAI_BASE_URL", "OPENAI_ORG_ID"]);
+        assert!(entries.iter().all(|e| e.app == "codex"));
+
+        let org = entries.iter().find(|e| e.name == "OPENAI_ORG_ID").unwrap();
+        assert!(org.set);
+        assert_eq!(org.value_masked.as_deref(), Some("org-...7890"));
+    }
+}
@@ -1,21 +1,79 @@
 //! list 命令实现
 
 use anyhow::Result;
-use ccswitch_core::{AppState, ProviderService};
+use ccswitch_core::{AppState, ProviderService, ProviderTestResult};
+use chrono::{Local, TimeZone};
 
-use crate::cli::AppTypeArg;
-use crate::output::{format_status, mask_api_key, print_providers, truncate, OutputContext, ProviderRow};
+use crate::cli::{AppTypeArg, ListSortArg};
+use crate::output::{
+    format_additive_status, format_status, mask_api_key, print_providers, sym, truncate, OutputContext, ProviderDetailRow,
+    ProviderRow,
+};
 
 /// 执行 list 命令
-pub fn list_providers(ctx: &OutputContext, app: AppTypeArg, _detail: bool, show_key: bool) -> Result<()> {
+pub fn list_providers(
+    ctx: &OutputContext,
+    app: AppTypeArg,
+    detail: bool,
+    show_key: bool,
+    category: Option<&str>,
+    current_only: bool,
+    sort: Option<ListSortArg>,
+) -> Result<()> {
     let state = AppState::init()?;
-    let app_types = app.to_app_types();
+    let rows = build_rows(&state, app, show_key, category, current_only, sort)?;
 
-    let mut rows: Vec<ProviderRow> = Vec::new();
+    if detail {
+        print_providers(ctx, rows);
+    } else {
+        let rows: Vec<ProviderRow> = rows
+            .into_iter()
+            .map(|row| ProviderRow {
+                id: row.id,
+                name: row.name,
+                app: row.app,
+                status: row.status,
+                base_url: row.base_url,
+                api_key: row.api_key,
+            })
+            .collect();
+        print_providers(ctx, rows);
+    }
+
+    Ok(())
+}
+
+/// 汇总所选应用的供应商行
+///
+/// `current_only` 时每个应用只保留当前供应商，累加模式应用没有当前供应商，直接跳过。
+fn build_rows(
+    state: &AppState,
+    app: AppTypeArg,
+    show_key: bool,
+    category: Option<&str>,
+    current_only: bool,
+    sort: Option<ListSortArg>,
+) -> Result<Vec<ProviderDetailRow>> {
+    let mut rows: Vec<(Option<i64>, ProviderDetailRow)> = Vec::new();
 
-    for app_type in app_types {
-        let providers = ProviderService::list(&state, app_type.clone())?;
-        let current_id = ProviderService::current(&state, app_type.clone())?;
+    for app_type in app.to_app_types() {
+        let providers = if current_only {
+            if app_type.is_additive_mode() {
+                continue;
+            }
+            ProviderService::current_provider(state, app_type)?
+                .filter(|p| category.map_or(true, |c| p.category.as_deref() == Some(c)))
+                .map(|p| (p.id.clone(), p))
+                .into_iter()
+                .collect()
+        } else {
+            match category {
+                Some(category) => ProviderService::list_by_category(state, app_type, category)?,
+                None => ProviderService::list(state, app_type)?,
+            }
+        };
+        let current_id = ProviderService::current(state, app_type)?;
+        let test_results = state.db.get_latest_test_results(app_type.as_str())?;
 
         for (id, provider) in providers {
             let is_current = id == current_id;
@@ -30,18 +88,171 @@ pub fn list_providers(ctx: &OutputContext, app: AppTypeArg, _detail: bool, show_
                 "-".to_string()
             };
 
-            rows.push(ProviderRow {
+            let row = ProviderDetailRow {
                 id: id.clone(),
                 name: provider.name.clone(),
                 app: app_type.display_name().to_string(),
-                status: format_status(is_current),
+                status: if app_type.is_additive_mode() {
+                    format_additive_status()
+                } else {
+                    format_status(is_current)
+                },
+                category: provider.category.clone().unwrap_or_else(|| "-".to_string()),
                 base_url: truncate(&base_url, 40),
+                model: provider.get_model().unwrap_or_else(|| "-".to_string()),
                 api_key,
-            });
+                last_used: format_last_used(provider.last_used_at),
+                last_test: format_last_test(test_results.get(&id), chrono::Utc::now().timestamp_millis()),
+                notes: provider
+                    .notes
+                    .as_deref()
+                    .map(|n| truncate(&n.replace('\n', " "), 30))
+                    .unwrap_or_else(|| "-".to_string()),
+            };
+            rows.push((provider.last_used_at, row));
         }
     }
 
-    print_providers(ctx, rows);
+    if sort == Some(ListSortArg::LastUsed) {
+        // None 小于任何 Some，倒序后从未使用的排在最后
+        rows.sort_by_key(|(used, _)| std::cmp::Reverse(*used));
+    }
 
-    Ok(())
+    Ok(rows.into_iter().map(|(_, row)| row).collect())
+}
+
+/// 格式化最近使用时间（毫秒时间戳）
+fn format_last_used(timestamp: Option<i64>) -> String {
+    timestamp
+        .and_then(|ms| Local.timestamp_millis_opt(ms).single())
+        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// 格式化最近一次保存的测试结果：状态、延迟与距今时间
+fn format_last_test(result: Option<&ProviderTestResult>, now_ms: i64) -> String {
+    let Some(result) = result else {
+        return "-".to_string();
+    };
+    let status = if result.ok { sym("✓") } else { sym("✗") };
+    let latency = result.latency_ms.map(|ms| format!(" {}ms", ms)).unwrap_or_default();
+    format!("{}{} · {}", status, latency, format_age(now_ms - result.tested_at))
+}
+
+/// 格式化距今时长（毫秒）
+fn format_age(elapsed_ms: i64) -> String {
+    let minutes = elapsed_ms.max(0) / 60_000;
+    match minutes {
+        0 => "刚刚".to_string(),
+        1..=59 => format!("{} 分钟前", minutes),
+        60..=1439 => format!("{} 小时前", minutes / 60),
+        _ => format!("{} 天前", minutes / 1440),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{config::DEFAULT_APP_SETTING, resolve_app_with};
+    use ccswitch_core::Provider;
+    use serde_json::json;
+
+    #[test]
+    fn test_list_without_app_uses_default_app_setting() {
+        let state = AppState::memory().unwrap();
+        state
+            .db
+            .save_provider("claude", &Provider::new("c1", "c1", json!({})))
+            .unwrap();
+        state
+            .db
+            .save_provider("codex", &Provider::new("x1", "x1", json!({})))
+            .unwrap();
+        state.db.set_setting(DEFAULT_APP_SETTING, "codex").unwrap();
+
+        let app = resolve_app_with(&state.db, AppTypeArg::Default, AppTypeArg::All);
+        assert_eq!(app, AppTypeArg::Codex);
+        let rows = build_rows(&state, app, false, None, false, None).unwrap();
+        let ids: Vec<&str> = rows.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["x1"]);
+
+        // 未设置时回退到命令自身的默认值
+        state.db.delete_setting(DEFAULT_APP_SETTING).unwrap();
+        assert_eq!(
+            resolve_app_with(&state.db, AppTypeArg::Default, AppTypeArg::All),
+            AppTypeArg::All
+        );
+        assert_eq!(
+            resolve_app_with(&state.db, AppTypeArg::Gemini, AppTypeArg::All),
+            AppTypeArg::Gemini
+        );
+    }
+
+    #[test]
+    fn test_list_current_only_omits_other_providers() {
+        let state = AppState::memory().unwrap();
+        for (app, id) in [("claude", "c1"), ("claude", "c2"), ("codex", "x1"), ("codex", "x2")] {
+            state
+                .db
+                .save_provider(app, &Provider::new(id, id, json!({})))
+                .unwrap();
+        }
+        state.db.set_current_provider("claude", "c2").unwrap();
+        state.db.set_current_provider("codex", "x1").unwrap();
+        state
+            .db
+            .save_provider("opencode", &Provider::new("o1", "o1", json!({})))
+            .unwrap();
+
+        let rows = build_rows(&state, AppTypeArg::All, false, None, true, None).unwrap();
+        let ids: Vec<&str> = rows.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["c2", "x1"]);
+    }
+
+    #[test]
+    fn test_list_sort_last_used_puts_most_recent_first() {
+        let state = AppState::memory().unwrap();
+        for id in ["a", "b", "c"] {
+            state
+                .db
+                .save_provider("claude", &Provider::new(id, id, json!({})))
+                .unwrap();
+        }
+        state.db.set_provider_last_used("claude", "a", 1_000).unwrap();
+        state.db.set_provider_last_used("claude", "c", 2_000).unwrap();
+
+        let sort = Some(ListSortArg::LastUsed);
+        let rows = build_rows(&state, AppTypeArg::Claude, false, None, false, sort).unwrap();
+        let ids: Vec<&str> = rows.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["c", "a", "b"]);
+        assert_eq!(rows[2].last_used, "-");
+        assert_ne!(rows[0].last_used, "-");
+    }
+
+    #[test]
+    fn test_detail_rows_show_latest_saved_test_result() {
+        let state = AppState::memory().unwrap();
+        for id in ["a", "b"] {
+            state
+                .db
+                .save_provider("claude", &Provider::new(id, id, json!({})))
+                .unwrap();
+        }
+        let now = chrono::Utc::now().timestamp_millis();
+        let saved = |ok, latency_ms, tested_at| ProviderTestResult {
+            provider_id: "a".to_string(),
+            ok,
+            latency_ms,
+            message: String::new(),
+            tested_at,
+        };
+        state.db.save_provider_test_result("claude", &saved(false, None, now - 86_400_000)).unwrap();
+        state.db.save_provider_test_result("claude", &saved(true, Some(120), now - 3 * 3_600_000)).unwrap();
+
+        let rows = build_rows(&state, AppTypeArg::Claude, false, None, false, None).unwrap();
+        assert_eq!(rows[0].last_test, "✓ 120ms · 3 小时前");
+        assert_eq!(rows[1].last_test, "-");
+        assert_eq!(format_age(90 * 60_000), "1 小时前");
+        assert_eq!(format_age(-5), "刚刚");
+    }
 }
@@ -2,12 +2,57 @@
 //!
 //! 实现 MCP 服务器管理的 CLI 命令。
 
+use std::path::PathBuf;
+
 use anyhow::{bail, Result};
-use ccswitch_core::{AppState, AppType, McpServer, McpService, McpStdioConfig};
+use ccswitch_core::services::mcp::McpBundle;
+use ccswitch_core::{AppError, AppState, AppType, McpScope, McpServer, McpService, McpStdioConfig};
+use indexmap::IndexMap;
+use serde::Serialize;
 use serde_json::json;
 
-use crate::cli::AppTypeArg;
-use crate::output::{print_error, print_info, print_success, print_warning, OutputContext};
+use crate::cli::{AppTypeArg, OutputFormat};
+use crate::output::{
+    glyphs, mask_api_key, print_error, print_info, print_success, print_warning, sym, OutputContext,
+};
+
+/// 检查所有 MCP 服务器，存在问题时返回错误
+pub fn validate(ctx: &OutputContext) -> Result<()> {
+    let state = AppState::init()?;
+    let results = McpService::validate_all(&state)?;
+    let failed = results.iter().filter(|r| !r.passed()).count();
+
+    match ctx.format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&results)?),
+        OutputFormat::Yaml => println!("{}", serde_yaml::to_string(&results)?),
+        OutputFormat::Table => {
+            if results.is_empty() {
+                print_info("暂无 MCP 服务器配置");
+                return Ok(());
+            }
+            for result in &results {
+                if result.passed() {
+                    print_success(&format!("{} ({})", result.id, result.transport));
+                } else {
+                    print_error(&format!("{} ({})", result.id, result.transport));
+                    for problem in &result.problems {
+                        println!("    - {}", problem);
+                    }
+                }
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(AppError::McpValidation(format!(
+            "{} / {} 个 MCP 服务器未通过检查",
+            failed,
+            results.len()
+        ))
+        .into());
+    }
+    Ok(())
+}
 
 /// 列出所有 MCP 服务器
 pub fn list(_ctx: &OutputContext, app: AppTypeArg, detail: bool) -> Result<()> {
@@ -37,7 +82,7 @@ pub fn list(_ctx: &OutputContext, app: AppTypeArg, detail: bool) -> Result<()> {
         return Ok(());
     }
 
-    println!("\n📦 MCP 服务器列表 ({} 个)\n", filtered.len());
+    println!("{}", glyphs(&format!("\n📦 MCP 服务器列表 ({} 个)\n", filtered.len())));
     println!("{:<20} {:<20} {:<30}", "ID", "名称", "启用的应用");
     println!("{}", "-".repeat(70));
 
@@ -65,48 +110,203 @@ pub fn list(_ctx: &OutputContext, app: AppTypeArg, detail: bool) -> Result<()> {
     }
 
     if !detail {
-        println!("\n💡 使用 --detail 查看详细配置");
+        println!("{}", glyphs("\n💡 使用 --detail 查看详细配置"));
     }
 
     Ok(())
 }
 
+/// MCP 服务器完整详情
+#[derive(Debug, Serialize)]
+pub struct McpServerDetail {
+    pub id: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub docs: Option<String>,
+    pub tags: Vec<String>,
+    pub transport: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    pub args: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    pub headers: IndexMap<String, String>,
+    pub env: IndexMap<String, String>,
+    pub enabled: bool,
+    pub apps: IndexMap<String, bool>,
+    pub synced_files: IndexMap<String, String>,
+}
+
+/// 判断键名是否像敏感信息
+pub(crate) fn is_secret_key(key: &str) -> bool {
+    let upper = key.to_uppercase();
+    ["KEY", "TOKEN", "SECRET", "PASSWORD", "AUTH", "CREDENTIAL"]
+        .iter()
+        .any(|k| upper.contains(k))
+}
+
+/// 读取字符串映射，敏感值脱敏
+fn masked_map(value: Option<&serde_json::Value>) -> IndexMap<String, String> {
+    value
+        .and_then(|v| v.as_object())
+        .map(|obj| {
+            obj.iter()
+                .map(|(k, v)| {
+                    let raw = v.as_str().map(str::to_string).unwrap_or_else(|| v.to_string());
+                    let shown = if is_secret_key(k) { mask_api_key(&raw) } else { raw };
+                    (k.clone(), shown)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 解析服务器配置为完整详情
+fn build_detail(server: &McpServer, synced: &[(AppType, PathBuf)]) -> McpServerDetail {
+    let config = &server.server_config;
+    let url = config.get("url").and_then(|v| v.as_str()).map(str::to_string);
+    let transport = config
+        .get("type")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .unwrap_or_else(|| if url.is_some() { "http" } else { "stdio" }.to_string());
+
+    McpServerDetail {
+        id: server.id.clone(),
+        name: server.name.clone(),
+        description: server.description.clone(),
+        homepage: server.homepage.clone(),
+        docs: server.docs.clone(),
+        tags: server.tags.clone(),
+        transport,
+        command: config.get("command").and_then(|v| v.as_str()).map(str::to_string),
+        args: config
+            .get("args")
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().filter_map(|v| v.as_str().map(str::to_string)).collect())
+            .unwrap_or_default(),
+        url,
+        headers: masked_map(config.get("headers")),
+        env: masked_map(config.get("env")),
+        enabled: server.enabled,
+        apps: AppType::all()
+            .iter()
+            .filter(|a| a.supports_mcp())
+            .map(|a| (a.as_str().to_string(), server.apps.is_enabled_for(a)))
+            .collect(),
+        synced_files: synced
+            .iter()
+            .map(|(a, p)| (a.as_str().to_string(), p.display().to_string()))
+            .collect(),
+    }
+}
+
 /// 显示单个 MCP 服务器详情
-pub fn show(_ctx: &OutputContext, id: &str) -> Result<()> {
+pub fn show(ctx: &OutputContext, id: &str, detail: bool) -> Result<()> {
     let state = AppState::init()?;
     let server = McpService::get(&state, id)?;
 
-    match server {
-        Some(s) => {
-            println!("\n📦 MCP 服务器: {}\n", s.name);
-            println!("ID:       {}", s.id);
-            println!("名称:     {}", s.name);
-            println!("启用应用: {}", s.enabled_apps_str());
+    let Some(s) = server else {
+        print_error(&format!("MCP 服务器 '{}' 不存在", id));
+        bail!("服务器不存在")
+    };
+
+    if detail || ctx.format != OutputFormat::Table {
+        let info = build_detail(&s, &McpService::synced_files(&s.id));
+        return print_detail(ctx, &info);
+    }
+
+    println!("{}", glyphs(&format!("\n📦 MCP 服务器: {}\n", s.name)));
+    println!("ID:       {}", s.id);
+    println!("名称:     {}", s.name);
+    println!("启用应用: {}", s.enabled_apps_str());
+
+    if let Some(desc) = &s.description {
+        println!("描述:     {}", desc);
+    }
+    if let Some(homepage) = &s.homepage {
+        println!("主页:     {}", homepage);
+    }
+    if !s.tags.is_empty() {
+        println!("标签:     {}", s.tags.join(", "));
+    }
+
+    println!("\n配置:");
+    let config_str = serde_json::to_string_pretty(&s.server_config)?;
+    println!("{}", config_str);
+    println!("{}", glyphs("\n💡 使用 --detail 查看完整解析结果"));
+
+    Ok(())
+}
 
-            if let Some(desc) = &s.description {
+/// 输出服务器完整详情
+fn print_detail(ctx: &OutputContext, info: &McpServerDetail) -> Result<()> {
+    match ctx.format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(info)?),
+        OutputFormat::Yaml => println!("{}", serde_yaml::to_string(info)?),
+        OutputFormat::Table => {
+            println!("{}", glyphs(&format!("\n📦 MCP 服务器: {}\n", info.name)));
+            println!("ID:       {}", info.id);
+            if let Some(desc) = &info.description {
                 println!("描述:     {}", desc);
             }
-            if let Some(homepage) = &s.homepage {
+            if let Some(homepage) = &info.homepage {
                 println!("主页:     {}", homepage);
             }
-            if !s.tags.is_empty() {
-                println!("标签:     {}", s.tags.join(", "));
+            if let Some(docs) = &info.docs {
+                println!("文档:     {}", docs);
+            }
+            if !info.tags.is_empty() {
+                println!("标签:     {}", info.tags.join(", "));
             }
+            println!("传输方式: {}", info.transport);
 
-            println!("\n配置:");
-            let config_str = serde_json::to_string_pretty(&s.server_config)?;
-            println!("{}", config_str);
+            if let Some(cmd) = &info.command {
+                println!("命令:     {} {}", cmd, info.args.join(" "));
+            }
+            if let Some(url) = &info.url {
+                println!("URL:      {}", url);
+            }
+            if !info.headers.is_empty() {
+                println!("\n请求头:");
+                for (k, v) in &info.headers {
+                    println!("  {} = {}", k, v);
+                }
+            }
+            if !info.env.is_empty() {
+                println!("\n环境变量:");
+                for (k, v) in &info.env {
+                    println!("  {} = {}", k, v);
+                }
+            }
 
-            Ok(())
-        }
-        None => {
-            print_error(&format!("MCP 服务器 '{}' 不存在", id));
-            bail!("服务器不存在")
+            if !info.enabled {
+                println!("\n全局状态: 已禁用（不会同步到任何应用）");
+            }
+            println!("\n应用状态:");
+            for (app, enabled) in &info.apps {
+                let status = if *enabled { format!("{} 启用", sym("✓")) } else { format!("{} 禁用", sym("✗")) };
+                println!("  {:<10} {}", app, status);
+            }
+
+            println!("\n已同步到:");
+            if info.synced_files.is_empty() {
+                println!("  (无)");
+            }
+            for (app, path) in &info.synced_files {
+                println!("  {:<10} {}", app, path);
+            }
         }
     }
+    Ok(())
 }
 
 /// 添加 MCP 服务器
+#[allow(clippy::too_many_arguments)]
 pub fn add(
     _ctx: &OutputContext,
     id: &str,
@@ -115,6 +315,7 @@ pub fn add(
     env: Vec<String>,
     name: Option<String>,
     description: Option<String>,
+    disabled: bool,
 ) -> Result<()> {
     let state = AppState::init()?;
 
@@ -122,12 +323,15 @@ pub fn add(
     let mut config = McpStdioConfig::new(command).with_args(args);
 
     // 解析环境变量
-    for env_str in env {
-        if let Some((key, value)) = env_str.split_once('=') {
-            config = config.with_env(key, value);
-        } else {
-            print_warning(&format!("忽略无效的环境变量格式: {}", env_str));
+    let env = match McpStdioConfig::parse_env(env) {
+        Ok(env) => env,
+        Err(e) => {
+            print_error(&e.to_string());
+            bail!("环境变量格式错误");
         }
+    };
+    for (key, value) in env {
+        config = config.with_env(key, value);
     }
 
     let display_name = name.unwrap_or_else(|| id.to_string());
@@ -136,15 +340,88 @@ pub fn add(
     if let Some(desc) = description {
         server = server.with_description(desc);
     }
+    server.enabled = !disabled;
 
     McpService::add(&state, server)?;
 
     print_success(&format!("已添加 MCP 服务器: {}", display_name));
+    if disabled {
+        print_info(&format!("该服务器已全局禁用，使用 'cc-switch mcp toggle {} --global --enable' 启用", id));
+    } else {
+        print_info("使用 'cc-switch mcp toggle <id> --app <app> --enable' 启用");
+    }
+
+    Ok(())
+}
+
+/// 从 JSON 文件导入原始服务器配置（`mcp add --from-json`）
+pub fn add_from_json(
+    _ctx: &OutputContext,
+    path: &str,
+    id: Option<&str>,
+    name: Option<String>,
+    description: Option<String>,
+) -> Result<()> {
+    let state = AppState::init()?;
+    let added = match add_servers_from_file(&state, path, id, name, description) {
+        Ok(added) => added,
+        Err(e) => {
+            print_error(&e.to_string());
+            bail!("导入 MCP 服务器失败");
+        }
+    };
+
+    for server in &added {
+        let transport = server
+            .server_config
+            .get("type")
+            .and_then(|v| v.as_str())
+            .unwrap_or("stdio");
+        print_success(&format!("已添加 MCP 服务器: {} ({})", server.name, transport));
+    }
     print_info("使用 'cc-switch mcp toggle <id> --app <app> --enable' 启用");
 
     Ok(())
 }
 
+/// 读取并校验 JSON 文件中的服务器，全部通过且 ID 均未占用后才写入
+fn add_servers_from_file(
+    state: &AppState,
+    path: &str,
+    id: Option<&str>,
+    name: Option<String>,
+    description: Option<String>,
+) -> Result<Vec<McpServer>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("读取 {} 失败: {}", path, e))?;
+    let value: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| anyhow::anyhow!("{} 不是有效的 JSON: {}", path, e))?;
+
+    let mut servers = McpService::parse_servers_json(&value, id)?;
+    if let [server] = servers.as_mut_slice() {
+        if let Some(name) = name {
+            server.name = name;
+        }
+        if let Some(desc) = description {
+            server.description = Some(desc);
+        }
+    }
+
+    let existing: Vec<&str> = servers
+        .iter()
+        .filter(|s| McpService::get(state, &s.id).ok().flatten().is_some())
+        .map(|s| s.id.as_str())
+        .collect();
+    if !existing.is_empty() {
+        bail!("MCP 服务器已存在: {}", existing.join(", "));
+    }
+
+    for server in &servers {
+        McpService::add(state, server.clone())?;
+    }
+    Ok(servers)
+}
+
 /// 更新 MCP 服务器
 pub fn update(
     _ctx: &OutputContext,
@@ -219,43 +496,94 @@ pub fn remove(_ctx: &OutputContext, id: &str, yes: bool) -> Result<()> {
 }
 
 /// 切换 MCP 服务器的应用启用状态
-pub fn toggle(_ctx: &OutputContext, id: &str, app: AppTypeArg, enable: bool) -> Result<()> {
+pub fn toggle(_ctx: &OutputContext, id: &str, app: AppTypeArg, enable: bool, scope: McpScope) -> Result<()> {
     let state = AppState::init()?;
 
     let app_types = app.to_app_types();
-    if app_types.is_empty() || matches!(app, AppTypeArg::All) {
-        print_error("请指定具体的应用类型 (claude/codex/gemini/opencode)");
+    if app_types.is_empty() {
+        print_error("请指定应用类型 (claude/codex/gemini/opencode/all)");
         bail!("需要指定应用类型")
     }
 
-    let app_type = app_types[0];
+    // 项目级启用全局禁用的服务器会直接报错；用户级仍记录启用状态，但不会写入应用配置
+    if scope == McpScope::User && enable && McpService::get(&state, id)?.is_some_and(|s| !s.enabled) {
+        print_warning(&format!(
+            "该服务器已全局禁用，不会写入应用配置；使用 'cc-switch mcp toggle {} --global --enable' 启用",
+            id
+        ));
+    }
 
-    McpService::toggle(&state, id, app_type, enable)?;
+    let toggled = McpService::toggle_apps_in_scope(&state, id, &app_types, enable, scope)?;
+    if toggled.is_empty() {
+        print_error("所选应用不支持 MCP");
+        bail!("应用不支持 MCP");
+    }
 
     let action = if enable { "启用" } else { "禁用" };
-    print_success(&format!(
-        "已为 {} {} MCP 服务器: {}",
-        app_type.display_name(),
-        action,
-        id
+    let names: Vec<&str> = toggled.iter().map(|a| a.display_name()).collect();
+    print_success(&format!("已为 {} {} MCP 服务器: {}", names.join(", "), action, id));
+    if scope == McpScope::Project {
+        for app in &toggled {
+            if let Ok(Some(path)) = McpService::scoped_config_path(app, scope) {
+                print_info(&format!("已写入项目配置: {}", path.display()));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// 切换 MCP 服务器的全局启用状态（`mcp toggle --global`）
+pub fn toggle_global(_ctx: &OutputContext, id: &str, enable: bool) -> Result<()> {
+    let state = AppState::init()?;
+
+    let server = McpService::set_enabled(&state, id, enable)?;
+
+    if enable {
+        print_success(&format!("已全局启用 MCP 服务器: {}", id));
+        print_info(&format!("已同步到: {}", server.enabled_apps_str()));
+    } else {
+        print_success(&format!("已全局禁用 MCP 服务器: {}", id));
+        print_info("各应用的启用状态已保留，重新启用后将恢复同步");
+    }
+
+    Ok(())
+}
+
+/// 复制 MCP 服务器
+pub fn duplicate(_ctx: &OutputContext, id: &str, new_id: &str) -> Result<()> {
+    let state = AppState::init()?;
+
+    let server = McpService::duplicate(&state, id, new_id)?;
+
+    print_success(&format!("已复制 MCP 服务器 '{}' 为 '{}'", id, server.id));
+    print_info(&format!(
+        "使用 'cc-switch mcp toggle {} --app <app> --enable' 启用",
+        server.id
     ));
 
     Ok(())
 }
 
 /// 从应用导入 MCP 服务器
-pub fn import(_ctx: &OutputContext, app: Option<AppTypeArg>) -> Result<()> {
+pub fn import(_ctx: &OutputContext, app: Option<AppTypeArg>, enable: bool, also: Vec<AppTypeArg>) -> Result<()> {
     let state = AppState::init()?;
 
     let apps = match app {
         Some(a) => a.to_app_types(),
         None => AppType::all().to_vec(),
     };
+    let also: Vec<AppType> = also.iter().flat_map(|a| a.to_app_types()).collect();
 
     let mut total_imported = 0;
 
     for app_type in apps {
-        match McpService::import_from_app(&state, app_type) {
+        let result = if enable {
+            McpService::import_and_enable(&state, app_type, &also)
+        } else {
+            McpService::import_from_app(&state, app_type)
+        };
+        match result {
             Ok(imported) => {
                 if !imported.is_empty() {
                     print_success(&format!(
@@ -264,6 +592,14 @@ pub fn import(_ctx: &OutputContext, app: Option<AppTypeArg>) -> Result<()> {
                         imported.len(),
                         imported.join(", ")
                     ));
+                    if enable {
+                        let names: Vec<&str> = std::iter::once(app_type)
+                            .chain(also.iter().copied())
+                            .filter(|a| a.supports_mcp())
+                            .map(|a| a.display_name())
+                            .collect();
+                        print_info(&format!("已启用并同步到: {}", names.join(", ")));
+                    }
                     total_imported += imported.len();
                 }
             }
@@ -278,4 +614,144 @@ pub fn import(_ctx: &OutputContext, app: Option<AppTypeArg>) -> Result<()> {
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// 导出所有 MCP 服务器
+pub fn export(ctx: &OutputContext, output: Option<&str>) -> Result<()> {
+    let state = AppState::init()?;
+    let bundle = McpService::export_bundle(&state)?;
+
+    let yaml = match output {
+        Some(path) => is_yaml_path(path),
+        None => ctx.format == OutputFormat::Yaml,
+    };
+    let content = if yaml {
+        serde_yaml::to_string(&bundle)?
+    } else {
+        serde_json::to_string_pretty(&bundle)?
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, content)?;
+            print_success(&format!("已导出 {} 个 MCP 服务器到 {}", bundle.servers.len(), path));
+        }
+        None => println!("{}", content),
+    }
+
+    Ok(())
+}
+
+/// 从 mcp export 生成的文件导入 MCP 服务器
+pub fn import_file(_ctx: &OutputContext, file: &str, overwrite: bool) -> Result<()> {
+    let state = AppState::init()?;
+
+    let content = std::fs::read_to_string(file)?;
+    let parsed = if is_yaml_path(file) {
+        serde_yaml::from_str::<McpBundle>(&content).map_err(anyhow::Error::from)
+    } else {
+        serde_json::from_str::<McpBundle>(&content).map_err(anyhow::Error::from)
+    };
+    let bundle = match parsed {
+        Ok(bundle) => bundle,
+        Err(e) => {
+            print_error(&format!("无法解析 MCP 导出文件: {}", e));
+            bail!("导入失败");
+        }
+    };
+
+    let report = McpService::import_bundle(&state, bundle, overwrite)?;
+    if !report.added.is_empty() {
+        print_success(&format!("新增 {} 个: {}", report.added.len(), report.added.join(", ")));
+    }
+    if !report.overwritten.is_empty() {
+        print_success(&format!(
+            "覆盖 {} 个: {}",
+            report.overwritten.len(),
+            report.overwritten.join(", ")
+        ));
+    }
+    if !report.skipped.is_empty() {
+        print_warning(&format!(
+            "跳过已存在的 {} 个（使用 --overwrite 覆盖）: {}",
+            report.skipped.len(),
+            report.skipped.join(", ")
+        ));
+    }
+    if report.added.is_empty() && report.overwritten.is_empty() && report.skipped.is_empty() {
+        print_info("导出文件中没有 MCP 服务器");
+    }
+
+    Ok(())
+}
+
+fn is_yaml_path(path: &str) -> bool {
+    let lower = path.to_lowercase();
+    lower.ends_with(".yaml") || lower.ends_with(".yml")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_detail_masks_secrets() {
+        let config = McpStdioConfig::new("npx")
+            .with_args(vec!["-y".to_string(), "@test/server".to_string()])
+            .with_env("GITHUB_TOKEN", "ghp_1234567890abcdef")
+            .with_env("LOG_LEVEL", "debug");
+        let mut server = McpServer::new("github", "GitHub", config.to_json());
+        server.apps.set_enabled_for(&AppType::Claude, true);
+        server.apps.set_enabled_for(&AppType::Gemini, true);
+
+        let synced = vec![(AppType::Claude, PathBuf::from("/tmp/.claude.json"))];
+        let detail = build_detail(&server, &synced);
+
+        assert_eq!(detail.transport, "stdio");
+        assert_eq!(detail.command.as_deref(), Some("npx"));
+        assert_eq!(detail.args, vec!["-y", "@test/server"]);
+        assert_eq!(detail.env["LOG_LEVEL"], "debug");
+        assert_ne!(detail.env["GITHUB_TOKEN"], "ghp_1234567890abcdef");
+        assert!(detail.env["GITHUB_TOKEN"].contains("..."));
+
+        assert!(detail.apps["claude"]);
+        assert!(!detail.apps["codex"]);
+        assert!(detail.apps["gemini"]);
+        assert!(!detail.apps.contains_key("openclaw"));
+        assert_eq!(detail.synced_files["claude"], "/tmp/.claude.json");
+    }
+
+    #[test]
+    fn test_add_servers_from_json_file() {
+        let state = AppState::memory().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+
+        let map = dir.path().join("mcp.json");
+        std::fs::write(
+            &map,
+            r#"{"mcpServers": {
+                "fs": {"command": "npx", "args": ["-y", "@mcp/fs"], "env": {"ROOT": "/tmp"}},
+                "remote": {"url": "https://mcp.example.com/mcp"}
+            }}"#,
+        )
+        .unwrap();
+        let added = add_servers_from_file(&state, map.to_str().unwrap(), None, None, None).unwrap();
+        assert_eq!(added.len(), 2);
+
+        let fs = McpService::get(&state, "fs").unwrap().unwrap();
+        assert_eq!(build_detail(&fs, &[]).transport, "stdio");
+        let remote = McpService::get(&state, "remote").unwrap().unwrap();
+        assert_eq!(remote.server_config["type"], "http");
+
+        // 单个对象需要 ID；已存在的 ID 整体拒绝
+        let single = dir.path().join("server.json");
+        std::fs::write(&single, r#"{"url": "http://localhost:3000/sse", "type": "sse"}"#).unwrap();
+        let path = single.to_str().unwrap();
+        assert!(add_servers_from_file(&state, path, None, None, None).is_err());
+        assert!(add_servers_from_file(&state, path, Some("fs"), None, None).is_err());
+        let added =
+            add_servers_from_file(&state, path, Some("local"), Some("Local".to_string()), None).unwrap();
+        assert_eq!(added[0].name, "Local");
+        assert_eq!(McpService::list(&state).unwrap().len(), 3);
+    }
+}
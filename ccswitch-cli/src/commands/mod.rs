@@ -4,6 +4,7 @@
 
 pub mod batch;
 pub mod config;
+pub mod doctor;
 pub mod env;
 pub mod failover;
 pub mod list;
@@ -12,32 +13,125 @@ pub mod openclaw;
 pub mod prompt;
 pub mod provider;
 pub mod proxy;
+pub mod prune;
+pub mod retry;
 pub mod skill;
 pub mod speedtest;
 pub mod status;
+pub mod summary;
 pub mod update;
 pub mod usage;
+pub mod version;
 pub mod webdav;
 
 // Re-export update types for use in main
 pub use update::check_on_startup;
 
 use anyhow::Result;
+use ccswitch_core::profile::{set_active_profile, DEFAULT_PROFILE};
+use ccswitch_core::{AppError, AppState, Database, ProfileService};
+use clap::ValueEnum;
 
-use crate::cli::{Cli, Commands, BatchAction, EnvAction, FailoverAction, McpAction, OpenclawAction, PromptAction, ProxyAction, SkillAction, SelfUpdateAction, UsageAction, WebdavAction};
-use crate::output::OutputContext;
+use crate::cli::{AppTypeArg, Cli, Commands, BatchAction, EnvAction, FailoverAction, McpAction, OpenclawAction, PromptAction, ProxyAction, SkillAction, SelfUpdateAction, UsageAction, WebdavAction};
+use crate::output::{glyphs, OutputContext, Theme, THEME_SETTING};
+
+/// 退出码：一般错误
+pub const EXIT_FAILURE: i32 = 1;
+/// 退出码：供应商等对象不存在（避开 clap 用法错误使用的 2）
+pub const EXIT_NOT_FOUND: i32 = 5;
+/// 退出码：输入或配置校验失败
+pub const EXIT_INVALID: i32 = 3;
+/// 退出码：网络请求失败
+pub const EXIT_NETWORK: i32 = 4;
+
+/// 按错误链中的 `AppError` 变体映射退出码
+pub fn exit_code(err: &anyhow::Error) -> i32 {
+    for cause in err.chain() {
+        if let Some(app_err) = cause.downcast_ref::<AppError>() {
+            return match app_err {
+                AppError::ProviderNotFound(_) | AppError::NoProvidersConfigured | AppError::NotFound(_) => {
+                    EXIT_NOT_FOUND
+                }
+                AppError::InvalidInput(_) | AppError::McpValidation(_) | AppError::Validation(_) => EXIT_INVALID,
+                AppError::Http(_) | AppError::Network(_) => EXIT_NETWORK,
+                _ => EXIT_FAILURE,
+            };
+        }
+        if cause.downcast_ref::<reqwest::Error>().is_some() {
+            return EXIT_NETWORK;
+        }
+    }
+    EXIT_FAILURE
+}
+
+/// 校验并激活 `--profile` 指定的 profile，此后打开的数据库均作用于该 profile
+pub fn activate_profile(profile: Option<&str>) -> Result<()> {
+    let Some(name) = profile.filter(|p| *p != DEFAULT_PROFILE) else {
+        return Ok(());
+    };
+    let state = AppState::init()?;
+    if !ProfileService::exists(&state, name)? {
+        return Err(AppError::InvalidInput(format!(
+            "profile '{}' 不存在，请先运行: cc-switch config profile create {}",
+            name, name
+        ))
+        .into());
+    }
+    set_active_profile(Some(name));
+    Ok(())
+}
+
+/// 读取 `config set theme` 的设置
+///
+/// 数据库尚未创建或无法读取时为深色主题，不会因此创建数据库。
+pub fn configured_theme() -> Theme {
+    if !ccswitch_core::config::get_database_path().exists() {
+        return Theme::Dark;
+    }
+    AppState::init()
+        .ok()
+        .and_then(|state| state.db.get_setting(THEME_SETTING).ok().flatten())
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(Theme::Dark)
+}
 
 /// 执行 CLI 命令
 pub fn execute(cli: Cli) -> Result<()> {
-    let ctx = OutputContext::new(cli.format, cli.no_color);
+    activate_profile(cli.profile.as_deref())?;
+    let theme = if cli.no_color { Theme::None } else { configured_theme() };
+    let ctx = OutputContext::new(cli.format, cli.no_color, cli.quiet, theme);
 
     // command 现在是 Option，但在 main.rs 中已经处理了 None 的情况
     let command = cli.command.expect("command should be Some when execute is called");
 
     match command {
-        Commands::List { app, detail, show_key } => list::list_providers(&ctx, app, detail, show_key),
-        Commands::Status { app } => status::show_status(&ctx, app),
-        Commands::Use { name, app } => provider::switch(&ctx, &name, app),
+        Commands::List {
+            app,
+            detail,
+            show_key,
+            category,
+            current_only,
+            sort,
+        } => list::list_providers(
+            &ctx,
+            resolve_app(app, AppTypeArg::All),
+            detail,
+            show_key,
+            category.as_deref(),
+            current_only,
+            sort,
+        ),
+        Commands::Status { app, watch: false, .. } => status::show_status(&ctx, resolve_app(app, AppTypeArg::All)),
+        Commands::Status { app, interval, .. } => status::watch_status(&ctx, resolve_app(app, AppTypeArg::All), interval),
+        Commands::Summary => summary::show_summary(&ctx),
+        Commands::Doctor => doctor::run(&ctx),
+        Commands::Use { name, previous, app, force } => {
+            let app = resolve_app(app, AppTypeArg::Claude);
+            match name {
+                Some(name) if !previous && name != "-" => provider::switch(&ctx, &name, app, force),
+                _ => provider::switch_previous(&ctx, app, force),
+            }
+        }
         Commands::Add {
             name,
             app,
@@ -46,8 +140,28 @@ pub fn execute(cli: Cli) -> Result<()> {
             model,
             small_model,
             from_file,
+            from_url,
+            file_format,
+            merge,
+            category,
+            skip_test,
+            deterministic_ids,
+        } => provider::add(
+            &ctx,
+            &name,
+            resolve_app(app, AppTypeArg::Claude),
+            api_key,
+            base_url,
+            model,
+            small_model,
+            from_file,
+            from_url,
+            file_format,
+            merge,
+            category,
             skip_test,
-        } => provider::add(&ctx, &name, app, api_key, base_url, model, small_model, from_file, skip_test),
+            deterministic_ids,
+        ),
         Commands::Edit {
             name,
             app,
@@ -56,7 +170,39 @@ pub fn execute(cli: Cli) -> Result<()> {
             model,
             small_model,
             new_name,
-        } => provider::edit(&ctx, &name, app, api_key, base_url, model, small_model, new_name),
+            category,
+        } => provider::edit(
+            &ctx,
+            &name,
+            resolve_app(app, AppTypeArg::Claude),
+            api_key,
+            base_url,
+            model,
+            small_model,
+            new_name,
+            category,
+        ),
+        Commands::SetField { name, pointer, value, app } => {
+            provider::set_field(&ctx, &name, resolve_app(app, AppTypeArg::Claude), &pointer, &value)
+        }
+        Commands::Compare { left, right, app } => provider::compare(&ctx, &left, &right, resolve_app(app, AppTypeArg::Claude)),
+        Commands::Move { name, from_app, to_app } => provider::move_to(&ctx, &name, from_app, to_app),
+        Commands::Show { name, app, show_key } => provider::show(&ctx, &name, resolve_app(app, AppTypeArg::Claude), show_key),
+        Commands::Notes { name, app, set, clear } => provider::notes(&ctx, &name, resolve_app(app, AppTypeArg::Claude), set, clear),
+        Commands::Test {
+            app,
+            timeout,
+            all: true,
+            concurrency,
+            retries,
+            ..
+        } => tokio::runtime::Runtime::new()?.block_on(provider::test_all(
+            &ctx,
+            resolve_app(app, AppTypeArg::Claude),
+            timeout,
+            concurrency,
+            retries,
+        )),
         Commands::Test {
             name,
             app,
@@ -64,11 +210,59 @@ pub fn execute(cli: Cli) -> Result<()> {
             base_url,
             model,
             timeout,
-        } => execute_test(&ctx, name, app, api_key, base_url, model, timeout),
-        Commands::Remove { name, app, yes } => provider::remove(&ctx, &name, app, yes),
+            cost,
+            record,
+            save_result,
+            retries,
+            ..
+        } => execute_test(
+            &ctx,
+            name,
+            resolve_app(app, AppTypeArg::Claude),
+            api_key,
+            base_url,
+            model,
+            timeout,
+            cost,
+            record,
+            save_result,
+            retries,
+        ),
+        Commands::Remove { name, app, yes } => provider::remove(&ctx, &name, resolve_app(app, AppTypeArg::Claude), yes),
+        Commands::Prune { app, older_than_days, failing, yes } => {
+            let filter = prune::PruneFilter { older_than_days, failing };
+            prune::prune(&ctx, resolve_app(app, AppTypeArg::Claude), filter, yes)
+        }
         Commands::Update { app } => provider::update(&ctx, app),
-        Commands::Export { format, out, app } => config::export(&ctx, format, out, app),
-        Commands::Import { file, app } => config::import(&ctx, &file, app),
+        Commands::Export {
+            file_format,
+            out,
+            app,
+            single,
+            redact,
+        } => config::export(&ctx, file_format, out, app, single.as_deref(), redact),
+        Commands::Import {
+            file,
+            app,
+            as_name,
+            as_prefix,
+            as_suffix,
+            merge,
+            deterministic_ids,
+        } => config::import(
+            &ctx,
+            &file,
+            app,
+            config::ImportNaming {
+                name: as_name,
+                prefix: as_prefix,
+                suffix: as_suffix,
+                deterministic_ids,
+            },
+            merge,
+        ),
+        Commands::Backup { output } => config::backup(&ctx, &output),
+        Commands::Restore { input, overwrite } => config::restore(&ctx, &input, overwrite),
         Commands::Config { action } => config::execute(&ctx, action),
         Commands::Mcp { action } => execute_mcp(&ctx, action),
         Commands::Prompt { action } => execute_prompt(&ctx, action),
@@ -81,32 +275,69 @@ pub fn execute(cli: Cli) -> Result<()> {
         Commands::Env { action } => execute_env(&ctx, action),
         Commands::Skill { action } => execute_skill(&ctx, action),
         Commands::Openclaw { action } => execute_openclaw(&ctx, action),
-        Commands::SelfUpdate { action, check, force } => execute_self_update(&ctx, action, check, force),
+        Commands::SelfUpdate { action, check, force, timeout } => {
+            execute_self_update(&ctx, action, check, force, timeout)
+        }
         Commands::Batch { action } => execute_batch(&ctx, action),
         Commands::Failover { action } => execute_failover(&ctx, action),
         Commands::Usage { action } => execute_usage(&ctx, action),
         Commands::Webdav { action } => execute_webdav(&ctx, action),
         Commands::Web { port, host, user, pass } => execute_web(&ctx, port, &host, &user, &pass),
-        Commands::Version => {
-            println!("cc-switch {}", ccswitch_core::VERSION);
-            Ok(())
-        }
+        Commands::Version => version::show_version(&ctx),
     }
 }
 
+/// 解析未指定的 `--app`：优先使用 `config set default-app` 的设置，否则使用命令自身的默认值
+fn resolve_app(app: AppTypeArg, fallback: AppTypeArg) -> AppTypeArg {
+    if app != AppTypeArg::Default {
+        return app;
+    }
+    match AppState::init() {
+        Ok(state) => resolve_app_with(&state.db, app, fallback),
+        Err(_) => fallback,
+    }
+}
+
+/// 使用给定数据库中的 default-app 设置解析 `--app`
+pub(crate) fn resolve_app_with(db: &Database, app: AppTypeArg, fallback: AppTypeArg) -> AppTypeArg {
+    if app != AppTypeArg::Default {
+        return app;
+    }
+    db.get_setting(config::DEFAULT_APP_SETTING)
+        .ok()
+        .flatten()
+        .and_then(|value| AppTypeArg::from_str(value.trim(), true).ok())
+        .filter(|resolved| *resolved != AppTypeArg::Default)
+        .unwrap_or(fallback)
+}
+
 /// 执行批量操作子命令
 fn execute_batch(ctx: &OutputContext, action: BatchAction) -> Result<()> {
     match action {
-        BatchAction::Switch { name } => batch::batch_switch(ctx, &name),
-        BatchAction::Test { app, timeout, verbose } => {
+        BatchAction::Switch { name, map, atomic } => match name {
+            Some(name) => batch::batch_switch(ctx, &name, atomic),
+            None => batch::batch_switch_map(ctx, &map, atomic),
+        },
+        BatchAction::Test { app, timeout, verbose, retries } => {
             tokio::runtime::Runtime::new()
                 .unwrap()
-                .block_on(batch::batch_test(ctx, app, timeout, verbose))
+                .block_on(batch::batch_test(ctx, app, timeout, verbose, retries))
         }
-        BatchAction::Export { output, app } => batch::batch_export(ctx, &output, app),
-        BatchAction::Import { input, overwrite } => batch::batch_import(ctx, &input, overwrite),
+        BatchAction::Export {
+            output,
+            app,
+            manifest,
+        } => batch::batch_export(ctx, &output, app, manifest),
+        BatchAction::Import { input, overwrite, force } => batch::batch_import(ctx, &input, overwrite, force),
         BatchAction::Remove { names, app, force } => batch::batch_remove(ctx, &names, app, force),
-        BatchAction::Sync { from, to, overwrite } => {
+        BatchAction::Sync {
+            from,
+            to,
+            overwrite,
+            pattern,
+            dry_run,
+            force,
+        } => {
             // 处理 from 和 to
             let from_app = from.to_app_types().into_iter().next()
                 .ok_or_else(|| anyhow::anyhow!("源应用类型无效"))?;
@@ -127,7 +358,7 @@ fn execute_batch(ctx: &OutputContext, action: BatchAction) -> Result<()> {
                 anyhow::bail!("没有有效的目标应用");
             }
 
-            batch::batch_sync(ctx, from_app, target_apps, overwrite)
+            batch::batch_sync(ctx, from_app, target_apps, overwrite, pattern.as_deref(), dry_run, force)
         }
         BatchAction::Edit { field, value, app, pattern } => {
             batch::batch_edit(ctx, app, &field, &value, pattern.as_deref())
@@ -141,12 +372,22 @@ fn execute_mcp(ctx: &OutputContext, action: McpAction) -> Result<()> {
         McpAction::List { app, detail } => mcp::list(ctx, app, detail),
         McpAction::Add {
             id,
-            command,
+            from_json: Some(path),
+            name,
+            description,
+            ..
+        } => mcp::add_from_json(ctx, &path, id.as_deref(), name, description),
+        McpAction::Add {
+            id: Some(id),
+            command: Some(command),
             args,
             env,
             name,
             description,
-        } => mcp::add(ctx, &id, &command, args, env, name, description),
+            disabled,
+            ..
+        } => mcp::add(ctx, &id, &command, args, env, name, description, disabled),
+        McpAction::Add { .. } => anyhow::bail!("请指定服务器 ID 与 --command，或使用 --from-json"),
         McpAction::Update {
             id,
             name,
@@ -158,8 +399,10 @@ fn execute_mcp(ctx: &OutputContext, action: McpAction) -> Result<()> {
         McpAction::Toggle {
             id,
             app,
+            global,
             enable,
             disable,
+            scope,
         } => {
             let enable_flag = if enable {
                 true
@@ -168,10 +411,21 @@ fn execute_mcp(ctx: &OutputContext, action: McpAction) -> Result<()> {
             } else {
                 true
             };
-            mcp::toggle(ctx, &id, app, enable_flag)
+            match app {
+                Some(app) if !global => mcp::toggle(ctx, &id, app, enable_flag, scope.to_scope()),
+                _ => mcp::toggle_global(ctx, &id, enable_flag),
+            }
         }
-        McpAction::Import { from } => mcp::import(ctx, from),
-        McpAction::Show { id } => mcp::show(ctx, &id),
+        McpAction::Duplicate { id, new_id } => mcp::duplicate(ctx, &id, &new_id),
+        McpAction::Export { output } => mcp::export(ctx, output.as_deref()),
+        McpAction::Import {
+            file: Some(file),
+            overwrite,
+            ..
+        } => mcp::import_file(ctx, &file, overwrite),
+        McpAction::Import { from, enable, also, .. } => mcp::import(ctx, from, enable, also),
+        McpAction::Show { id, detail } => mcp::show(ctx, &id, detail),
+        McpAction::Validate => mcp::validate(ctx),
     }
 }
 
@@ -194,7 +448,9 @@ fn execute_prompt(ctx: &OutputContext, action: PromptAction) -> Result<()> {
             description,
         } => prompt::update(ctx, app, &id, name, content, description),
         PromptAction::Remove { id, app, yes } => prompt::remove(ctx, app, &id, yes),
-        PromptAction::Enable { id, app } => prompt::enable(ctx, app, &id),
+        PromptAction::Enable { id, app, vars } => prompt::enable(ctx, app, &id, &vars),
+        PromptAction::Render { id, app, vars } => prompt::render(ctx, app, &id, &vars),
+        PromptAction::Disable { id, app } => prompt::disable(ctx, app, &id),
         PromptAction::Show { id, app } => prompt::show(ctx, app, &id),
         PromptAction::Import { app } => prompt::import(ctx, app),
     }
@@ -204,17 +460,19 @@ fn execute_prompt(ctx: &OutputContext, action: PromptAction) -> Result<()> {
 fn execute_proxy(ctx: &OutputContext, action: ProxyAction) -> Result<()> {
     match action {
         ProxyAction::Get => proxy::get(ctx),
-        ProxyAction::Set { url } => proxy::set(ctx, &url),
+        ProxyAction::Set { url, no_proxy } => proxy::set(ctx, &url, no_proxy.as_deref()),
         ProxyAction::Clear => proxy::clear(ctx),
         ProxyAction::Test { url } => {
             tokio::runtime::Runtime::new()
                 .unwrap()
                 .block_on(proxy::test(ctx, url))
         }
-        ProxyAction::Scan => {
+        ProxyAction::Apply { app } => proxy::apply(ctx, app),
+        ProxyAction::Unapply { app } => proxy::unapply(ctx, app),
+        ProxyAction::Scan { yes } => {
             tokio::runtime::Runtime::new()
                 .unwrap()
-                .block_on(proxy::scan(ctx))
+                .block_on(proxy::scan(ctx, yes))
         }
     }
 }
@@ -227,6 +485,7 @@ fn execute_speedtest(ctx: &OutputContext, urls: Vec<String>, timeout: u64, use_p
 }
 
 /// 执行 API Test 命令
+#[allow(clippy::too_many_arguments)]
 fn execute_test(
     ctx: &OutputContext,
     name: Option<String>,
@@ -235,17 +494,22 @@ fn execute_test(
     base_url: Option<String>,
     model: Option<String>,
     timeout: u64,
+    cost: bool,
+    record: bool,
+    save_result: bool,
+    retries: u32,
 ) -> Result<()> {
-    tokio::runtime::Runtime::new()
-        .unwrap()
-        .block_on(provider::test_api(ctx, name, app, api_key, base_url, model, timeout))
+    tokio::runtime::Runtime::new().unwrap().block_on(provider::test_api(
+        ctx, name, app, api_key, base_url, model, timeout, cost, record, save_result, retries,
+    ))
 }
 
 /// 执行 Env 子命令
 fn execute_env(ctx: &OutputContext, action: EnvAction) -> Result<()> {
     match action {
-        EnvAction::Check { app } => env::check(ctx, app),
+        EnvAction::Check { app, fix, yes } => env::check(ctx, app, fix, yes),
         EnvAction::List { app } => env::list(ctx, app),
+        EnvAction::Restore { backup, list, yes } => env::restore(ctx, backup.as_deref(), list, yes),
     }
 }
 
@@ -253,7 +517,7 @@ fn execute_env(ctx: &OutputContext, action: EnvAction) -> Result<()> {
 fn execute_skill(ctx: &OutputContext, action: SkillAction) -> Result<()> {
     match action {
         SkillAction::List { app, detail } => skill::list(ctx, app, detail),
-        SkillAction::Install { repo, branch, app } => skill::install(ctx, &repo, branch, app),
+        SkillAction::Install { repo, branch, rev, app } => skill::install(ctx, &repo, branch, rev, app),
         SkillAction::Uninstall { id, yes } => skill::uninstall(ctx, &id, yes),
         SkillAction::Toggle {
             id,
@@ -270,6 +534,14 @@ fn execute_skill(ctx: &OutputContext, action: SkillAction) -> Result<()> {
             };
             skill::toggle(ctx, &id, app, enable_flag)
         }
+        SkillAction::Status => skill::status(ctx),
+        SkillAction::Update { id, unpin } => skill::update(ctx, &id, unpin),
+        SkillAction::Search { query, refresh } => {
+            tokio::runtime::Runtime::new()
+                .unwrap()
+                .block_on(skill::search(ctx, &query, refresh))
+        }
+        SkillAction::Index { url, reset } => skill::index(ctx, url.as_deref(), reset),
         SkillAction::Scan => skill::scan(ctx),
         SkillAction::Sync => skill::sync(ctx),
         SkillAction::Show { id } => skill::show(ctx, &id),
@@ -282,21 +554,23 @@ fn execute_self_update(
     action: Option<SelfUpdateAction>,
     check: bool,
     force: bool,
+    timeout: u64,
 ) -> Result<()> {
     let rt = tokio::runtime::Runtime::new().unwrap();
+    let timeout = std::time::Duration::from_secs(timeout);
 
     match action {
         Some(SelfUpdateAction::Check) => {
-            rt.block_on(update::show_status(ctx, true))
+            rt.block_on(update::show_status(ctx, true, timeout))
         }
         Some(SelfUpdateAction::Run { force }) => {
-            rt.block_on(update::self_update(ctx, force))
+            rt.block_on(update::self_update(ctx, force, timeout))
         }
         None => {
             if check {
-                rt.block_on(update::show_status(ctx, true))
+                rt.block_on(update::show_status(ctx, true, timeout))
             } else {
-                rt.block_on(update::self_update(ctx, force))
+                rt.block_on(update::self_update(ctx, force, timeout))
             }
         }
     }
@@ -447,18 +721,18 @@ fn execute_web(_ctx: &OutputContext, port: u16, host: &str, user: &str, pass: &s
             .map_err(|e| anyhow::anyhow!("无效的地址: {}", e))?;
 
         println!();
-        println!("╔══════════════════════════════════════════════════════════════╗");
-        println!("║           🌐 CC-Switch Web 控制器已启动                      ║");
-        println!("╠══════════════════════════════════════════════════════════════╣");
-        println!("║  访问地址: http://{}:{}                                   ║", host, port);
-        println!("║  登录账号: {}                                               ║", user);
-        println!("║                                                              ║");
-        println!("║  ⚠️  安全提示:                                               ║");
-        println!("║  • 此服务绑定所有网络接口，可从公网访问                       ║");
-        println!("║  • 已启用身份验证，请使用设置的账号密码登录                   ║");
-        println!("║  • 配置完成后请及时关闭 (Ctrl+C)                             ║");
-        println!("║  • 建议在防火墙后使用或使用临时会话                          ║");
-        println!("╚══════════════════════════════════════════════════════════════╝");
+        println!("{}", glyphs("╔══════════════════════════════════════════════════════════════╗"));
+        println!("{}", glyphs("║           🌐 CC-Switch Web 控制器已启动                      ║"));
+        println!("{}", glyphs("╠══════════════════════════════════════════════════════════════╣"));
+        println!("{}", glyphs(&format!("║  访问地址: http://{}:{}                                   ║", host, port)));
+        println!("{}", glyphs(&format!("║  登录账号: {}                                               ║", user)));
+        println!("{}", glyphs("║                                                              ║"));
+        println!("{}", glyphs("║  ⚠️  安全提示:                                               ║"));
+        println!("{}", glyphs("║  • 此服务绑定所有网络接口，可从公网访问                       ║"));
+        println!("{}", glyphs("║  • 已启用身份验证，请使用设置的账号密码登录                   ║"));
+        println!("{}", glyphs("║  • 配置完成后请及时关闭 (Ctrl+C)                             ║"));
+        println!("{}", glyphs("║  • 建议在防火墙后使用或使用临时会话                          ║"));
+        println!("{}", glyphs("╚══════════════════════════════════════════════════════════════╝"));
         println!();
 
         let listener = tokio::net::TcpListener::bind(addr).await?;
@@ -467,3 +741,32 @@ fn execute_web(_ctx: &OutputContext, port: u16, host: &str, user: &str, pass: &s
         Ok(())
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ccswitch_core::test_support::isolated_home;
+    use clap::Parser;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn test_use_missing_provider_maps_to_not_found() {
+        let _home = isolated_home();
+
+        let cli = Cli::try_parse_from(["cc-switch", "use", "missing", "--app", "claude"]).unwrap();
+        let err = execute(cli).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<AppError>(),
+            Some(AppError::ProviderNotFound(name)) if name == "missing"
+        ));
+        assert_eq!(exit_code(&err), EXIT_NOT_FOUND);
+
+        assert_eq!(exit_code(&AppError::InvalidInput("x".into()).into()), EXIT_INVALID);
+        assert_eq!(exit_code(&AppError::Http("timeout".into()).into()), EXIT_NETWORK);
+        assert_eq!(exit_code(&AppError::Network("timeout".into()).into()), EXIT_NETWORK);
+        assert_eq!(exit_code(&AppError::NotFound("x".into()).into()), EXIT_NOT_FOUND);
+        assert_eq!(exit_code(&AppError::Validation("x".into()).into()), EXIT_INVALID);
+        assert_eq!(exit_code(&anyhow::anyhow!("boom")), EXIT_FAILURE);
+    }
+}
@@ -4,10 +4,11 @@
 
 use anyhow::{bail, Result};
 use ccswitch_core::{AppState, AppType, Prompt, PromptService};
+use indexmap::IndexMap;
 use std::fs;
 
 use crate::cli::AppTypeArg;
-use crate::output::{print_error, print_info, print_success, print_warning, OutputContext};
+use crate::output::{glyphs, print_error, print_info, print_success, print_warning, OutputContext};
 
 /// 列出所有 Prompts
 pub fn list(_ctx: &OutputContext, app: AppTypeArg) -> Result<()> {
@@ -25,12 +26,20 @@ pub fn list(_ctx: &OutputContext, app: AppTypeArg) -> Result<()> {
 
         has_prompts = true;
 
-        println!("\n📝 {} Prompts ({} 个)\n", app_type.display_name(), prompts.len());
+        println!("{}", glyphs(&format!("\n📝 {} Prompts ({} 个)\n", app_type.display_name(), prompts.len())));
         println!("{:<20} {:<30} {:<10}", "ID", "名称", "状态");
         println!("{}", "-".repeat(60));
 
+        let active = PromptService::active(&state, app_type)?;
+
         for (_, prompt) in &prompts {
-            let status = if prompt.enabled { "✓ 启用" } else { "○ 禁用" };
+            let status = glyphs(if active.as_deref() == Some(prompt.id.as_str()) {
+                "● 当前"
+            } else if prompt.enabled {
+                "✓ 启用"
+            } else {
+                "○ 禁用"
+            });
             println!("{:<20} {:<30} {:<10}", prompt.id, prompt.name, status);
         }
     }
@@ -50,7 +59,7 @@ pub fn show(_ctx: &OutputContext, app: AppTypeArg, id: &str) -> Result<()> {
 
     for app_type in app_types {
         if let Some(prompt) = PromptService::get(&state, app_type, id)? {
-            println!("\n📝 Prompt: {}\n", prompt.name);
+            println!("{}", glyphs(&format!("\n📝 Prompt: {}\n", prompt.name)));
             println!("ID:     {}", prompt.id);
             println!("名称:   {}", prompt.name);
             println!("状态:   {}", if prompt.enabled { "启用" } else { "禁用" });
@@ -60,6 +69,17 @@ pub fn show(_ctx: &OutputContext, app: AppTypeArg, id: &str) -> Result<()> {
                 println!("描述:   {}", desc);
             }
 
+            let placeholders = prompt.placeholders();
+            if !placeholders.is_empty() {
+                println!("变量:");
+                for name in placeholders {
+                    match prompt.variables.get(&name) {
+                        Some(value) => println!("  {} = {}", name, value),
+                        None => println!("  {} (未设置)", name),
+                    }
+                }
+            }
+
             println!("\n内容:\n{}", "-".repeat(40));
             println!("{}", prompt.content);
 
@@ -204,7 +224,7 @@ pub fn remove(_ctx: &OutputContext, app: AppTypeArg, id: &str, yes: bool) -> Res
 }
 
 /// 启用 Prompt
-pub fn enable(_ctx: &OutputContext, app: AppTypeArg, id: &str) -> Result<()> {
+pub fn enable(_ctx: &OutputContext, app: AppTypeArg, id: &str, vars: &[String]) -> Result<()> {
     let state = AppState::init()?;
 
     let app_types = app.to_app_types();
@@ -214,8 +234,12 @@ pub fn enable(_ctx: &OutputContext, app: AppTypeArg, id: &str) -> Result<()> {
     }
 
     let app_type = app_types[0];
+    let vars = parse_vars(vars)?;
 
-    PromptService::enable(&state, app_type, id)?;
+    if let Err(e) = PromptService::enable_with_vars(&state, app_type, id, &vars) {
+        print_error(&e.to_string());
+        bail!("启用 Prompt 失败");
+    }
 
     print_success(&format!(
         "已为 {} 启用 Prompt: {}",
@@ -226,6 +250,65 @@ pub fn enable(_ctx: &OutputContext, app: AppTypeArg, id: &str) -> Result<()> {
     Ok(())
 }
 
+/// 预览替换变量后的 Prompt 内容
+pub fn render(_ctx: &OutputContext, app: AppTypeArg, id: &str, vars: &[String]) -> Result<()> {
+    let state = AppState::init()?;
+
+    let app_types = app.to_app_types();
+    if app_types.is_empty() || matches!(app, AppTypeArg::All) {
+        print_error("请指定具体的应用类型 (claude/codex/gemini/opencode)");
+        bail!("需要指定应用类型")
+    }
+
+    let vars = parse_vars(vars)?;
+    match PromptService::render(&state, app_types[0], id, &vars) {
+        Ok(content) => {
+            println!("{}", content);
+            Ok(())
+        }
+        Err(e) => {
+            print_error(&e.to_string());
+            bail!("渲染 Prompt 失败")
+        }
+    }
+}
+
+/// 解析 `KEY=VALUE` 形式的模板变量
+fn parse_vars(vars: &[String]) -> Result<IndexMap<String, String>> {
+    let mut parsed = IndexMap::new();
+    for var in vars {
+        let Some((key, value)) = var.split_once('=') else {
+            print_error(&format!("无效的变量格式: {} (应为 KEY=VALUE)", var));
+            bail!("无效的变量格式");
+        };
+        parsed.insert(key.trim().to_string(), value.to_string());
+    }
+    Ok(parsed)
+}
+
+/// 禁用 Prompt
+pub fn disable(_ctx: &OutputContext, app: AppTypeArg, id: &str) -> Result<()> {
+    let state = AppState::init()?;
+
+    let app_types = app.to_app_types();
+    if app_types.is_empty() || matches!(app, AppTypeArg::All) {
+        print_error("请指定具体的应用类型 (claude/codex/gemini/opencode)");
+        bail!("需要指定应用类型")
+    }
+
+    let app_type = app_types[0];
+
+    PromptService::disable(&state, app_type, id)?;
+
+    print_success(&format!(
+        "已为 {} 禁用 Prompt: {}",
+        app_type.display_name(),
+        id
+    ));
+
+    Ok(())
+}
+
 /// 从应用导入 Prompt
 pub fn import(_ctx: &OutputContext, app: AppTypeArg) -> Result<()> {
     let state = AppState::init()?;
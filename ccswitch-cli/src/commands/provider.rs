@@ -1,33 +1,97 @@
 //! 供应商操作命令实现
 
 use anyhow::{bail, Result};
-use ccswitch_core::{AppState, AppType, Provider, ProviderService};
+use ccswitch_core::services::provider::format_test_note;
+use ccswitch_core::{AppError, AppState, AppType, DiffKind, Provider, ProviderService, ProviderTestResult};
+use chrono::{Local, TimeZone};
+use colored::Colorize;
+use futures::future::join_all;
+use indexmap::IndexMap;
+use serde::Serialize;
 use serde_json::json;
 use std::io::{self, Write};
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tabled::{Table, Tabled};
+
+use crate::commands::retry::{retry_with_backoff, CheckFailure, RETRY_BACKOFF};
+use crate::cli::{AppTypeArg, ImportFormatArg, OutputFormat};
+use crate::output::{
+    confirm_interactive, print_error, print_info, print_success, print_warning, styled_table, sym, glyphs, OutputContext, mask_api_key,
+};
+
+/// 按名称或 ID 查找唯一的供应商，匹配到多个时列出候选并报错
+pub(crate) fn find_unique(state: &AppState, app_type: AppType, name: &str) -> Result<Option<Provider>> {
+    let mut matches = ProviderService::find_all(state, app_type, name)?;
+    if matches.len() > 1 {
+        print_error(&format!("\"{}\" 匹配到多个供应商，请使用完整名称或 ID:", name));
+        for p in &matches {
+            println!("  - {} ({})", p.name, p.id);
+        }
+        bail!("供应商名称不明确: {}", name);
+    }
+    Ok(matches.pop())
+}
+
+/// 切回上一个供应商（`use --previous` / `use -`）
+pub fn switch_previous(_ctx: &OutputContext, app: AppTypeArg, force: bool) -> Result<()> {
+    let state = AppState::init()?;
+    let app_types = app.to_app_types();
+
+    if app_types.len() > 1 {
+        print_error("切换供应商时请指定具体的应用类型，例如: --app claude");
+        return Err(AppError::InvalidInput("未指定应用类型".to_string()).into());
+    }
 
-use crate::cli::AppTypeArg;
-use crate::output::{print_error, print_info, print_success, print_warning, OutputContext, mask_api_key};
+    let app_type = app_types[0];
+    match ProviderService::switch_to_previous(&state, app_type, force) {
+        Ok(p) => {
+            print_success(&format!(
+                "已切回上一个供应商: {} ({})",
+                p.name,
+                app_type.display_name()
+            ));
+            if let Some(url) = p.get_base_url() {
+                print_info(&format!("Base URL: {}", url));
+            }
+            Ok(())
+        }
+        Err(e @ (AppError::NotFound(_) | AppError::ProviderNotFound(_))) => {
+            print_error(&e.to_string());
+            Err(e.into())
+        }
+        Err(e) => {
+            print_error(&e.to_string());
+            print_info("如需忽略校验强制切换，请添加 --force");
+            bail!("切换失败");
+        }
+    }
+}
 
 /// 切换供应商
-pub fn switch(_ctx: &OutputContext, name: &str, app: AppTypeArg) -> Result<()> {
+pub fn switch(_ctx: &OutputContext, name: &str, app: AppTypeArg, force: bool) -> Result<()> {
     let state = AppState::init()?;
     let app_types = app.to_app_types();
 
     // 如果选择了 "all"，需要明确指定应用
     if app_types.len() > 1 {
         print_error("切换供应商时请指定具体的应用类型，例如: --app claude");
-        bail!("未指定应用类型");
+        return Err(AppError::InvalidInput("未指定应用类型".to_string()).into());
     }
 
     let app_type = app_types[0];
 
     // 查找供应商
-    let provider = ProviderService::find(&state, app_type, name)?;
+    let provider = find_unique(&state, app_type, name)?;
 
     match provider {
         Some(p) => {
-            ProviderService::switch(&state, app_type, &p.id)?;
+            if let Err(e) = ProviderService::switch_with_options(&state, app_type, &p.id, force) {
+                print_error(&e.to_string());
+                if !matches!(e, AppError::InvalidInput(_)) {
+                    print_info("如需忽略校验强制切换，请添加 --force");
+                }
+                bail!("切换失败");
+            }
             print_success(&format!(
                 "已切换到供应商: {} ({})",
                 p.name,
@@ -53,12 +117,13 @@ pub fn switch(_ctx: &OutputContext, name: &str, app: AppTypeArg) -> Result<()> {
                 }
             }
 
-            bail!("供应商不存在");
+            Err(AppError::ProviderNotFound(name.to_string()).into())
         }
     }
 }
 
 /// 添加供应商
+#[allow(clippy::too_many_arguments)]
 pub fn add(
     _ctx: &OutputContext,
     name: &str,
@@ -68,21 +133,33 @@ pub fn add(
     model: Option<String>,
     small_model: Option<String>,
     from_file: Option<String>,
+    from_url: Option<String>,
+    file_format: Option<ImportFormatArg>,
+    merge: bool,
+    category: Option<String>,
     skip_test: bool,
+    deterministic_ids: bool,
 ) -> Result<()> {
     let state = AppState::init()?;
+    let deterministic_ids = deterministic_ids || ccswitch_core::settings::deterministic_ids();
+    let skip_test = skip_test || !crate::commands::config::auto_test_on_add(&state);
     let app_types = app.to_app_types();
 
     if app_types.len() > 1 {
         print_error("添加供应商时请指定具体的应用类型，例如: --app claude");
-        bail!("未指定应用类型");
+        return Err(AppError::InvalidInput("未指定应用类型".to_string()).into());
     }
 
     let app_type = app_types[0].clone();
 
     // 从文件导入
     if let Some(file_path) = from_file {
-        return add_from_file(&state, app_type, name, &file_path);
+        return add_from_file(&state, app_type, name, &file_path, file_format, merge, category, deterministic_ids);
+    }
+
+    // 从 URL 导入
+    if let Some(url) = from_url {
+        return add_from_url(&state, app_type, name, &url, file_format, merge, category, deterministic_ids, skip_test);
     }
 
     // 根据应用类型构建配置
@@ -117,32 +194,7 @@ pub fn add(
                 anyhow::anyhow!("缺少 API Key")
             })?;
 
-            let base_url_val = base_url.clone().unwrap_or_else(|| "https://api.openai.com/v1".to_string());
-            let model_name = model.clone().unwrap_or_else(|| "gpt-4".to_string());
-
-            let config = format!(
-                r#"model_provider = "openai"
-model = "{}"
-
-[model_providers.openai]
-name = "OpenAI"
-base_url = "{}"
-wire_api = "responses"
-"#,
-                model_name, base_url_val
-            );
-
-            let auth = format!(
-                r#"[openai]
-api_key = "{}"
-"#,
-                api_key_val
-            );
-
-            json!({
-                "config": config,
-                "auth": auth
-            })
+            codex_settings(&api_key_val, base_url.as_deref(), model.as_deref())
         }
         AppType::Gemini => {
             let api_key_val = api_key.clone().ok_or_else(|| {
@@ -150,19 +202,7 @@ api_key = "{}"
                 anyhow::anyhow!("缺少 API Key")
             })?;
 
-            let mut config = serde_json::Map::new();
-            config.insert("apiKey".to_string(), json!(api_key_val));
-            config.insert(
-                "baseUrl".to_string(),
-                json!(base_url.clone().unwrap_or_else(|| "https://generativelanguage.googleapis.com".to_string())),
-            );
-
-            // 添加模型配置
-            if let Some(m) = &model {
-                config.insert("model".to_string(), json!(m));
-            }
-
-            json!(config)
+            gemini_settings(&api_key_val, base_url.as_deref(), model.as_deref())
         }
         AppType::OpenCode | AppType::OpenClaw => {
             print_warning("该应用供应商添加功能尚未完全实现");
@@ -171,50 +211,20 @@ api_key = "{}"
     };
 
     // 添加前测试 API Key
-    if !skip_test && api_key.is_some() {
-        print_info("正在测试 API Key 有效性...");
-
-        let test_result = tokio::runtime::Runtime::new()
-            .unwrap()
-            .block_on(test_api_key(
-                &app_type,
-                api_key.as_ref().unwrap(),
-                base_url.as_deref(),
-                model.as_deref(),
-                30,
-            ));
-
-        match test_result {
-            Ok(true) => {
-                print_success("API Key 测试通过!");
-            }
-            Ok(false) => {
-                print_warning("API Key 测试未返回预期结果，但仍将继续添加");
-            }
-            Err(e) => {
-                print_error(&format!("API Key 测试失败: {}", e));
-                print!("是否仍要添加此供应商? [y/N] ");
-                io::stdout().flush()?;
-
-                let mut input = String::new();
-                io::stdin().read_line(&mut input)?;
-
-                if !input.trim().eq_ignore_ascii_case("y") {
-                    print_info("已取消");
-                    return Ok(());
-                }
+    if !skip_test {
+        if let Some(key) = &api_key {
+            if !test_before_add(&app_type, key, base_url.as_deref(), model.as_deref())? {
+                print_info("已取消");
+                return Ok(());
             }
         }
     }
 
     // 生成 ID
-    let id = format!(
-        "{}-{}",
-        ccswitch_core::config::sanitize_name(name),
-        chrono::Utc::now().timestamp()
-    );
+    let id = ProviderService::generate_id(&state, app_type, name, deterministic_ids)?;
 
-    let provider = Provider::new(id, name, settings_config);
+    let mut provider = Provider::new(id, name, settings_config);
+    provider.category = category;
 
     ProviderService::add(&state, app_type.clone(), provider)?;
     print_success(&format!(
@@ -226,39 +236,267 @@ api_key = "{}"
     Ok(())
 }
 
+/// 添加前测试 API Key，测试失败时询问是否继续；返回 false 表示取消添加
+fn test_before_add(app_type: &AppType, api_key: &str, base_url: Option<&str>, model: Option<&str>) -> Result<bool> {
+    print_info("正在测试 API Key 有效性...");
+
+    let test_result = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(test_api_key(app_type, api_key, base_url, model, 30, DEFAULT_TEST_RETRIES));
+
+    match test_result {
+        Ok(outcome) if outcome.passed => {
+            print_success("API Key 测试通过!");
+        }
+        Ok(_) => {
+            print_warning("API Key 测试未返回预期结果，但仍将继续添加");
+        }
+        Err(e) => {
+            print_error(&format!("API Key 测试失败: {}", e));
+            return confirm_interactive(
+                "是否仍要添加此供应商?",
+                "已中止：API Key 测试失败，非交互模式下可添加 --skip-test 跳过测试",
+            );
+        }
+    }
+    Ok(true)
+}
+
+/// 构建 Codex 供应商配置（config.toml + auth）
+fn codex_settings(api_key: &str, base_url: Option<&str>, model: Option<&str>) -> serde_json::Value {
+    let config = format!(
+        r#"model_provider = "openai"
+model = "{}"
+
+[model_providers.openai]
+name = "OpenAI"
+base_url = "{}"
+wire_api = "responses"
+"#,
+        model.unwrap_or("gpt-4"),
+        base_url.unwrap_or("https://api.openai.com/v1")
+    );
+
+    let auth = format!(
+        r#"[openai]
+api_key = "{}"
+"#,
+        api_key
+    );
+
+    json!({
+        "config": config,
+        "auth": auth
+    })
+}
+
+/// 构建 Gemini 供应商配置
+fn gemini_settings(api_key: &str, base_url: Option<&str>, model: Option<&str>) -> serde_json::Value {
+    let mut config = serde_json::Map::new();
+    config.insert("apiKey".to_string(), json!(api_key));
+    config.insert(
+        "baseUrl".to_string(),
+        json!(base_url.unwrap_or("https://generativelanguage.googleapis.com")),
+    );
+
+    // 添加模型配置
+    if let Some(m) = model {
+        config.insert("model".to_string(), json!(m));
+    }
+
+    json!(config)
+}
+
+/// 是否为 `.env` 风格的文件名（`.env`、`xxx.env`、`.env.local` 等）
+fn is_env_file(file_path: &str) -> bool {
+    let name = std::path::Path::new(file_path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(file_path);
+    name == ".env" || name.starts_with(".env.") || name.ends_with(".env")
+}
+
+/// 解析 `.env` 内容，忽略空行与 `#` 注释，支持 `export` 前缀与引号包裹的值
+fn parse_env_file(content: &str) -> Result<IndexMap<String, String>> {
+    let mut vars = IndexMap::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").map(str::trim_start).unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            bail!("第 {} 行不是 KEY=VALUE 格式: {}", index + 1, line);
+        };
+        let key = key.trim();
+        if key.is_empty() {
+            bail!("第 {} 行缺少变量名", index + 1);
+        }
+
+        let value = value.trim();
+        let quoted = ['"', '\'']
+            .iter()
+            .find(|&&q| value.len() >= 2 && value.starts_with(q) && value.ends_with(q));
+        let value = match quoted {
+            Some(_) => &value[1..value.len() - 1],
+            // 未加引号时去掉行尾注释
+            None => value.split(" #").next().unwrap_or_default().trim_end(),
+        };
+        vars.insert(key.to_string(), value.to_string());
+    }
+    Ok(vars)
+}
+
+/// 由 `.env` 变量构建目标应用的供应商配置
+///
+/// Claude 直接写入 `env`；Codex 与 Gemini 从常见变量名中提取 API Key、Base URL 与模型。
+fn settings_from_env(app_type: AppType, vars: &IndexMap<String, String>) -> Result<serde_json::Value> {
+    let first = |keys: &[&str]| keys.iter().find_map(|k| vars.get(*k).filter(|v| !v.is_empty()).cloned());
+
+    match app_type {
+        AppType::Claude => Ok(json!({ "env": vars })),
+        AppType::Codex => {
+            let Some(api_key) = first(&["OPENAI_API_KEY", "CODEX_API_KEY"]) else {
+                bail!(".env 中缺少 OPENAI_API_KEY");
+            };
+            let base_url = first(&["OPENAI_BASE_URL", "OPENAI_API_BASE", "CODEX_BASE_URL"]);
+            let model = first(&["OPENAI_MODEL", "CODEX_MODEL"]);
+            Ok(codex_settings(&api_key, base_url.as_deref(), model.as_deref()))
+        }
+        AppType::Gemini => {
+            let Some(api_key) = first(&["GEMINI_API_KEY", "GOOGLE_GEMINI_API_KEY", "GOOGLE_API_KEY"]) else {
+                bail!(".env 中缺少 GEMINI_API_KEY");
+            };
+            let base_url = first(&["GOOGLE_GEMINI_BASE_URL", "GEMINI_BASE_URL"]);
+            let model = first(&["GEMINI_MODEL"]);
+            Ok(gemini_settings(&api_key, base_url.as_deref(), model.as_deref()))
+        }
+        AppType::OpenCode | AppType::OpenClaw => {
+            bail!("{} 暂不支持从 .env 导入", app_type.display_name())
+        }
+    }
+}
+
 /// 从文件添加供应商
+#[allow(clippy::too_many_arguments)]
 fn add_from_file(
     state: &AppState,
     app_type: AppType,
     name: &str,
     file_path: &str,
+    file_format: Option<ImportFormatArg>,
+    merge: bool,
+    category: Option<String>,
+    deterministic_ids: bool,
 ) -> Result<()> {
     let content = std::fs::read_to_string(file_path)?;
+    let file_format = file_format.or_else(|| format_from_extension(file_path));
+    let settings_config = parse_import_settings(app_type, &content, file_format)?;
 
-    let settings_config: serde_json::Value = if file_path.ends_with(".json") {
-        serde_json::from_str(&content)?
-    } else if file_path.ends_with(".yaml") || file_path.ends_with(".yml") {
-        serde_yaml::from_str(&content)?
-    } else if file_path.ends_with(".toml") {
-        // 对于 TOML，包装成 JSON
-        json!({ "config": content })
-    } else {
-        // 尝试自动检测
-        serde_json::from_str(&content)
-            .or_else(|_| serde_yaml::from_str(&content).map_err(anyhow::Error::from))?
-    };
+    let id = ProviderService::generate_id(state, app_type, name, deterministic_ids)?;
 
-    let id = format!(
-        "{}-{}",
-        ccswitch_core::config::sanitize_name(name),
-        chrono::Utc::now().timestamp()
-    );
+    let mut provider = Provider::new(id, name, settings_config);
+    provider.category = category;
+
+    let action = if save_imported(state, app_type, provider, merge)? { "更新" } else { "导入" };
+    print_success(&format!(
+        "已从文件{}供应商: {} ({})",
+        action,
+        name,
+        app_type.display_name()
+    ));
+
+    Ok(())
+}
 
-    let provider = Provider::new(id, name, settings_config);
+/// 保存导入的供应商，返回是否为更新
+///
+/// `merge` 为 true 时，同名供应商会原地更新配置（保留 ID 与创建时间）；否则新建并提示同名冲突。
+fn save_imported(state: &AppState, app_type: AppType, provider: Provider, merge: bool) -> Result<bool> {
+    let same_name = ProviderService::list(state, app_type)?
+        .into_values()
+        .find(|p| p.name.eq_ignore_ascii_case(&provider.name));
+
+    match same_name {
+        Some(mut target) if merge => {
+            target.settings_config = provider.settings_config;
+            if provider.category.is_some() {
+                target.category = provider.category;
+            }
+            ProviderService::update(state, app_type, target)?;
+            return Ok(true);
+        }
+        Some(_) => print_warning(&format!(
+            "存在同名供应商: {} ({})，已作为新供应商添加，可使用 --merge 更新已有配置",
+            provider.name,
+            app_type.display_name()
+        )),
+        None => {}
+    }
 
     ProviderService::add(state, app_type, provider)?;
+    Ok(false)
+}
+
+/// 远程配置的大小上限
+const MAX_REMOTE_CONFIG_BYTES: usize = 1024 * 1024;
+
+/// 从 URL 下载配置并添加供应商
+#[allow(clippy::too_many_arguments)]
+fn add_from_url(
+    state: &AppState,
+    app_type: AppType,
+    name: &str,
+    url: &str,
+    file_format: Option<ImportFormatArg>,
+    merge: bool,
+    category: Option<String>,
+    deterministic_ids: bool,
+    skip_test: bool,
+) -> Result<()> {
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        print_error("--from-url 仅支持 http:// 或 https:// 地址");
+        return Err(AppError::InvalidInput(format!("无效的 URL: {}", url)).into());
+    }
+
+    print_info(&format!("正在下载配置: {}", url));
+    let (content, content_type) = tokio::runtime::Runtime::new()?.block_on(fetch_remote_config(url))?;
+
+    // 优先使用 --file-format，其次按 Content-Type，最后按 URL 扩展名
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let file_format = file_format
+        .or_else(|| content_type.as_deref().and_then(format_from_content_type))
+        .or_else(|| format_from_extension(path));
+    let mut settings_config = parse_import_settings(app_type, &content, file_format)?;
+
+    // 远程配置不可信，只保留凭据与模型字段，避免 hooks、apiKeyHelper 等在本机执行命令
+    let dropped = sanitize_remote_settings(app_type, &mut settings_config);
+    if !dropped.is_empty() {
+        print_warning(&format!(
+            "已忽略远程配置中的以下字段（仅导入凭据与模型设置）: {}",
+            dropped.join(", ")
+        ));
+    }
+
+    let id = ProviderService::generate_id(state, app_type, name, deterministic_ids)?;
+    let mut provider = Provider::new(id, name, settings_config);
+    provider.category = category;
+
+    // 仅使用配置中的静态 API Key 测试
+    if !skip_test {
+        let (api_key, base_url) = ProviderService::extract_credentials(&provider, &app_type)?;
+        if !api_key.is_empty()
+            && !test_before_add(&app_type, &api_key, Some(&base_url), provider.get_model().as_deref())?
+        {
+            print_info("已取消");
+            return Ok(());
+        }
+    }
+
+    let action = if save_imported(state, app_type, provider, merge)? { "更新" } else { "导入" };
     print_success(&format!(
-        "已从文件导入供应商: {} ({})",
+        "已从 URL {}供应商: {} ({})",
+        action,
         name,
         app_type.display_name()
     ));
@@ -266,6 +504,181 @@ fn add_from_file(
     Ok(())
 }
 
+/// 远程 Claude 配置允许保留的 env 变量
+const REMOTE_CLAUDE_ENV_KEYS: &[&str] = &[
+    "ANTHROPIC_AUTH_TOKEN",
+    "ANTHROPIC_API_KEY",
+    "ANTHROPIC_BASE_URL",
+    "ANTHROPIC_MODEL",
+    "ANTHROPIC_SMALL_FAST_MODEL",
+    "ANTHROPIC_DEFAULT_HAIKU_MODEL",
+    "ANTHROPIC_DEFAULT_SONNET_MODEL",
+    "ANTHROPIC_DEFAULT_OPUS_MODEL",
+];
+
+/// 远程 Codex config.toml 允许保留的键
+const REMOTE_CODEX_CONFIG_KEYS: &[&str] = &["model", "model_provider", "model_providers", "model_reasoning_effort"];
+
+/// 远程 Codex `model_providers.*` 允许保留的键
+const REMOTE_CODEX_PROVIDER_KEYS: &[&str] = &["name", "base_url", "wire_api", "env_key", "requires_openai_auth"];
+
+/// 只保留 `allowed` 中的键，被移除的键以 `prefix` 加键名记入 `dropped`
+fn retain_keys(map: &mut serde_json::Map<String, serde_json::Value>, allowed: &[&str], prefix: &str, dropped: &mut Vec<String>) {
+    map.retain(|key, _| {
+        let keep = allowed.contains(&key.as_str());
+        if !keep {
+            dropped.push(format!("{}{}", prefix, key));
+        }
+        keep
+    });
+}
+
+/// 按白名单清理远程配置，返回被移除的字段
+///
+/// Claude 与 Gemini 的配置会整体写入 live 文件，Codex 的 config 会写入 config.toml，
+/// 因此只保留凭据与模型相关字段；OpenCode、OpenClaw 的 live 写入只取已知字段，不做处理。
+fn sanitize_remote_settings(app_type: AppType, config: &mut serde_json::Value) -> Vec<String> {
+    let mut dropped = Vec::new();
+    let Some(root) = config.as_object_mut() else {
+        return dropped;
+    };
+    match app_type {
+        AppType::Claude => {
+            retain_keys(root, &["env", "model"], "", &mut dropped);
+            if let Some(env) = root.get_mut("env").and_then(|v| v.as_object_mut()) {
+                retain_keys(env, REMOTE_CLAUDE_ENV_KEYS, "env.", &mut dropped);
+            }
+        }
+        AppType::Gemini => retain_keys(root, &["apiKey", "baseUrl", "model"], "", &mut dropped),
+        AppType::Codex => {
+            retain_keys(root, &["config", "auth"], "", &mut dropped);
+            let parsed = root
+                .get("config")
+                .and_then(|v| v.as_str())
+                .and_then(|text| toml::from_str::<toml::Table>(text).ok());
+            if let Some(mut table) = parsed {
+                table.retain(|key, _| {
+                    let keep = REMOTE_CODEX_CONFIG_KEYS.contains(&key);
+                    if !keep {
+                        dropped.push(format!("config.{}", key));
+                    }
+                    keep
+                });
+                if let Some(providers) = table.get_mut("model_providers").and_then(|v| v.as_table_mut()) {
+                    for (id, entry) in providers.iter_mut() {
+                        if let Some(entry) = entry.as_table_mut() {
+                            entry.retain(|key, _| {
+                                let keep = REMOTE_CODEX_PROVIDER_KEYS.contains(&key);
+                                if !keep {
+                                    dropped.push(format!("config.model_providers.{}.{}", id, key));
+                                }
+                                keep
+                            });
+                        }
+                    }
+                }
+                if let Ok(text) = toml::to_string(&table) {
+                    root.insert("config".to_string(), serde_json::Value::String(text));
+                }
+            }
+        }
+        AppType::OpenCode | AppType::OpenClaw => {}
+    }
+    dropped
+}
+
+/// 下载远程配置，返回内容与 Content-Type；非 2xx 或超过大小上限时报错
+async fn fetch_remote_config(url: &str) -> Result<(String, Option<String>)> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()?;
+    let mut response = client.get(url).send().await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        bail!("下载配置失败: HTTP {}", status);
+    }
+    let too_large = || anyhow::anyhow!("远程配置超过 {} KB 上限", MAX_REMOTE_CONFIG_BYTES / 1024);
+    if response
+        .content_length()
+        .is_some_and(|len| len > MAX_REMOTE_CONFIG_BYTES as u64)
+    {
+        return Err(too_large());
+    }
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > MAX_REMOTE_CONFIG_BYTES {
+            return Err(too_large());
+        }
+        body.extend_from_slice(&chunk);
+    }
+    let content = String::from_utf8(body).map_err(|_| anyhow::anyhow!("远程配置不是有效的 UTF-8 文本"))?;
+
+    Ok((content, content_type))
+}
+
+/// 按扩展名判断导入格式
+fn format_from_extension(path: &str) -> Option<ImportFormatArg> {
+    if path.ends_with(".json") {
+        Some(ImportFormatArg::Json)
+    } else if path.ends_with(".yaml") || path.ends_with(".yml") {
+        Some(ImportFormatArg::Yaml)
+    } else if path.ends_with(".toml") {
+        Some(ImportFormatArg::Toml)
+    } else if is_env_file(path) {
+        Some(ImportFormatArg::Env)
+    } else {
+        None
+    }
+}
+
+/// 按 Content-Type 判断导入格式（text/plain 等无法判断时返回 None）
+fn format_from_content_type(content_type: &str) -> Option<ImportFormatArg> {
+    let mime = content_type.split(';').next().unwrap_or("").trim().to_lowercase();
+    if mime == "application/json" || mime.ends_with("+json") {
+        Some(ImportFormatArg::Json)
+    } else if mime.contains("yaml") {
+        Some(ImportFormatArg::Yaml)
+    } else if mime.contains("toml") {
+        Some(ImportFormatArg::Toml)
+    } else {
+        None
+    }
+}
+
+/// 按格式解析导入内容为供应商配置；未指定格式时自动检测
+fn parse_import_settings(
+    app_type: AppType,
+    content: &str,
+    file_format: Option<ImportFormatArg>,
+) -> Result<serde_json::Value> {
+    let settings_config: serde_json::Value = match file_format {
+        Some(ImportFormatArg::Json) => serde_json::from_str(content)?,
+        Some(ImportFormatArg::Yaml) => serde_yaml::from_str(content)?,
+        // 对于 TOML，包装成 JSON
+        Some(ImportFormatArg::Toml) => json!({ "config": content }),
+        Some(ImportFormatArg::Env) => settings_from_env(app_type, &parse_env_file(content)?)?,
+        None => {
+            // 尝试自动检测，无法解析为对象时按 .env 处理
+            let parsed: Option<serde_json::Value> = serde_json::from_str(content)
+                .ok()
+                .or_else(|| serde_yaml::from_str(content).ok());
+            match parsed {
+                Some(value) if value.is_object() => value,
+                _ => settings_from_env(app_type, &parse_env_file(content)?)?,
+            }
+        }
+    };
+
+    Ok(settings_config)
+}
+
 /// 删除供应商
 pub fn remove(_ctx: &OutputContext, name: &str, app: AppTypeArg, yes: bool) -> Result<()> {
     let state = AppState::init()?;
@@ -273,13 +686,13 @@ pub fn remove(_ctx: &OutputContext, name: &str, app: AppTypeArg, yes: bool) -> R
 
     if app_types.len() > 1 {
         print_error("删除供应商时请指定具体的应用类型，例如: --app claude");
-        bail!("未指定应用类型");
+        return Err(AppError::InvalidInput("未指定应用类型".to_string()).into());
     }
 
     let app_type = app_types[0];
 
     // 查找供应商
-    let provider = ProviderService::find(&state, app_type, name)?;
+    let provider = find_unique(&state, app_type, name)?;
 
     match provider {
         Some(p) => {
@@ -315,9 +728,44 @@ pub fn remove(_ctx: &OutputContext, name: &str, app: AppTypeArg, yes: bool) -> R
         }
         None => {
             print_error(&format!("未找到供应商: {}", name));
-            bail!("供应商不存在");
+            Err(AppError::ProviderNotFound(name.to_string()).into())
+        }
+    }
+}
+
+/// 将供应商移动到另一个应用
+pub fn move_to(_ctx: &OutputContext, name: &str, from: AppTypeArg, to: AppTypeArg) -> Result<()> {
+    let (from_types, to_types) = (from.to_app_types(), to.to_app_types());
+    if from_types.len() > 1 || to_types.len() > 1 {
+        print_error("移动供应商时请指定具体的应用类型，例如: --from claude --to gemini");
+        return Err(AppError::InvalidInput("未指定应用类型".to_string()).into());
+    }
+    let (from_type, to_type) = (from_types[0], to_types[0]);
+
+    let state = AppState::init()?;
+    let Some(provider) = find_unique(&state, from_type, name)? else {
+        print_error(&format!("未找到供应商: {}", name));
+        return Err(AppError::ProviderNotFound(name.to_string()).into());
+    };
+
+    let (moved, unmapped) = match ProviderService::move_provider(&state, &provider.id, from_type, to_type) {
+        Ok(result) => result,
+        Err(e) => {
+            print_error(&format!("移动失败: {}", e));
+            return Err(e.into());
         }
+    };
+
+    if !unmapped.is_empty() {
+        print_warning(&format!("以下字段无法映射，未迁移: {}", unmapped.join(", ")));
     }
+    print_success(&format!(
+        "已将供应商 {} 从 {} 移动到 {}",
+        moved.name,
+        from_type.display_name(),
+        to_type.display_name()
+    ));
+    Ok(())
 }
 
 /// 更新配置
@@ -344,19 +792,20 @@ pub fn edit(
     model: Option<String>,
     small_model: Option<String>,
     new_name: Option<String>,
+    category: Option<String>,
 ) -> Result<()> {
     let state = AppState::init()?;
     let app_types = app.to_app_types();
 
     if app_types.len() > 1 {
         print_error("编辑供应商时请指定具体的应用类型，例如: --app claude");
-        bail!("未指定应用类型");
+        return Err(AppError::InvalidInput("未指定应用类型".to_string()).into());
     }
 
     let app_type = app_types[0].clone();
 
     // 查找供应商
-    let provider = ProviderService::find(&state, app_type.clone(), name)?;
+    let provider = find_unique(&state, app_type, name)?;
 
     match provider {
         Some(mut p) => {
@@ -367,122 +816,20 @@ pub fn edit(
                 p.name = n.clone();
             }
 
-            // 根据应用类型更新配置
-            match app_type {
-                AppType::Claude => {
-                    if let Some(env) = p.settings_config.get_mut("env") {
-                        if let Some(obj) = env.as_object_mut() {
-                            if let Some(key) = &api_key {
-                                obj.insert("ANTHROPIC_AUTH_TOKEN".to_string(), json!(key));
-                            }
-                            if let Some(url) = &base_url {
-                                obj.insert("ANTHROPIC_BASE_URL".to_string(), json!(url));
-                            }
-                            if let Some(m) = &model {
-                                obj.insert("ANTHROPIC_MODEL".to_string(), json!(m));
-                            }
-                            if let Some(sm) = &small_model {
-                                obj.insert("ANTHROPIC_SMALL_FAST_MODEL".to_string(), json!(sm));
-                            }
-                        }
-                    }
-                }
-                AppType::Codex => {
-                    // Codex 使用 TOML 格式，需要重建配置
-                    if api_key.is_some() || base_url.is_some() || model.is_some() {
-                        let current_config = p.settings_config.get("config")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("");
-
-                        // 提取当前值
-                        let mut current_base_url = "https://api.openai.com/v1".to_string();
-                        let mut current_model = "gpt-4".to_string();
-                        for line in current_config.lines() {
-                            if line.trim().starts_with("base_url") {
-                                if let Some(v) = line.split('=').nth(1) {
-                                    current_base_url = v.trim().trim_matches('"').to_string();
-                                }
-                            }
-                            if line.trim().starts_with("model =") {
-                                if let Some(v) = line.split('=').nth(1) {
-                                    current_model = v.trim().trim_matches('"').to_string();
-                                }
-                            }
-                        }
-
-                        let base_url_updated = base_url.is_some();
-                        let model_updated = model.is_some();
-                        let new_base_url = base_url.unwrap_or(current_base_url);
-                        let new_model = model.unwrap_or(current_model);
-
-                        let config = format!(
-                            r#"model_provider = "openai"
-model = "{}"
-
-[model_providers.openai]
-name = "OpenAI"
-base_url = "{}"
-wire_api = "responses"
-"#,
-                            new_model, new_base_url
-                        );
-
-                        if let Some(obj) = p.settings_config.as_object_mut() {
-                            obj.insert("config".to_string(), json!(config));
-                        }
-
-                        if let Some(key) = &api_key {
-                            let auth = format!(
-                                r#"[openai]
-api_key = "{}"
-"#,
-                                key
-                            );
-                            if let Some(obj) = p.settings_config.as_object_mut() {
-                                obj.insert("auth".to_string(), json!(auth));
-                            }
-                        }
-
-                        // 保存更新
-                        ProviderService::update(&state, app_type.clone(), p)?;
-                        print_success(&format!(
-                            "已更新供应商: {} ({})",
-                            new_name.as_ref().map(|n| n.as_str()).unwrap_or(&old_name),
-                            app_type.display_name()
-                        ));
-
-                        // 显示更新的字段
-                        if api_key.is_some() {
-                            print_info("  - API Key 已更新");
-                        }
-                        if base_url_updated {
-                            print_info("  - Base URL 已更新");
-                        }
-                        if model_updated {
-                            print_info("  - 模型 已更新");
-                        }
-                        
-                        return Ok(());
-                    }
-                }
-                AppType::Gemini => {
-                    if let Some(obj) = p.settings_config.as_object_mut() {
-                        if let Some(key) = &api_key {
-                            obj.insert("apiKey".to_string(), json!(key));
-                        }
-                        if let Some(url) = &base_url {
-                            obj.insert("baseUrl".to_string(), json!(url));
-                        }
-                        if let Some(m) = &model {
-                            obj.insert("model".to_string(), json!(m));
-                        }
-                    }
-                }
-                AppType::OpenCode | AppType::OpenClaw => {
-                    print_warning("该应用供应商编辑功能尚未完全实现");
-                }
+            // 更新分类，空字符串表示清除
+            if let Some(c) = &category {
+                p.category = (!c.trim().is_empty()).then(|| c.trim().to_string());
             }
 
+            // 根据应用类型更新配置
+            let fields = ProviderEditFields {
+                api_key: api_key.as_deref(),
+                base_url: base_url.as_deref(),
+                model: model.as_deref(),
+                small_model: small_model.as_deref(),
+            };
+            apply_provider_edit(&mut p.settings_config, &app_type, &fields)?;
+
             // 保存更新
             ProviderService::update(&state, app_type.clone(), p)?;
             print_success(&format!(
@@ -504,6 +851,9 @@ api_key = "{}"
             if small_model.is_some() {
                 print_info("  - 小模型 已更新");
             }
+            if category.is_some() {
+                print_info("  - 分类 已更新");
+            }
 
             Ok(())
         }
@@ -519,32 +869,474 @@ api_key = "{}"
                 }
             }
 
-            bail!("供应商不存在");
+            Err(AppError::ProviderNotFound(name.to_string()).into())
         }
     }
 }
 
-/// 测试 API 命令
-pub async fn test_api(
-    _ctx: &OutputContext,
-    name: Option<String>,
-    app: AppTypeArg,
-    api_key: Option<String>,
-    base_url: Option<String>,
-    model: Option<String>,
-    timeout: u64,
-) -> Result<()> {
+/// 按 JSON Pointer 修改供应商配置字段
+pub fn set_field(_ctx: &OutputContext, name: &str, app: AppTypeArg, pointer: &str, value: &str) -> Result<()> {
+    let app_types = app.to_app_types();
+    if app_types.len() > 1 {
+        print_error("修改字段时请指定具体的应用类型，例如: --app claude");
+        return Err(AppError::InvalidInput("未指定应用类型".to_string()).into());
+    }
+    let app_type = app_types[0];
+
+    let state = AppState::init()?;
+    let Some(provider) = find_unique(&state, app_type, name)? else {
+        print_error(&format!("未找到供应商: {}", name));
+        return Err(AppError::ProviderNotFound(name.to_string()).into());
+    };
+
+    let value = parse_field_value(value);
+    let rendered = value.to_string();
+    if let Err(e) = ProviderService::set_field(&state, app_type, &provider.id, pointer, value) {
+        print_error(&e.to_string());
+        return Err(e.into());
+    }
+    print_success(&format!("已更新 {} 的 {} = {}", provider.name, pointer, rendered));
+
+    Ok(())
+}
+
+/// 优先按 JSON 解析字段值，失败时作为普通字符串
+fn parse_field_value(raw: &str) -> serde_json::Value {
+    serde_json::from_str(raw).unwrap_or_else(|_| json!(raw))
+}
+
+/// 供应商编辑字段，None 表示保持不变
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct ProviderEditFields<'a> {
+    pub api_key: Option<&'a str>,
+    pub base_url: Option<&'a str>,
+    pub model: Option<&'a str>,
+    pub small_model: Option<&'a str>,
+}
+
+/// 按应用类型把编辑字段写入供应商配置（edit 与 batch edit 共用）
+///
+/// Claude 写入 `env`，Codex 改写 TOML，Gemini 与 OpenClaw 写入顶层键，OpenCode 写入 `options`。
+/// OpenCode/OpenClaw 的模型为列表，不支持通过编辑修改。
+pub(crate) fn apply_provider_edit(
+    settings: &mut serde_json::Value,
+    app_type: &AppType,
+    fields: &ProviderEditFields,
+) -> Result<()> {
+    match app_type {
+        AppType::Claude => {
+            let Some(obj) = settings.as_object_mut() else {
+                bail!("Claude 供应商配置格式无效");
+            };
+            let env = obj.entry("env").or_insert_with(|| json!({}));
+            let Some(env) = env.as_object_mut() else {
+                bail!("Claude 供应商配置中的 env 格式无效");
+            };
+            if let Some(key) = fields.api_key {
+                env.insert("ANTHROPIC_AUTH_TOKEN".to_string(), json!(key));
+            }
+            if let Some(url) = fields.base_url {
+                env.insert("ANTHROPIC_BASE_URL".to_string(), json!(url));
+            }
+            if let Some(m) = fields.model {
+                env.insert("ANTHROPIC_MODEL".to_string(), json!(m));
+            }
+            if let Some(sm) = fields.small_model {
+                env.insert("ANTHROPIC_SMALL_FAST_MODEL".to_string(), json!(sm));
+            }
+        }
+        AppType::Codex => {
+            apply_codex_edit(settings, fields.api_key, fields.base_url, fields.model)?;
+        }
+        AppType::Gemini => {
+            if let Some(obj) = settings.as_object_mut() {
+                if let Some(key) = fields.api_key {
+                    obj.insert("apiKey".to_string(), json!(key));
+                }
+                if let Some(url) = fields.base_url {
+                    obj.insert("baseUrl".to_string(), json!(url));
+                }
+                if let Some(m) = fields.model {
+                    obj.insert("model".to_string(), json!(m));
+                }
+            }
+        }
+        AppType::OpenCode | AppType::OpenClaw => {
+            if fields.model.is_some() || fields.small_model.is_some() {
+                return Err(AppError::InvalidInput(format!(
+                    "{} 的模型以列表形式配置，不支持通过编辑修改",
+                    app_type.display_name()
+                ))
+                .into());
+            }
+            let Some(obj) = settings.as_object_mut() else {
+                bail!("{} 供应商配置格式无效", app_type.display_name());
+            };
+            let (target, url_key) = if *app_type == AppType::OpenCode {
+                let options = obj.entry("options").or_insert_with(|| json!({}));
+                let Some(options) = options.as_object_mut() else {
+                    bail!("OpenCode 供应商配置中的 options 格式无效");
+                };
+                (options, "baseURL")
+            } else {
+                (obj, "baseUrl")
+            };
+            if let Some(key) = fields.api_key {
+                target.insert("apiKey".to_string(), json!(key));
+            }
+            if let Some(url) = fields.base_url {
+                target.insert(url_key.to_string(), json!(url));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 按需修改 Codex 供应商配置
+///
+/// 仅在指定 base_url / model 时改写 config，并且只修改对应字段，
+/// 其余键与 `[model_providers.*]` 段落保持不变。
+fn apply_codex_edit(
+    settings: &mut serde_json::Value,
+    api_key: Option<&str>,
+    base_url: Option<&str>,
+    model: Option<&str>,
+) -> Result<()> {
+    let Some(obj) = settings.as_object_mut() else {
+        bail!("Codex 供应商配置格式无效");
+    };
+
+    if base_url.is_some() || model.is_some() {
+        let current = obj.get("config").and_then(|v| v.as_str()).unwrap_or("");
+        let mut table: toml::Table = toml::from_str(current)
+            .map_err(|e| anyhow::anyhow!("解析 Codex config.toml 失败: {}", e))?;
+
+        if let Some(m) = model {
+            table.insert("model".to_string(), toml::Value::String(m.to_string()));
+        }
+
+        if let Some(url) = base_url {
+            let provider_key = match table.get("model_provider").and_then(|v| v.as_str()) {
+                Some(key) => key.to_string(),
+                None => {
+                    table.insert(
+                        "model_provider".to_string(),
+                        toml::Value::String("openai".to_string()),
+                    );
+                    "openai".to_string()
+                }
+            };
+
+            let providers = table
+                .entry("model_providers")
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+            let Some(providers) = providers.as_table_mut() else {
+                bail!("Codex 配置中的 model_providers 格式无效");
+            };
+            let section = providers.entry(provider_key.clone()).or_insert_with(|| {
+                let mut section = toml::Table::new();
+                section.insert("name".to_string(), toml::Value::String(provider_key));
+                section.insert(
+                    "wire_api".to_string(),
+                    toml::Value::String("responses".to_string()),
+                );
+                toml::Value::Table(section)
+            });
+            let Some(section) = section.as_table_mut() else {
+                bail!("Codex 配置中的 model_providers 格式无效");
+            };
+            section.insert("base_url".to_string(), toml::Value::String(url.to_string()));
+        }
+
+        obj.insert("config".to_string(), json!(toml::to_string(&table)?));
+    }
+
+    if let Some(key) = api_key {
+        let auth = format!(
+            r#"[openai]
+api_key = "{}"
+"#,
+            key
+        );
+        obj.insert("auth".to_string(), json!(auth));
+    }
+
+    Ok(())
+}
+
+/// 显示单个供应商的完整信息
+pub fn show(ctx: &OutputContext, name: &str, app: AppTypeArg, show_key: bool) -> Result<()> {
+    let state = AppState::init()?;
+    let app_types = app.to_app_types();
+
+    if app_types.len() > 1 {
+        print_error("请指定具体的应用类型，例如: --app claude");
+        return Err(AppError::InvalidInput("未指定应用类型".to_string()).into());
+    }
+
+    let app_type = app_types[0];
+    let Some(provider) = ProviderService::find(&state, app_type, name)? else {
+        print_error(&format!("未找到供应商: {}", name));
+        return Err(AppError::ProviderNotFound(name.to_string()).into());
+    };
+
+    match ctx.format {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&show_json(provider, app_type, show_key))?);
+        }
+        OutputFormat::Yaml => {
+            println!("{}", serde_yaml::to_string(&show_json(provider, app_type, show_key))?);
+        }
+        OutputFormat::Table => {
+            let or_dash = |v: Option<String>| v.unwrap_or_else(|| "-".to_string());
+            let created_at = provider
+                .created_at
+                .and_then(|ts| Local.timestamp_opt(ts, 0).single())
+                .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string());
+            let small_model = match app_type {
+                AppType::Claude => provider
+                    .settings_config
+                    .pointer("/env/ANTHROPIC_SMALL_FAST_MODEL")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string),
+                _ => None,
+            };
+            let icon = match (&provider.icon, &provider.icon_color) {
+                (Some(icon), Some(color)) => Some(format!("{} ({})", icon, color)),
+                (Some(icon), None) => Some(icon.clone()),
+                (None, Some(color)) => Some(color.clone()),
+                (None, None) => None,
+            };
+            let credential = ProviderService::extract_credentials(&provider, &app_type)
+                .ok()
+                .map(|(key, _)| key)
+                .filter(|key| !key.is_empty())
+                .map(|key| if show_key { key } else { mask_api_key(&key) });
+
+            println!();
+            println!("{}", glyphs(&format!("═══ {} ═══", provider.name)).cyan().bold());
+            println!("  ID:       {}", provider.id);
+            println!("  应用:     {}", app_type.display_name());
+            println!("  分类:     {}", or_dash(provider.category.clone()));
+            println!("  创建时间: {}", or_dash(created_at));
+            println!("  Base URL: {}", or_dash(provider.get_base_url()));
+            println!("  模型:     {}", or_dash(provider.get_model()));
+            println!("  小模型:   {}", or_dash(small_model));
+            println!("  图标:     {}", or_dash(icon));
+            println!("  故障转移: {}", if provider.in_failover_queue { "是" } else { "否" });
+            println!("  API Key:  {}", or_dash(credential));
+            if let Some(notes) = &provider.notes {
+                println!("  备注:");
+                for line in notes.lines() {
+                    println!("    {}", line);
+                }
+            } else {
+                println!("  备注:     -");
+            }
+            println!();
+        }
+    }
+
+    Ok(())
+}
+
+/// `show -o json` 的输出内容，未指定 `--show-key` 时移除凭据字段
+fn show_json(mut provider: Provider, app_type: AppType, show_key: bool) -> serde_json::Value {
+    if !show_key {
+        ProviderService::redact_credentials(&mut provider, &app_type);
+    }
+    serde_json::to_value(&provider).unwrap_or_default()
+}
+
+/// 查看或修改供应商备注
+pub fn notes(_ctx: &OutputContext, name: &str, app: AppTypeArg, set: Option<String>, clear: bool) -> Result<()> {
+    let state = AppState::init()?;
+    let app_types = app.to_app_types();
+
+    if app_types.len() > 1 {
+        print_error("请指定具体的应用类型，例如: --app claude");
+        return Err(AppError::InvalidInput("未指定应用类型".to_string()).into());
+    }
+
+    let app_type = app_types[0];
+    let modify = set.is_some() || clear;
+
+    let Some((provider, notes)) = apply_notes(&state, app_type, name, set, clear)? else {
+        print_error(&format!("未找到供应商: {}", name));
+        return Err(AppError::ProviderNotFound(name.to_string()).into());
+    };
+
+    match (modify, notes) {
+        (true, Some(_)) => print_success(&format!("已更新备注: {}", provider.name)),
+        (true, None) => print_success(&format!("已清除备注: {}", provider.name)),
+        (false, Some(notes)) => println!("{}", notes),
+        (false, None) => print_info(&format!("供应商 {} 没有备注", provider.name)),
+    }
+
+    Ok(())
+}
+
+/// 按参数设置或清除备注，返回供应商与操作后的备注；找不到供应商时返回 `None`
+fn apply_notes(
+    state: &AppState,
+    app_type: AppType,
+    name: &str,
+    set: Option<String>,
+    clear: bool,
+) -> Result<Option<(Provider, Option<String>)>> {
+    let Some(provider) = ProviderService::find(state, app_type, name)? else {
+        return Ok(None);
+    };
+
+    if set.is_none() && !clear {
+        let notes = provider.notes.clone();
+        return Ok(Some((provider, notes)));
+    }
+
+    let notes = if clear { None } else { set };
+    ProviderService::update_notes(state, app_type, &provider.id, notes)?;
+    let notes = ProviderService::find(state, app_type, &provider.id)?.and_then(|p| p.notes);
+    Ok(Some((provider, notes)))
+}
+
+/// 对比两个供应商
+pub fn compare(ctx: &OutputContext, left: &str, right: &str, app: AppTypeArg) -> Result<()> {
+    let app_types = app.to_app_types();
+    if app_types.len() > 1 {
+        print_error("对比供应商时请指定具体的应用类型，例如: --app claude");
+        return Err(AppError::InvalidInput("未指定应用类型".to_string()).into());
+    }
+    let app_type = app_types[0];
+
+    let state = AppState::init()?;
+    let find = |name: &str| -> Result<Provider> {
+        match find_unique(&state, app_type, name)? {
+            Some(p) => Ok(p),
+            None => {
+                print_error(&format!("未找到供应商: {}", name));
+                Err(AppError::ProviderNotFound(name.to_string()).into())
+            }
+        }
+    };
+    let (a, b) = (find(left)?, find(right)?);
+
+    let mut comparison = ProviderService::compare(&a, &b, &app_type);
+    for diff in &mut comparison.settings_diff {
+        for value in [diff.left.as_mut(), diff.right.as_mut()].into_iter().flatten() {
+            mask_diff_value(&diff.path, value);
+        }
+    }
+    let diff = &mut comparison.diff;
+    for (path, value) in diff.only_in_a.iter_mut().chain(diff.only_in_b.iter_mut()) {
+        mask_diff_value(path, value);
+    }
+    for (path, changed) in diff.changed.iter_mut() {
+        mask_diff_value(path, &mut changed.a);
+        mask_diff_value(path, &mut changed.b);
+    }
+
+    match ctx.format {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&comparison)?);
+            return Ok(());
+        }
+        OutputFormat::Yaml => {
+            println!("{}", serde_yaml::to_string(&comparison)?);
+            return Ok(());
+        }
+        OutputFormat::Table => {}
+    }
+
+    println!("\n{} 供应商对比 ({})\n", sym("🔀"), app_type.display_name());
+    println!("  {:<10} {:<36} {:<36}", "字段", comparison.left, comparison.right);
+    println!("  {}", "-".repeat(84));
+    for field in &comparison.fields {
+        let marker = if field.differs() { sym("≠") } else { " " };
+        println!(
+            "{} {:<10} {:<36} {:<36}",
+            marker,
+            field.field,
+            field.left.as_deref().unwrap_or("-"),
+            field.right.as_deref().unwrap_or("-")
+        );
+    }
+
+    println!();
+    if comparison.settings_diff.is_empty() {
+        print_success("配置内容完全相同");
+        return Ok(());
+    }
+
+    println!("配置差异 ({} 项):", comparison.settings_diff.len());
+    let show = |v: &Option<serde_json::Value>| {
+        v.as_ref().map(|v| v.to_string()).unwrap_or_else(|| "-".to_string())
+    };
+    for diff in &comparison.settings_diff {
+        let symbol = match diff.kind {
+            DiffKind::Added => "+",
+            DiffKind::Removed => "-",
+            DiffKind::Changed => "~",
+        };
+        println!("  {} {}: {} {} {}", symbol, diff.path, show(&diff.left), sym("→"), show(&diff.right));
+    }
+
+    Ok(())
+}
+
+/// 遮蔽差异值中的凭据：密钥路径直接遮蔽，对象按键名递归遮蔽
+fn mask_diff_value(path: &str, value: &mut serde_json::Value) {
+    if is_sensitive_path(path) {
+        *value = mask_json_value(value.take());
+    } else {
+        super::config::mask_json_secrets(value);
+    }
+}
+
+/// JSON Pointer 路径是否指向密钥类字段
+fn is_sensitive_path(path: &str) -> bool {
+    let key = path.rsplit('/').next().unwrap_or_default().to_uppercase();
+    ["KEY", "TOKEN", "SECRET", "PASSWORD"].iter().any(|s| key.contains(s))
+}
+
+/// 隐藏 JSON 字符串值
+fn mask_json_value(value: serde_json::Value) -> serde_json::Value {
+    match value.as_str() {
+        Some(s) => serde_json::Value::String(mask_api_key(s)),
+        None => value,
+    }
+}
+
+/// 测试 API 命令
+#[allow(clippy::too_many_arguments)]
+pub async fn test_api(
+    _ctx: &OutputContext,
+    name: Option<String>,
+    app: AppTypeArg,
+    api_key: Option<String>,
+    base_url: Option<String>,
+    model: Option<String>,
+    timeout: u64,
+    cost: bool,
+    record: bool,
+    save_result: bool,
+    retries: u32,
+) -> Result<()> {
     let app_types = app.to_app_types();
 
     if app_types.len() > 1 {
         print_error("测试 API 时请指定具体的应用类型，例如: --app claude");
-        bail!("未指定应用类型");
+        return Err(AppError::InvalidInput("未指定应用类型".to_string()).into());
     }
 
     let app_type = app_types[0].clone();
+    // 需要记录或保存测试结果的供应商
+    let mut record_target: Option<(AppState, String)> = None;
 
     // 获取测试参数
     let (test_key, test_url, test_model) = if let Some(key) = api_key {
+        if record || save_result {
+            print_warning("--record 与 --save-result 仅在测试已保存的供应商时生效");
+        }
         // 直接使用传入的参数
         let url = base_url.unwrap_or_else(|| get_default_base_url(&app_type));
         let model = model.unwrap_or_else(|| get_default_model(&app_type));
@@ -556,16 +1348,19 @@ pub async fn test_api(
 
         match provider {
             Some(p) => {
-                let (key, url) = ProviderService::extract_credentials(&p, &app_type)?;
+                let (key, url) = ProviderService::resolve_credentials(&p, &app_type)?;
                 let model = model.unwrap_or_else(|| p.get_model().unwrap_or_else(|| get_default_model(&app_type)));
 
                 print_info(&format!("测试供应商: {} ({})", p.name, mask_api_key(&key)));
 
+                if record || save_result {
+                    record_target = Some((state, p.id.clone()));
+                }
                 (key, url, model)
             }
             None => {
                 print_error(&format!("未找到供应商: {}", provider_name));
-                bail!("供应商不存在");
+                return Err(AppError::ProviderNotFound(provider_name.to_string()).into());
             }
         }
     } else {
@@ -578,7 +1373,7 @@ pub async fn test_api(
         bail!("API Key 为空");
     }
 
-    println!("\n🧪 API 测试\n");
+    println!("{}", glyphs("\n🧪 API 测试\n"));
     println!("  应用类型: {}", app_type.display_name());
     println!("  Base URL: {}", test_url);
     println!("  模型: {}", test_model);
@@ -587,34 +1382,317 @@ pub async fn test_api(
 
     print_info("正在测试...");
 
-    match test_api_key(&app_type, &test_key, Some(&test_url), Some(&test_model), timeout).await {
-        Ok(true) => {
-            print_success("✓ API 测试通过！");
-            Ok(())
+    let started = Instant::now();
+    let result = test_api_key(&app_type, &test_key, Some(&test_url), Some(&test_model), timeout, retries).await;
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+
+    if let Some((state, id)) = record_target.as_ref().filter(|_| record) {
+        let status = match &result {
+            Ok(outcome) if outcome.passed => "OK",
+            Ok(_) => "WARN",
+            Err(_) => "FAIL",
+        };
+        let line = format_test_note(Local::now().date_naive(), status, elapsed_ms);
+        match ProviderService::record_test_note(state, app_type, id, &line) {
+            Ok(()) => print_info(&format!("已记录到备注: {}", line)),
+            Err(e) => print_warning(&format!("记录测试结果失败: {}", e)),
         }
-        Ok(false) => {
-            print_warning("⚠ API 测试未返回预期结果");
+    }
+
+    if let Some((state, id)) = record_target.as_ref().filter(|_| save_result) {
+        let saved = ProviderTestResult {
+            provider_id: id.clone(),
+            ok: matches!(&result, Ok(outcome) if outcome.passed),
+            latency_ms: result.is_ok().then_some(elapsed_ms),
+            message: match &result {
+                Ok(outcome) if outcome.passed => String::new(),
+                Ok(_) => "未返回预期结果".to_string(),
+                Err(e) => e.to_string(),
+            },
+            tested_at: chrono::Utc::now().timestamp_millis(),
+        };
+        match state.db.save_provider_test_result(app_type.as_str(), &saved) {
+            Ok(_) => print_info("已保存测试结果"),
+            Err(e) => print_warning(&format!("保存测试结果失败: {}", e)),
+        }
+    }
+
+    match result {
+        Ok(outcome) => {
+            if outcome.passed {
+                print_success("API 测试通过！");
+            } else {
+                print_warning("API 测试未返回预期结果");
+            }
+            if cost {
+                print_test_cost(&test_model, outcome.usage);
+            }
             Ok(())
         }
         Err(e) => {
-            print_error(&format!("✗ API 测试失败: {}", e));
+            print_error(&format!("API 测试失败: {}", e));
             bail!("API 测试失败");
         }
     }
 }
 
-/// 测试 API Key 有效性
-async fn test_api_key(
+/// `test --all` 的单条结果
+#[derive(Debug, Clone, Serialize, Tabled)]
+pub struct TestAllRow {
+    #[tabled(rename = "供应商")]
+    pub provider: String,
+    #[tabled(rename = "状态", display = "display_test_status")]
+    pub ok: bool,
+    #[tabled(rename = "延迟", display = "display_latency")]
+    pub latency_ms: Option<u64>,
+    #[tabled(rename = "说明")]
+    pub message: String,
+}
+
+fn display_test_status(ok: &bool) -> String {
+    if *ok {
+        format!("{} 可用", sym("✓"))
+    } else {
+        format!("{} 失败", sym("✗"))
+    }
+}
+
+fn display_latency(latency_ms: &Option<u64>) -> String {
+    latency_ms.map(|ms| format!("{}ms", ms)).unwrap_or_else(|| "-".to_string())
+}
+
+/// 并发测试应用下的所有供应商（`cc-switch test --all`）
+pub async fn test_all(
+    ctx: &OutputContext,
+    app: AppTypeArg,
+    timeout: u64,
+    concurrency: usize,
+    retries: u32,
+) -> Result<()> {
+    let app_types = app.to_app_types();
+    if app_types.len() > 1 {
+        print_error("测试 API 时请指定具体的应用类型，例如: --app claude");
+        return Err(AppError::InvalidInput("未指定应用类型".to_string()).into());
+    }
+    let app_type = app_types[0];
+
+    let state = AppState::init()?;
+    let providers: Vec<(String, Provider)> = ProviderService::list(&state, app_type)?
+        .into_iter()
+        .map(|(_, p)| (p.name.clone(), p))
+        .collect();
+    if providers.is_empty() {
+        print_info(&format!("{} 暂无供应商", app_type.display_name()));
+        return Ok(());
+    }
+
+    let human = ctx.format == OutputFormat::Table;
+    if human {
+        print_info(&format!(
+            "正在测试 {} 个 {} 供应商（并发 {}）...",
+            providers.len(),
+            app_type.display_name(),
+            concurrency.max(1)
+        ));
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(timeout))
+        .build()?;
+    let rows = run_concurrent_tests(providers, concurrency, |provider: Provider| {
+        let client = &client;
+        async move {
+            let (key, url) = ProviderService::resolve_credentials(&provider, &app_type)?;
+            if key.is_empty() {
+                bail!("未找到有效的 API Key");
+            }
+            let model = provider.get_model().unwrap_or_else(|| get_default_model(&app_type));
+            retry_with_backoff(retries.saturating_add(1), RETRY_BACKOFF, || {
+                test_api_key_with(client, &app_type, &key, Some(&url), Some(&model))
+            })
+            .await
+        }
+    })
+    .await;
+
+    let failed = rows.iter().filter(|r| !r.ok).count();
+    match ctx.format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&rows)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(&rows)?),
+        OutputFormat::Table => {
+            println!("{}", styled_table(&mut Table::new(&rows)));
+            let summary = format!("可用 {} 个，失败 {} 个", rows.len() - failed, failed);
+            if failed == 0 {
+                print_success(&summary);
+            } else {
+                print_warning(&summary);
+            }
+        }
+    }
+
+    // 存在失败时以非零状态退出（与输出格式无关），便于 CI 判断
+    if failed > 0 {
+        bail!("{} 个供应商测试失败", failed);
+    }
+    Ok(())
+}
+
+/// 以最多 `concurrency` 个并发执行 `tester`，结果按延迟升序排列（失败排在最后）
+async fn run_concurrent_tests<T, F, Fut>(items: Vec<(String, T)>, concurrency: usize, tester: F) -> Vec<TestAllRow>
+where
+    F: Fn(T) -> Fut,
+    Fut: std::future::Future<Output = Result<TestOutcome>>,
+{
+    let queue = std::sync::Mutex::new(items.into_iter());
+    let next = || queue.lock().ok().and_then(|mut q| q.next());
+
+    let workers = (0..concurrency.max(1)).map(|_| async {
+        let mut rows = Vec::new();
+        while let Some((name, item)) = next() {
+            let started = Instant::now();
+            let result = tester(item).await;
+            let latency_ms = started.elapsed().as_millis() as u64;
+            rows.push(match result {
+                Ok(outcome) => TestAllRow {
+                    provider: name,
+                    ok: outcome.passed,
+                    latency_ms: Some(latency_ms),
+                    message: if outcome.passed { String::new() } else { "未返回预期结果".to_string() },
+                },
+                Err(e) => TestAllRow {
+                    provider: name,
+                    ok: false,
+                    latency_ms: None,
+                    message: e.to_string(),
+                },
+            });
+        }
+        rows
+    });
+
+    let mut rows: Vec<TestAllRow> = join_all(workers).await.into_iter().flatten().collect();
+    rows.sort_by_key(|r| (!r.ok, r.latency_ms.unwrap_or(u64::MAX)));
+    rows
+}
+
+/// 打印探测请求的 token 用量与估算费用
+fn print_test_cost(model: &str, usage: Option<TokenUsage>) {
+    let Some(usage) = usage else {
+        print_info("响应中未包含用量信息");
+        return;
+    };
+
+    println!();
+    println!("  输入 tokens: {}", usage.input_tokens);
+    println!("  输出 tokens: {}", usage.output_tokens);
+
+    let pricing = AppState::init()
+        .ok()
+        .and_then(|state| state.db.get_model_pricing(model).ok().flatten());
+    match pricing {
+        Some(p) => println!(
+            "  估算费用: ${:.6} ({})",
+            p.estimate_cost(usage.input_tokens, usage.output_tokens),
+            p.display_name
+        ),
+        None => println!("  估算费用: 未知（无 {} 的定价信息）", model),
+    }
+}
+
+/// API 测试结果
+#[derive(Debug, Clone, Default)]
+pub struct TestOutcome {
+    /// 是否通过
+    pub passed: bool,
+    /// 探测请求消耗的 token（响应中包含 usage 时）
+    pub usage: Option<TokenUsage>,
+}
+
+impl TestOutcome {
+    fn passed(usage: Option<TokenUsage>) -> Self {
+        Self { passed: true, usage }
+    }
+}
+
+/// Token 用量
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TokenUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+/// 按应用类型解析响应中的 usage 字段
+fn parse_usage(app_type: &AppType, body: &serde_json::Value) -> Option<TokenUsage> {
+    let (block, input_key, output_key) = match app_type {
+        AppType::Claude => (body.get("usage")?, "input_tokens", "output_tokens"),
+        AppType::Codex | AppType::OpenCode | AppType::OpenClaw => {
+            (body.get("usage")?, "prompt_tokens", "completion_tokens")
+        }
+        AppType::Gemini => (
+            body.get("usageMetadata")?,
+            "promptTokenCount",
+            "candidatesTokenCount",
+        ),
+    };
+
+    Some(TokenUsage {
+        input_tokens: block.get(input_key).and_then(|v| v.as_u64()).unwrap_or(0),
+        output_tokens: block.get(output_key).and_then(|v| v.as_u64()).unwrap_or(0),
+    })
+}
+
+/// API 测试遇到瞬时错误时的默认重试次数
+pub(crate) const DEFAULT_TEST_RETRIES: u32 = 2;
+
+/// 将请求发送错误分类，连接失败或被重置时标记为可重试
+pub(crate) fn classify_send_error(err: reqwest::Error) -> CheckFailure {
+    let reset = std::iter::successors(std::error::Error::source(&err), |e| e.source()).any(|e| {
+        e.downcast_ref::<io::Error>()
+            .is_some_and(|io| io.kind() == io::ErrorKind::ConnectionReset)
+    });
+    if err.is_connect() || reset {
+        CheckFailure::transient(err.into())
+    } else {
+        CheckFailure::fatal(err.into())
+    }
+}
+
+/// 非预期 HTTP 状态的错误，5xx 标记为可重试
+pub(crate) fn http_status_error(status: reqwest::StatusCode, body: &str) -> CheckFailure {
+    let error = anyhow::anyhow!("HTTP {}: {}", status, body);
+    if status.is_server_error() {
+        CheckFailure::transient(error)
+    } else {
+        CheckFailure::fatal(error)
+    }
+}
+
+/// 测试 API Key 有效性（瞬时错误时最多重试 `retries` 次）
+pub(crate) async fn test_api_key(
     app_type: &AppType,
     api_key: &str,
     base_url: Option<&str>,
     model: Option<&str>,
     timeout_secs: u64,
-) -> Result<bool> {
+    retries: u32,
+) -> Result<TestOutcome> {
     let client = reqwest::Client::builder()
         .timeout(Duration::from_secs(timeout_secs))
         .build()?;
+    retry_with_backoff(retries.saturating_add(1), RETRY_BACKOFF, || {
+        test_api_key_with(&client, app_type, api_key, base_url, model)
+    })
+    .await
+}
 
+/// 使用给定的 HTTP 客户端测试 API Key（多个测试可共享连接池）
+async fn test_api_key_with(
+    client: &reqwest::Client,
+    app_type: &AppType,
+    api_key: &str,
+    base_url: Option<&str>,
+    model: Option<&str>,
+) -> std::result::Result<TestOutcome, CheckFailure> {
     match app_type {
         AppType::Claude => {
             let url = format!(
@@ -634,21 +1712,23 @@ async fn test_api_key(
                     "messages": [{"role": "user", "content": "hi"}]
                 }))
                 .send()
-                .await?;
+                .await
+                .map_err(classify_send_error)?;
 
             let status = response.status();
             if status.is_success() {
-                Ok(true)
+                let body: serde_json::Value = response.json().await.unwrap_or_default();
+                Ok(TestOutcome::passed(parse_usage(app_type, &body)))
             } else if status.as_u16() == 401 {
-                bail!("API Key 无效或已过期");
+                Err(CheckFailure::fatal(anyhow::anyhow!("API Key 无效或已过期")))
             } else if status.as_u16() == 403 {
-                bail!("权限不足");
+                Err(CheckFailure::fatal(anyhow::anyhow!("权限不足")))
             } else if status.as_u16() == 429 {
                 // Rate limit 说明 key 是有效的
-                Ok(true)
+                Ok(TestOutcome::passed(None))
             } else {
                 let body: String = response.text().await.unwrap_or_default();
-                bail!("HTTP {}: {}", status, body);
+                Err(http_status_error(status, &body))
             }
         }
         AppType::Codex | AppType::OpenCode | AppType::OpenClaw => {
@@ -669,18 +1749,20 @@ async fn test_api_key(
                     "messages": [{"role": "user", "content": "hi"}]
                 }))
                 .send()
-                .await?;
+                .await
+                .map_err(classify_send_error)?;
 
             let status = response.status();
             if status.is_success() {
-                Ok(true)
+                let body: serde_json::Value = response.json().await.unwrap_or_default();
+                Ok(TestOutcome::passed(parse_usage(app_type, &body)))
             } else if status.as_u16() == 401 {
-                bail!("API Key 无效或已过期");
+                Err(CheckFailure::fatal(anyhow::anyhow!("API Key 无效或已过期")))
             } else if status.as_u16() == 429 {
-                Ok(true)
+                Ok(TestOutcome::passed(None))
             } else {
                 let body: String = response.text().await.unwrap_or_default();
-                bail!("HTTP {}: {}", status, body);
+                Err(http_status_error(status, &body))
             }
         }
         AppType::Gemini => {
@@ -698,22 +1780,24 @@ async fn test_api_key(
                     "contents": [{"parts": [{"text": "hi"}]}]
                 }))
                 .send()
-                .await?;
+                .await
+                .map_err(classify_send_error)?;
 
             let status = response.status();
             if status.is_success() {
-                Ok(true)
+                let body: serde_json::Value = response.json().await.unwrap_or_default();
+                Ok(TestOutcome::passed(parse_usage(app_type, &body)))
             } else if status.as_u16() == 400 {
                 let body: String = response.text().await.unwrap_or_default();
                 if body.contains("API_KEY_INVALID") {
-                    bail!("API Key 无效");
+                    return Err(CheckFailure::fatal(anyhow::anyhow!("API Key 无效")));
                 }
-                bail!("请求错误: {}", body);
+                Err(CheckFailure::fatal(anyhow::anyhow!("请求错误: {}", body)))
             } else if status.as_u16() == 429 {
-                Ok(true)
+                Ok(TestOutcome::passed(None))
             } else {
                 let body: String = response.text().await.unwrap_or_default();
-                bail!("HTTP {}: {}", status, body);
+                Err(http_status_error(status, &body))
             }
         }
     }
@@ -729,10 +1813,310 @@ fn get_default_base_url(app_type: &AppType) -> String {
 }
 
 /// 获取默认模型
-fn get_default_model(app_type: &AppType) -> String {
+pub(crate) fn get_default_model(app_type: &AppType) -> String {
     match app_type {
         AppType::Claude => "claude-sonnet-4-20250514".to_string(),
         AppType::Codex | AppType::OpenCode | AppType::OpenClaw => "gpt-4".to_string(),
         AppType::Gemini => "gemini-1.5-flash".to_string(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MULTI_PROVIDER_CONFIG: &str = r#"model_provider = "relay"
+model = "gpt-5-codex"
+model_reasoning_effort = "high"
+
+[model_providers.relay]
+name = "Relay"
+base_url = "https://relay.example.com/v1"
+wire_api = "responses"
+requires_openai_auth = true
+
+[model_providers.backup]
+name = "Backup"
+base_url = "https://backup.example.com/v1"
+wire_api = "chat"
+"#;
+
+    #[test]
+    fn test_codex_edit_api_key_keeps_config() {
+        let mut settings = json!({
+            "config": MULTI_PROVIDER_CONFIG,
+            "auth": "[openai]\napi_key = \"sk-old\"\n"
+        });
+
+        apply_codex_edit(&mut settings, Some("sk-new"), None, None).unwrap();
+
+        assert_eq!(settings["config"], MULTI_PROVIDER_CONFIG);
+        assert!(settings["auth"].as_str().unwrap().contains("sk-new"));
+    }
+
+    #[test]
+    fn test_codex_edit_base_url_patches_active_provider() {
+        let mut settings = json!({ "config": MULTI_PROVIDER_CONFIG });
+
+        apply_codex_edit(&mut settings, None, Some("https://new.example.com/v1"), None).unwrap();
+
+        let table: toml::Table = toml::from_str(settings["config"].as_str().unwrap()).unwrap();
+        let providers = table["model_providers"].as_table().unwrap();
+        assert_eq!(
+            providers["relay"]["base_url"].as_str(),
+            Some("https://new.example.com/v1")
+        );
+        assert_eq!(providers["relay"]["requires_openai_auth"].as_bool(), Some(true));
+        assert_eq!(
+            providers["backup"]["base_url"].as_str(),
+            Some("https://backup.example.com/v1")
+        );
+        assert_eq!(table["model"].as_str(), Some("gpt-5-codex"));
+        assert_eq!(table["model_reasoning_effort"].as_str(), Some("high"));
+    }
+
+    #[test]
+    fn test_parse_usage_from_probe_response() {
+        let claude = json!({
+            "id": "msg_1",
+            "content": [{"type": "text", "text": "hi"}],
+            "usage": { "input_tokens": 8, "output_tokens": 5 }
+        });
+        assert_eq!(
+            parse_usage(&AppType::Claude, &claude),
+            Some(TokenUsage { input_tokens: 8, output_tokens: 5 })
+        );
+
+        let openai = json!({
+            "choices": [],
+            "usage": { "prompt_tokens": 9, "completion_tokens": 3, "total_tokens": 12 }
+        });
+        assert_eq!(
+            parse_usage(&AppType::Codex, &openai),
+            Some(TokenUsage { input_tokens: 9, output_tokens: 3 })
+        );
+
+        let gemini = json!({
+            "usageMetadata": { "promptTokenCount": 2, "candidatesTokenCount": 7 }
+        });
+        assert_eq!(
+            parse_usage(&AppType::Gemini, &gemini),
+            Some(TokenUsage { input_tokens: 2, output_tokens: 7 })
+        );
+
+        assert_eq!(parse_usage(&AppType::Claude, &json!({})), None);
+    }
+
+    #[test]
+    fn test_notes_set_show_clear() {
+        let state = AppState::memory().unwrap();
+        let provider = Provider::new("p1", "云雾", json!({"env": {"ANTHROPIC_AUTH_TOKEN": "sk"}}));
+        state.db.save_provider("claude", &provider).unwrap();
+
+        let (_, notes) = apply_notes(&state, AppType::Claude, "云雾", Some("月底到期".into()), false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(notes.as_deref(), Some("月底到期"));
+
+        let (_, shown) = apply_notes(&state, AppType::Claude, "p1", None, false).unwrap().unwrap();
+        assert_eq!(shown.as_deref(), Some("月底到期"));
+
+        let (_, cleared) = apply_notes(&state, AppType::Claude, "云雾", None, true).unwrap().unwrap();
+        assert_eq!(cleared, None);
+        assert_eq!(state.db.get_all_providers("claude").unwrap()["p1"].notes, None);
+
+        assert!(apply_notes(&state, AppType::Claude, "missing", None, true).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_show_json_fields() {
+        let mut provider = Provider::new(
+            "yw",
+            "云雾",
+            json!({
+                "env": {
+                    "ANTHROPIC_AUTH_TOKEN": "sk-secret",
+                    "ANTHROPIC_BASE_URL": "https://api.example.com",
+                    "ANTHROPIC_MODEL": "claude-sonnet-4"
+                }
+            }),
+        );
+        provider.category = Some("relay".to_string());
+        provider.notes = Some("月底到期".to_string());
+
+        let value = show_json(provider.clone(), AppType::Claude, false);
+        assert_eq!(value["id"], "yw");
+        assert_eq!(value["name"], "云雾");
+        assert_eq!(value["category"], "relay");
+        assert_eq!(value["notes"], "月底到期");
+        assert!(value["createdAt"].is_i64());
+        assert_eq!(value["inFailoverQueue"], false);
+        assert_eq!(value["settingsConfig"]["env"]["ANTHROPIC_BASE_URL"], "https://api.example.com");
+        assert_eq!(value["settingsConfig"]["env"]["ANTHROPIC_MODEL"], "claude-sonnet-4");
+        assert!(value["settingsConfig"]["env"].get("ANTHROPIC_AUTH_TOKEN").is_none());
+
+        let value = show_json(provider, AppType::Claude, true);
+        assert_eq!(value["settingsConfig"]["env"]["ANTHROPIC_AUTH_TOKEN"], "sk-secret");
+    }
+
+    #[test]
+    fn test_find_unique_rejects_ambiguous_prefix() {
+        let state = AppState::memory().unwrap();
+        for (id, name) in [("a", "OpenAI"), ("b", "OpenAI-backup")] {
+            state
+                .db
+                .save_provider("codex", &Provider::new(id, name, json!({})))
+                .unwrap();
+        }
+
+        assert!(find_unique(&state, AppType::Codex, "Open").is_err());
+        let exact = find_unique(&state, AppType::Codex, "OpenAI").unwrap().unwrap();
+        assert_eq!(exact.id, "a");
+        assert!(find_unique(&state, AppType::Codex, "missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_run_concurrent_tests_respects_bound() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let in_flight = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let items: Vec<(String, usize)> = (0..10).map(|i| (format!("p{}", i), i)).collect();
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let rows = rt.block_on(run_concurrent_tests(items, 3, |i| {
+            let (in_flight, peak) = (&in_flight, &peak);
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                if i == 7 {
+                    bail!("API Key 无效");
+                }
+                Ok(TestOutcome::passed(None))
+            }
+        }));
+
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+        assert_eq!(rows.len(), 10);
+        let mut names: Vec<&str> = rows.iter().map(|r| r.provider.as_str()).collect();
+        names.sort();
+        assert_eq!(names, ["p0", "p1", "p2", "p3", "p4", "p5", "p6", "p7", "p8", "p9"]);
+
+        // 失败的排在最后，其余按延迟升序
+        let last = rows.last().unwrap();
+        assert_eq!((last.provider.as_str(), last.ok), ("p7", false));
+        assert!(rows[..9].windows(2).all(|w| w[0].latency_ms <= w[1].latency_ms));
+    }
+
+    #[test]
+    fn test_retry_recovers_from_transient_5xx() {
+        let responses = std::cell::RefCell::new(vec![
+            Ok(reqwest::StatusCode::OK),
+            Err(reqwest::StatusCode::BAD_GATEWAY),
+        ]);
+        let attempts = std::cell::Cell::new(0);
+        let responder = || {
+            attempts.set(attempts.get() + 1);
+            let next = responses.borrow_mut().pop().unwrap();
+            async move {
+                match next {
+                    Ok(_) => Ok(TestOutcome::passed(None)),
+                    Err(status) => Err(http_status_error(status, "bad gateway")),
+                }
+            }
+        };
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let outcome = rt.block_on(retry_with_backoff(3, Duration::ZERO, responder)).unwrap();
+        assert!(outcome.passed);
+        assert_eq!(attempts.get(), 2);
+
+        // 401 等非瞬时错误不重试
+        attempts.set(0);
+        let result: Result<()> = rt.block_on(retry_with_backoff(3, Duration::ZERO, || {
+            attempts.set(attempts.get() + 1);
+            async { Err(http_status_error(reqwest::StatusCode::UNAUTHORIZED, "")) }
+        }));
+        assert!(result.is_err());
+        assert_eq!(attempts.get(), 1);
+
+        // 重试耗尽后返回最后一次的错误
+        attempts.set(0);
+        let result: Result<()> = rt.block_on(retry_with_backoff(3, Duration::ZERO, || {
+            attempts.set(attempts.get() + 1);
+            async { Err(http_status_error(reqwest::StatusCode::SERVICE_UNAVAILABLE, "")) }
+        }));
+        assert!(result.unwrap_err().to_string().starts_with("HTTP 503"));
+        assert_eq!(attempts.get(), 3);
+    }
+
+    #[test]
+    fn test_claude_env_file_builds_env_block() {
+        let content = r#"
+# 云雾 API
+export ANTHROPIC_AUTH_TOKEN="sk-env-123"
+ANTHROPIC_BASE_URL=https://api.example.com # 中转地址
+
+API_TIMEOUT_MS='600000'
+"#;
+        assert!(is_env_file("/tmp/provider.env") && is_env_file(".env.local"));
+        assert!(!is_env_file("provider.json"));
+
+        let vars = parse_env_file(content).unwrap();
+        let settings = settings_from_env(AppType::Claude, &vars).unwrap();
+        assert_eq!(
+            settings,
+            json!({"env": {
+                "ANTHROPIC_AUTH_TOKEN": "sk-env-123",
+                "ANTHROPIC_BASE_URL": "https://api.example.com",
+                "API_TIMEOUT_MS": "600000"
+            }})
+        );
+
+        let gemini = settings_from_env(
+            AppType::Gemini,
+            &parse_env_file("GEMINI_API_KEY=g-1\nGEMINI_MODEL=gemini-2.5-pro").unwrap(),
+        )
+        .unwrap();
+        assert_eq!((gemini["apiKey"].as_str(), gemini["model"].as_str()), (Some("g-1"), Some("gemini-2.5-pro")));
+        assert!(settings_from_env(AppType::Codex, &vars).is_err());
+        assert!(parse_env_file("NOT A PAIR").is_err());
+    }
+
+    #[test]
+    fn test_import_format_from_content_type() {
+        assert!(matches!(format_from_content_type("application/json; charset=utf-8"), Some(ImportFormatArg::Json)));
+        assert!(matches!(format_from_content_type("application/vnd.api+json"), Some(ImportFormatArg::Json)));
+        assert!(matches!(format_from_content_type("application/x-yaml"), Some(ImportFormatArg::Yaml)));
+        assert!(matches!(format_from_content_type("application/toml"), Some(ImportFormatArg::Toml)));
+        // 无法判断时交给扩展名或内容自动检测
+        assert!(format_from_content_type("text/plain").is_none());
+        assert!(matches!(format_from_extension("https://example.com/p.yml"), Some(ImportFormatArg::Yaml)));
+    }
+
+    #[test]
+    fn test_sanitize_remote_codex_config_keeps_model_settings() {
+        let mut config = json!({
+            "config": "model = \"gpt-5\"\nnotify = [\"sh\", \"-c\", \"evil\"]\n\n[model_providers.relay]\nbase_url = \"https://relay.example.com/v1\"\nenv_http_headers = { X = \"Y\" }\n\n[mcp_servers.evil]\ncommand = \"sh\"\n",
+            "auth": "[openai]\napi_key = \"sk-remote\"\n",
+            "extra": true
+        });
+        let dropped = sanitize_remote_settings(AppType::Codex, &mut config);
+        assert_eq!(
+            dropped,
+            [
+                "extra",
+                "config.mcp_servers",
+                "config.notify",
+                "config.model_providers.relay.env_http_headers"
+            ]
+        );
+
+        let table: toml::Table = toml::from_str(config["config"].as_str().unwrap()).unwrap();
+        assert_eq!(table["model"].as_str(), Some("gpt-5"));
+        assert_eq!(table["model_providers"]["relay"]["base_url"].as_str(), Some("https://relay.example.com/v1"));
+        assert!(config["auth"].as_str().unwrap().contains("sk-remote"));
+    }
+}
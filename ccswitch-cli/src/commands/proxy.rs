@@ -2,10 +2,12 @@
 //!
 //! 实现全局代理设置的 CLI 命令。
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use ccswitch_core::{AppState, ProxyService};
+use std::io::{self, Write};
 
-use crate::output::{print_info, print_success, OutputContext};
+use crate::cli::AppTypeArg;
+use crate::output::{glyphs, print_error, print_info, print_success, OutputContext};
 
 /// 获取当前代理设置
 pub fn get(_ctx: &OutputContext) -> Result<()> {
@@ -13,8 +15,14 @@ pub fn get(_ctx: &OutputContext) -> Result<()> {
 
     match ProxyService::get(&state)? {
         Some(proxy) => {
-            println!("\n🌐 当前代理设置\n");
-            println!("代理地址: {}", proxy);
+            let no_proxy = ProxyService::get_no_proxy(&state)?;
+            println!("{}", glyphs("\n🌐 当前代理设置\n"));
+            println!("代理地址: {}", mask_proxy_password(&proxy));
+            if no_proxy.is_empty() {
+                println!("不走代理: (无)");
+            } else {
+                println!("不走代理: {}", no_proxy.join(", "));
+            }
         }
         None => {
             print_info("未设置全局代理");
@@ -25,16 +33,37 @@ pub fn get(_ctx: &OutputContext) -> Result<()> {
 }
 
 /// 设置全局代理
-pub fn set(_ctx: &OutputContext, url: &str) -> Result<()> {
+pub fn set(_ctx: &OutputContext, url: &str, no_proxy: Option<&str>) -> Result<()> {
     let state = AppState::init()?;
 
     ProxyService::set(&state, url)?;
+    if no_proxy.is_some() {
+        ProxyService::set_no_proxy(&state, no_proxy)?;
+    }
 
-    print_success(&format!("已设置全局代理: {}", url));
+    print_success(&format!("已设置全局代理: {}", mask_proxy_password(url)));
+    let no_proxy = ProxyService::get_no_proxy(&state)?;
+    if !no_proxy.is_empty() {
+        print_info(&format!("以下主机不走代理: {}", no_proxy.join(", ")));
+    }
 
     Ok(())
 }
 
+/// 隐藏代理 URL 中的密码
+pub(crate) fn mask_proxy_password(proxy: &str) -> String {
+    match proxy.split_once("://") {
+        Some((scheme, rest)) => match rest.rsplit_once('@') {
+            Some((auth, host)) => match auth.split_once(':') {
+                Some((user, _)) => format!("{}://{}:****@{}", scheme, user, host),
+                None => proxy.to_string(),
+            },
+            None => proxy.to_string(),
+        },
+        None => proxy.to_string(),
+    }
+}
+
 /// 清除全局代理设置
 pub fn clear(_ctx: &OutputContext) -> Result<()> {
     let state = AppState::init()?;
@@ -46,6 +75,61 @@ pub fn clear(_ctx: &OutputContext) -> Result<()> {
     Ok(())
 }
 
+/// 将全局代理写入当前供应商配置
+pub fn apply(_ctx: &OutputContext, app: AppTypeArg) -> Result<()> {
+    let state = AppState::init()?;
+    let mut failed = 0;
+
+    for app_type in app.to_app_types() {
+        match ProxyService::apply(&state, app_type) {
+            Ok(provider) => print_success(&format!(
+                "{}: 已将代理写入供应商 '{}' 并同步配置",
+                app_type.display_name(),
+                provider.name
+            )),
+            Err(e) => {
+                failed += 1;
+                print_error(&format!("{}: {}", app_type.display_name(), e));
+            }
+        }
+    }
+
+    if failed > 0 {
+        bail!("{} 个应用写入代理失败", failed);
+    }
+    Ok(())
+}
+
+/// 从当前供应商配置中移除代理
+pub fn unapply(_ctx: &OutputContext, app: AppTypeArg) -> Result<()> {
+    let state = AppState::init()?;
+    let mut failed = 0;
+
+    for app_type in app.to_app_types() {
+        match ProxyService::unapply(&state, app_type) {
+            Ok((provider, true)) => print_success(&format!(
+                "{}: 已从供应商 '{}' 移除代理并同步配置",
+                app_type.display_name(),
+                provider.name
+            )),
+            Ok((provider, false)) => print_info(&format!(
+                "{}: 供应商 '{}' 未设置代理",
+                app_type.display_name(),
+                provider.name
+            )),
+            Err(e) => {
+                failed += 1;
+                print_error(&format!("{}: {}", app_type.display_name(), e));
+            }
+        }
+    }
+
+    if failed > 0 {
+        bail!("{} 个应用移除代理失败", failed);
+    }
+    Ok(())
+}
+
 /// 测试代理连接
 pub async fn test(_ctx: &OutputContext, proxy_url: Option<String>) -> Result<()> {
     let state = AppState::init()?;
@@ -56,15 +140,16 @@ pub async fn test(_ctx: &OutputContext, proxy_url: Option<String>) -> Result<()>
         None => ProxyService::get(&state)?,
     };
 
-    println!("\n🔍 测试代理连接...\n");
+    println!("{}", glyphs("\n🔍 测试代理连接...\n"));
 
-    let results = ProxyService::test(proxy.as_deref(), None).await;
+    let no_proxy = ProxyService::get_no_proxy(&state)?;
+    let results = ProxyService::test(proxy.as_deref(), &no_proxy, None).await;
 
     println!("{:<45} {:<10} {:<10}", "目标", "状态", "延迟");
     println!("{}", "-".repeat(65));
 
     for result in &results {
-        let status = if result.success { "✓ 成功" } else { "✗ 失败" };
+        let status = glyphs(if result.success { "✓ 成功" } else { "✗ 失败" });
         let latency = result
             .latency_ms
             .map(|ms| format!("{}ms", ms))
@@ -81,21 +166,38 @@ pub async fn test(_ctx: &OutputContext, proxy_url: Option<String>) -> Result<()>
 }
 
 /// 扫描本地代理
-pub async fn scan(_ctx: &OutputContext) -> Result<()> {
-    println!("\n🔍 扫描本地代理...\n");
+pub async fn scan(_ctx: &OutputContext, yes: bool) -> Result<()> {
+    println!("{}", glyphs("\n🔍 扫描本地代理...\n"));
 
     let found = ProxyService::scan_local().await;
 
-    if found.is_empty() {
-        print_info("未发现本地代理");
-    } else {
-        println!("发现 {} 个本地代理:\n", found.len());
-        for proxy in &found {
-            println!("  {}", proxy);
+    let Some(fastest) = found.first() else {
+        print_info("未发现可用的本地代理");
+        return Ok(());
+    };
+
+    println!("发现 {} 个可用的本地代理:\n", found.len());
+    println!("{:<30} {:<10}", "代理", "延迟");
+    println!("{}", "-".repeat(40));
+    for result in &found {
+        println!("{:<30} {}ms", result.url, result.latency_ms);
+    }
+    println!();
+
+    if !yes {
+        print!("是否将最快的代理 {} 设为全局代理? [y/N] ", fastest.url);
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        if !input.trim().eq_ignore_ascii_case("y") {
+            print_info("使用 'cc-switch proxy set <url>' 手动设置代理");
+            return Ok(());
         }
-        println!();
-        print_info("使用 'cc-switch proxy set <url>' 设置代理");
     }
 
+    let state = AppState::init()?;
+    ProxyService::set(&state, &fastest.url)?;
+    print_success(&format!("已设置全局代理: {}", fastest.url));
+
     Ok(())
 }
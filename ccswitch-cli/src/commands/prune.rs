@@ -0,0 +1,203 @@
+//! prune 命令实现：清理长期未使用或测试失败的供应商
+
+use anyhow::{bail, Result};
+use ccswitch_core::{AppError, AppState, Provider, ProviderService, ProviderTestResult};
+use serde::Serialize;
+use tabled::{Table, Tabled};
+
+use crate::cli::{AppTypeArg, OutputFormat};
+use crate::output::{confirm_destructive, print_error, print_info, print_success, styled_table, OutputContext};
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// 待清理的供应商
+#[derive(Debug, Clone, PartialEq, Tabled, Serialize)]
+pub struct PruneCandidate {
+    #[tabled(rename = "名称")]
+    pub name: String,
+    #[tabled(rename = "ID")]
+    pub id: String,
+    #[tabled(rename = "原因")]
+    pub reason: String,
+}
+
+/// 清理条件，同时指定时需全部满足
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PruneFilter {
+    /// 最近使用（未使用过则按创建时间）早于指定天数
+    pub older_than_days: Option<i64>,
+    /// 最近一次保存的测试结果为失败
+    pub failing: bool,
+}
+
+/// 判断供应商是否应被清理，返回原因；当前供应商永不清理
+fn prune_reason(
+    provider: &Provider,
+    is_current: bool,
+    last_test: Option<&ProviderTestResult>,
+    filter: PruneFilter,
+    now_ms: i64,
+) -> Option<String> {
+    if is_current {
+        return None;
+    }
+
+    let mut reasons = Vec::new();
+    if let Some(days) = filter.older_than_days {
+        // last_used_at 为毫秒，created_at 为秒
+        let active_at = provider
+            .last_used_at
+            .or_else(|| provider.created_at.map(|s| s * 1000))?;
+        let idle_days = (now_ms - active_at) / DAY_MS;
+        if idle_days < days {
+            return None;
+        }
+        let label = if provider.last_used_at.is_some() { "未使用" } else { "创建后未使用" };
+        reasons.push(format!("{} {} 天", label, idle_days));
+    }
+    if filter.failing {
+        let test = last_test.filter(|t| !t.ok)?;
+        let message = if test.message.is_empty() { "失败" } else { test.message.as_str() };
+        reasons.push(format!("最近测试失败: {}", message));
+    }
+
+    (!reasons.is_empty()).then(|| reasons.join("；"))
+}
+
+/// 按输出格式打印供应商列表
+fn print_candidates(ctx: &OutputContext, rows: &[PruneCandidate]) -> Result<()> {
+    match ctx.format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(rows)?),
+        OutputFormat::Yaml => println!("{}", serde_yaml::to_string(rows)?),
+        OutputFormat::Table => println!("{}", styled_table(&mut Table::new(rows))),
+    }
+    Ok(())
+}
+
+/// 执行 prune 命令
+pub fn prune(ctx: &OutputContext, app: AppTypeArg, filter: PruneFilter, yes: bool) -> Result<()> {
+    if filter.older_than_days.is_none() && !filter.failing {
+        print_error("请至少指定一个清理条件: --older-than-days <天数> 或 --failing");
+        return Err(AppError::InvalidInput("未指定清理条件".to_string()).into());
+    }
+
+    let app_types = app.to_app_types();
+    if app_types.len() > 1 {
+        print_error("清理供应商时请指定具体的应用类型，例如: --app claude");
+        return Err(AppError::InvalidInput("未指定应用类型".to_string()).into());
+    }
+    let app_type = app_types[0];
+
+    let state = AppState::init()?;
+    let current = ProviderService::current(&state, app_type)?;
+    let test_results = state.db.get_latest_test_results(app_type.as_str())?;
+    let now_ms = chrono::Utc::now().timestamp_millis();
+
+    let candidates: Vec<PruneCandidate> = ProviderService::list(&state, app_type)?
+        .values()
+        .filter_map(|p| {
+            let reason = prune_reason(p, p.id == current, test_results.get(&p.id), filter, now_ms)?;
+            Some(PruneCandidate {
+                name: p.name.clone(),
+                id: p.id.clone(),
+                reason,
+            })
+        })
+        .collect();
+
+    // 结构化输出时不加 --yes 仅列出候选；加 --yes 则直接删除并输出已删除的列表
+    let structured = !matches!(ctx.format, OutputFormat::Table);
+    if structured && !yes {
+        return print_candidates(ctx, &candidates);
+    }
+
+    if !structured {
+        if candidates.is_empty() {
+            print_info(&format!("{} 没有需要清理的供应商", app_type.display_name()));
+            return Ok(());
+        }
+
+        print_candidates(ctx, &candidates)?;
+
+        if !confirm_destructive(&format!("确定要删除以上 {} 个供应商?", candidates.len()), yes)? {
+            print_info("已取消");
+            return Ok(());
+        }
+    }
+
+    let total = candidates.len();
+    let mut deleted = Vec::with_capacity(total);
+    for candidate in candidates {
+        match ProviderService::delete(&state, app_type, &candidate.id) {
+            Ok(()) => deleted.push(candidate),
+            Err(e) => print_error(&format!("删除 {} 失败: {}", candidate.name, e)),
+        }
+    }
+    let failed = total - deleted.len();
+
+    if structured {
+        print_candidates(ctx, &deleted)?;
+    } else {
+        print_success(&format!("已清理 {} 个供应商 ({})", deleted.len(), app_type.display_name()));
+    }
+    if failed > 0 {
+        bail!("{} 个供应商删除失败", failed);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const NOW: i64 = 1_700_000_000_000;
+
+    fn provider(created_days_ago: Option<i64>, used_days_ago: Option<i64>) -> Provider {
+        let mut p = Provider::new("p", "p", json!({}));
+        p.created_at = created_days_ago.map(|d| (NOW - d * DAY_MS) / 1000);
+        p.last_used_at = used_days_ago.map(|d| NOW - d * DAY_MS);
+        p
+    }
+
+    fn test_result(ok: bool) -> ProviderTestResult {
+        ProviderTestResult {
+            provider_id: "p".to_string(),
+            ok,
+            latency_ms: None,
+            message: if ok { String::new() } else { "HTTP 401".to_string() },
+            tested_at: NOW,
+        }
+    }
+
+    #[test]
+    fn test_prune_reason_by_age_and_test_status() {
+        let stale = PruneFilter { older_than_days: Some(30), failing: false };
+        let failing = PruneFilter { older_than_days: None, failing: true };
+        let both = PruneFilter { older_than_days: Some(30), failing: true };
+
+        // 按最近使用时间，未使用过时按创建时间
+        assert!(prune_reason(&provider(Some(100), Some(40)), false, None, stale, NOW).is_some());
+        assert!(prune_reason(&provider(Some(100), Some(5)), false, None, stale, NOW).is_none());
+        assert_eq!(
+            prune_reason(&provider(Some(45), None), false, None, stale, NOW).as_deref(),
+            Some("创建后未使用 45 天")
+        );
+        assert!(prune_reason(&provider(None, None), false, None, stale, NOW).is_none());
+
+        // 按最近测试结果，未测试过的不清理
+        let old = provider(Some(100), Some(40));
+        assert!(prune_reason(&old, false, Some(&test_result(false)), failing, NOW).is_some());
+        assert!(prune_reason(&old, false, Some(&test_result(true)), failing, NOW).is_none());
+        assert!(prune_reason(&old, false, None, failing, NOW).is_none());
+
+        // 同时指定时需全部满足
+        let recent = provider(Some(100), Some(1));
+        assert!(prune_reason(&recent, false, Some(&test_result(false)), both, NOW).is_none());
+        assert!(prune_reason(&old, false, Some(&test_result(false)), both, NOW).is_some());
+
+        // 当前供应商永不清理
+        assert!(prune_reason(&old, true, Some(&test_result(false)), both, NOW).is_none());
+    }
+}
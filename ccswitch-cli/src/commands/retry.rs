@@ -0,0 +1,110 @@
+//! 网络请求重试
+//!
+//! 版本检测与供应商 API 测试共用的指数退避重试。
+
+use std::time::Duration;
+
+use anyhow::Result;
+
+/// 重试前的基础等待时间，每次重试翻倍
+pub(crate) const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// 单次请求失败，`retryable` 表示是否为可重试的临时错误
+#[derive(Debug)]
+pub(crate) struct CheckFailure {
+    error: anyhow::Error,
+    retryable: bool,
+}
+
+impl CheckFailure {
+    pub(crate) fn transient(error: anyhow::Error) -> Self {
+        Self { error, retryable: true }
+    }
+
+    pub(crate) fn fatal(error: anyhow::Error) -> Self {
+        Self { error, retryable: false }
+    }
+}
+
+impl From<anyhow::Error> for CheckFailure {
+    fn from(error: anyhow::Error) -> Self {
+        Self::fatal(error)
+    }
+}
+
+impl From<reqwest::Error> for CheckFailure {
+    fn from(error: reqwest::Error) -> Self {
+        Self::fatal(error.into())
+    }
+}
+
+/// 按指数退避重试 `op`，遇到不可重试的错误或用完次数后返回最后一次错误
+pub(crate) async fn retry_with_backoff<T, F, Fut>(attempts: u32, backoff: Duration, mut op: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = std::result::Result<T, CheckFailure>>,
+{
+    let mut delay = backoff;
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(failure) if failure.retryable && attempt < attempts => {
+                log::debug!("第 {} 次请求失败，{:?} 后重试: {}", attempt, delay, failure.error);
+                tokio::time::sleep(delay).await;
+                delay = delay.saturating_mul(2);
+                attempt += 1;
+            }
+            Err(failure) => return Err(failure.error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_after_transient_failure() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let mut calls = 0;
+        let result = rt.block_on(retry_with_backoff(2, Duration::ZERO, || {
+            calls += 1;
+            let outcome = if calls == 1 {
+                Err(CheckFailure::transient(anyhow::anyhow!("timeout")))
+            } else {
+                Ok(calls)
+            };
+            async move { outcome }
+        }));
+        assert_eq!(result.unwrap(), 2);
+
+        // 不可重试的错误与用尽次数都直接失败
+        let mut calls = 0;
+        let result: Result<()> = rt.block_on(retry_with_backoff(2, Duration::ZERO, || {
+            calls += 1;
+            async { Err(CheckFailure::fatal(anyhow::anyhow!("404"))) }
+        }));
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+
+        let mut calls = 0;
+        let result: Result<()> = rt.block_on(retry_with_backoff(2, Duration::ZERO, || {
+            calls += 1;
+            async { Err(CheckFailure::transient(anyhow::anyhow!("503"))) }
+        }));
+        assert!(result.is_err());
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn test_retries_flag_is_bounded() {
+        use clap::Parser;
+
+        let parse = |args: &[&str]| crate::cli::Cli::try_parse_from([&["cc-switch"], args].concat());
+        assert!(parse(&["test", "demo", "--retries", "4294967295"]).is_err());
+        assert!(parse(&["test", "demo", "--retries", "11"]).is_err());
+        assert!(parse(&["test", "demo", "--retries", "10"]).is_ok());
+        assert!(parse(&["batch", "test", "--retries", "4294967295"]).is_err());
+    }
+}
@@ -6,10 +6,13 @@ use anyhow::Result;
 use colored::Colorize;
 use std::io::{self, Write};
 
-use ccswitch_core::{AppState, Skill, SkillService};
+use ccswitch_core::{AppError, AppState, Skill, SkillService};
+use indexmap::IndexMap;
+use serde::Serialize;
+use tabled::{Table, Tabled};
 
-use crate::cli::AppTypeArg;
-use crate::output::{print_info, print_success, OutputContext};
+use crate::cli::{AppTypeArg, OutputFormat};
+use crate::output::{print_info, print_success, styled_table, sym, OutputContext};
 
 /// 列出所有 Skills
 pub fn list(_ctx: &OutputContext, app: AppTypeArg, detail: bool) -> Result<()> {
@@ -67,25 +70,42 @@ fn print_skill_summary(skill: &Skill, detail: bool) {
         if let Some(url) = skill.repo_url() {
             println!("    {}: {}", "仓库".dimmed(), url);
         }
+        if let Some(rev) = pinned_rev_str(skill) {
+            println!("    {}: {}", "固定版本".dimmed(), rev);
+        }
         println!();
     }
 }
 
+/// 固定版本描述，如 `v1.2.0 (4f2c9d1)`；未固定时返回 None
+fn pinned_rev_str(skill: &Skill) -> Option<String> {
+    let rev = skill.repo_rev.as_deref()?;
+    let commit: Option<String> = skill.repo_commit.as_deref().map(|c| c.chars().take(7).collect());
+    Some(match commit {
+        Some(commit) if !rev.starts_with(&commit) => format!("{} ({})", rev, commit),
+        _ => rev.to_string(),
+    })
+}
+
 /// 安装 Skill
 pub fn install(
     _ctx: &OutputContext,
     repo: &str,
     branch: Option<String>,
+    rev: Option<String>,
     app: Option<AppTypeArg>,
 ) -> Result<()> {
     let state = AppState::init()?;
 
     print_info(&format!("正在从 {} 安装 Skill...", repo));
 
-    let skill = SkillService::install(&state, repo, branch)?;
+    let skill = SkillService::install(&state, repo, branch, rev)?;
 
     print_success(&format!("Skill '{}' 安装成功", skill.id));
     println!("  {}: {}", "目录".dimmed(), skill.directory);
+    if let Some(rev) = pinned_rev_str(&skill) {
+        println!("  {}: {}", "固定版本".dimmed(), rev);
+    }
 
     // 如果指定了应用，自动启用
     if let Some(app_arg) = app {
@@ -98,6 +118,38 @@ pub fn install(
     Ok(())
 }
 
+/// 更新 Skill
+pub fn update(_ctx: &OutputContext, id: &str, unpin: bool) -> Result<()> {
+    let state = AppState::init()?;
+
+    print_info(&format!("正在检查 Skill '{}' 的更新...", id));
+
+    let result = SkillService::update(&state, id, unpin)?;
+    let short = |commit: &Option<String>| {
+        commit
+            .as_deref()
+            .map(|c| c.chars().take(7).collect::<String>())
+            .unwrap_or_else(|| "未知".to_string())
+    };
+
+    if result.updated {
+        print_success(&format!(
+            "Skill '{}' 已更新: {} -> {}",
+            id,
+            short(&result.previous_commit),
+            short(&result.skill.repo_commit)
+        ));
+    } else {
+        print_info(&format!(
+            "Skill '{}' 已是最新 ({})",
+            id,
+            short(&result.skill.repo_commit)
+        ));
+    }
+
+    Ok(())
+}
+
 /// 卸载 Skill
 pub fn uninstall(_ctx: &OutputContext, id: &str, yes: bool) -> Result<()> {
     let state = AppState::init()?;
@@ -130,16 +182,127 @@ pub fn uninstall(_ctx: &OutputContext, id: &str, yes: bool) -> Result<()> {
 pub fn toggle(_ctx: &OutputContext, id: &str, app: AppTypeArg, enable: bool) -> Result<()> {
     let state = AppState::init()?;
 
-    for app_type in app.to_app_types() {
-        SkillService::toggle(&state, id, app_type.clone(), enable)?;
+    let applied = SkillService::toggle_apps(&state, id, &app.to_app_types(), enable)?;
+    if applied.is_empty() {
+        return Err(AppError::InvalidInput("所选应用不支持 Skills".to_string()).into());
+    }
+
+    let action = if enable { "启用" } else { "禁用" };
+    let names: Vec<&str> = applied.iter().map(|a| a.display_name()).collect();
+    print_success(&format!("已为 {} {} Skill '{}'", names.join("、"), action, id));
 
-        let action = if enable { "启用" } else { "禁用" };
-        print_success(&format!(
-            "已为 {} {} Skill '{}'",
-            app_type.display_name(),
-            action,
-            id
-        ));
+    Ok(())
+}
+
+/// Skill × 应用启用状态矩阵中的一行
+#[derive(Debug, Tabled, Serialize)]
+pub struct SkillStatusRow {
+    #[tabled(rename = "Skill")]
+    pub id: String,
+    #[tabled(rename = "Claude", display = "enabled_mark")]
+    pub claude: bool,
+    #[tabled(rename = "Codex", display = "enabled_mark")]
+    pub codex: bool,
+    #[tabled(rename = "Gemini", display = "enabled_mark")]
+    pub gemini: bool,
+    #[tabled(rename = "OpenCode", display = "enabled_mark")]
+    pub opencode: bool,
+}
+
+fn enabled_mark(enabled: &bool) -> String {
+    if *enabled { sym("✓") } else { "-" }.to_string()
+}
+
+fn build_status_rows(skills: &IndexMap<String, Skill>) -> Vec<SkillStatusRow> {
+    skills
+        .values()
+        .map(|skill| SkillStatusRow {
+            id: skill.id.clone(),
+            claude: skill.apps.claude,
+            codex: skill.apps.codex,
+            gemini: skill.apps.gemini,
+            opencode: skill.apps.opencode,
+        })
+        .collect()
+}
+
+/// 显示各 Skill 在各应用中的启用状态
+pub fn status(ctx: &OutputContext) -> Result<()> {
+    let state = AppState::init()?;
+    let rows = build_status_rows(&SkillService::list(&state)?);
+
+    match ctx.format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&rows)?),
+        OutputFormat::Yaml => println!("{}", serde_yaml::to_string(&rows)?),
+        OutputFormat::Table => {
+            if rows.is_empty() {
+                print_info("暂无已安装的 Skills");
+                return Ok(());
+            }
+            println!("{}", styled_table(&mut Table::new(&rows)));
+        }
+    }
+
+    Ok(())
+}
+
+/// 在 Skill 索引中搜索
+pub async fn search(_ctx: &OutputContext, query: &str, refresh: bool) -> Result<()> {
+    let state = AppState::init()?;
+
+    let results = SkillService::search(&state, query, refresh).await?;
+
+    if results.is_empty() {
+        print_info(&format!("未找到与 '{}' 匹配的 Skills", query));
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!("找到 {} 个匹配的 Skills", results.len()).bold()
+    );
+    println!();
+
+    for entry in &results {
+        println!("  {} - {}", entry.name.cyan(), entry.repo);
+        if let Some(desc) = &entry.description {
+            println!("    {}", desc.dimmed());
+        }
+        if !entry.tags.is_empty() {
+            println!("    {}: {}", "标签".dimmed(), entry.tags.join(", "));
+        }
+        let branch = entry
+            .branch
+            .as_deref()
+            .map(|b| format!(" --branch {}", b))
+            .unwrap_or_default();
+        println!(
+            "    {}: cc-switch skill install {}{}",
+            "安装".dimmed(),
+            entry.repo,
+            branch
+        );
+        println!();
+    }
+
+    Ok(())
+}
+
+/// 查看或设置 Skill 索引地址
+pub fn index(_ctx: &OutputContext, url: Option<&str>, reset: bool) -> Result<()> {
+    let state = AppState::init()?;
+
+    if reset {
+        SkillService::set_index_url(&state, None)?;
+        print_success("已清除 Skill 索引地址");
+    } else if let Some(url) = url {
+        SkillService::set_index_url(&state, Some(url))?;
+        print_success(&format!("已设置 Skill 索引地址: {}", url));
+    }
+
+    match SkillService::index_url(&state)? {
+        Some(url) => println!("{}: {}", "索引地址".dimmed(), url),
+        None => print_info("未配置 Skill 索引，使用 cc-switch skill index <URL> 设置后即可 skill search"),
     }
 
     Ok(())
@@ -202,6 +365,14 @@ pub fn show(_ctx: &OutputContext, id: &str) -> Result<()> {
         println!("{}: {}", "分支".dimmed(), branch);
     }
 
+    if let Some(commit) = &skill.repo_commit {
+        println!("{}: {}", "提交".dimmed(), commit);
+    }
+
+    if let Some(rev) = &skill.repo_rev {
+        println!("{}: {}", "固定版本".dimmed(), rev);
+    }
+
     println!("{}: {}", "启用应用".dimmed(), skill.enabled_apps_str());
 
     if let Some(ts) = skill.installed_at {
@@ -213,3 +384,40 @@ pub fn show(_ctx: &OutputContext, id: &str) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_rows_reflect_enabled_apps() {
+        let mut skill = Skill::new("pdf", "PDF", "/tmp/pdf");
+        for app in SkillService::supported_apps() {
+            skill.apps.set_enabled_for(&app, true);
+        }
+        let mut idle = Skill::new("idle", "Idle", "/tmp/idle");
+        idle.apps.gemini = true;
+
+        let skills: IndexMap<String, Skill> =
+            [skill, idle].into_iter().map(|s| (s.id.clone(), s)).collect();
+        let rows = build_status_rows(&skills);
+
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0].claude && rows[0].codex && rows[0].gemini && rows[0].opencode);
+        assert!(!rows[1].claude && !rows[1].codex && rows[1].gemini);
+    }
+
+    #[test]
+    fn test_pinned_rev_str() {
+        let commit = "4f2c9d1e8a7b6c5d4e3f2a1b0c9d8e7f6a5b4c3d".to_string();
+        let skill = Skill::new("acme-tools", "tools", "/tmp/tools").with_commit(Some(commit.clone()));
+        assert_eq!(pinned_rev_str(&skill), None);
+
+        let tagged = skill.clone().with_rev(Some("v1.2.0".to_string()));
+        assert_eq!(pinned_rev_str(&tagged).as_deref(), Some("v1.2.0 (4f2c9d1)"));
+
+        // 固定到提交时不重复显示提交号
+        let by_sha = skill.with_rev(Some(commit[..10].to_string()));
+        assert_eq!(pinned_rev_str(&by_sha).as_deref(), Some("4f2c9d1e8a"));
+    }
+}
@@ -5,7 +5,7 @@
 use anyhow::Result;
 use ccswitch_core::{AppState, ProxyService, SpeedtestService};
 
-use crate::output::{print_info, OutputContext};
+use crate::output::{glyphs, print_info, OutputContext};
 
 /// 测试端点延迟
 pub async fn test(
@@ -22,6 +22,7 @@ pub async fn test(
     } else {
         None
     };
+    let no_proxy = ProxyService::get_no_proxy(&state)?;
 
     // 使用提供的 URL 或默认目标
     let targets = if urls.is_empty() {
@@ -30,13 +31,13 @@ pub async fn test(
         Some(urls)
     };
 
-    println!("\n⚡ 端点测速\n");
+    println!("{}", glyphs("\n⚡ 端点测速\n"));
 
     if let Some(ref p) = proxy {
         println!("使用代理: {}\n", p);
     }
 
-    let results = SpeedtestService::test_endpoints(targets, timeout, proxy.as_deref()).await;
+    let results = SpeedtestService::test_endpoints(targets, timeout, proxy.as_deref(), &no_proxy).await;
 
     // 按延迟排序
     let mut sorted_results = results.clone();
@@ -53,7 +54,7 @@ pub async fn test(
     println!("{}", "-".repeat(65));
 
     for result in &sorted_results {
-        let status = if result.success { "✓ 成功" } else { "✗ 失败" };
+        let status = glyphs(if result.success { "✓ 成功" } else { "✗ 失败" });
         let latency = result
             .latency_ms
             .map(|ms| format!("{}ms", ms))
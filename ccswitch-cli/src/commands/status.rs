@@ -1,47 +1,228 @@
 //! status 命令实现
 
+use std::time::Duration;
+
 use anyhow::Result;
-use ccswitch_core::{AppState, ProviderService};
+use ccswitch_core::{AppError, AppState, AppType, ProviderService};
+use chrono::Local;
+use serde::Serialize;
+use tabled::Table;
+
+use crate::cli::{AppTypeArg, OutputFormat};
+use crate::output::{print_error, print_status, styled_table, OutputContext, StatusRow, ADDITIVE_MODE_LABEL};
 
-use crate::cli::AppTypeArg;
-use crate::output::{print_status, OutputContext, StatusRow};
+/// 单个应用的状态（`-o json` / `-o yaml` 输出的稳定结构）
+#[derive(Debug, Serialize)]
+pub struct StatusEntry {
+    pub app: String,
+    pub current_id: Option<String>,
+    pub current_name: Option<String>,
+    pub base_url: Option<String>,
+    pub model: Option<String>,
+    pub configured: bool,
+    #[serde(skip)]
+    pub display_name: &'static str,
+    #[serde(skip)]
+    pub provider_count: usize,
+    /// 累加模式应用没有单一的当前供应商
+    #[serde(skip)]
+    pub additive: bool,
+}
 
 /// 执行 status 命令
 pub fn show_status(ctx: &OutputContext, app: AppTypeArg) -> Result<()> {
     let state = AppState::init()?;
+    let entries = build_status_entries(&state, &app.to_app_types())?;
+
+    match ctx.format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&entries)?),
+        OutputFormat::Yaml => println!("{}", serde_yaml::to_string(&entries)?),
+        OutputFormat::Table => print_status(ctx, status_rows(entries)),
+    }
+
+    Ok(())
+}
+
+/// 持续刷新状态表格（`status --watch`），直到 Ctrl-C
+///
+/// 每轮重新加载 `AppState`，以反映其他进程或故障转移带来的变化。
+pub fn watch_status(ctx: &OutputContext, app: AppTypeArg, interval: u64) -> Result<()> {
+    if ctx.format != OutputFormat::Table {
+        print_error("--watch 仅支持表格输出，不能与 -o json/yaml 同时使用");
+        return Err(AppError::InvalidInput("--watch 不支持 -o json/yaml".to_string()).into());
+    }
+
     let app_types = app.to_app_types();
+    loop {
+        let state = AppState::init()?;
+        let table = render_status_table(&state, &app_types)?;
+
+        // 清屏并将光标移到左上角
+        print!("\x1B[2J\x1B[1;1H");
+        println!(
+            "每 {} 秒刷新 · {} · 按 Ctrl-C 退出\n",
+            interval,
+            Local::now().format("%H:%M:%S")
+        );
+        println!("{}", table);
+
+        std::thread::sleep(Duration::from_secs(interval));
+    }
+}
+
+/// 渲染一次状态表格（`--watch` 每轮调用）
+fn render_status_table(state: &AppState, app_types: &[AppType]) -> Result<String> {
+    let rows = status_rows(build_status_entries(state, app_types)?);
+    Ok(styled_table(&mut Table::new(&rows)))
+}
+
+/// 将状态条目转换为表格行
+fn status_rows(entries: Vec<StatusEntry>) -> Vec<StatusRow> {
+    entries
+        .into_iter()
+        .map(|entry| StatusRow {
+            app: entry.display_name.to_string(),
+            current_provider: if entry.additive && entry.provider_count > 0 {
+                ADDITIVE_MODE_LABEL.to_string()
+            } else {
+                entry
+                    .current_name
+                    .or(entry.current_id)
+                    .unwrap_or_else(|| "未设置".to_string())
+            },
+            provider_count: entry.provider_count.to_string(),
+            config_status: if entry.provider_count > 0 {
+                "已配置".to_string()
+            } else {
+                "未配置".to_string()
+            },
+        })
+        .collect()
+}
 
-    let mut rows: Vec<StatusRow> = Vec::new();
-
-    for app_type in app_types {
-        let providers = ProviderService::list(&state, app_type)?;
-        let current_id = ProviderService::current(&state, app_type)?;
-
-        let current_name = if current_id.is_empty() {
-            "未设置".to_string()
-        } else {
-            providers
-                .get(&current_id)
-                .map(|p| p.name.clone())
-                .unwrap_or_else(|| current_id.clone())
-        };
-
-        let provider_count = providers.len();
-        let config_status = if provider_count > 0 {
-            "已配置".to_string()
-        } else {
-            "未配置".to_string()
-        };
-
-        rows.push(StatusRow {
-            app: app_type.display_name().to_string(),
-            current_provider: current_name,
-            provider_count: provider_count.to_string(),
-            config_status,
+/// 汇总各应用的当前供应商信息
+fn build_status_entries(state: &AppState, app_types: &[AppType]) -> Result<Vec<StatusEntry>> {
+    let mut entries = Vec::new();
+
+    for &app_type in app_types {
+        let provider_count = ProviderService::list(state, app_type)?.len();
+        let current_id = ProviderService::current(state, app_type)?;
+        let current = ProviderService::current_provider(state, app_type)?;
+
+        entries.push(StatusEntry {
+            app: app_type.as_str().to_string(),
+            current_id: (!current_id.is_empty()).then_some(current_id),
+            current_name: current.as_ref().map(|p| p.name.clone()),
+            base_url: current.as_ref().and_then(|p| p.get_base_url()),
+            model: current.as_ref().and_then(|p| p.get_model()),
+            configured: current.is_some() || (app_type.is_additive_mode() && provider_count > 0),
+            display_name: app_type.display_name(),
+            provider_count,
+            additive: app_type.is_additive_mode(),
         });
     }
 
-    print_status(ctx, rows);
+    Ok(entries)
+}
 
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ccswitch_core::Provider;
+    use serde_json::json;
+
+    #[test]
+    fn test_status_json_schema() {
+        let state = AppState::memory().unwrap();
+        let provider = Provider::new(
+            "yw",
+            "云雾",
+            json!({
+                "env": {
+                    "ANTHROPIC_AUTH_TOKEN": "sk-test",
+                    "ANTHROPIC_BASE_URL": "https://api.example.com",
+                    "ANTHROPIC_MODEL": "claude-sonnet-4"
+                }
+            }),
+        );
+        state.db.save_provider("claude", &provider).unwrap();
+        state.db.set_current_provider("claude", "yw").unwrap();
+
+        let entries = build_status_entries(&state, &[AppType::Claude, AppType::Codex]).unwrap();
+        let value = serde_json::to_value(&entries).unwrap();
+
+        assert_eq!(
+            value[0],
+            json!({
+                "app": "claude",
+                "current_id": "yw",
+                "current_name": "云雾",
+                "base_url": "https://api.example.com",
+                "model": "claude-sonnet-4",
+                "configured": true
+            })
+        );
+        assert_eq!(
+            value[1],
+            json!({
+                "app": "codex",
+                "current_id": null,
+                "current_name": null,
+                "base_url": null,
+                "model": null,
+                "configured": false
+            })
+        );
+    }
+
+    #[test]
+    fn test_render_status_table_reflects_current_provider() {
+        let state = AppState::memory().unwrap();
+        let table = render_status_table(&state, &[AppType::Claude]).unwrap();
+        assert!(table.contains("Claude Code") && table.contains("未设置"));
+
+        state
+            .db
+            .save_provider("claude", &Provider::new("yw", "云雾", json!({})))
+            .unwrap();
+        state.db.set_current_provider("claude", "yw").unwrap();
+        let table = render_status_table(&state, &[AppType::Claude]).unwrap();
+        assert!(table.contains("云雾") && table.contains("已配置"));
+    }
+
+    #[test]
+    fn test_additive_app_status_is_not_reported_unconfigured() {
+        let state = AppState::memory().unwrap();
+        state
+            .db
+            .save_provider("opencode", &Provider::new("oc", "OC", json!({"npm": "@ai-sdk/openai"})))
+            .unwrap();
+
+        let entries = build_status_entries(&state, &[AppType::OpenCode]).unwrap();
+        assert!(entries[0].configured && entries[0].current_id.is_none());
+
+        let rows = status_rows(entries);
+        assert_eq!(rows[0].current_provider, ADDITIVE_MODE_LABEL);
+        assert_eq!(rows[0].config_status, "已配置");
+        assert!(!render_status_table(&state, &[AppType::OpenCode]).unwrap().contains("未设置"));
+    }
+
+    #[test]
+    fn test_watch_interval_parsing() {
+        use crate::cli::{Cli, Commands};
+        use clap::Parser;
+
+        let parse = |args: &[&str]| Cli::try_parse_from([&["cc-switch", "status"], args].concat());
+        match parse(&["--watch"]).unwrap().command {
+            Some(Commands::Status { watch, interval, .. }) => assert!(watch && interval == 2),
+            other => panic!("unexpected command: {:?}", other),
+        }
+        match parse(&["-w", "--interval", "5"]).unwrap().command {
+            Some(Commands::Status { interval, .. }) => assert_eq!(interval, 5),
+            other => panic!("unexpected command: {:?}", other),
+        }
+        assert!(parse(&["--watch", "--interval", "0"]).is_err());
+        assert!(parse(&["--watch", "--interval", "abc"]).is_err());
+        assert!(parse(&["--interval", "5"]).is_err());
+    }
 }
@@ -0,0 +1,151 @@
+//! summary 命令实现
+
+use anyhow::Result;
+use ccswitch_core::{AppState, AppType};
+use serde::Serialize;
+use tabled::{Table, Tabled};
+
+use crate::cli::OutputFormat;
+use crate::output::{styled_table, OutputContext};
+
+/// 单个应用的数量统计
+#[derive(Debug, Clone, PartialEq, Tabled, Serialize)]
+pub struct AppSummary {
+    #[tabled(rename = "应用")]
+    pub app: String,
+    #[tabled(rename = "供应商")]
+    pub providers: usize,
+    #[tabled(rename = "MCP 服务器")]
+    pub mcp_servers: usize,
+    #[tabled(rename = "Prompts")]
+    pub prompts: usize,
+    #[tabled(rename = "Skills")]
+    pub skills: usize,
+}
+
+/// 全局数量统计（MCP 与 Skills 按条目计，不按应用重复计数）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SummaryTotals {
+    pub providers: usize,
+    pub mcp_servers: usize,
+    pub prompts: usize,
+    pub skills: usize,
+}
+
+/// summary 输出
+#[derive(Debug, Serialize)]
+pub struct Summary {
+    pub apps: Vec<AppSummary>,
+    pub totals: SummaryTotals,
+}
+
+/// 执行 summary 命令
+pub fn show_summary(ctx: &OutputContext) -> Result<()> {
+    let state = AppState::init()?;
+    let summary = build_summary(&state)?;
+
+    match ctx.format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&summary)?),
+        OutputFormat::Yaml => println!("{}", serde_yaml::to_string(&summary)?),
+        OutputFormat::Table => {
+            let mut rows: Vec<AppSummary> = summary
+                .apps
+                .iter()
+                .map(|row| AppSummary {
+                    app: row
+                        .app
+                        .parse::<AppType>()
+                        .map(|a| a.display_name().to_string())
+                        .unwrap_or_else(|_| row.app.clone()),
+                    ..row.clone()
+                })
+                .collect();
+            rows.push(AppSummary {
+                app: "合计".to_string(),
+                providers: summary.totals.providers,
+                mcp_servers: summary.totals.mcp_servers,
+                prompts: summary.totals.prompts,
+                skills: summary.totals.skills,
+            });
+            println!("{}", styled_table(&mut Table::new(&rows)));
+        }
+    }
+
+    Ok(())
+}
+
+/// 统计各应用的供应商、MCP 服务器、Prompts 与 Skills 数量
+fn build_summary(state: &AppState) -> Result<Summary> {
+    let mcp_servers = state.db.get_all_mcp_servers()?;
+    let skills = state.db.get_all_skills()?;
+
+    let mut apps = Vec::new();
+    for &app_type in AppType::all() {
+        apps.push(AppSummary {
+            app: app_type.as_str().to_string(),
+            providers: state.db.get_provider_count(app_type.as_str())?,
+            mcp_servers: mcp_servers
+                .values()
+                .filter(|s| s.apps.is_enabled_for(&app_type))
+                .count(),
+            prompts: state.db.get_all_prompts(app_type.as_str())?.len(),
+            skills: skills.values().filter(|s| s.apps.is_enabled_for(&app_type)).count(),
+        });
+    }
+
+    let totals = SummaryTotals {
+        providers: apps.iter().map(|a| a.providers).sum(),
+        mcp_servers: state.db.get_mcp_server_count()?,
+        prompts: apps.iter().map(|a| a.prompts).sum(),
+        skills: skills.len(),
+    };
+
+    Ok(Summary { apps, totals })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ccswitch_core::{McpServer, Provider};
+    use serde_json::json;
+
+    #[test]
+    fn test_summary_counts_seeded_state() {
+        let state = AppState::memory().unwrap();
+        for id in ["a", "b", "c"] {
+            state
+                .db
+                .save_provider("claude", &Provider::new(id, id, json!({})))
+                .unwrap();
+        }
+        state
+            .db
+            .save_provider("codex", &Provider::new("x", "x", json!({})))
+            .unwrap();
+
+        let mut fs_server = McpServer::new("fs", "fs", json!({"command": "npx"}));
+        fs_server.apps.claude = true;
+        fs_server.apps.codex = true;
+        state.db.save_mcp_server(&fs_server).unwrap();
+        state
+            .db
+            .save_mcp_server(&McpServer::new("idle", "idle", json!({"command": "npx"})))
+            .unwrap();
+
+        let summary = build_summary(&state).unwrap();
+        let claude = &summary.apps[0];
+        assert_eq!((claude.app.as_str(), claude.providers, claude.mcp_servers), ("claude", 3, 1));
+        let codex = &summary.apps[1];
+        assert_eq!((codex.providers, codex.mcp_servers), (1, 1));
+        assert_eq!(summary.apps[2].providers, 0);
+        assert_eq!(
+            summary.totals,
+            SummaryTotals {
+                providers: 4,
+                mcp_servers: 2,
+                prompts: 0,
+                skills: 0,
+            }
+        );
+    }
+}
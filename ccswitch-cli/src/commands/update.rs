@@ -10,7 +10,8 @@ use serde::Deserialize;
 use std::process::Command;
 use std::time::Duration;
 
-use crate::output::OutputContext;
+use crate::commands::retry::{retry_with_backoff, CheckFailure, RETRY_BACKOFF};
+use crate::output::{glyphs, sym, OutputContext};
 
 /// 版本更新类型
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -109,6 +110,45 @@ pub struct RateLimitInfo {
 const GITHUB_REPO: &str = "DoBestone/cc-switch-cli";
 const GITHUB_API_URL: &str = "https://api.github.com/repos/DoBestone/cc-switch-cli/releases/latest";
 
+/// 版本检测默认超时（秒）
+pub const DEFAULT_CHECK_TIMEOUT_SECS: u64 = 10;
+/// 版本检测最多尝试次数
+const CHECK_ATTEMPTS: u32 = 2;
+/// 启动时版本检测的超时（秒），只尝试一次，避免离线时拖慢菜单
+const STARTUP_CHECK_TIMEOUT_SECS: u64 = 3;
+/// GitHub Token 环境变量（按优先级），用于提高 API 限额
+const GITHUB_TOKEN_ENV_VARS: [&str; 2] = ["CC_SWITCH_GITHUB_TOKEN", "GITHUB_TOKEN"];
+
+/// 读取 GitHub Token，`lookup` 用于查询环境变量
+fn github_token(lookup: impl Fn(&str) -> Option<String>) -> Option<String> {
+    GITHUB_TOKEN_ENV_VARS
+        .iter()
+        .filter_map(|key| lookup(key))
+        .map(|token| token.trim().to_string())
+        .find(|token| !token.is_empty())
+}
+
+/// 有 Token 时附加 `Authorization: Bearer` 头，否则保持匿名请求
+fn authorize(request: reqwest::RequestBuilder, token: Option<&str>) -> reqwest::RequestBuilder {
+    match token {
+        Some(token) => request.bearer_auth(token),
+        None => request,
+    }
+}
+
+/// 将限流重置时间（Unix 秒）格式化为本地时间提示
+fn format_rate_limit_reset(reset: u64, now: u64) -> String {
+    let wait_mins = (reset.saturating_sub(now) + 59) / 60;
+    match chrono::DateTime::from_timestamp(reset as i64, 0) {
+        Some(at) => format!(
+            "请在 {}（约 {} 分钟后）重试",
+            at.with_timezone(&chrono::Local).format("%H:%M:%S"),
+            wait_mins
+        ),
+        None => "请稍后重试".to_string(),
+    }
+}
+
 /// 解析版本字符串，支持多种格式
 fn parse_version(version_str: &str) -> Result<Version> {
     // 移除常见的版本前缀
@@ -137,57 +177,28 @@ fn parse_version(version_str: &str) -> Result<Version> {
 
 /// 检测新版本
 #[allow(dead_code)]
-pub async fn check_update(_ctx: &OutputContext) -> Result<Option<VersionInfo>> {
-    check_update_internal().await
+pub async fn check_update(_ctx: &OutputContext, timeout: Duration) -> Result<Option<VersionInfo>> {
+    check_update_internal(timeout, CHECK_ATTEMPTS).await
 }
 
-/// 内部版本检查实现
-async fn check_update_internal() -> Result<Option<VersionInfo>> {
+/// 内部版本检查实现，`attempts` 为最多请求次数
+async fn check_update_internal(timeout: Duration, attempts: u32) -> Result<Option<VersionInfo>> {
     log::debug!("正在检测更新...");
 
     let client = reqwest::Client::builder()
         .user_agent("cc-switch-cli")
-        .timeout(Duration::from_secs(15))
+        .timeout(timeout)
         .build()?;
 
-    let response = match client.get(GITHUB_API_URL).send().await {
-        Ok(resp) => resp,
-        Err(e) => {
-            let error_msg = if e.is_timeout() {
-                "网络请求超时，请检查网络连接".to_string()
-            } else if e.is_connect() {
-                "无法连接到 GitHub API，请检查网络".to_string()
-            } else if e.is_request() {
-                format!("请求失败: {}", e)
-            } else {
-                format!("网络错误: {}", e)
-            };
-            log::debug!("网络请求失败: {}", e);
-            bail!("{}", error_msg);
-        }
-    };
+    let token = github_token(|key| std::env::var(key).ok());
+    let response = retry_with_backoff(attempts, RETRY_BACKOFF, || {
+        fetch_latest_release(authorize(client.get(GITHUB_API_URL), token.as_deref()))
+    })
+    .await?;
 
     // 检查 GitHub API 限流
     let rate_limit = extract_rate_limit(&response);
 
-    if !response.status().is_success() {
-        let status = response.status();
-        if status.as_u16() == 403 {
-            if let Some(info) = rate_limit {
-                if info.remaining == 0 {
-                    bail!(
-                        "GitHub API 请求次数已用尽，将在 {} 秒后重置",
-                        info.reset_time.unwrap_or(0)
-                    );
-                }
-            }
-            bail!("GitHub API 访问被拒绝，请稍后重试");
-        } else if status.as_u16() == 404 {
-            bail!("未找到发布版本");
-        }
-        bail!("GitHub API 返回错误: {}", status);
-    }
-
     // 记录限流信息
     if let Some(info) = &rate_limit {
         log::debug!(
@@ -235,6 +246,57 @@ async fn check_update_internal() -> Result<Option<VersionInfo>> {
     Ok(Some(version_info))
 }
 
+/// 请求最新 Release，区分可重试的网络/服务端错误与其他错误
+async fn fetch_latest_release(
+    request: reqwest::RequestBuilder,
+) -> std::result::Result<reqwest::Response, CheckFailure> {
+    let response = match request.send().await {
+        Ok(resp) => resp,
+        Err(e) => {
+            log::debug!("网络请求失败: {}", e);
+            return Err(if e.is_timeout() {
+                CheckFailure::transient(anyhow::anyhow!("网络请求超时，请检查网络连接或调大 --timeout"))
+            } else if e.is_connect() {
+                CheckFailure::transient(anyhow::anyhow!("无法连接到 GitHub API，请检查网络"))
+            } else if e.is_request() {
+                CheckFailure::transient(anyhow::anyhow!("请求失败: {}", e))
+            } else {
+                CheckFailure::fatal(anyhow::anyhow!("网络错误: {}", e))
+            });
+        }
+    };
+
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    if status.as_u16() == 403 {
+        if let Some(info) = extract_rate_limit(&response) {
+            if info.remaining == 0 {
+                let hint = match info.reset_time {
+                    Some(reset) => format_rate_limit_reset(reset, chrono::Utc::now().timestamp().max(0) as u64),
+                    None => "请稍后重试".to_string(),
+                };
+                return Err(CheckFailure::fatal(anyhow::anyhow!(
+                    "GitHub API 请求次数已用尽，{}（可设置 CC_SWITCH_GITHUB_TOKEN 或 GITHUB_TOKEN 提高限额）",
+                    hint
+                )));
+            }
+        }
+        return Err(CheckFailure::fatal(anyhow::anyhow!("GitHub API 访问被拒绝，请稍后重试")));
+    }
+    if status.as_u16() == 404 {
+        return Err(CheckFailure::fatal(anyhow::anyhow!("未找到发布版本")));
+    }
+
+    let error = anyhow::anyhow!("GitHub API 返回错误: {}", status);
+    if status.is_server_error() || status.as_u16() == 429 {
+        Err(CheckFailure::transient(error))
+    } else {
+        Err(CheckFailure::fatal(error))
+    }
+}
+
 /// 从响应头提取限流信息
 fn extract_rate_limit(response: &reqwest::Response) -> Option<RateLimitInfo> {
     let headers = response.headers();
@@ -301,14 +363,14 @@ fn get_platform_asset(assets: &[ReleaseAsset]) -> Option<String> {
 }
 
 /// 显示版本状态
-pub async fn show_status(_ctx: &OutputContext, check_only: bool) -> Result<()> {
-    match check_update_internal().await {
+pub async fn show_status(_ctx: &OutputContext, check_only: bool, timeout: Duration) -> Result<()> {
+    match check_update_internal(timeout, CHECK_ATTEMPTS).await {
         Ok(Some(info)) => {
             println!();
             if info.has_update {
                 print_update_notification(&info, check_only);
             } else {
-                println!("{}", "✓ 已是最新版本".green());
+                println!("{}", glyphs("✓ 已是最新版本").green());
                 println!(
                     "  当前版本: {}",
                     format!("v{}", info.current).green()
@@ -330,12 +392,12 @@ pub async fn show_status(_ctx: &OutputContext, check_only: bool) -> Result<()> {
 
 /// 打印更新通知
 fn print_update_notification(info: &VersionInfo, check_only: bool) {
-    println!("{}", "╔════════════════════════════════════════╗".green());
+    println!("{}", glyphs("╔════════════════════════════════════════╗").green());
     println!(
         "{}",
-        format!("║           🎉 发现新版本!               ║").green().bold()
+        glyphs(&format!("║           🎉 发现新版本!               ║")).green().bold()
     );
-    println!("{}", "╚════════════════════════════════════════╝".green());
+    println!("{}", glyphs("╚════════════════════════════════════════╝").green());
     println!();
 
     // 显示版本更新类型
@@ -378,7 +440,7 @@ fn print_update_notification(info: &VersionInfo, check_only: bool) {
 fn print_update_instructions(update_type: &UpdateType) {
     match update_type {
         UpdateType::Major => {
-            println!("{}", "⚠️  大版本更新可能包含不兼容变更，请查看更新说明".yellow());
+            println!("{}", glyphs("⚠️  大版本更新可能包含不兼容变更，请查看更新说明").yellow());
             println!();
             println!("{}", "运行以下命令更新:".white().bold());
             println!("  {}", "cc-switch self-update".green());
@@ -404,27 +466,27 @@ fn print_update_instructions(update_type: &UpdateType) {
 
 /// 启动时检查版本（静默模式）
 pub async fn check_on_startup() {
-    // 仅在后台静默检查，失败不报错
-    match check_update_internal().await {
+    // 仅在后台静默检查，失败不报错；不重试，离线时尽快进入菜单
+    match check_update_internal(Duration::from_secs(STARTUP_CHECK_TIMEOUT_SECS), 1).await {
         Ok(Some(info)) if info.has_update => {
             println!();
             println!(
                 "{}",
-                format!("╭─ {} ─────────────────────────╮", info.update_type.get_message())
+                glyphs(&format!("╭─ {} ─────────────────────────╮", info.update_type.get_message()))
                     .cyan()
             );
             println!(
                 "{}",
-                format!("│ 当前: v{} → 最新: v{} │", info.current, info.latest)
+                glyphs(&format!("│ 当前: v{} → 最新: v{} │", info.current, info.latest))
                     .cyan()
             );
             println!(
                 "{}",
-                "│ 运行 cc-switch self-update 更新  │".cyan()
+                glyphs("│ 运行 cc-switch self-update 更新  │").cyan()
             );
             println!(
                 "{}",
-                "╰─────────────────────────────────╯".cyan()
+                glyphs("╰─────────────────────────────────╯").cyan()
             );
             println!();
         }
@@ -433,25 +495,23 @@ pub async fn check_on_startup() {
 }
 
 /// 执行自动更新
-pub async fn self_update(_ctx: &OutputContext, force: bool) -> Result<()> {
+pub async fn self_update(_ctx: &OutputContext, force: bool, timeout: Duration) -> Result<()> {
     // 首先检查是否有新版本
-    let version_info = match check_update_internal().await? {
+    let version_info = match check_update_internal(timeout, CHECK_ATTEMPTS).await? {
         Some(info) => info,
-        None => {
-            println!("{}", "无法获取版本信息".yellow());
-            return Ok(());
-        }
+        None => bail!("无法获取版本信息"),
     };
 
     // 大版本更新强制提示
     if version_info.update_type.is_forced() && !force {
         println!();
-        println!("{}", "⚠️  检测到大版本更新！".red().bold());
+        println!("{}", glyphs("⚠️  检测到大版本更新！").red().bold());
         println!(
             "{}",
             format!(
-                "  {} → {}",
+                "  {} {} {}",
                 format!("v{}", version_info.current).yellow(),
+                sym("→"),
                 format!("v{}", version_info.latest).green().bold()
             )
         );
@@ -476,7 +536,7 @@ pub async fn self_update(_ctx: &OutputContext, force: bool) -> Result<()> {
     }
 
     if !version_info.has_update && !force {
-        println!("{}", "✓ 已是最新版本，无需更新".green());
+        println!("{}", glyphs("✓ 已是最新版本，无需更新").green());
         println!(
             "  当前版本: {}",
             format!("v{}", version_info.current).green()
@@ -485,13 +545,14 @@ pub async fn self_update(_ctx: &OutputContext, force: bool) -> Result<()> {
     }
 
     println!();
-    println!("{}", "╔════════════════════════════════════════╗".cyan());
-    println!("{}", "║           🔄 开始更新...               ║".cyan().bold());
-    println!("{}", "╚════════════════════════════════════════╝".cyan());
+    println!("{}", glyphs("╔════════════════════════════════════════╗").cyan());
+    println!("{}", glyphs("║           🔄 开始更新...               ║").cyan().bold());
+    println!("{}", glyphs("╚════════════════════════════════════════╝").cyan());
     println!();
     println!(
-        "  {} → {}",
+        "  {} {} {}",
         format!("v{}", version_info.current).yellow(),
+        sym("→"),
         format!("v{}", version_info.latest).green()
     );
     println!();
@@ -503,9 +564,9 @@ pub async fn self_update(_ctx: &OutputContext, force: bool) -> Result<()> {
         match download_and_install(download_url).await {
             Ok(()) => {
                 println!();
-                println!("{}", "╔════════════════════════════════════════╗".green());
-                println!("{}", "║           ✓ 更新成功!                  ║".green().bold());
-                println!("{}", "╚════════════════════════════════════════╝".green());
+                println!("{}", glyphs("╔════════════════════════════════════════╗").green());
+                println!("{}", glyphs("║           ✓ 更新成功!                  ║").green().bold());
+                println!("{}", glyphs("╚════════════════════════════════════════╝").green());
                 println!();
                 println!(
                     "  新版本: {}",
@@ -540,9 +601,9 @@ pub async fn self_update(_ctx: &OutputContext, force: bool) -> Result<()> {
     match status {
         Ok(s) if s.success() => {
             println!();
-            println!("{}", "╔════════════════════════════════════════╗".green());
-            println!("{}", "║           ✓ 更新成功!                  ║".green().bold());
-            println!("{}", "╚════════════════════════════════════════╝".green());
+            println!("{}", glyphs("╔════════════════════════════════════════╗").green());
+            println!("{}", glyphs("║           ✓ 更新成功!                  ║").green().bold());
+            println!("{}", glyphs("╚════════════════════════════════════════╝").green());
             println!();
             Ok(())
         }
@@ -667,10 +728,48 @@ mod tests {
         assert_eq!(UpdateType::from_versions(&v1_0_0, &v1_0_0), UpdateType::None);
     }
 
+    #[test]
+    fn test_github_token_sets_bearer_header() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |key: &str| vars.iter().find(|(k, _)| *k == key).map(|(_, v)| v.to_string())
+        };
+        assert_eq!(github_token(env(&[])), None);
+        assert_eq!(github_token(env(&[("GITHUB_TOKEN", "  ")])), None);
+        assert_eq!(github_token(env(&[("GITHUB_TOKEN", "gh-1")])).as_deref(), Some("gh-1"));
+        assert_eq!(
+            github_token(env(&[("GITHUB_TOKEN", "gh-1"), ("CC_SWITCH_GITHUB_TOKEN", "cc-1")])).as_deref(),
+            Some("cc-1")
+        );
+
+        let client = reqwest::Client::new();
+        let request = authorize(client.get(GITHUB_API_URL), Some("gh-1")).build().unwrap();
+        assert_eq!(request.headers()["authorization"], "Bearer gh-1");
+        let request = authorize(client.get(GITHUB_API_URL), None).build().unwrap();
+        assert!(request.headers().get("authorization").is_none());
+    }
+
+    #[test]
+    fn test_format_rate_limit_reset() {
+        assert!(format_rate_limit_reset(1_000 + 90, 1_000).contains("约 2 分钟后"));
+        assert!(format_rate_limit_reset(500, 1_000).contains("约 0 分钟后"));
+    }
+
     #[test]
     fn test_update_type_message() {
         assert!(UpdateType::Major.get_message().contains("大版本"));
         assert!(UpdateType::Minor.get_message().contains("中版本"));
         assert!(UpdateType::Patch.get_message().contains("小版本"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_check_timeout_must_be_positive() {
+        use clap::Parser;
+
+        let parse = |args: &[&str]| crate::cli::Cli::try_parse_from([&["cc-switch", "self-update"], args].concat());
+        assert!(parse(&["--check", "--timeout", "0"]).is_err());
+        match parse(&["--check"]).unwrap().command {
+            Some(crate::cli::Commands::SelfUpdate { timeout, .. }) => assert_eq!(timeout, DEFAULT_CHECK_TIMEOUT_SECS),
+            _ => panic!("应解析为 self-update"),
+        }
+    }
+}
@@ -4,7 +4,7 @@
 
 use anyhow::Result;
 use ccswitch_core::{AppType, AppState, UsageStatsService};
-use crate::output::{print_success, print_info};
+use crate::output::{print_success, print_info, sym};
 use crate::cli::OutputFormat;
 use crate::output::OutputContext;
 
@@ -123,9 +123,9 @@ pub fn check_limit(ctx: &OutputContext, app: AppType, provider_id: &str) -> Resu
                 println!("  月限额: ${:.2} / 已使用: ${:.2}", monthly, status.monthly_used);
             }
             if status.is_exceeded {
-                println!("  状态: ⚠️ 已超限");
+                println!("  状态: {} 已超限", sym("⚠️"));
             } else {
-                println!("  状态: ✓ 正常");
+                println!("  状态: {} 正常", sym("✓"));
             }
         }
     }
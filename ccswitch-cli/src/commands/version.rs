@@ -0,0 +1,41 @@
+//! version 命令实现
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::cli::OutputFormat;
+use crate::output::OutputContext;
+
+/// 版本与构建信息（构建时由 build.rs 嵌入）
+#[derive(Debug, Serialize)]
+pub struct VersionInfo {
+    pub version: &'static str,
+    pub app_name: &'static str,
+    pub git_sha: Option<&'static str>,
+    pub build_date: &'static str,
+    pub rustc: Option<&'static str>,
+}
+
+impl VersionInfo {
+    pub fn current() -> Self {
+        let non_empty = |s: &'static str| (!s.is_empty()).then_some(s);
+        Self {
+            version: ccswitch_core::VERSION,
+            app_name: ccswitch_core::APP_NAME,
+            git_sha: non_empty(env!("CC_SWITCH_GIT_SHA")),
+            build_date: env!("CC_SWITCH_BUILD_DATE"),
+            rustc: non_empty(env!("CC_SWITCH_RUSTC")),
+        }
+    }
+}
+
+/// 执行 version 命令
+pub fn show_version(ctx: &OutputContext) -> Result<()> {
+    let info = VersionInfo::current();
+    match ctx.format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&info)?),
+        OutputFormat::Yaml => println!("{}", serde_yaml::to_string(&info)?),
+        OutputFormat::Table => println!("{} {}", info.app_name, info.version),
+    }
+    Ok(())
+}
@@ -4,20 +4,128 @@
 
 use anyhow::{bail, Result};
 use colored::Colorize;
+use std::fmt;
 use std::io::{self, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
 
-use ccswitch_core::{AppState, AppType};
+use ccswitch_core::{AppState, AppType, McpStdioConfig, Provider, ProviderService};
 
 use crate::cli::AppTypeArg;
 use crate::commands;
-use crate::output::OutputContext;
+use crate::output::{glyphs, mask_api_key, sym, OutputContext};
+
+/// 交互输入结束的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InputEnded {
+    /// 标准输入已关闭（Ctrl-D）
+    Eof,
+    /// 超过空闲超时仍无输入
+    Idle(Duration),
+}
+
+impl fmt::Display for InputEnded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Eof => write!(f, "输入已结束"),
+            Self::Idle(timeout) => write!(f, "{} 秒内无输入", timeout.as_secs()),
+        }
+    }
+}
+
+impl std::error::Error for InputEnded {}
+
+/// 按需在后台线程读取一行的输入源
+///
+/// 只有请求时才读取，不会抢走子命令自己的 stdin 输入；等待时可设置超时。
+struct LineReader {
+    requests: Sender<()>,
+    lines: Receiver<Option<String>>,
+    timeout: Option<Duration>,
+    ended: Option<InputEnded>,
+}
+
+impl LineReader {
+    fn spawn<F>(mut read_line: F, timeout: Option<Duration>) -> Self
+    where
+        F: FnMut(&mut String) -> io::Result<usize> + Send + 'static,
+    {
+        let (requests, request_rx) = mpsc::channel::<()>();
+        let (line_tx, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for () in request_rx {
+                let mut line = String::new();
+                let line = match read_line(&mut line) {
+                    Ok(0) | Err(_) => None,
+                    Ok(_) => Some(line),
+                };
+                let eof = line.is_none();
+                if line_tx.send(line).is_err() || eof {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            requests,
+            lines,
+            timeout,
+            ended: None,
+        }
+    }
+
+    fn stdin() -> Self {
+        Self::spawn(
+            |buf| io::stdin().read_line(buf),
+            ccswitch_core::settings::interactive_idle_timeout(),
+        )
+    }
+
+    fn read_line(&mut self) -> Result<String, InputEnded> {
+        if let Some(ended) = self.ended {
+            return Err(ended);
+        }
+
+        let line = if self.requests.send(()).is_err() {
+            None
+        } else {
+            match self.timeout {
+                Some(timeout) => match self.lines.recv_timeout(timeout) {
+                    Ok(line) => line,
+                    Err(RecvTimeoutError::Timeout) => {
+                        self.ended = Some(InputEnded::Idle(timeout));
+                        return Err(InputEnded::Idle(timeout));
+                    }
+                    Err(RecvTimeoutError::Disconnected) => None,
+                },
+                None => self.lines.recv().ok().flatten(),
+            }
+        };
+
+        line.ok_or_else(|| {
+            self.ended = Some(InputEnded::Eof);
+            InputEnded::Eof
+        })
+    }
+}
+
+/// 交互模式共用的输入源（首次读取时绑定 stdin）
+static INPUT: Mutex<Option<LineReader>> = Mutex::new(None);
+
+/// 从共用输入源读取一行
+fn next_line() -> Result<String> {
+    let mut input = INPUT.lock().unwrap_or_else(|e| e.into_inner());
+    let reader = input.get_or_insert_with(LineReader::stdin);
+    Ok(reader.read_line()?)
+}
 
 /// 读取用户输入
 fn read_input(prompt: &str) -> Result<String> {
     print!("{}", prompt);
     io::stdout().flush()?;
-    let mut input = String::new();
-    io::stdin().read_line(&mut input)?;
+    let input = next_line()?;
     Ok(input.trim().to_string())
 }
 
@@ -86,8 +194,13 @@ fn select_provider(state: &AppState, app_type: AppType) -> Result<Option<String>
     let provider_list: Vec<_> = providers.iter().collect();
     for (i, (id, p)) in provider_list.iter().enumerate() {
         let is_current = *id == &current_id;
-        let status = if is_current { "●".green() } else { "○".dimmed() };
-        let current = if is_current { " (当前)".green().to_string() } else { String::new() };
+        let (status, current) = if app_type.is_additive_mode() {
+            (sym("●").green(), " (已启用)".green().to_string())
+        } else if is_current {
+            (sym("●").green(), " (当前)".green().to_string())
+        } else {
+            (sym("○").dimmed(), String::new())
+        };
         println!("  {} {} {}{}", format!("{}.", i + 1).green(), status, p.name, current);
     }
     println!("  {} {}", "0.".green(), "返回".white());
@@ -104,14 +217,35 @@ fn select_provider(state: &AppState, app_type: AppType) -> Result<Option<String>
             if num > 0 && num <= provider_list.len() {
                 return Ok(Some(provider_list[num - 1].1.name.clone()));
             }
+            // 数字不参与模糊匹配，避免 "12" 误选名称中含 12 的供应商
+            println!("{}", format!("序号超出范围 (1-{})，请重新输入", provider_list.len()).yellow());
+            continue;
         }
 
-        // 尝试匹配名称
-        if providers.values().any(|p| p.name == choice) || providers.contains_key(&choice) {
-            return Ok(Some(choice));
+        // 序号之外按名称/ID 模糊匹配
+        let candidates = ccswitch_core::ProviderService::fuzzy_find(state, app_type, &choice)?;
+        match candidates.as_slice() {
+            [] => println!("{}", "无效选择，请重新输入".yellow()),
+            [provider] => {
+                if provider.name != choice && provider.id != choice {
+                    println!("{}", format!("匹配到: {}", provider.name).dimmed());
+                }
+                return Ok(Some(provider.name.clone()));
+            }
+            candidates => {
+                println!("{}", "匹配到多个供应商，请输入更完整的名称或序号:".yellow());
+                for provider in candidates.iter().take(5) {
+                    let index = provider_list
+                        .iter()
+                        .position(|(id, _)| **id == provider.id)
+                        .map_or(0, |i| i + 1);
+                    println!("  {} {}", format!("{}.", index).green(), provider.name);
+                }
+                if candidates.len() > 5 {
+                    println!("  {}", format!("... 另有 {} 个", candidates.len() - 5).dimmed());
+                }
+            }
         }
-
-        println!("{}", "无效选择，请重新输入".yellow());
     }
 }
 
@@ -120,8 +254,7 @@ fn pause() {
     println!();
     print!("{}", "按 Enter 键返回主菜单...".dimmed());
     io::stdout().flush().ok();
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).ok();
+    next_line().ok();
 }
 
 /// 清屏（可选）
@@ -166,57 +299,57 @@ fn show_welcome_banner() -> Result<()> {
         .unwrap_or_else(|| "~".to_string());
 
     // 顶部边框
-    println!("{}", "┌─────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────┐".cyan());
+    println!("{}", glyphs("┌─────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────┐").cyan());
 
     // 标题行：版本和欢迎信息
     let version = format!("CC-Switch v{}", ccswitch_core::VERSION);
     println!("{} {:^48} {} {:104} {}",
-        "│".cyan(),
+        sym("│").cyan(),
         version.bright_white().bold(),
-        "│".cyan(),
+        sym("│").cyan(),
         "Tips for getting started".yellow(),
-        "│".cyan()
+        sym("│").cyan()
     );
 
     println!("{} {:^48} {} {:104} {}",
-        "│".cyan(),
+        sym("│").cyan(),
         "Welcome back!".bright_white().bold(),
-        "│".cyan(),
+        sym("│").cyan(),
         format!("Run {} to list all providers", "cc-switch list".green()),
-        "│".cyan()
+        sym("│").cyan()
     );
 
     // ASCII Art (简化的图标)
     println!("{} {:^48} {} {:104} {}",
-        "│".cyan(),
+        sym("│").cyan(),
         "",
-        "│".cyan(),
+        sym("│").cyan(),
         format!("Run {} to see current status", "cc-switch status".green()),
-        "│".cyan()
+        sym("│").cyan()
     );
 
     println!("{} {:^48} {} {:104} {}",
-        "│".cyan(),
-        "    ╔═══╗".yellow(),
-        "│".cyan(),
+        sym("│").cyan(),
+        glyphs("    ╔═══╗").yellow(),
+        sym("│").cyan(),
         "",
-        "│".cyan()
+        sym("│").cyan()
     );
 
     println!("{} {:^48} {} {:104} {}",
-        "│".cyan(),
-        "    ║ ∞ ║".yellow(),
-        "│".cyan(),
+        sym("│").cyan(),
+        glyphs("    ║ ∞ ║").yellow(),
+        sym("│").cyan(),
         "Current providers".yellow().bold(),
-        "│".cyan()
+        sym("│").cyan()
     );
 
     println!("{} {:^48} {} {:104} {}",
-        "│".cyan(),
-        "    ╚═══╝".yellow(),
-        "│".cyan(),
+        sym("│").cyan(),
+        glyphs("    ╚═══╝").yellow(),
+        sym("│").cyan(),
         "",
-        "│".cyan()
+        sym("│").cyan()
     );
 
     // 当前供应商信息 - Claude
@@ -224,17 +357,17 @@ fn show_welcome_banner() -> Result<()> {
         let model = provider.settings_config.get("model")
             .and_then(|v| v.as_str())
             .unwrap_or("unknown");
-        format!("{} → {} ({})", "Claude Code".cyan(), provider.name.green(), model.dimmed())
+        format!("{} {} {} ({})", "Claude Code".cyan(), sym("→"), provider.name.green(), model.dimmed())
     } else {
-        format!("{} → {}", "Claude Code".cyan(), "Not configured".dimmed())
+        format!("{} {} {}", "Claude Code".cyan(), sym("→"), "Not configured".dimmed())
     };
 
     println!("{} {:^48} {} {:104} {}",
-        "│".cyan(),
+        sym("│").cyan(),
         "",
-        "│".cyan(),
+        sym("│").cyan(),
         claude_info,
-        "│".cyan()
+        sym("│").cyan()
     );
 
     // 当前供应商信息 - Codex
@@ -242,17 +375,17 @@ fn show_welcome_banner() -> Result<()> {
         let model = provider.settings_config.get("model")
             .and_then(|v| v.as_str())
             .unwrap_or("unknown");
-        format!("{} → {} ({})", "Codex      ".cyan(), provider.name.green(), model.dimmed())
+        format!("{} {} {} ({})", "Codex      ".cyan(), sym("→"), provider.name.green(), model.dimmed())
     } else {
-        format!("{} → {}", "Codex      ".cyan(), "Not configured".dimmed())
+        format!("{} {} {}", "Codex      ".cyan(), sym("→"), "Not configured".dimmed())
     };
 
     println!("{} {:^48} {} {:104} {}",
-        "│".cyan(),
+        sym("│").cyan(),
         format!("Working Directory").white(),
-        "│".cyan(),
+        sym("│").cyan(),
         codex_info,
-        "│".cyan()
+        sym("│").cyan()
     );
 
     // 当前供应商信息 - Gemini
@@ -260,38 +393,38 @@ fn show_welcome_banner() -> Result<()> {
         let model = provider.settings_config.get("model")
             .and_then(|v| v.as_str())
             .unwrap_or("unknown");
-        format!("{} → {} ({})", "Gemini CLI ".cyan(), provider.name.green(), model.dimmed())
+        format!("{} {} {} ({})", "Gemini CLI ".cyan(), sym("→"), provider.name.green(), model.dimmed())
     } else {
-        format!("{} → {}", "Gemini CLI ".cyan(), "Not configured".dimmed())
+        format!("{} {} {}", "Gemini CLI ".cyan(), sym("→"), "Not configured".dimmed())
     };
 
     println!("{} {:^48} {} {:104} {}",
-        "│".cyan(),
+        sym("│").cyan(),
         current_dir.dimmed(),
-        "│".cyan(),
+        sym("│").cyan(),
         gemini_info,
-        "│".cyan()
+        sym("│").cyan()
     );
 
     // 底部提示
     println!("{} {:^48} {} {:104} {}",
-        "│".cyan(),
+        sym("│").cyan(),
         "",
-        "│".cyan(),
+        sym("│").cyan(),
         "",
-        "│".cyan()
+        sym("│").cyan()
     );
 
     println!("{} {:^48} {} {:104} {}",
-        "│".cyan(),
+        sym("│").cyan(),
         format!("Type {} for batch operations", "batch".green()),
-        "│".cyan(),
+        sym("│").cyan(),
         format!("Quick tips: {} for switch, {} for add provider", "3".green(), "4".green()),
-        "│".cyan()
+        sym("│").cyan()
     );
 
     // 底部边框
-    println!("{}", "└─────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────┘".cyan());
+    println!("{}", glyphs("└─────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────┘").cyan());
     println!();
 
     Ok(())
@@ -299,7 +432,12 @@ fn show_welcome_banner() -> Result<()> {
 
 /// 主菜单
 pub fn main_menu() -> Result<()> {
-    let ctx = OutputContext::new(crate::cli::OutputFormat::Table, false);
+    let ctx = OutputContext::new(
+        crate::cli::OutputFormat::Table,
+        false,
+        false,
+        crate::commands::configured_theme(),
+    );
 
     // 首次显示欢迎信息
     clear_screen();
@@ -310,16 +448,41 @@ pub fn main_menu() -> Result<()> {
     // 暂停以便用户查看
     pause();
 
+    run_menu(ctx)
+}
+
+/// 输入结束时退出菜单，其他错误原样返回
+fn end_session(e: anyhow::Error) -> Result<()> {
+    match e.downcast_ref::<InputEnded>() {
+        Some(InputEnded::Idle(timeout)) => {
+            println!();
+            println!(
+                "{}",
+                format!("{} 秒内无输入，已自动退出。", timeout.as_secs()).yellow()
+            );
+            Ok(())
+        }
+        Some(InputEnded::Eof) => {
+            println!();
+            println!("{}", "再见！".green());
+            Ok(())
+        }
+        None => Err(e),
+    }
+}
+
+/// 主菜单循环
+fn run_menu(ctx: OutputContext) -> Result<()> {
     loop {
         clear_screen();
 
-        println!("{}", "╔════════════════════════════════════════╗".cyan());
-        println!("{}", "║     CC-Switch - AI CLI 配置管理器      ║".cyan().bold());
-        println!("{}", "╚════════════════════════════════════════╝".cyan());
+        println!("{}", glyphs("╔════════════════════════════════════════╗").cyan());
+        println!("{}", glyphs("║     CC-Switch - AI CLI 配置管理器      ║").cyan().bold());
+        println!("{}", glyphs("╚════════════════════════════════════════╝").cyan());
         println!();
         println!("{}", "请选择操作:".white().bold());
         println!();
-        println!("{}", "── 供应商管理 ──".dimmed());
+        println!("{}", glyphs("── 供应商管理 ──").dimmed());
         println!("  {} {} - 查看所有供应商配置", "1.".green(), "列出供应商".white());
         println!("  {} {} - 查看当前使用的供应商", "2.".green(), "查看状态".white());
         println!("  {} {} - 切换到其他供应商", "3.".green(), "切换供应商".white());
@@ -328,19 +491,19 @@ pub fn main_menu() -> Result<()> {
         println!("  {} {} - 测试供应商 API", "6.".green(), "测试供应商".white());
         println!("  {} {} - 删除供应商配置", "7.".green(), "删除供应商".white());
         println!();
-        println!("{}", "── 扩展功能 ──".dimmed());
+        println!("{}", glyphs("── 扩展功能 ──").dimmed());
         println!("  {} {} - 管理 MCP 服务器", "8.".green(), "MCP 服务器".white());
         println!("  {} {} - 管理系统提示词", "9.".green(), "Prompts".white());
         println!(" {} {} - 管理 Skills 扩展", "10.".green(), "Skills".white());
         println!(" {} {} - 管理 OpenClaw 配置", "11.".green(), "OpenClaw".white());
         println!();
-        println!("{}", "── 高级功能 ──".dimmed());
+        println!("{}", glyphs("── 高级功能 ──").dimmed());
         println!(" {} {} - 管理故障转移队列", "12.".green(), "故障转移".white());
         println!(" {} {} - 查看使用量统计", "13.".green(), "使用统计".white());
         println!(" {} {} - WebDAV 云端同步", "14.".green(), "云端同步".white());
         println!(" {} {} - 启动 Web 控制器", "15.".green(), "Web 控制器".white());
         println!();
-        println!("{}", "── 工具 ──".dimmed());
+        println!("{}", glyphs("── 工具 ──").dimmed());
         println!(" {} {} - 设置全局代理", "16.".green(), "代理设置".white());
         println!(" {} {} - 测试 API 端点延迟", "17.".green(), "端点测速".white());
         println!(" {} {} - 检测环境变量冲突", "18.".green(), "环境检测".white());
@@ -351,12 +514,15 @@ pub fn main_menu() -> Result<()> {
         println!("  {} {} - 退出程序", "0.".green(), "退出".white());
         println!();
 
-        let choice = read_input("请输入操作编号: ")?;
+        let choice = match read_input("请输入操作编号: ") {
+            Ok(choice) => choice,
+            Err(e) => return end_session(e),
+        };
 
         match choice.as_str() {
             "1" | "list" | "ls" => {
                 clear_screen();
-                commands::list::list_providers(&ctx, AppTypeArg::All, false, true)?;
+                commands::list::list_providers(&ctx, AppTypeArg::All, false, true, None, false, None)?;
                 pause();
             }
             "2" | "status" => {
@@ -515,7 +681,7 @@ pub fn main_menu() -> Result<()> {
 
 /// 交互式切换供应商
 fn interactive_switch(ctx: &OutputContext) -> Result<()> {
-    println!("\n{}", "═══ 切换供应商 ═══".cyan().bold());
+    println!("\n{}", glyphs("═══ 切换供应商 ═══").cyan().bold());
 
     let Some(app_type) = select_app_type()? else { return Ok(()); };
     let state = AppState::init()?;
@@ -529,13 +695,13 @@ fn interactive_switch(ctx: &OutputContext) -> Result<()> {
         AppType::OpenClaw => AppTypeArg::Openclaw,
     };
 
-    commands::provider::switch(ctx, &name, app_arg)?;
+    commands::provider::switch(ctx, &name, app_arg, false)?;
     Ok(())
 }
 
 /// 交互式添加供应商
 fn interactive_add(ctx: &OutputContext) -> Result<()> {
-    println!("\n{}", "═══ 添加新供应商 ═══".cyan().bold());
+    println!("\n{}", glyphs("═══ 添加新供应商 ═══").cyan().bold());
 
     let Some(app_type) = select_app_type()? else { return Ok(()); };
     let name = read_required("供应商名称")?;
@@ -559,7 +725,7 @@ fn interactive_add(ctx: &OutputContext) -> Result<()> {
 
             commands::provider::add(
                 ctx, &name, app_arg,
-                Some(api_key), base_url, model, small_model, None, false
+                Some(api_key), base_url, model, small_model, None, None, None, false, None, false, false
             )?;
         }
         AppType::Codex => {
@@ -570,7 +736,7 @@ fn interactive_add(ctx: &OutputContext) -> Result<()> {
 
             commands::provider::add(
                 ctx, &name, app_arg,
-                Some(api_key), base_url, model, None, None, false
+                Some(api_key), base_url, model, None, None, None, None, false, None, false, false
             )?;
         }
         AppType::Gemini => {
@@ -581,7 +747,7 @@ fn interactive_add(ctx: &OutputContext) -> Result<()> {
 
             commands::provider::add(
                 ctx, &name, app_arg,
-                Some(api_key), base_url, model, None, None, false
+                Some(api_key), base_url, model, None, None, None, None, false, None, false, false
             )?;
         }
         AppType::OpenCode | AppType::OpenClaw => {
@@ -595,7 +761,7 @@ fn interactive_add(ctx: &OutputContext) -> Result<()> {
 
 /// 交互式删除供应商
 fn interactive_remove(ctx: &OutputContext) -> Result<()> {
-    println!("\n{}", "═══ 删除供应商 ═══".cyan().bold());
+    println!("\n{}", glyphs("═══ 删除供应商 ═══").cyan().bold());
 
     let Some(app_type) = select_app_type()? else { return Ok(()); };
     let state = AppState::init()?;
@@ -613,9 +779,49 @@ fn interactive_remove(ctx: &OutputContext) -> Result<()> {
     Ok(())
 }
 
+/// 编辑供应商时预填的当前值（API Key 已遮蔽）
+#[derive(Debug, Default, PartialEq)]
+struct EditDefaults {
+    name: String,
+    api_key: Option<String>,
+    base_url: Option<String>,
+    model: Option<String>,
+    small_model: Option<String>,
+}
+
+/// 从供应商配置提取编辑默认值
+fn edit_defaults(provider: &Provider, app_type: &AppType) -> EditDefaults {
+    let api_key = ProviderService::extract_credentials(provider, app_type)
+        .ok()
+        .map(|(key, _)| key)
+        .filter(|key| !key.is_empty())
+        .map(|key| mask_api_key(&key));
+    let small_model = match app_type {
+        AppType::Claude => provider
+            .settings_config
+            .pointer("/env/ANTHROPIC_SMALL_FAST_MODEL")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+        _ => None,
+    };
+
+    EditDefaults {
+        name: provider.name.clone(),
+        api_key,
+        base_url: provider.get_base_url(),
+        model: provider.get_model(),
+        small_model,
+    }
+}
+
+/// 输入与默认值相同（直接回车）时视为未修改
+fn changed_value(input: Option<String>, default: Option<&str>) -> Option<String> {
+    input.filter(|v| Some(v.as_str()) != default)
+}
+
 /// 交互式编辑供应商
 fn interactive_edit(ctx: &OutputContext) -> Result<()> {
-    println!("\n{}", "═══ 编辑供应商 ═══".cyan().bold());
+    println!("\n{}", glyphs("═══ 编辑供应商 ═══").cyan().bold());
 
     let Some(app_type) = select_app_type()? else { return Ok(()); };
     let state = AppState::init()?;
@@ -629,14 +835,22 @@ fn interactive_edit(ctx: &OutputContext) -> Result<()> {
         AppType::OpenClaw => AppTypeArg::Openclaw,
     };
 
-    println!("\n{}", "修改配置 (留空保持不变):".white().bold());
+    let Some(provider) = commands::provider::find_unique(&state, app_type, &name)? else {
+        bail!("未找到供应商: {}", name);
+    };
+    let defaults = edit_defaults(&provider, &app_type);
+
+    println!("\n{}", "修改配置 (回车保留当前值):".white().bold());
 
-    let new_name = read_optional("新名称", None)?;
-    let api_key = read_optional("新 API Key", None)?;
-    let base_url = read_optional("新 Base URL", None)?;
-    let model = read_optional("新模型", None)?;
+    let ask = |prompt: &str, default: Option<&str>| -> Result<Option<String>> {
+        Ok(changed_value(read_optional(prompt, default)?, default))
+    };
+    let new_name = ask("名称", Some(&defaults.name))?;
+    let api_key = ask("API Key", defaults.api_key.as_deref())?;
+    let base_url = ask("Base URL", defaults.base_url.as_deref())?;
+    let model = ask("模型", defaults.model.as_deref())?;
     let small_model = if matches!(app_type, AppType::Claude) {
-        read_optional("新小模型", None)?
+        ask("小模型", defaults.small_model.as_deref())?
     } else {
         None
     };
@@ -647,13 +861,13 @@ fn interactive_edit(ctx: &OutputContext) -> Result<()> {
         return Ok(());
     }
 
-    commands::provider::edit(ctx, &name, app_arg, api_key, base_url, model, small_model, new_name)?;
+    commands::provider::edit(ctx, &name, app_arg, api_key, base_url, model, small_model, new_name, None)?;
     Ok(())
 }
 
 /// 交互式测试供应商
 fn interactive_test(ctx: &OutputContext) -> Result<()> {
-    println!("\n{}", "═══ 测试供应商 API ═══".cyan().bold());
+    println!("\n{}", glyphs("═══ 测试供应商 API ═══").cyan().bold());
     println!();
     println!("  {} {} - 测试已配置的供应商", "1.".green(), "选择供应商".white());
     println!("  {} {} - 直接输入 API Key 测试", "2.".green(), "手动测试".white());
@@ -678,7 +892,7 @@ fn interactive_test(ctx: &OutputContext) -> Result<()> {
 
                 tokio::runtime::Runtime::new()
                     .unwrap()
-                    .block_on(commands::provider::test_api(ctx, Some(name), app_arg, None, None, None, 30))?;
+                    .block_on(commands::provider::test_api(ctx, Some(name), app_arg, None, None, None, 30, false, false, false, commands::provider::DEFAULT_TEST_RETRIES))?;
                 return Ok(());
             }
             "2" => {
@@ -697,7 +911,7 @@ fn interactive_test(ctx: &OutputContext) -> Result<()> {
 
                 tokio::runtime::Runtime::new()
                     .unwrap()
-                    .block_on(commands::provider::test_api(ctx, None, app_arg, Some(api_key), base_url, model, 30))?;
+                    .block_on(commands::provider::test_api(ctx, None, app_arg, Some(api_key), base_url, model, 30, false, false, false, commands::provider::DEFAULT_TEST_RETRIES))?;
                 return Ok(());
             }
             "0" | "q" | "back" => return Ok(()),
@@ -710,7 +924,7 @@ fn interactive_test(ctx: &OutputContext) -> Result<()> {
 fn interactive_mcp(ctx: &OutputContext) -> Result<()> {
     loop {
         clear_screen();
-        println!("{}", "═══ MCP 服务器管理 ═══".cyan().bold());
+        println!("{}", glyphs("═══ MCP 服务器管理 ═══").cyan().bold());
         println!();
         println!("  {} {} - 列出所有 MCP 服务器", "1.".green(), "列出".white());
         println!("  {} {} - 添加 MCP 服务器", "2.".green(), "添加".white());
@@ -734,15 +948,24 @@ fn interactive_mcp(ctx: &OutputContext) -> Result<()> {
                 let args: Vec<String> = args_str
                     .map(|s| s.split_whitespace().map(|s| s.to_string()).collect())
                     .unwrap_or_default();
+                let env_str = read_optional("环境变量 (KEY=VALUE，空格分隔)", None)?;
+                let env: Vec<String> = env_str
+                    .map(|s| s.split_whitespace().map(|s| s.to_string()).collect())
+                    .unwrap_or_default();
                 let name = read_optional("显示名称", None)?;
                 let description = read_optional("描述", None)?;
 
-                commands::mcp::add(ctx, &id, &command, args, vec![], name, description)?;
+                if let Err(e) = McpStdioConfig::parse_env(env.clone()) {
+                    println!("{}", format!("错误: {}", e).red());
+                    pause();
+                    continue;
+                }
+                commands::mcp::add(ctx, &id, &command, args, env, name, description, false)?;
                 pause();
             }
             "3" | "import" => {
                 clear_screen();
-                commands::mcp::import(ctx, None)?;
+                commands::mcp::import(ctx, None, false, Vec::new())?;
                 pause();
             }
             "0" | "q" | "back" => return Ok(()),
@@ -759,7 +982,7 @@ fn interactive_mcp(ctx: &OutputContext) -> Result<()> {
 fn interactive_prompt(ctx: &OutputContext) -> Result<()> {
     loop {
         clear_screen();
-        println!("{}", "═══ Prompts 管理 ═══".cyan().bold());
+        println!("{}", glyphs("═══ Prompts 管理 ═══").cyan().bold());
         println!();
         println!("  {} {} - 列出所有 Prompts", "1.".green(), "列出".white());
         println!("  {} {} - 添加 Prompt", "2.".green(), "添加".white());
@@ -811,7 +1034,7 @@ fn interactive_prompt(ctx: &OutputContext) -> Result<()> {
 fn interactive_skill(ctx: &OutputContext) -> Result<()> {
     loop {
         clear_screen();
-        println!("{}", "═══ Skills 管理 ═══".cyan().bold());
+        println!("{}", glyphs("═══ Skills 管理 ═══").cyan().bold());
         println!();
         println!("  {} {} - 列出所有 Skills", "1.".green(), "列出".white());
         println!("  {} {} - 从 GitHub 安装", "2.".green(), "安装".white());
@@ -833,7 +1056,7 @@ fn interactive_skill(ctx: &OutputContext) -> Result<()> {
                 let repo = read_required("GitHub 仓库 (owner/name)")?;
                 let branch = read_optional("分支", Some("main"))?;
 
-                commands::skill::install(ctx, &repo, branch, None)?;
+                commands::skill::install(ctx, &repo, branch, None, None)?;
                 pause();
             }
             "3" | "scan" => {
@@ -860,7 +1083,7 @@ fn interactive_skill(ctx: &OutputContext) -> Result<()> {
 fn interactive_proxy(ctx: &OutputContext) -> Result<()> {
     loop {
         clear_screen();
-        println!("{}", "═══ 代理设置 ═══".cyan().bold());
+        println!("{}", glyphs("═══ 代理设置 ═══").cyan().bold());
         println!();
         println!("  {} {} - 查看当前代理", "1.".green(), "查看".white());
         println!("  {} {} - 设置代理", "2.".green(), "设置".white());
@@ -880,7 +1103,7 @@ fn interactive_proxy(ctx: &OutputContext) -> Result<()> {
             "2" | "set" => {
                 clear_screen();
                 let url = read_required("代理 URL (如 http://127.0.0.1:7890)")?;
-                commands::proxy::set(ctx, &url)?;
+                commands::proxy::set(ctx, &url, None)?;
                 pause();
             }
             "3" | "clear" => {
@@ -899,7 +1122,7 @@ fn interactive_proxy(ctx: &OutputContext) -> Result<()> {
                 clear_screen();
                 tokio::runtime::Runtime::new()
                     .unwrap()
-                    .block_on(commands::proxy::scan(ctx))?;
+                    .block_on(commands::proxy::scan(ctx, false))?;
                 pause();
             }
             "0" | "q" | "back" => return Ok(()),
@@ -916,7 +1139,7 @@ fn interactive_proxy(ctx: &OutputContext) -> Result<()> {
 fn interactive_speedtest(ctx: &OutputContext) -> Result<()> {
     loop {
         clear_screen();
-        println!("{}", "═══ 端点测速 ═══".cyan().bold());
+        println!("{}", glyphs("═══ 端点测速 ═══").cyan().bold());
         println!();
         println!("将测试以下端点的延迟:");
         println!("  - https://api.anthropic.com");
@@ -949,7 +1172,7 @@ fn interactive_speedtest(ctx: &OutputContext) -> Result<()> {
 fn interactive_env(ctx: &OutputContext) -> Result<()> {
     loop {
         clear_screen();
-        println!("{}", "═══ 环境变量检测 ═══".cyan().bold());
+        println!("{}", glyphs("═══ 环境变量检测 ═══").cyan().bold());
         println!();
         println!("  {} {} - 检查环境变量冲突", "1.".green(), "检查".white());
         println!("  {} {} - 列出相关环境变量", "2.".green(), "列出".white());
@@ -960,7 +1183,7 @@ fn interactive_env(ctx: &OutputContext) -> Result<()> {
         match choice.as_str() {
             "1" | "check" => {
                 clear_screen();
-                commands::env::check(ctx, AppTypeArg::All)?;
+                commands::env::check(ctx, AppTypeArg::All, false, false)?;
                 pause();
             }
             "2" | "list" => {
@@ -980,9 +1203,10 @@ fn interactive_env(ctx: &OutputContext) -> Result<()> {
 
 /// 交互式更新检测
 fn interactive_update(ctx: &OutputContext) -> Result<()> {
+    let timeout = Duration::from_secs(commands::update::DEFAULT_CHECK_TIMEOUT_SECS);
     loop {
         clear_screen();
-        println!("{}", "═══ 检测更新 ═══".cyan().bold());
+        println!("{}", glyphs("═══ 检测更新 ═══").cyan().bold());
         println!();
         println!("  {} {} - 仅检查是否有新版本", "1.".green(), "检测更新".white());
         println!("  {} {} - 检测并执行自动更新", "2.".green(), "自动更新".white());
@@ -996,21 +1220,21 @@ fn interactive_update(ctx: &OutputContext) -> Result<()> {
                 clear_screen();
                 tokio::runtime::Runtime::new()
                     .unwrap()
-                    .block_on(commands::update::show_status(ctx, true))?;
+                    .block_on(commands::update::show_status(ctx, true, timeout))?;
                 pause();
             }
             "2" | "update" | "upgrade" => {
                 clear_screen();
                 tokio::runtime::Runtime::new()
                     .unwrap()
-                    .block_on(commands::update::self_update(ctx, false))?;
+                    .block_on(commands::update::self_update(ctx, false, timeout))?;
                 pause();
             }
             "3" | "force" => {
                 clear_screen();
                 tokio::runtime::Runtime::new()
                     .unwrap()
-                    .block_on(commands::update::self_update(ctx, true))?;
+                    .block_on(commands::update::self_update(ctx, true, timeout))?;
                 pause();
             }
             "0" | "q" | "back" => return Ok(()),
@@ -1027,7 +1251,7 @@ fn interactive_update(ctx: &OutputContext) -> Result<()> {
 fn interactive_openclaw(ctx: &OutputContext) -> Result<()> {
     loop {
         clear_screen();
-        println!("{}", "═══ OpenClaw 配置管理 ═══".cyan().bold());
+        println!("{}", glyphs("═══ OpenClaw 配置管理 ═══").cyan().bold());
         println!();
         println!("  {} {} - 列出 OpenClaw 供应商", "1.".green(), "列出".white());
         println!("  {} {} - 查看配置路径", "2.".green(), "配置路径".white());
@@ -1072,7 +1296,7 @@ fn interactive_openclaw(ctx: &OutputContext) -> Result<()> {
 fn interactive_failover(ctx: &OutputContext) -> Result<()> {
     loop {
         clear_screen();
-        println!("{}", "═══ 故障转移队列管理 ═══".cyan().bold());
+        println!("{}", glyphs("═══ 故障转移队列管理 ═══").cyan().bold());
         println!();
         println!("  {} {} - 查看故障转移队列", "1.".green(), "查看队列".white());
         println!("  {} {} - 添加供应商到队列", "2.".green(), "添加".white());
@@ -1123,7 +1347,7 @@ fn interactive_failover(ctx: &OutputContext) -> Result<()> {
 fn interactive_usage(ctx: &OutputContext) -> Result<()> {
     loop {
         clear_screen();
-        println!("{}", "═══ 使用量统计 ═══".cyan().bold());
+        println!("{}", glyphs("═══ 使用量统计 ═══").cyan().bold());
         println!();
         println!("  {} {} - 查看使用量汇总", "1.".green(), "汇总".white());
         println!("  {} {} - 查看每日趋势", "2.".green(), "趋势".white());
@@ -1172,7 +1396,7 @@ fn interactive_usage(ctx: &OutputContext) -> Result<()> {
 fn interactive_webdav(ctx: &OutputContext) -> Result<()> {
     loop {
         clear_screen();
-        println!("{}", "═══ WebDAV 云端同步 ═══".cyan().bold());
+        println!("{}", glyphs("═══ WebDAV 云端同步 ═══").cyan().bold());
         println!();
         println!("  {} {} - 查看 WebDAV 配置", "1.".green(), "查看配置".white());
         println!("  {} {} - 配置 WebDAV 连接", "2.".green(), "配置".white());
@@ -1231,14 +1455,14 @@ fn interactive_webdav(ctx: &OutputContext) -> Result<()> {
 
 /// 交互式 Web 控制器
 fn interactive_web(_ctx: &OutputContext) -> Result<()> {
-    println!("\n{}", "═══ Web 控制器 ═══".cyan().bold());
+    println!("\n{}", glyphs("═══ Web 控制器 ═══").cyan().bold());
     println!();
     println!("启动 Web UI 服务，通过浏览器管理配置。");
     println!();
-    println!("{}", "⚠️  安全警告:".yellow());
-    println!("  • 服务绑定所有网络接口，可从公网访问");
-    println!("  • 配置完成后请及时关闭 (Ctrl+C)");
-    println!("  • 建议在可信网络环境中使用");
+    println!("{}", glyphs("⚠️  安全警告:").yellow());
+    println!("  {} 服务绑定所有网络接口，可从公网访问", sym("•"));
+    println!("  {} 配置完成后请及时关闭 (Ctrl+C)", sym("•"));
+    println!("  {} 建议在可信网络环境中使用", sym("•"));
     println!();
 
     let port_str = read_optional("端口", Some("8000"))?;
@@ -1267,18 +1491,18 @@ fn interactive_web(_ctx: &OutputContext) -> Result<()> {
             .parse()
             .map_err(|e| anyhow::anyhow!("无效的地址: {}", e))?;
 
-        println!("{}", "╔══════════════════════════════════════════════════════════════╗".cyan());
-        println!("{}", "║           🌐 CC-Switch Web 控制器已启动                      ║".cyan());
-        println!("{}", "╠══════════════════════════════════════════════════════════════╣".cyan());
-        println!("║  访问地址: http://{}:{}                                   ║", host, port);
-        println!("║  登录账号: {}                                               ║", user);
-        println!("{}", "║                                                              ║".cyan());
-        println!("{}", "║  ⚠️  安全提示:                                               ║".cyan());
-        println!("{}", "║  • 此服务绑定所有网络接口，可从公网访问                       ║".cyan());
-        println!("{}", "║  • 已启用身份验证，请使用设置的账号密码登录                   ║".cyan());
-        println!("{}", "║  • 配置完成后请及时关闭 (Ctrl+C)                             ║".cyan());
-        println!("{}", "║  • 建议在防火墙后使用或使用临时会话                          ║".cyan());
-        println!("{}", "╚══════════════════════════════════════════════════════════════╝".cyan());
+        println!("{}", glyphs("╔══════════════════════════════════════════════════════════════╗").cyan());
+        println!("{}", glyphs("║           🌐 CC-Switch Web 控制器已启动                      ║").cyan());
+        println!("{}", glyphs("╠══════════════════════════════════════════════════════════════╣").cyan());
+        println!("{}", glyphs(&format!("║  访问地址: http://{}:{}                                   ║", host, port)));
+        println!("{}", glyphs(&format!("║  登录账号: {}                                               ║", user)));
+        println!("{}", glyphs("║                                                              ║").cyan());
+        println!("{}", glyphs("║  ⚠️  安全提示:                                               ║").cyan());
+        println!("{}", glyphs("║  • 此服务绑定所有网络接口，可从公网访问                       ║").cyan());
+        println!("{}", glyphs("║  • 已启用身份验证，请使用设置的账号密码登录                   ║").cyan());
+        println!("{}", glyphs("║  • 配置完成后请及时关闭 (Ctrl+C)                             ║").cyan());
+        println!("{}", glyphs("║  • 建议在防火墙后使用或使用临时会话                          ║").cyan());
+        println!("{}", glyphs("╚══════════════════════════════════════════════════════════════╝").cyan());
         println!();
 
         let listener = tokio::net::TcpListener::bind(addr).await?;
@@ -1292,7 +1516,7 @@ fn interactive_web(_ctx: &OutputContext) -> Result<()> {
 fn interactive_batch(ctx: &OutputContext) -> Result<()> {
     loop {
         clear_screen();
-        println!("{}", "═══ 批量操作 ═══".cyan().bold());
+        println!("{}", glyphs("═══ 批量操作 ═══").cyan().bold());
         println!();
         println!("  {} {} - 批量切换所有应用", "1.".green(), "批量切换".white());
         println!("  {} {} - 批量测试所有供应商", "2.".green(), "批量测试".white());
@@ -1307,21 +1531,25 @@ fn interactive_batch(ctx: &OutputContext) -> Result<()> {
             "1" | "switch" => {
                 clear_screen();
                 let name = read_required("供应商名称")?;
-                commands::batch::batch_switch(ctx, &name)?;
+                commands::batch::batch_switch(ctx, &name, false)?;
                 pause();
             }
             "2" | "test" => {
                 clear_screen();
                 println!("{}", "正在测试所有供应商...".yellow());
-                tokio::runtime::Runtime::new()
+                // 存在失败时汇总已列出失败项，这里只提示错误并留在菜单中
+                if let Err(e) = tokio::runtime::Runtime::new()
                     .unwrap()
-                    .block_on(commands::batch::batch_test(ctx, AppTypeArg::All, 30, true))?;
+                    .block_on(commands::batch::batch_test(ctx, AppTypeArg::All, 30, true, commands::provider::DEFAULT_TEST_RETRIES))
+                {
+                    println!("{}", format!("错误: {}", e).red());
+                }
                 pause();
             }
             "3" | "export" => {
                 clear_screen();
                 let output = read_required("输出文件路径")?;
-                commands::batch::batch_export(ctx, &output, AppTypeArg::All)?;
+                commands::batch::batch_export(ctx, &output, AppTypeArg::All, false)?;
                 pause();
             }
             "4" | "import" => {
@@ -1329,7 +1557,7 @@ fn interactive_batch(ctx: &OutputContext) -> Result<()> {
                 let input = read_required("输入文件路径")?;
                 let overwrite_str = read_optional("覆盖已存在的配置? (y/N)", None)?;
                 let overwrite = overwrite_str.map(|s| s.to_lowercase() == "y").unwrap_or(false);
-                commands::batch::batch_import(ctx, &input, overwrite)?;
+                commands::batch::batch_import(ctx, &input, overwrite, true)?;
                 pause();
             }
             "5" | "sync" => {
@@ -1360,7 +1588,7 @@ fn interactive_batch(ctx: &OutputContext) -> Result<()> {
                 };
 
                 commands::batch::batch_sync(ctx, from_app.to_app_types().into_iter().next().unwrap(),
-                    from_app.to_app_types(), false)?;
+                    from_app.to_app_types(), false, None, false, false)?;
                 pause();
             }
             "0" | "q" | "back" => return Ok(()),
@@ -1377,7 +1605,7 @@ fn interactive_batch(ctx: &OutputContext) -> Result<()> {
 #[allow(dead_code)]
 pub fn quick_start() -> Result<()> {
     println!();
-    println!("{}", "🚀 欢迎使用 CC-Switch!".cyan().bold());
+    println!("{}", glyphs("🚀 欢迎使用 CC-Switch!").cyan().bold());
     println!();
     println!("CC-Switch 帮助你管理多个 AI CLI 工具的供应商配置。");
     println!("支持: {} | {} | {} | {}",
@@ -1403,3 +1631,75 @@ pub fn quick_start() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufRead;
+
+    fn reader_from(data: &'static str, timeout: Option<Duration>) -> LineReader {
+        let mut cursor = io::Cursor::new(data);
+        LineReader::spawn(move |buf| cursor.read_line(buf), timeout)
+    }
+
+    #[test]
+    fn test_edit_defaults_from_claude_provider() {
+        let provider = Provider::new(
+            "p1",
+            "云雾",
+            serde_json::json!({"env": {
+                "ANTHROPIC_AUTH_TOKEN": "sk-1234567890abcdef",
+                "ANTHROPIC_BASE_URL": "https://relay.example.com",
+                "ANTHROPIC_MODEL": "claude-sonnet-4",
+                "ANTHROPIC_SMALL_FAST_MODEL": "claude-haiku-4",
+            }}),
+        );
+
+        let defaults = edit_defaults(&provider, &AppType::Claude);
+        assert_eq!(
+            defaults,
+            EditDefaults {
+                name: "云雾".to_string(),
+                api_key: Some(mask_api_key("sk-1234567890abcdef")),
+                base_url: Some("https://relay.example.com".to_string()),
+                model: Some("claude-sonnet-4".to_string()),
+                small_model: Some("claude-haiku-4".to_string()),
+            }
+        );
+        assert_ne!(defaults.api_key.as_deref(), Some("sk-1234567890abcdef"));
+
+        // 回车沿用默认值视为未修改，输入新值则替换
+        assert_eq!(changed_value(defaults.api_key.clone(), defaults.api_key.as_deref()), None);
+        assert_eq!(
+            changed_value(Some("sk-new".to_string()), defaults.api_key.as_deref()).as_deref(),
+            Some("sk-new")
+        );
+    }
+
+    #[test]
+    fn test_menu_exits_on_closed_stdin() {
+        *INPUT.lock().unwrap() = Some(reader_from("", None));
+        let ctx = OutputContext::new(crate::cli::OutputFormat::Table, true, false, crate::output::Theme::None);
+        assert!(run_menu(ctx).is_ok());
+        // 输入关闭后不再阻塞，后续读取立即结束
+        assert!(read_required("名称").is_err());
+    }
+
+    #[test]
+    fn test_line_reader_eof_and_idle_timeout() {
+        let mut reader = reader_from("1\n", None);
+        assert_eq!(reader.read_line().unwrap(), "1\n");
+        assert_eq!(reader.read_line(), Err(InputEnded::Eof));
+        assert_eq!(reader.read_line(), Err(InputEnded::Eof));
+
+        let timeout = Duration::from_millis(50);
+        let mut idle = LineReader::spawn(
+            |_| {
+                thread::sleep(Duration::from_secs(5));
+                Ok(0)
+            },
+            Some(timeout),
+        );
+        assert_eq!(idle.read_line(), Err(InputEnded::Idle(timeout)));
+    }
+}
@@ -26,23 +26,33 @@ mod output;
 mod tui;
 mod web;
 
+use std::io::Write;
+
 use anyhow::Result;
+use ccswitch_core::audit::AUDIT_LOG_TARGET;
 use clap::Parser;
 
 use cli::Cli;
 use commands::execute;
 
 fn main() -> Result<()> {
-    // 初始化日志
-    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn"))
-        .format_timestamp(None)
-        .init();
-
     // 解析命令行参数
     let cli = Cli::parse();
 
+    // 初始化日志
+    init_logger(cli.log_json);
+    output::init_plain(cli.plain);
+    ccswitch_core::env_interp::set_resolve_env(cli.resolve_env);
+
+    // --config-dir 需在打开数据库前生效
+    if let Some(dir) = &cli.config_dir {
+        ccswitch_core::config::set_app_config_dir_override(dir);
+    }
+
     // 如果没有子命令，检查是否启用 TUI 模式
     if cli.command.is_none() {
+        commands::activate_profile(cli.profile.as_deref())?;
+
         // 启动时检查版本更新（静默模式，异步执行）
         let rt = tokio::runtime::Runtime::new()?;
         rt.block_on(commands::check_on_startup());
@@ -54,6 +64,45 @@ fn main() -> Result<()> {
         }
     }
 
-    // 执行命令
-    execute(cli)
+    // 执行命令，按错误类型设置退出码
+    if let Err(err) = execute(cli) {
+        eprintln!("Error: {:?}", err);
+        std::process::exit(commands::exit_code(&err));
+    }
+
+    Ok(())
+}
+
+/// 初始化日志
+///
+/// `--log-json` 时每条日志输出为一行 JSON，审计事件（live 配置写入、数据库变更）的字段直接展开。
+fn init_logger(json: bool) {
+    let default_filter = if json {
+        format!("warn,{}=info", AUDIT_LOG_TARGET)
+    } else {
+        "warn".to_string()
+    };
+    let mut builder = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(default_filter));
+
+    if json {
+        builder.format(|buf, record| {
+            let message = record.args().to_string();
+            let mut entry = match serde_json::from_str::<serde_json::Value>(&message) {
+                Ok(serde_json::Value::Object(fields)) if record.target() == AUDIT_LOG_TARGET => fields,
+                _ => {
+                    let mut fields = serde_json::Map::new();
+                    fields.insert("message".to_string(), message.into());
+                    fields
+                }
+            };
+            entry.insert("ts".to_string(), chrono::Local::now().to_rfc3339().into());
+            entry.insert("level".to_string(), record.level().as_str().into());
+            entry.insert("target".to_string(), record.target().into());
+            writeln!(buf, "{}", serde_json::Value::Object(entry))
+        });
+    } else {
+        builder.format_timestamp(None);
+    }
+
+    builder.init();
 }
@@ -2,26 +2,248 @@
 //!
 //! 处理表格、JSON、YAML 等输出格式。
 
-use colored::Colorize;
+use std::io::{IsTerminal, Write};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+use ccswitch_core::{AppError, AppType};
+use colored::{Color, Colorize};
+use indexmap::IndexMap;
 use serde::Serialize;
 use tabled::{settings::Style, Table, Tabled};
 
 use crate::cli::OutputFormat;
 
+/// 主题设置在数据库中的键名
+pub const THEME_SETTING: &str = "theme";
+
+/// 输出配色主题
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Theme {
+    /// 深色终端（默认）
+    Dark,
+    /// 浅色终端
+    Light,
+    /// 不使用颜色，等同于 --no-color
+    None,
+}
+
+impl Theme {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Dark => "dark",
+            Self::Light => "light",
+            Self::None => "none",
+        }
+    }
+
+    /// 成功、错误、警告、信息消息的颜色
+    fn palette(&self) -> [Color; 4] {
+        match self {
+            Self::Dark | Self::None => [Color::Green, Color::Red, Color::Yellow, Color::Blue],
+            Self::Light => [
+                Color::Green,
+                Color::Red,
+                Color::TrueColor { r: 175, g: 95, b: 0 },
+                Color::Blue,
+            ],
+        }
+    }
+}
+
+impl FromStr for Theme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "dark" => Ok(Self::Dark),
+            "light" => Ok(Self::Light),
+            "none" => Ok(Self::None),
+            other => Err(format!("未知的主题: {}（可选: dark, light, none）", other)),
+        }
+    }
+}
+
+/// 当前主题（print_* 系列函数使用），由 `OutputContext::new` 设置
+static THEME: AtomicU8 = AtomicU8::new(Theme::Dark as u8);
+
+fn set_theme(theme: Theme) {
+    // 无颜色主题同时关闭其他位置的 colored 输出
+    if theme == Theme::None {
+        colored::control::set_override(false);
+    }
+    THEME.store(theme as u8, Ordering::Relaxed);
+}
+
+fn current_theme() -> Theme {
+    match THEME.load(Ordering::Relaxed) {
+        1 => Theme::Light,
+        2 => Theme::None,
+        _ => Theme::Dark,
+    }
+}
+
+/// 静默模式（--quiet）：省略信息消息、横幅与分节标题
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// 纯文本符号模式（--plain / CC_SWITCH_ASCII=1）：装饰性符号替换为 ASCII
+static PLAIN: AtomicBool = AtomicBool::new(false);
+
+/// 启用纯文本符号模式的环境变量
+pub const PLAIN_ENV: &str = "CC_SWITCH_ASCII";
+
+/// 设置纯文本符号模式；`flag` 为 false 时读取 `CC_SWITCH_ASCII`
+pub fn init_plain(flag: bool) {
+    let from_env = std::env::var(PLAIN_ENV)
+        .map(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes" | "on"))
+        .unwrap_or(false);
+    PLAIN.store(flag || from_env, Ordering::Relaxed);
+}
+
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+/// 装饰性符号与对应的 ASCII 替代（带变体选择符的写法需排在前面）
+///
+/// 替代为空的图标在纯文本模式下连同其后的一个空格一起省略。
+const SYMBOLS: &[(&str, &str)] = &[
+    ("⚠️", "!"),
+    ("⚠", "!"),
+    ("✓", "OK"),
+    ("✗", "X"),
+    ("❌", "X"),
+    ("ℹ", "i"),
+    ("★", "*"),
+    ("●", "*"),
+    ("○", "o"),
+    ("•", "-"),
+    ("→", "->"),
+    ("≠", "*"),
+    ("➜", "->"),
+    ("▶", ">"),
+    ("↑", "^"),
+    ("↓", "v"),
+    ("⚡", "*"),
+    ("∞", "8"),
+    ("═", "="),
+    ("─", "-"),
+    ("│", "|"),
+    ("║", "|"),
+    ("┌", "+"),
+    ("┐", "+"),
+    ("└", "+"),
+    ("┘", "+"),
+    ("╔", "+"),
+    ("╗", "+"),
+    ("╚", "+"),
+    ("╝", "+"),
+    ("╠", "+"),
+    ("╣", "+"),
+    ("╭", "+"),
+    ("╮", "+"),
+    ("╰", "+"),
+    ("╯", "+"),
+    ("↩️", ""),
+    ("📄", ""),
+    ("📋", ""),
+    ("📝", ""),
+    ("📦", ""),
+    ("🔄", ""),
+    ("🔍", ""),
+    ("🔀", ""),
+    ("💡", ""),
+    ("🔧", ""),
+    ("🤖", ""),
+    ("🧪", ""),
+    ("🧹", ""),
+    ("💾", ""),
+    ("🌐", ""),
+    ("🚀", ""),
+    ("✨", ""),
+    ("🎉", ""),
+    ("\u{fe0f}", ""),
+];
+
+/// 加载动画帧
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+const SPINNER_ASCII: [&str; 10] = ["|", "/", "-", "\\", "|", "/", "-", "\\", "|", "/"];
+
+fn lookup_symbol(glyph: &'static str, plain: bool) -> &'static str {
+    if !plain {
+        return glyph;
+    }
+    SYMBOLS
+        .iter()
+        .find(|(unicode, _)| *unicode == glyph)
+        .map(|(_, ascii)| *ascii)
+        .unwrap_or(glyph)
+}
+
+fn replace_symbols(text: &str, plain: bool) -> String {
+    if !plain {
+        return text.to_string();
+    }
+    SYMBOLS.iter().fold(text.to_string(), |acc, (unicode, ascii)| {
+        if ascii.is_empty() {
+            acc.replace(&format!("{} ", unicode), "").replace(unicode, "")
+        } else {
+            acc.replace(unicode, ascii)
+        }
+    })
+}
+
+/// 按当前模式返回符号（纯文本模式下为 ASCII 替代）
+pub fn sym(glyph: &'static str) -> &'static str {
+    lookup_symbol(glyph, is_plain())
+}
+
+/// 替换文本中的全部装饰性符号（用于边框、分隔线等整行文本）
+pub fn glyphs(text: &str) -> String {
+    replace_symbols(text, is_plain())
+}
+
+/// 加载动画帧
+pub fn spinner_frames() -> &'static [&'static str] {
+    if is_plain() {
+        &SPINNER_ASCII
+    } else {
+        &SPINNER
+    }
+}
+
+/// 为表格应用边框样式（纯文本模式下使用 ASCII 边框）
+pub fn styled_table(table: &mut Table) -> String {
+    if is_plain() {
+        table.with(Style::ascii()).to_string()
+    } else {
+        table.with(Style::rounded()).to_string()
+    }
+}
+
 /// 输出上下文
 pub struct OutputContext {
     pub format: OutputFormat,
     #[allow(dead_code)]
     pub no_color: bool,
+    #[allow(dead_code)]
+    pub quiet: bool,
 }
 
 impl OutputContext {
-    pub fn new(format: OutputFormat, no_color: bool) -> Self {
-        // 如果禁用颜色，设置环境变量
-        if no_color {
-            colored::control::set_override(false);
-        }
-        Self { format, no_color }
+    /// `theme` 为 `config set theme` 的设置，`no_color` 时忽略
+    pub fn new(format: OutputFormat, no_color: bool, quiet: bool, theme: Theme) -> Self {
+        set_theme(if no_color { Theme::None } else { theme });
+        set_quiet(quiet);
+        Self { format, no_color, quiet }
     }
 }
 
@@ -42,6 +264,33 @@ pub struct ProviderRow {
     pub api_key: String,
 }
 
+/// 供应商详细列表行（`list --detail`）
+#[derive(Tabled, Serialize)]
+pub struct ProviderDetailRow {
+    #[tabled(rename = "ID")]
+    pub id: String,
+    #[tabled(rename = "名称")]
+    pub name: String,
+    #[tabled(rename = "应用")]
+    pub app: String,
+    #[tabled(rename = "状态")]
+    pub status: String,
+    #[tabled(rename = "分类")]
+    pub category: String,
+    #[tabled(rename = "Base URL")]
+    pub base_url: String,
+    #[tabled(rename = "模型")]
+    pub model: String,
+    #[tabled(rename = "API Key")]
+    pub api_key: String,
+    #[tabled(rename = "最近使用")]
+    pub last_used: String,
+    #[tabled(rename = "最近测试")]
+    pub last_test: String,
+    #[tabled(rename = "备注")]
+    pub notes: String,
+}
+
 /// 状态行
 #[derive(Tabled, Serialize)]
 pub struct StatusRow {
@@ -66,37 +315,66 @@ pub struct PathRow {
     pub settings_file: String,
 }
 
-/// 打印表格
-#[allow(dead_code)]
-pub fn print_table<T: Tabled>(ctx: &OutputContext, data: Vec<T>) {
-    match ctx.format {
-        OutputFormat::Table => {
-            if data.is_empty() {
-                println!("{}", "没有数据".dimmed());
-                return;
-            }
-            let table = Table::new(data).with(Style::rounded()).to_string();
-            println!("{}", table);
-        }
-        OutputFormat::Json => {
-            // 对于 JSON 输出，需要使用 Serialize
-            println!("JSON 输出暂不支持");
-        }
-        OutputFormat::Yaml => {
-            println!("YAML 输出暂不支持");
-        }
+/// 导入结果类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportOutcome {
+    Added,
+    Overwritten,
+    Skipped,
+    Failed,
+}
+
+/// 单个应用的导入结果
+#[derive(Debug, Default, Serialize)]
+pub struct AppImportSummary {
+    pub added: Vec<String>,
+    pub overwritten: Vec<String>,
+    pub skipped: Vec<String>,
+    pub failed: Vec<String>,
+}
+
+/// 导入结果汇总（按应用分组）
+#[derive(Debug, Default, Serialize)]
+pub struct ImportSummary {
+    pub apps: IndexMap<String, AppImportSummary>,
+}
+
+impl ImportSummary {
+    /// 记录一条导入结果
+    pub fn record(&mut self, app_type: AppType, outcome: ImportOutcome, name: impl Into<String>) {
+        let entry = self.apps.entry(app_type.as_str().to_string()).or_default();
+        let list = match outcome {
+            ImportOutcome::Added => &mut entry.added,
+            ImportOutcome::Overwritten => &mut entry.overwritten,
+            ImportOutcome::Skipped => &mut entry.skipped,
+            ImportOutcome::Failed => &mut entry.failed,
+        };
+        list.push(name.into());
+    }
+
+    /// 统计某类结果的总数
+    pub fn count(&self, outcome: ImportOutcome) -> usize {
+        self.apps
+            .values()
+            .map(|s| match outcome {
+                ImportOutcome::Added => s.added.len(),
+                ImportOutcome::Overwritten => s.overwritten.len(),
+                ImportOutcome::Skipped => s.skipped.len(),
+                ImportOutcome::Failed => s.failed.len(),
+            })
+            .sum()
     }
 }
 
 /// 打印供应商列表（支持所有格式）
-pub fn print_providers(ctx: &OutputContext, rows: Vec<ProviderRow>) {
+pub fn print_providers<T: Tabled + Serialize>(ctx: &OutputContext, rows: Vec<T>) {
     match ctx.format {
         OutputFormat::Table => {
             if rows.is_empty() {
                 println!("{}", "没有配置供应商".dimmed());
                 return;
             }
-            let table = Table::new(&rows).with(Style::rounded()).to_string();
+            let table = styled_table(&mut Table::new(&rows));
             println!("{}", table);
         }
         OutputFormat::Json => {
@@ -118,7 +396,7 @@ pub fn print_status(ctx: &OutputContext, rows: Vec<StatusRow>) {
                 println!("{}", "没有配置应用".dimmed());
                 return;
             }
-            let table = Table::new(&rows).with(Style::rounded()).to_string();
+            let table = styled_table(&mut Table::new(&rows));
             println!("{}", table);
         }
         OutputFormat::Json => {
@@ -136,7 +414,7 @@ pub fn print_status(ctx: &OutputContext, rows: Vec<StatusRow>) {
 pub fn print_paths(ctx: &OutputContext, rows: Vec<PathRow>) {
     match ctx.format {
         OutputFormat::Table => {
-            let table = Table::new(&rows).with(Style::rounded()).to_string();
+            let table = styled_table(&mut Table::new(&rows));
             println!("{}", table);
         }
         OutputFormat::Json => {
@@ -150,35 +428,158 @@ pub fn print_paths(ctx: &OutputContext, rows: Vec<PathRow>) {
     }
 }
 
+/// 打印导入汇总
+pub fn print_import_summary(ctx: &OutputContext, summary: &ImportSummary) {
+    match ctx.format {
+        OutputFormat::Table => {
+            print_header("导入汇总");
+            for (app, s) in &summary.apps {
+                let display = app
+                    .parse::<AppType>()
+                    .map(|a| a.display_name().to_string())
+                    .unwrap_or_else(|_| app.clone());
+                println!();
+                println!(
+                    "{}: 新增 {}，覆盖 {}，跳过 {}，失败 {}",
+                    display.cyan(),
+                    s.added.len().to_string().green(),
+                    s.overwritten.len().to_string().yellow(),
+                    s.skipped.len().to_string().dimmed(),
+                    s.failed.len().to_string().red()
+                );
+                let groups = [
+                    ("新增", &s.added),
+                    ("覆盖", &s.overwritten),
+                    ("跳过", &s.skipped),
+                    ("失败", &s.failed),
+                ];
+                for (label, names) in groups {
+                    if !names.is_empty() {
+                        println!("  {} {}: {}", sym("→").dimmed(), label, names.join(", "));
+                    }
+                }
+            }
+            println!();
+            println!(
+                "合计: 新增 {}，覆盖 {}，跳过 {}，失败 {}",
+                summary.count(ImportOutcome::Added),
+                summary.count(ImportOutcome::Overwritten),
+                summary.count(ImportOutcome::Skipped),
+                summary.count(ImportOutcome::Failed)
+            );
+            println!();
+        }
+        OutputFormat::Json => {
+            let json = serde_json::to_string_pretty(summary).unwrap_or_default();
+            println!("{}", json);
+        }
+        OutputFormat::Yaml => {
+            let yaml = serde_yaml::to_string(summary).unwrap_or_default();
+            println!("{}", yaml);
+        }
+    }
+}
+
+/// 按主题为消息着色：图标加粗，`color_text` 为 false 时正文保持默认颜色
+fn themed_message(theme: Theme, icon: &str, message: &str, slot: usize, color_text: bool) -> String {
+    if theme == Theme::None {
+        return format!("{} {}", icon, message);
+    }
+    let color = theme.palette()[slot];
+    let text = if color_text {
+        message.color(color).to_string()
+    } else {
+        message.to_string()
+    };
+    format!("{} {}", icon.color(color).bold(), text)
+}
+
 /// 打印成功消息
 pub fn print_success(message: &str) {
-    println!("{} {}", "✓".green().bold(), message.green());
+    println!("{}", themed_message(current_theme(), sym("✓"), message, 0, true));
 }
 
 /// 打印错误消息
 pub fn print_error(message: &str) {
-    eprintln!("{} {}", "✗".red().bold(), message.red());
+    eprintln!("{}", themed_message(current_theme(), sym("✗"), message, 1, true));
 }
 
 /// 打印警告消息
 pub fn print_warning(message: &str) {
-    println!("{} {}", "⚠".yellow().bold(), message.yellow());
+    println!("{}", themed_message(current_theme(), sym("⚠"), message, 2, true));
+}
+
+/// 打印警告消息到 stderr（标准输出需保持可被 shell 或程序解析时使用）
+pub fn eprint_warning(message: &str) {
+    eprintln!("{}", themed_message(current_theme(), sym("⚠"), message, 2, true));
 }
 
-/// 打印信息消息
+/// 打印信息消息（静默模式下省略）
 pub fn print_info(message: &str) {
-    println!("{} {}", "ℹ".blue().bold(), message);
+    if is_quiet() {
+        return;
+    }
+    println!("{}", themed_message(current_theme(), sym("ℹ"), message, 3, false));
+}
+
+/// 确认破坏性操作；`force` 时直接通过
+///
+/// 静默模式或标准输入不是终端时不提示，直接以需要 `--force` 的错误中止，避免脚本阻塞在 stdin。
+pub fn confirm_destructive(prompt: &str, force: bool) -> anyhow::Result<bool> {
+    if force {
+        return Ok(true);
+    }
+    confirm_interactive(prompt, "已中止：非交互模式下需添加 --force（-y）确认")
+}
+
+/// 在终端中询问 `[y/N]`；静默模式或标准输入不是终端时以 `abort_message` 报错，不阻塞
+pub fn confirm_interactive(prompt: &str, abort_message: &str) -> anyhow::Result<bool> {
+    if is_quiet() || !std::io::stdin().is_terminal() {
+        return Err(AppError::InvalidInput(abort_message.to_string()).into());
+    }
+
+    print!("{} [y/N]: ", prompt);
+    std::io::stdout().flush()?;
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    Ok(input.trim().eq_ignore_ascii_case("y"))
+}
+
+/// 打印分节标题（静默模式下省略）
+pub fn print_header(title: &str) {
+    if is_quiet() {
+        return;
+    }
+    println!("{}", glyphs(&format!("═══ {} ═══", title)).cyan().bold());
+}
+
+/// 打印命令横幅，前后各空一行（静默模式下省略）
+pub fn print_banner(title: &str) {
+    if is_quiet() {
+        return;
+    }
+    println!();
+    print_header(title);
+    println!();
 }
 
 /// 格式化状态标签
 pub fn format_status(is_current: bool) -> String {
     if is_current {
-        "● 当前".green().bold().to_string()
+        format!("{} 当前", sym("●")).green().bold().to_string()
     } else {
-        "○".dimmed().to_string()
+        sym("○").dimmed().to_string()
     }
 }
 
+/// 累加模式应用（OpenCode、OpenClaw）在“当前供应商”位置显示的说明
+pub const ADDITIVE_MODE_LABEL: &str = "累加模式（全部启用）";
+
+/// 格式化累加模式应用的供应商状态：所有供应商同时生效
+pub fn format_additive_status() -> String {
+    format!("{} 启用", sym("●")).green().bold().to_string()
+}
+
 /// 截断字符串
 pub fn truncate(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
@@ -210,3 +611,36 @@ pub fn mask_api_key(key: &str) -> String {
         &key[len - suffix_len..]
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_none_yields_plain_strings() {
+        colored::control::set_override(true);
+        assert_eq!(themed_message(Theme::None, "✓", "完成", 0, true), "✓ 完成");
+        assert!(themed_message(Theme::Dark, "✓", "完成", 0, true).contains('\x1b'));
+        assert_ne!(
+            themed_message(Theme::Dark, "⚠", "注意", 2, true),
+            themed_message(Theme::Light, "⚠", "注意", 2, true)
+        );
+        colored::control::unset_override();
+
+        assert_eq!("Light".parse::<Theme>(), Ok(Theme::Light));
+        assert!("blue".parse::<Theme>().is_err());
+    }
+
+    #[test]
+    fn test_plain_mode_uses_ascii_symbols() {
+        assert_eq!(lookup_symbol("✓", true), "OK");
+        assert_eq!(lookup_symbol("⚠", true), "!");
+        assert_eq!(lookup_symbol("★", true), "*");
+        assert_eq!(lookup_symbol("✓", false), "✓");
+        assert_eq!(replace_symbols("═══ 标题 ═══", true), "=== 标题 ===");
+        assert_eq!(replace_symbols("┌──┐ ⚠️ 注意", true), "+--+ ! 注意");
+        assert_eq!(replace_symbols(" 📄 详细信息 ", true), " 详细信息 ");
+        assert!(SYMBOLS.iter().all(|(_, ascii)| ascii.is_ascii()));
+        assert!(SPINNER_ASCII.iter().all(|frame| frame.is_ascii()));
+    }
+}
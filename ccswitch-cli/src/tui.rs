@@ -4,21 +4,51 @@
 
 use anyhow::{Context, Result};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, MouseButton,
+        MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{
     backend::CrosstermBackend,
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
+    symbols::border,
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
     Frame, Terminal,
 };
+use std::cell::Cell;
 use std::io;
+use std::time::Instant;
+
+use ccswitch_core::{settings, AppState, AppType, ProviderService};
+
+use crate::commands::provider::{get_default_model, test_api_key, TestOutcome, DEFAULT_TEST_RETRIES};
+use crate::output::{glyphs, is_plain, spinner_frames, sym, ADDITIVE_MODE_LABEL};
+
+/// 纯文本模式下的 ASCII 边框
+const ASCII_BORDER: border::Set = border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
 
-use ccswitch_core::{AppState, AppType, ProviderService};
+/// 带边框样式的区块（纯文本模式下使用 ASCII 边框）
+fn bordered_block<'a>() -> Block<'a> {
+    if is_plain() {
+        Block::default().border_set(ASCII_BORDER)
+    } else {
+        Block::default()
+    }
+}
 
 /// 视图类型
 #[derive(Clone, Debug, PartialEq)]
@@ -35,6 +65,8 @@ enum ViewType {
     AddDefaultProvider,
     /// 环境冲突管理
     EnvConflictManage,
+    /// 测试供应商
+    TestProvider,
     /// 消息显示
     MessageBox {
         title: String,
@@ -82,6 +114,44 @@ struct App {
     env_actions: Vec<EnvAction>,
     /// 应用状态
     app_state: Option<AppState>,
+    /// 共享的异步运行时（用于 API 测试）
+    runtime: Option<tokio::runtime::Runtime>,
+    /// 可测试的供应商及其结果
+    test_rows: Vec<TestRow>,
+    /// 进行中的测试
+    pending_test: Option<PendingTest>,
+    /// 加载动画帧计数
+    spinner_tick: usize,
+    /// 最近一次绘制的列表位置（用于鼠标点击定位）
+    list_layout: Cell<Option<ListLayout>>,
+    /// 打开当前视图的菜单操作（用于刷新）
+    last_action: Option<MenuAction>,
+}
+
+/// 已绘制列表的位置信息
+#[derive(Clone, Copy, Debug)]
+struct ListLayout {
+    /// 列表内容区域（不含边框）
+    area: Rect,
+    /// 首个可见项的索引
+    offset: usize,
+}
+
+/// 测试视图中的一行
+#[derive(Clone, Debug)]
+struct TestRow {
+    app_type: AppType,
+    id: String,
+    name: String,
+    /// 测试结果：(是否通过, 说明)
+    result: Option<(bool, String)>,
+}
+
+/// 进行中的测试
+struct PendingTest {
+    index: usize,
+    started: Instant,
+    handle: tokio::task::JoinHandle<Result<TestOutcome>>,
 }
 
 /// 环境冲突操作
@@ -315,6 +385,12 @@ impl App {
             default_providers,
             env_actions,
             app_state,
+            runtime: tokio::runtime::Runtime::new().ok(),
+            test_rows: Vec::new(),
+            pending_test: None,
+            spinner_tick: 0,
+            list_layout: Cell::new(None),
+            last_action: None,
         })
     }
 
@@ -327,6 +403,8 @@ impl App {
             self.default_providers.len()
         } else if matches!(self.current_view, ViewType::EnvConflictManage) {
             self.env_actions.len()
+        } else if matches!(self.current_view, ViewType::TestProvider) {
+            self.test_rows.len()
         } else {
             return;
         };
@@ -358,6 +436,8 @@ impl App {
             self.default_providers.len()
         } else if matches!(self.current_view, ViewType::EnvConflictManage) {
             self.env_actions.len()
+        } else if matches!(self.current_view, ViewType::TestProvider) {
+            self.test_rows.len()
         } else {
             return;
         };
@@ -396,12 +476,21 @@ impl App {
             ViewType::EnvConflictManage => {
                 self.handle_env_action()?;
             }
+            ViewType::TestProvider => {
+                self.start_test()?;
+            }
             _ => {}
         }
         Ok(())
     }
 
     fn handle_menu_action(&mut self, action: MenuAction) -> Result<()> {
+        // 进入视图前重新读取设置，获取其他进程的修改
+        if let Err(e) = self.reload_state() {
+            self.show_message("加载失败".to_string(), format!("无法读取配置: {}", e), true);
+            return Ok(());
+        }
+        self.last_action = Some(action.clone());
         match action {
             MenuAction::Exit => {
                 self.should_quit = true;
@@ -428,11 +517,7 @@ impl App {
                 self.load_config_view()?;
             }
             MenuAction::TestProvider => {
-                self.show_message(
-                    "测试供应商".to_string(),
-                    "此功能需要异步支持，请使用 CLI: cc-switch test".to_string(),
-                    false,
-                );
+                self.load_test_provider_view()?;
             }
             _ => {
                 self.show_message(
@@ -445,6 +530,46 @@ impl App {
         Ok(())
     }
 
+    /// 重新读取设置；启动时数据库打开失败的话再尝试一次
+    ///
+    /// SQLite 连接本身即可读到其他进程已提交的修改，无需重新打开。
+    fn reload_state(&mut self) -> Result<()> {
+        settings::reload_settings()?;
+        if self.app_state.is_none() {
+            self.app_state = Some(AppState::init()?);
+        }
+        Ok(())
+    }
+
+    /// 按 r 刷新：重新读取设置与数据库并重建当前视图，尽量保持选中项
+    fn refresh(&mut self) -> Result<()> {
+        let refreshable = matches!(
+            self.current_view,
+            ViewType::ListProviders
+                | ViewType::ViewStatus
+                | ViewType::SwitchProvider
+                | ViewType::TestProvider
+        );
+        if !refreshable || self.pending_test.is_some() {
+            return Ok(());
+        }
+        let Some(action) = self.last_action.clone() else {
+            return Ok(());
+        };
+
+        let selected = self.list_state.selected();
+        self.handle_menu_action(action)?;
+        let len = match self.current_view {
+            ViewType::SwitchProvider => self.providers.len(),
+            ViewType::TestProvider => self.test_rows.len(),
+            _ => return Ok(()),
+        };
+        if let Some(i) = selected.filter(|_| len > 0) {
+            self.list_state.select(Some(i.min(len - 1)));
+        }
+        Ok(())
+    }
+
     fn load_providers_list(&mut self) -> Result<()> {
         let state = self
             .app_state
@@ -457,7 +582,7 @@ impl App {
         self.content_buffer.push("═".repeat(60));
         self.content_buffer.push("".to_string());
 
-        for app_type in &[AppType::Claude, AppType::Codex, AppType::Gemini] {
+        for app_type in AppType::all() {
             self.content_buffer.push(format!("【{}】", app_type.display_name()));
             self.content_buffer.push("─".repeat(60));
 
@@ -470,7 +595,9 @@ impl App {
                         let current = ProviderService::current(state, *app_type).ok();
 
                         for (id, provider) in providers {
-                            let is_current = current.as_ref().map(|c| c == &id).unwrap_or(false);
+                            // 累加模式应用的供应商全部生效
+                            let is_current = app_type.is_additive_mode()
+                                || current.as_ref().map(|c| c == &id).unwrap_or(false);
                             let marker = if is_current { "★" } else { " " };
                             self.content_buffer.push(format!(
                                 "  {} {} ({})",
@@ -491,7 +618,7 @@ impl App {
         }
 
         self.content_buffer.push("".to_string());
-        self.content_buffer.push("提示: 按 Esc 或 q 返回主菜单".to_string());
+        self.content_buffer.push("提示: 按 r 刷新，Esc 或 q 返回主菜单".to_string());
 
         self.current_view = ViewType::ListProviders;
         Ok(())
@@ -509,15 +636,22 @@ impl App {
         self.content_buffer.push("═".repeat(60));
         self.content_buffer.push("".to_string());
 
-        for app_type in &[AppType::Claude, AppType::Codex, AppType::Gemini] {
+        for app_type in AppType::all() {
             let current = ProviderService::current(state, *app_type)
                 .unwrap_or_else(|_| "未设置".to_string());
 
             let display_name = app_type.display_name();
+            let shown = if app_type.is_additive_mode() {
+                ADDITIVE_MODE_LABEL
+            } else if current.is_empty() {
+                "未设置"
+            } else {
+                current.as_str()
+            };
             self.content_buffer.push(format!(
                 "{:<15} ➜  {}",
                 format!("{}:", display_name),
-                current
+                shown
             ));
 
             // 获取详细信息
@@ -535,7 +669,7 @@ impl App {
         }
 
         self.content_buffer.push("".to_string());
-        self.content_buffer.push("提示: 按 Esc 或 q 返回主菜单".to_string());
+        self.content_buffer.push("提示: 按 r 刷新，Esc 或 q 返回主菜单".to_string());
 
         self.current_view = ViewType::ViewStatus;
         Ok(())
@@ -616,6 +750,107 @@ impl App {
         Ok(())
     }
 
+    fn load_test_provider_view(&mut self) -> Result<()> {
+        let state = self
+            .app_state
+            .as_ref()
+            .context("应用状态未初始化")?;
+
+        self.test_rows.clear();
+        for app_type in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+            for (id, provider) in ProviderService::list(state, app_type)? {
+                self.test_rows.push(TestRow {
+                    app_type,
+                    id,
+                    name: provider.name,
+                    result: None,
+                });
+            }
+        }
+
+        if self.test_rows.is_empty() {
+            self.show_message(
+                "无可用供应商".to_string(),
+                "当前没有配置任何供应商。\n请使用 CLI 添加供应商。".to_string(),
+                false,
+            );
+            return Ok(());
+        }
+
+        self.selected = 0;
+        self.list_state.select(Some(0));
+        self.current_view = ViewType::TestProvider;
+        Ok(())
+    }
+
+    /// 在共享运行时上启动选中供应商的 API 测试
+    fn start_test(&mut self) -> Result<()> {
+        if self.pending_test.is_some() {
+            return Ok(());
+        }
+        let Some(row) = self.test_rows.get(self.selected).cloned() else {
+            return Ok(());
+        };
+        let state = self
+            .app_state
+            .as_ref()
+            .context("应用状态未初始化")?;
+        let runtime = self.runtime.as_ref().context("异步运行时初始化失败")?;
+
+        let provider = ProviderService::list(state, row.app_type)?
+            .shift_remove(&row.id)
+            .context("供应商不存在")?;
+        let (key, url) = match ProviderService::resolve_credentials(&provider, &row.app_type) {
+            Ok(credentials) => credentials,
+            Err(e) => {
+                self.test_rows[self.selected].result = Some((false, e.to_string()));
+                return Ok(());
+            }
+        };
+        let model = provider
+            .get_model()
+            .unwrap_or_else(|| get_default_model(&row.app_type));
+
+        let app_type = row.app_type;
+        let handle = runtime.spawn(async move {
+            test_api_key(&app_type, &key, Some(&url), Some(&model), 30, DEFAULT_TEST_RETRIES).await
+        });
+        self.test_rows[self.selected].result = None;
+        self.pending_test = Some(PendingTest {
+            index: self.selected,
+            started: Instant::now(),
+            handle,
+        });
+        Ok(())
+    }
+
+    /// 检查进行中的测试，完成时记录结果（不阻塞界面刷新）
+    fn poll_test(&mut self) {
+        self.spinner_tick = self.spinner_tick.wrapping_add(1);
+
+        if !self
+            .pending_test
+            .as_ref()
+            .is_some_and(|pending| pending.handle.is_finished())
+        {
+            return;
+        }
+        let (Some(pending), Some(runtime)) = (self.pending_test.take(), self.runtime.as_ref()) else {
+            return;
+        };
+
+        let elapsed_ms = pending.started.elapsed().as_millis();
+        let result = match runtime.block_on(pending.handle) {
+            Ok(Ok(outcome)) if outcome.passed => (true, format!("通过 {}ms", elapsed_ms)),
+            Ok(Ok(_)) => (false, format!("未返回预期结果 {}ms", elapsed_ms)),
+            Ok(Err(e)) => (false, format!("失败: {}", e)),
+            Err(e) => (false, format!("失败: {}", e)),
+        };
+        if let Some(row) = self.test_rows.get_mut(pending.index) {
+            row.result = Some(result);
+        }
+    }
+
     fn load_add_default_provider_view(&mut self) -> Result<()> {
         self.content_buffer.clear();
         self.current_view = ViewType::AddDefaultProvider;
@@ -869,15 +1104,13 @@ impl App {
         }
 
         self.content_buffer.push("".to_string());
-        self.content_buffer.push("提示: 按 Esc 或 q 返回主菜单".to_string());
+        self.content_buffer.push("提示: 按 r 刷新，Esc 或 q 返回主菜单".to_string());
 
         self.current_view = ViewType::ViewStatus;
         Ok(())
     }
 
     fn load_tool_check(&mut self) -> Result<()> {
-        use std::process::Command;
-
         self.content_buffer.clear();
         self.content_buffer.push("═".repeat(60));
         self.content_buffer.push("AI CLI 工具检测".to_string());
@@ -886,7 +1119,7 @@ impl App {
 
         // 定义要检测的工具
         let tools = vec![
-            ("Claude Code", "claude-code", "https://github.com/anthropics/claude-code"),
+            ("Claude Code", "claude", "https://github.com/anthropics/claude-code"),
             ("Codex CLI", "codex", "https://developers.openai.com/codex/cli/"),
             ("Gemini CLI", "gemini", "https://github.com/google-gemini/gemini-cli"),
         ];
@@ -896,24 +1129,15 @@ impl App {
             self.content_buffer.push("─".repeat(60));
 
             // 检测工具是否安装
-            let installed = Command::new("which")
-                .arg(cmd)
-                .output()
-                .map(|output| output.status.success())
-                .unwrap_or(false);
-
-            if installed {
-                // 已安装，尝试获取版本
-                if let Ok(output) = Command::new(cmd).arg("--version").output() {
-                    if output.status.success() {
-                        let version = String::from_utf8_lossy(&output.stdout);
-                        let version_line = version.lines().next().unwrap_or("未知版本");
-                        self.content_buffer.push(format!("  ✓ 已安装: {}", version_line));
-                    } else {
-                        self.content_buffer.push("  ✓ 已安装（无法获取版本）".to_string());
-                    }
-                } else {
-                    self.content_buffer.push("  ✓ 已安装".to_string());
+            let status = ccswitch_core::EnvCheckerService::detect_tool(cmd);
+
+            if status.installed {
+                match status.version {
+                    Some(version) => self.content_buffer.push(format!("  ✓ 已安装: {}", version)),
+                    None => self.content_buffer.push("  ✓ 已安装（无法获取版本）".to_string()),
+                }
+                if let Some(path) = status.path {
+                    self.content_buffer.push(format!("    路径: {}", path.display()));
                 }
             } else {
                 self.content_buffer.push("  ✗ 未安装".to_string());
@@ -921,7 +1145,7 @@ impl App {
 
                 // 根据不同工具提供安装指南
                 match cmd {
-                    "claude-code" => {
+                    "claude" => {
                         self.content_buffer.push("  安装方法:".to_string());
                         self.content_buffer.push("    1. 访问 https://claude.com/claude-code".to_string());
                         self.content_buffer.push("    2. 下载 macOS 版本并安装".to_string());
@@ -952,7 +1176,7 @@ impl App {
         }
 
         self.content_buffer.push("".to_string());
-        self.content_buffer.push("提示: 按 Esc 或 q 返回主菜单".to_string());
+        self.content_buffer.push("提示: 按 r 刷新，Esc 或 q 返回主菜单".to_string());
 
         self.current_view = ViewType::ViewStatus;
         Ok(())
@@ -987,12 +1211,53 @@ impl App {
         ));
 
         self.content_buffer.push("".to_string());
-        self.content_buffer.push("提示: 按 Esc 或 q 返回主菜单".to_string());
+        self.content_buffer.push("提示: 按 r 刷新，Esc 或 q 返回主菜单".to_string());
 
         self.current_view = ViewType::ViewStatus;
         Ok(())
     }
 
+    /// 鼠标位置对应的列表项索引，不在列表内时返回 None
+    fn list_index_at(&self, column: u16, row: u16) -> Option<usize> {
+        let layout = self.list_layout.get()?;
+        let area = layout.area;
+        if column < area.x || column >= area.x + area.width || row < area.y || row >= area.y + area.height {
+            return None;
+        }
+        let index = layout.offset + (row - area.y) as usize;
+        let len = match self.current_view {
+            ViewType::MainMenu => self.menu_items.len(),
+            ViewType::SwitchProvider => self.providers.len(),
+            ViewType::TestProvider => self.test_rows.len(),
+            _ => return None,
+        };
+        (index < len).then_some(index)
+    }
+
+    /// 处理鼠标事件：滚轮移动选中项，左键点击选中并执行
+    fn handle_mouse(&mut self, mouse: MouseEvent) -> Result<()> {
+        if !matches!(
+            self.current_view,
+            ViewType::MainMenu | ViewType::SwitchProvider | ViewType::TestProvider
+        ) {
+            return Ok(());
+        }
+
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.next(),
+            MouseEventKind::ScrollUp => self.previous(),
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(index) = self.list_index_at(mouse.column, mouse.row) {
+                    self.selected = index;
+                    self.list_state.select(Some(index));
+                    self.select()?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
     fn show_message(&mut self, title: String, message: String, is_error: bool) {
         self.content_buffer.clear();
         self.content_buffer.push(title.clone());
@@ -1007,6 +1272,9 @@ impl App {
     }
 
     fn back_to_main_menu(&mut self) {
+        if let Some(pending) = self.pending_test.take() {
+            pending.handle.abort();
+        }
         self.current_view = ViewType::MainMenu;
         self.content_buffer.clear();
         self.providers.clear();
@@ -1048,10 +1316,15 @@ fn run_app<B: ratatui::backend::Backend>(
     app: &mut App,
 ) -> Result<()> {
     loop {
+        app.poll_test();
         terminal.draw(|f| ui(f, app))?;
 
         if event::poll(std::time::Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
+            let event = event::read()?;
+            if let Event::Mouse(mouse) = event {
+                app.handle_mouse(mouse)?;
+            }
+            if let Event::Key(key) = event {
                 if key.kind == KeyEventKind::Press {
                     match app.current_view {
                         ViewType::MainMenu => match key.code {
@@ -1079,7 +1352,7 @@ fn run_app<B: ratatui::backend::Backend>(
                             }
                             _ => {}
                         },
-                        ViewType::SwitchProvider => match key.code {
+                        ViewType::SwitchProvider | ViewType::TestProvider => match key.code {
                             KeyCode::Char('q') | KeyCode::Esc => {
                                 app.back_to_main_menu();
                             }
@@ -1092,12 +1365,18 @@ fn run_app<B: ratatui::backend::Backend>(
                             KeyCode::Enter | KeyCode::Char(' ') => {
                                 app.select()?;
                             }
+                            KeyCode::Char('r') => {
+                                app.refresh()?;
+                            }
                             _ => {}
                         },
                         _ => match key.code {
                             KeyCode::Char('q') | KeyCode::Esc | KeyCode::Enter | KeyCode::Char(' ') => {
                                 app.back_to_main_menu();
                             }
+                            KeyCode::Char('r') => {
+                                app.refresh()?;
+                            }
                             _ => {}
                         },
                     }
@@ -1120,6 +1399,7 @@ fn ui(f: &mut Frame, app: &App) {
         ViewType::SwitchProvider => render_switch_provider_view(f, app, size),
         ViewType::AddDefaultProvider => render_add_default_provider_view(f, app, size),
         ViewType::EnvConflictManage => render_env_conflict_manage_view(f, app, size),
+        ViewType::TestProvider => render_test_provider_view(f, app, size),
         ViewType::MessageBox { title, message, is_error } => {
             render_message_box(f, size, title, message, *is_error)
         }
@@ -1163,13 +1443,13 @@ fn render_content_view(f: &mut Frame, app: &App, area: Rect) {
     let content: Vec<Line> = app
         .content_buffer
         .iter()
-        .map(|s| Line::from(s.as_str()))
+        .map(|s| Line::from(glyphs(s)))
         .collect();
 
     let paragraph = Paragraph::new(content)
         .block(
-            Block::default()
-                .title(" 📄 详细信息 ")
+            bordered_block()
+                .title(glyphs(" 📄 详细信息 "))
                 .title_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
@@ -1183,12 +1463,14 @@ fn render_content_view(f: &mut Frame, app: &App, area: Rect) {
         Span::styled("Esc", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
         Span::styled(" 或 ", Style::default().fg(Color::DarkGray)),
         Span::styled("q", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
-        Span::styled(" 返回主菜单", Style::default().fg(Color::DarkGray)),
+        Span::styled(" 返回主菜单  ", Style::default().fg(Color::DarkGray)),
+        Span::styled("r", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::styled(" 刷新", Style::default().fg(Color::DarkGray)),
     ])];
 
     let footer = Paragraph::new(footer_text)
         .block(
-            Block::default()
+            bordered_block()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan))
         )
@@ -1226,8 +1508,8 @@ fn render_switch_provider_view(f: &mut Frame, app: &App, area: Rect) {
 
     let list = List::new(items)
         .block(
-            Block::default()
-                .title(" 🔄 选择供应商 (Claude) ")
+            bordered_block()
+                .title(glyphs(" 🔄 选择供应商 (Claude) "))
                 .title_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
@@ -1238,22 +1520,109 @@ fn render_switch_provider_view(f: &mut Frame, app: &App, area: Rect) {
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD)
         )
-        .highlight_symbol("▶ ");
+        .highlight_symbol(if is_plain() { "> " } else { "▶ " });
 
-    f.render_stateful_widget(list, chunks[1], &mut app.list_state.clone());
+    render_list(f, app, list, chunks[1]);
 
     let footer_text = vec![Line::from(vec![
-        Span::styled("↑↓", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::styled(glyphs("↑↓"), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
         Span::styled(" 选择  ", Style::default().fg(Color::DarkGray)),
         Span::styled("Enter", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
         Span::styled(" 确认  ", Style::default().fg(Color::DarkGray)),
+        Span::styled("r", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::styled(" 刷新  ", Style::default().fg(Color::DarkGray)),
         Span::styled("Esc", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
         Span::styled(" 取消", Style::default().fg(Color::DarkGray)),
     ])];
 
     let footer = Paragraph::new(footer_text)
         .block(
-            Block::default()
+            bordered_block()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+        )
+        .alignment(Alignment::Center);
+
+    f.render_widget(footer, chunks[2]);
+}
+
+fn render_test_provider_view(f: &mut Frame, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // 标题
+            Constraint::Min(0),     // 列表
+            Constraint::Length(3),  // 状态栏
+        ])
+        .split(area);
+
+    render_header(f, chunks[0]);
+
+    let running = app.pending_test.as_ref().map(|p| p.index);
+    let items: Vec<ListItem> = app
+        .test_rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let style = if i == app.selected {
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+
+            let status = if running == Some(i) {
+                Span::styled(
+                    format!("{} 测试中...", spinner_frames()[app.spinner_tick % spinner_frames().len()]),
+                    Style::default().fg(Color::Yellow),
+                )
+            } else {
+                match &row.result {
+                    Some((true, text)) => Span::styled(format!("{} {}", sym("✓"), text), Style::default().fg(Color::Green)),
+                    Some((false, text)) => Span::styled(format!("{} {}", sym("✗"), text), Style::default().fg(Color::Red)),
+                    None => Span::raw(""),
+                }
+            };
+
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:<24}", row.name), style),
+                Span::styled(format!("{:<14}", row.app_type.display_name()), Style::default().fg(Color::DarkGray)),
+                status,
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            bordered_block()
+                .title(glyphs(" 🧪 测试供应商 "))
+                .title_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        )
+        .highlight_symbol(if is_plain() { "> " } else { "▶ " });
+
+    render_list(f, app, list, chunks[1]);
+
+    let footer_text = vec![Line::from(vec![
+        Span::styled(glyphs("↑↓"), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::styled(" 选择  ", Style::default().fg(Color::DarkGray)),
+        Span::styled("Enter", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::styled(" 测试  ", Style::default().fg(Color::DarkGray)),
+        Span::styled("r", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::styled(" 刷新  ", Style::default().fg(Color::DarkGray)),
+        Span::styled("Esc", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::styled(" 返回", Style::default().fg(Color::DarkGray)),
+    ])];
+
+    let footer = Paragraph::new(footer_text)
+        .block(
+            bordered_block()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan))
         )
@@ -1286,15 +1655,15 @@ fn render_add_default_provider_view(f: &mut Frame, app: &App, area: Rect) {
             };
 
             let icon = match template.app_type {
-                AppType::Claude => "🤖",
-                AppType::Codex => "🔧",
-                AppType::Gemini => "✨",
-                _ => "📦",
+                AppType::Claude => sym("🤖"),
+                AppType::Codex => sym("🔧"),
+                AppType::Gemini => sym("✨"),
+                _ => sym("📦"),
             };
 
             let content = vec![
                 Line::from(vec![
-                    Span::styled(format!("{} ", icon), style),
+                    Span::styled(glyphs(&format!("{} ", icon)), style),
                     Span::styled(&template.name, style),
                 ]),
                 Line::from(vec![
@@ -1309,8 +1678,8 @@ fn render_add_default_provider_view(f: &mut Frame, app: &App, area: Rect) {
 
     let list = List::new(items)
         .block(
-            Block::default()
-                .title(" ⚡ 添加官方默认供应商 ")
+            bordered_block()
+                .title(glyphs(" ⚡ 添加官方默认供应商 "))
                 .title_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
@@ -1321,12 +1690,12 @@ fn render_add_default_provider_view(f: &mut Frame, app: &App, area: Rect) {
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD)
         )
-        .highlight_symbol("▶ ");
+        .highlight_symbol(if is_plain() { "> " } else { "▶ " });
 
     f.render_stateful_widget(list, chunks[1], &mut app.list_state.clone());
 
     let footer_text = vec![Line::from(vec![
-        Span::styled("↑↓", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::styled(glyphs("↑↓"), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
         Span::styled(" 选择  ", Style::default().fg(Color::DarkGray)),
         Span::styled("Enter", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
         Span::styled(" 查看命令  ", Style::default().fg(Color::DarkGray)),
@@ -1336,7 +1705,7 @@ fn render_add_default_provider_view(f: &mut Frame, app: &App, area: Rect) {
 
     let footer = Paragraph::new(footer_text)
         .block(
-            Block::default()
+            bordered_block()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan))
         )
@@ -1369,16 +1738,16 @@ fn render_env_conflict_manage_view(f: &mut Frame, app: &App, area: Rect) {
             };
 
             let icon = match action.action_type {
-                EnvActionType::ViewConflicts => "🔍",
-                EnvActionType::ClearConflicts => "🧹",
-                EnvActionType::BackupConfigs => "💾",
-                EnvActionType::RestoreBackup => "↩️",
-                EnvActionType::GenerateUnsetScript => "📝",
+                EnvActionType::ViewConflicts => sym("🔍"),
+                EnvActionType::ClearConflicts => sym("🧹"),
+                EnvActionType::BackupConfigs => sym("💾"),
+                EnvActionType::RestoreBackup => sym("↩️"),
+                EnvActionType::GenerateUnsetScript => sym("📝"),
             };
 
             let content = vec![
                 Line::from(vec![
-                    Span::styled(format!("{} ", icon), style),
+                    Span::styled(glyphs(&format!("{} ", icon)), style),
                     Span::styled(&action.title, style),
                 ]),
                 Line::from(vec![
@@ -1393,8 +1762,8 @@ fn render_env_conflict_manage_view(f: &mut Frame, app: &App, area: Rect) {
 
     let list = List::new(items)
         .block(
-            Block::default()
-                .title(" ⚠️  环境冲突管理 ")
+            bordered_block()
+                .title(glyphs(" ⚠️  环境冲突管理 "))
                 .title_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
@@ -1405,12 +1774,12 @@ fn render_env_conflict_manage_view(f: &mut Frame, app: &App, area: Rect) {
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD)
         )
-        .highlight_symbol("▶ ");
+        .highlight_symbol(if is_plain() { "> " } else { "▶ " });
 
     f.render_stateful_widget(list, chunks[1], &mut app.list_state.clone());
 
     let footer_text = vec![Line::from(vec![
-        Span::styled("↑↓", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::styled(glyphs("↑↓"), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
         Span::styled(" 选择  ", Style::default().fg(Color::DarkGray)),
         Span::styled("Enter", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
         Span::styled(" 执行  ", Style::default().fg(Color::DarkGray)),
@@ -1420,7 +1789,7 @@ fn render_env_conflict_manage_view(f: &mut Frame, app: &App, area: Rect) {
 
     let footer = Paragraph::new(footer_text)
         .block(
-            Block::default()
+            bordered_block()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan))
         )
@@ -1442,17 +1811,17 @@ fn render_message_box(f: &mut Frame, area: Rect, title: &str, message: &str, is_
     render_header(f, chunks[0]);
 
     let (border_color, title_icon) = if is_error {
-        (Color::Red, "❌ ")
+        (Color::Red, sym("❌"))
     } else {
-        (Color::Green, "✓ ")
+        (Color::Green, sym("✓"))
     };
 
-    let content: Vec<Line> = message.lines().map(|s| Line::from(s)).collect();
+    let content: Vec<Line> = message.lines().map(|s| Line::from(glyphs(s))).collect();
 
     let paragraph = Paragraph::new(content)
         .block(
-            Block::default()
-                .title(format!(" {}{} ", title_icon, title))
+            bordered_block()
+                .title(glyphs(&format!(" {} {} ", title_icon, title)))
                 .title_style(Style::default().fg(border_color).add_modifier(Modifier::BOLD))
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(border_color).add_modifier(Modifier::BOLD))
@@ -1470,7 +1839,7 @@ fn render_message_box(f: &mut Frame, area: Rect, title: &str, message: &str, is_
 
     let footer = Paragraph::new(footer_text)
         .block(
-            Block::default()
+            bordered_block()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan))
         )
@@ -1482,7 +1851,7 @@ fn render_message_box(f: &mut Frame, area: Rect, title: &str, message: &str, is_
 fn render_header(f: &mut Frame, area: Rect) {
     let title = vec![Line::from(vec![
         Span::styled(
-            " ⚡ CC-Switch ",
+            glyphs(" ⚡ CC-Switch "),
             Style::default()
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD),
@@ -1501,7 +1870,7 @@ fn render_header(f: &mut Frame, area: Rect) {
 
     let header = Paragraph::new(title)
         .block(
-            Block::default()
+            bordered_block()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
         )
@@ -1510,6 +1879,16 @@ fn render_header(f: &mut Frame, area: Rect) {
     f.render_widget(header, area);
 }
 
+/// 绘制可选列表，并记录其位置供鼠标点击定位
+fn render_list(f: &mut Frame, app: &App, list: List, area: Rect) {
+    let mut state = app.list_state.clone();
+    f.render_stateful_widget(list, area, &mut state);
+    app.list_layout.set(Some(ListLayout {
+        area: area.inner(Margin::new(1, 1)),
+        offset: state.offset(),
+    }));
+}
+
 fn render_menu(f: &mut Frame, app: &App, area: Rect) {
     let items: Vec<ListItem> = app
         .menu_items
@@ -1543,8 +1922,8 @@ fn render_menu(f: &mut Frame, app: &App, area: Rect) {
 
     let list = List::new(items)
         .block(
-            Block::default()
-                .title(" 📋 主菜单 ")
+            bordered_block()
+                .title(glyphs(" 📋 主菜单 "))
                 .title_style(
                     Style::default()
                         .fg(Color::Yellow)
@@ -1559,9 +1938,9 @@ fn render_menu(f: &mut Frame, app: &App, area: Rect) {
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD),
         )
-        .highlight_symbol("▶ ");
+        .highlight_symbol(if is_plain() { "> " } else { "▶ " });
 
-    f.render_stateful_widget(list, area, &mut app.list_state.clone());
+    render_list(f, app, list, area);
 }
 
 fn render_description(f: &mut Frame, app: &App, area: Rect) {
@@ -1596,11 +1975,11 @@ fn render_description(f: &mut Frame, app: &App, area: Rect) {
                     .add_modifier(Modifier::BOLD),
             )]),
             Line::from(vec![
-                Span::styled("  ↑/k  ", Style::default().fg(Color::Green)),
+                Span::styled(glyphs("  ↑/k  "), Style::default().fg(Color::Green)),
                 Span::raw("- 上移"),
             ]),
             Line::from(vec![
-                Span::styled("  ↓/j  ", Style::default().fg(Color::Green)),
+                Span::styled(glyphs("  ↓/j  "), Style::default().fg(Color::Green)),
                 Span::raw("- 下移"),
             ]),
             Line::from(vec![
@@ -1622,8 +2001,8 @@ fn render_description(f: &mut Frame, app: &App, area: Rect) {
 
     let paragraph = Paragraph::new(description)
         .block(
-            Block::default()
-                .title(" 📝 详细信息 ")
+            bordered_block()
+                .title(glyphs(" 📝 详细信息 "))
                 .title_style(
                     Style::default()
                         .fg(Color::Yellow)
@@ -1640,23 +2019,23 @@ fn render_description(f: &mut Frame, app: &App, area: Rect) {
 fn render_footer(f: &mut Frame, app: &App, area: Rect) {
     let status = if let Some(msg) = &app.status_message {
         vec![Line::from(vec![
-            Span::styled("⚡ 状态: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::styled(glyphs("⚡ 状态: "), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
             Span::styled(msg, Style::default().fg(Color::Green)),
         ])]
     } else {
         vec![Line::from(vec![
-            Span::styled("✓ ", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+            Span::styled(glyphs("✓ "), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
             Span::styled("就绪", Style::default().fg(Color::Green)),
-            Span::raw(" │ "),
+            Span::raw(glyphs(" │ ")),
             Span::styled(
-                "↑↓/j/k",
+                glyphs("↑↓/j/k"),
                 Style::default().fg(Color::Cyan),
             ),
             Span::styled(" 导航", Style::default().fg(Color::DarkGray)),
-            Span::raw(" │ "),
+            Span::raw(glyphs(" │ ")),
             Span::styled("Enter", Style::default().fg(Color::Cyan)),
             Span::styled(" 确认", Style::default().fg(Color::DarkGray)),
-            Span::raw(" │ "),
+            Span::raw(glyphs(" │ ")),
             Span::styled("q/Esc", Style::default().fg(Color::Cyan)),
             Span::styled(" 退出", Style::default().fg(Color::DarkGray)),
         ])]
@@ -1664,7 +2043,7 @@ fn render_footer(f: &mut Frame, app: &App, area: Rect) {
 
     let footer = Paragraph::new(status)
         .block(
-            Block::default()
+            bordered_block()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan)),
         )
@@ -790,6 +790,8 @@ pub struct InstallSkillRequest {
     pub repo: String,
     #[serde(default)]
     pub branch: Option<String>,
+    #[serde(default)]
+    pub rev: Option<String>,
 }
 
 pub async fn install_skill(Json(req): Json<InstallSkillRequest>) -> impl IntoResponse {
@@ -800,7 +802,7 @@ pub async fn install_skill(Json(req): Json<InstallSkillRequest>) -> impl IntoRes
         }
     };
 
-    match SkillService::install(&state, &req.repo, req.branch) {
+    match SkillService::install(&state, &req.repo, req.branch, req.rev) {
         Ok(_) => (StatusCode::OK, Json(ApiResponse::success("Skill 安装成功".to_string()))),
         Err(e) => (StatusCode::BAD_REQUEST, Json(ApiResponse::<String>::error(&e.to_string()))),
     }
@@ -1216,14 +1218,7 @@ pub async fn config_paths() -> impl IntoResponse {
 // ==================== 辅助函数 ====================
 
 fn parse_app_type(s: &str) -> Option<AppType> {
-    match s.to_lowercase().as_str() {
-        "claude" => Some(AppType::Claude),
-        "codex" => Some(AppType::Codex),
-        "gemini" => Some(AppType::Gemini),
-        "opencode" => Some(AppType::OpenCode),
-        "openclaw" => Some(AppType::OpenClaw),
-        _ => None,
-    }
+    s.parse().ok()
 }
 
 async fn test_api_connection(api_key: &str, base_url: &str, model: &str) -> Result<(), String> {
@@ -0,0 +1,46 @@
+//! add --from-file --merge 的端到端测试
+
+mod common;
+
+use common::cc_switch;
+
+fn list_claude(home: &std::path::Path) -> Vec<serde_json::Value> {
+    let output = cc_switch(home).args(["list", "-o", "json"]).output().unwrap();
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    rows.as_array().unwrap().clone()
+}
+
+#[test]
+fn test_add_from_file_merge_updates_same_name() {
+    let home = tempfile::tempdir().unwrap();
+    let file = home.path().join("work.json");
+
+    std::fs::write(&file, r#"{"env":{"ANTHROPIC_AUTH_TOKEN":"sk-old"}}"#).unwrap();
+    cc_switch(home.path())
+        .args(["add", "work", "--from-file", file.to_str().unwrap(), "--merge"])
+        .assert()
+        .success();
+    let first = list_claude(home.path());
+    assert_eq!(first.len(), 1);
+
+    std::fs::write(&file, r#"{"env":{"ANTHROPIC_AUTH_TOKEN":"sk-new"}}"#).unwrap();
+    cc_switch(home.path())
+        .args(["add", "work", "--from-file", file.to_str().unwrap(), "--merge"])
+        .assert()
+        .success();
+    let second = list_claude(home.path());
+    assert_eq!(second.len(), 1);
+    assert_eq!(second[0]["id"], first[0]["id"]);
+
+    let settings = std::fs::read_to_string(home.path().join(".claude/settings.json")).unwrap();
+    assert!(settings.contains("sk-new"), "{}", settings);
+}
+
+#[test]
+fn test_add_merge_requires_import_source() {
+    let home = tempfile::tempdir().unwrap();
+    cc_switch(home.path())
+        .args(["add", "work", "--api-key", "sk-test", "--merge", "--skip-test"])
+        .assert()
+        .code(2);
+}
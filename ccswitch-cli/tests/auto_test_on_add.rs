@@ -0,0 +1,28 @@
+//! auto-test-on-add 设置的端到端测试
+
+mod common;
+
+use common::cc_switch;
+
+#[test]
+fn test_add_skips_api_test_when_disabled() {
+    let home = tempfile::tempdir().unwrap();
+    cc_switch(home.path())
+        .args(["config", "set", "auto-test-on-add", "false"])
+        .assert()
+        .success();
+
+    // 不可达的地址：若仍发起测试，会失败并等待确认，stdin 为空时取消添加
+    let output = cc_switch(home.path())
+        .args(["add", "offline", "--api-key", "sk-test", "--base-url", "http://127.0.0.1:1"])
+        .write_stdin("")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains("正在测试"), "{}", stdout);
+
+    let output = cc_switch(home.path()).args(["list", "-o", "json"]).output().unwrap();
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(rows[0]["name"], "offline");
+}
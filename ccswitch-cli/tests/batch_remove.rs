@@ -0,0 +1,36 @@
+//! 非交互模式下批量删除的端到端测试
+
+mod common;
+
+use std::time::Duration;
+
+use common::cc_switch;
+
+#[test]
+fn test_non_interactive_batch_remove_requires_force() {
+    let home = tempfile::tempdir().unwrap();
+    cc_switch(home.path())
+        .args(["add", "demo", "--api-key", "sk-test", "--skip-test", "--quiet"])
+        .assert()
+        .success();
+
+    // stdin 不是终端时不等待输入，直接中止
+    let output = cc_switch(home.path())
+        .args(["batch", "remove", "demo", "--app", "claude"])
+        .write_stdin("y\n")
+        .timeout(Duration::from_secs(30))
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("已中止") && stderr.contains("--force"), "stderr: {}", stderr);
+
+    let list = cc_switch(home.path()).args(["list", "-o", "json"]).output().unwrap();
+    let rows: serde_json::Value = serde_json::from_slice(&list.stdout).unwrap();
+    assert_eq!(rows.as_array().map(Vec::len), Some(1));
+
+    cc_switch(home.path())
+        .args(["batch", "remove", "demo", "--app", "claude", "--force", "--quiet"])
+        .assert()
+        .success();
+}
@@ -0,0 +1,26 @@
+//! batch test 退出状态的端到端测试
+
+mod common;
+
+use std::time::Duration;
+
+use common::cc_switch;
+
+#[test]
+fn test_batch_test_failure_exit_status_ignores_format() {
+    let home = tempfile::tempdir().unwrap();
+    // Base URL 指向未监听的本机端口，测试必然失败
+    cc_switch(home.path())
+        .args(["add", "demo", "--api-key", "sk-test", "--base-url", "http://127.0.0.1:9", "--skip-test", "--quiet"])
+        .assert()
+        .success();
+
+    for format in ["table", "json"] {
+        let output = cc_switch(home.path())
+            .args(["batch", "test", "--app", "claude", "--timeout", "5", "-o", format])
+            .timeout(Duration::from_secs(60))
+            .output()
+            .unwrap();
+        assert!(!output.status.success(), "{} 格式下应以非零状态退出", format);
+    }
+}
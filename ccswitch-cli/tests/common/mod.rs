@@ -0,0 +1,25 @@
+//! 集成测试共用的辅助函数
+
+use assert_cmd::Command;
+
+/// 会影响配置路径的环境变量，测试中一律清除，避免读写真实用户配置
+const PATH_OVERRIDES: &[&str] = &[
+    "CCSWITCH_CONFIG_DIR",
+    "CC_SWITCH_HOME",
+    "CCSWITCH_CLAUDE_CONFIG_DIR",
+    "CCSWITCH_CLAUDE_MCP_PATH",
+    "CCSWITCH_CODEX_CONFIG_DIR",
+    "CCSWITCH_GEMINI_CONFIG_DIR",
+    "CCSWITCH_OPENCODE_CONFIG_DIR",
+    "CCSWITCH_OPENCLAW_CONFIG_DIR",
+];
+
+/// 以 `home` 作为主目录运行 cc-switch
+pub fn cc_switch(home: &std::path::Path) -> Command {
+    let mut cmd = Command::cargo_bin("cc-switch").unwrap();
+    cmd.env("CCSWITCH_HOME", home).env("HOME", home).env("NO_COLOR", "1");
+    for key in PATH_OVERRIDES {
+        cmd.env_remove(key);
+    }
+    cmd
+}
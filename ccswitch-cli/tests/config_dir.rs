@@ -0,0 +1,52 @@
+//! --config-dir 全局参数的端到端测试
+
+mod common;
+
+use common::cc_switch;
+
+fn provider_names(home: &std::path::Path, config_dir: &std::path::Path) -> Vec<String> {
+    let output = cc_switch(home)
+        .args(["list", "-o", "json", "--quiet", "--config-dir"])
+        .arg(config_dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    rows.as_array()
+        .unwrap()
+        .iter()
+        .map(|row| row["name"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_config_dirs_use_independent_databases() {
+    let home = tempfile::tempdir().unwrap();
+    let first = home.path().join("first");
+    let second = home.path().join("second");
+
+    for (dir, name) in [(&first, "alpha"), (&second, "beta")] {
+        cc_switch(home.path())
+            .args(["add", name, "--api-key", "sk-test", "--skip-test", "--quiet", "--config-dir"])
+            .arg(dir)
+            .assert()
+            .success();
+    }
+
+    assert!(first.join("cc-switch.db").is_file());
+    assert!(second.join("cc-switch.db").is_file());
+    assert_eq!(provider_names(home.path(), &first), vec!["alpha"]);
+    assert_eq!(provider_names(home.path(), &second), vec!["beta"]);
+}
+
+#[test]
+fn test_commands_without_database_do_not_create_it() {
+    let home = tempfile::tempdir().unwrap();
+    let config_dir = home.path().join("data");
+    cc_switch(home.path())
+        .args(["env", "check", "--config-dir"])
+        .arg(&config_dir)
+        .assert()
+        .success();
+    assert!(!config_dir.join("cc-switch.db").exists());
+}
@@ -0,0 +1,17 @@
+//! --plain 纯文本符号模式的端到端测试
+
+mod common;
+
+use common::cc_switch;
+
+#[test]
+fn test_plain_mode_prints_no_decorative_glyphs() {
+    let home = tempfile::tempdir().unwrap();
+    for args in [&["--plain", "doctor"][..], &["--plain", "env", "check"], &["--plain", "env", "list"]] {
+        let output = cc_switch(home.path()).args(args).output().unwrap();
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        for glyph in ["✓", "⚠", "✗", "🔍", "📋", "═"] {
+            assert!(!stdout.contains(glyph), "{:?} 输出中含有 {}: {}", args, glyph, stdout);
+        }
+    }
+}
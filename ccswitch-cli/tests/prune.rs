@@ -0,0 +1,41 @@
+//! prune 命令的端到端测试
+
+mod common;
+
+use common::cc_switch;
+
+fn add(home: &std::path::Path, name: &str) {
+    cc_switch(home)
+        .args(["add", name, "--api-key", "sk-test", "--skip-test", "--quiet"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_prune_rejects_negative_days() {
+    let home = tempfile::tempdir().unwrap();
+    cc_switch(home.path())
+        .args(["prune", "--older-than-days", "-1", "--app", "claude"])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn test_prune_json_with_yes_prints_deleted_providers() {
+    let home = tempfile::tempdir().unwrap();
+    add(home.path(), "current");
+    add(home.path(), "stale");
+
+    let output = cc_switch(home.path())
+        .args(["prune", "--older-than-days", "0", "--app", "claude", "-o", "json", "--yes"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let deleted: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let names: Vec<&str> = deleted.as_array().unwrap().iter().map(|r| r["name"].as_str().unwrap()).collect();
+    assert_eq!(names, ["stale"]);
+
+    let list = cc_switch(home.path()).args(["list", "-o", "json"]).output().unwrap();
+    let rows: serde_json::Value = serde_json::from_slice(&list.stdout).unwrap();
+    assert_eq!(rows.as_array().map(Vec::len), Some(1));
+}
@@ -0,0 +1,26 @@
+//! --quiet 全局参数的端到端测试
+
+mod common;
+
+use common::cc_switch;
+
+#[test]
+fn test_quiet_json_list_emits_only_json() {
+    let home = tempfile::tempdir().unwrap();
+    cc_switch(home.path())
+        .args(["add", "demo", "--api-key", "sk-test", "--skip-test", "--quiet"])
+        .assert()
+        .success();
+
+    let output = cc_switch(home.path())
+        .args(["list", "-o", "json", "--quiet"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let rows: serde_json::Value = serde_json::from_str(&stdout).expect("stdout 应只包含 JSON");
+    assert_eq!(rows.as_array().map(Vec::len), Some(1));
+    assert_eq!(rows[0]["name"], "demo");
+    assert!(!stdout.contains('═') && !stdout.contains('ℹ'));
+}
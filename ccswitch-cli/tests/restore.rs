@@ -0,0 +1,25 @@
+//! backup / restore 的端到端测试
+
+mod common;
+
+use common::cc_switch;
+
+#[test]
+fn test_restore_rewrites_live_config_of_current_provider() {
+    let home = tempfile::tempdir().unwrap();
+    cc_switch(home.path())
+        .args(["add", "work", "--api-key", "sk-work", "--skip-test", "--quiet"])
+        .assert()
+        .success();
+    let backup = home.path().join("backup.json");
+    cc_switch(home.path()).arg("backup").arg(&backup).assert().success();
+
+    // 在另一个 home 中恢复，此前没有 live 配置
+    let other = tempfile::tempdir().unwrap();
+    let live = other.path().join(".claude/settings.json");
+    assert!(!live.exists());
+    cc_switch(other.path()).arg("restore").arg(&backup).assert().success();
+
+    let settings = std::fs::read_to_string(&live).unwrap();
+    assert!(settings.contains("sk-work"), "{}", settings);
+}
@@ -0,0 +1,25 @@
+//! version 命令的端到端测试
+
+mod common;
+
+use common::cc_switch;
+
+#[test]
+fn test_version_json_reports_build_metadata() {
+    let home = tempfile::tempdir().unwrap();
+    let output = cc_switch(home.path()).args(["version", "-o", "json"]).output().unwrap();
+    assert!(output.status.success());
+
+    let info: serde_json::Value = serde_json::from_slice(&output.stdout).expect("stdout 应为 JSON");
+    assert_eq!(info["version"], ccswitch_core::VERSION);
+    assert_eq!(info["app_name"], ccswitch_core::APP_NAME);
+    assert_eq!(info["build_date"].as_str().map(str::len), Some(10));
+    for key in ["git_sha", "rustc"] {
+        assert!(info.get(key).is_some(), "缺少字段 {}", key);
+    }
+
+    // 默认格式仍为单行
+    let output = cc_switch(home.path()).arg("version").output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.trim(), format!("cc-switch {}", ccswitch_core::VERSION));
+}
@@ -4,8 +4,16 @@
 
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::path::PathBuf;
 use std::str::FromStr;
 
+use crate::config::{
+    get_claude_config_dir, get_claude_mcp_path, get_claude_settings_path, get_codex_auth_path,
+    get_codex_config_dir, get_codex_config_path, get_gemini_config_dir, get_gemini_settings_path,
+    get_openclaw_config_dir, get_opencode_config_dir,
+};
+use crate::error::AppError;
+
 /// 支持的应用类型
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -70,6 +78,58 @@ impl AppType {
     pub fn supports_skills(&self) -> bool {
         !matches!(self, Self::OpenClaw)
     }
+
+    /// 获取应用的配置文件路径
+    pub fn config_paths(&self) -> AppConfigPaths {
+        match self {
+            Self::Claude => AppConfigPaths {
+                config_dir: get_claude_config_dir(),
+                settings_path: get_claude_settings_path(),
+                mcp_path: Some(get_claude_mcp_path()),
+                auth_path: None,
+            },
+            Self::Codex => AppConfigPaths {
+                config_dir: get_codex_config_dir(),
+                settings_path: get_codex_config_path(),
+                mcp_path: Some(get_codex_config_path()),
+                auth_path: Some(get_codex_auth_path()),
+            },
+            Self::Gemini => AppConfigPaths {
+                config_dir: get_gemini_config_dir(),
+                settings_path: get_gemini_settings_path(),
+                mcp_path: Some(get_gemini_settings_path()),
+                auth_path: None,
+            },
+            Self::OpenCode => {
+                let config = get_opencode_config_dir().join("opencode.json");
+                AppConfigPaths {
+                    config_dir: get_opencode_config_dir(),
+                    settings_path: config.clone(),
+                    mcp_path: Some(config),
+                    auth_path: None,
+                }
+            }
+            Self::OpenClaw => AppConfigPaths {
+                config_dir: get_openclaw_config_dir(),
+                settings_path: crate::openclaw_config::get_openclaw_config_path(),
+                mcp_path: None,
+                auth_path: None,
+            },
+        }
+    }
+}
+
+/// 应用特定的配置路径
+#[derive(Debug, Clone)]
+pub struct AppConfigPaths {
+    /// 配置目录
+    pub config_dir: PathBuf,
+    /// 主配置文件
+    pub settings_path: PathBuf,
+    /// MCP 配置文件（不支持 MCP 的应用为 None）
+    pub mcp_path: Option<PathBuf>,
+    /// 认证文件（如有）
+    pub auth_path: Option<PathBuf>,
 }
 
 impl fmt::Display for AppType {
@@ -79,20 +139,31 @@ impl fmt::Display for AppType {
 }
 
 impl FromStr for AppType {
-    type Err = String;
+    type Err = AppError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
+        match s.trim().to_lowercase().as_str() {
             "claude" | "claude-code" | "claude_code" => Ok(Self::Claude),
             "codex" | "codex-cli" | "codex_cli" => Ok(Self::Codex),
             "gemini" | "gemini-cli" | "gemini_cli" => Ok(Self::Gemini),
             "opencode" | "open-code" | "open_code" => Ok(Self::OpenCode),
             "openclaw" | "open-claw" | "open_claw" => Ok(Self::OpenClaw),
-            _ => Err(format!("未知的应用类型: {}", s)),
+            _ => Err(AppError::InvalidInput(format!(
+                "未知的应用类型: {}（可选: claude, codex, gemini, opencode, openclaw）",
+                s
+            ))),
         }
     }
 }
 
+impl TryFrom<&str> for AppType {
+    type Error = AppError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
 /// MCP 服务器应用状态（标记应用到哪些客户端）
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct McpApps {
@@ -228,6 +299,8 @@ impl SkillApps {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::isolated_home;
+    use serial_test::serial;
 
     #[test]
     fn test_app_type_from_str() {
@@ -241,6 +314,21 @@ mod tests {
         assert!(AppType::from_str("unknown").is_err());
     }
 
+    #[test]
+    fn test_app_type_parse_mixed_case_and_errors() {
+        assert_eq!("CoDeX".parse::<AppType>().unwrap(), AppType::Codex);
+        assert_eq!(" GEMINI ".parse::<AppType>().unwrap(), AppType::Gemini);
+        assert_eq!(AppType::try_from("OpenCode").unwrap(), AppType::OpenCode);
+        assert_eq!(AppType::try_from("Claude-Code").unwrap(), AppType::Claude);
+
+        for bad in ["", "claud", "gpt"] {
+            match AppType::try_from(bad) {
+                Err(AppError::InvalidInput(msg)) => assert!(msg.contains("未知的应用类型")),
+                other => panic!("期望 InvalidInput，实际: {:?}", other),
+            }
+        }
+    }
+
     #[test]
     fn test_app_type_all() {
         let all = AppType::all();
@@ -314,4 +402,22 @@ mod tests {
         assert_eq!(enabled.len(), 1);
         assert_eq!(enabled[0], AppType::Claude);
     }
+
+    #[test]
+    #[serial]
+    fn test_config_paths_are_home_relative() {
+        let home = isolated_home();
+        for app in AppType::all() {
+            let paths = app.config_paths();
+            let optional = [paths.mcp_path.as_ref(), paths.auth_path.as_ref()];
+            let all = [&paths.config_dir, &paths.settings_path].into_iter().chain(optional.into_iter().flatten());
+            for path in all {
+                assert!(path.file_name().is_some(), "{}: {:?}", app, path);
+                assert!(path.starts_with(home.path()), "{}: {:?}", app, path);
+            }
+            assert!(paths.settings_path.starts_with(&paths.config_dir), "{}", app);
+            assert_eq!(paths.mcp_path.is_some(), app.supports_mcp(), "{}", app);
+        }
+        assert!(AppType::Codex.config_paths().auth_path.is_some());
+    }
 }
@@ -0,0 +1,82 @@
+//! 审计日志
+//!
+//! live 配置写入与数据库变更以 JSON 对象作为日志消息输出到固定 target，
+//! CLI 的 `--log-json` 会将其展开为结构化字段。
+
+use serde_json::{Map, Value};
+
+/// 审计日志的 target
+pub const AUDIT_LOG_TARGET: &str = "ccswitch::audit";
+
+/// 记录一条审计事件，`fields` 应为 JSON 对象
+pub(crate) fn log_event(event: &str, fields: Value) {
+    if !log::log_enabled!(target: AUDIT_LOG_TARGET, log::Level::Info) {
+        return;
+    }
+    let mut object = match fields {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    };
+    object.insert("event".to_string(), Value::String(event.to_string()));
+    log::info!(target: AUDIT_LOG_TARGET, "{}", Value::Object(object));
+}
+
+/// 记录一次数据库变更
+pub(crate) fn log_db_write(op: &str, mut fields: Value) {
+    if let Value::Object(map) = &mut fields {
+        map.insert("op".to_string(), Value::String(op.to_string()));
+    }
+    log_event("db_write", fields);
+}
+
+#[cfg(test)]
+pub(crate) mod capture {
+    //! 测试用日志捕获器，收集所有审计事件
+
+    use std::sync::{Mutex, OnceLock};
+
+    use super::AUDIT_LOG_TARGET;
+
+    struct CaptureLogger {
+        events: Mutex<Vec<serde_json::Value>>,
+    }
+
+    impl log::Log for CaptureLogger {
+        fn enabled(&self, metadata: &log::Metadata) -> bool {
+            metadata.target() == AUDIT_LOG_TARGET
+        }
+
+        fn log(&self, record: &log::Record) {
+            if !self.enabled(record.metadata()) {
+                return;
+            }
+            if let Ok(event) = serde_json::from_str(&record.args().to_string()) {
+                if let Ok(mut events) = self.events.lock() {
+                    events.push(event);
+                }
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: OnceLock<CaptureLogger> = OnceLock::new();
+
+    /// 安装捕获器（进程内仅首次生效）
+    pub(crate) fn install() {
+        let logger = LOGGER.get_or_init(|| CaptureLogger {
+            events: Mutex::new(Vec::new()),
+        });
+        if log::set_logger(logger).is_ok() {
+            log::set_max_level(log::LevelFilter::Info);
+        }
+    }
+
+    /// 已捕获的审计事件
+    pub(crate) fn events() -> Vec<serde_json::Value> {
+        LOGGER
+            .get()
+            .and_then(|l| l.events.lock().ok().map(|e| e.clone()))
+            .unwrap_or_default()
+    }
+}
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 use crate::error::AppError;
 
@@ -142,12 +143,27 @@ pub fn get_openclaw_providers_path() -> PathBuf {
 ///
 /// 默认: `~/.cc-switch`
 /// Linux 服务器建议: `~/.config/cc-switch` 或使用默认
+///
+/// 优先级: `--config-dir` > `CCSWITCH_CONFIG_DIR` > `CC_SWITCH_HOME` > `XDG_CONFIG_HOME/cc-switch`（Linux）> `~/.cc-switch`
 pub fn get_app_config_dir() -> PathBuf {
+    // 命令行 --config-dir 覆盖
+    if let Some(dir) = CONFIG_DIR_OVERRIDE.get() {
+        return dir.clone();
+    }
+
     // 支持环境变量覆盖
     if let Ok(dir) = std::env::var("CCSWITCH_CONFIG_DIR") {
         return PathBuf::from(dir);
     }
 
+    // 用户指定的数据目录（CI、容器、多配置场景），数据库与设置一起迁移
+    if let Ok(dir) = std::env::var("CC_SWITCH_HOME") {
+        let trimmed = dir.trim();
+        if !trimmed.is_empty() {
+            return PathBuf::from(trimmed);
+        }
+    }
+
     // XDG Base Directory 规范支持 (Linux)
     #[cfg(target_os = "linux")]
     if let Ok(xdg_config) = std::env::var("XDG_CONFIG_HOME") {
@@ -158,6 +174,14 @@ pub fn get_app_config_dir() -> PathBuf {
     get_home_dir().join(".cc-switch")
 }
 
+/// 命令行指定的配置目录（`--config-dir`）
+static CONFIG_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// 设置本进程使用的配置目录，需在 `AppState::init` 之前调用，仅首次调用生效
+pub fn set_app_config_dir_override(dir: impl Into<PathBuf>) {
+    let _ = CONFIG_DIR_OVERRIDE.set(dir.into());
+}
+
 /// 获取应用配置文件路径
 pub fn get_app_config_path() -> PathBuf {
     get_app_config_dir().join("config.json")
@@ -200,7 +224,7 @@ pub fn write_text_file(path: &Path, data: &str) -> Result<(), AppError> {
     atomic_write(path, data.as_bytes())
 }
 
-/// 原子写入：写入临时文件后 rename 替换，避免半写状态
+/// 原子写入：写入同目录临时文件并 fsync 后 rename 替换，避免半写状态
 pub fn atomic_write(path: &Path, data: &[u8]) -> Result<(), AppError> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
@@ -224,10 +248,14 @@ pub fn atomic_write(path: &Path, data: &[u8]) -> Result<(), AppError> {
     let mut tmp = parent.to_path_buf();
     tmp.push(format!("{file_name}.tmp.{ts}"));
 
-    {
-        let mut f = fs::File::create(&tmp).map_err(|e| AppError::io(&tmp, e))?;
-        f.write_all(data).map_err(|e| AppError::io(&tmp, e))?;
-        f.flush().map_err(|e| AppError::io(&tmp, e))?;
+    let written = fs::File::create(&tmp).and_then(|mut f| {
+        f.write_all(data)?;
+        f.flush()?;
+        f.sync_all()
+    });
+    if let Err(e) = written {
+        let _ = fs::remove_file(&tmp);
+        return Err(AppError::io(&tmp, e));
     }
 
     // Unix: 保留原文件权限
@@ -241,10 +269,19 @@ pub fn atomic_write(path: &Path, data: &[u8]) -> Result<(), AppError> {
     }
 
     // 原子替换
-    fs::rename(&tmp, path).map_err(|e| AppError::IoContext {
-        context: format!("原子替换失败: {} -> {}", tmp.display(), path.display()),
-        source: e,
-    })?;
+    if let Err(e) = fs::rename(&tmp, path) {
+        let _ = fs::remove_file(&tmp);
+        return Err(AppError::IoContext {
+            context: format!("原子替换失败: {} -> {}", tmp.display(), path.display()),
+            source: e,
+        });
+    }
+
+    // Unix: 同步父目录，确保 rename 落盘
+    #[cfg(unix)]
+    if let Ok(dir) = fs::File::open(parent) {
+        let _ = dir.sync_all();
+    }
 
     Ok(())
 }
@@ -263,8 +300,25 @@ pub fn sanitize_name(name: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::isolated_home;
+    use serial_test::serial;
     use tempfile::tempdir;
 
+    #[test]
+    #[serial]
+    fn test_cc_switch_home_overrides_app_config_dir() {
+        let _home = isolated_home();
+        let data = tempdir().unwrap();
+        std::env::remove_var("CCSWITCH_CONFIG_DIR");
+        std::env::set_var("CC_SWITCH_HOME", data.path());
+
+        assert_eq!(get_app_config_dir(), data.path());
+        assert_eq!(get_database_path(), data.path().join("cc-switch.db"));
+        assert_eq!(get_app_config_path(), data.path().join("config.json"));
+
+        std::env::remove_var("CC_SWITCH_HOME");
+    }
+
     #[test]
     fn test_atomic_write() {
         let dir = tempdir().unwrap();
@@ -277,6 +331,48 @@ mod tests {
         assert_eq!(content, data);
     }
 
+    #[test]
+    fn test_atomic_write_replaces_without_leftovers() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+        fs::write(&path, r#"{"old": true}"#).unwrap();
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+        }
+
+        let data = serde_json::json!({"env": {"KEY": "x".repeat(64 * 1024)}});
+        write_json_file(&path, &data).unwrap();
+
+        let content: serde_json::Value = read_json_file(&path).unwrap();
+        assert_eq!(content, data);
+        let entries: Vec<_> = fs::read_dir(dir.path()).unwrap().collect();
+        assert_eq!(entries.len(), 1, "临时文件应已被 rename 或清理");
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode() & 0o777;
+            assert_eq!(mode, 0o600);
+        }
+    }
+
+    #[test]
+    fn test_atomic_write_failure_keeps_target_intact() {
+        let dir = tempdir().unwrap();
+        // 目标是非空目录，rename 必然失败
+        let target = dir.path().join("settings.json");
+        fs::create_dir(&target).unwrap();
+        fs::write(target.join("keep"), "original").unwrap();
+
+        assert!(atomic_write(&target, b"new content").is_err());
+        assert_eq!(fs::read_to_string(target.join("keep")).unwrap(), "original");
+        let entries: Vec<_> = fs::read_dir(dir.path()).unwrap().collect();
+        assert_eq!(entries.len(), 1, "失败时不应遗留临时文件");
+    }
+
     #[test]
     fn test_sanitize_name() {
         assert_eq!(sanitize_name("Test/Name"), "test-name");
@@ -19,6 +19,7 @@ pub struct FailoverQueueItem {
 impl Database {
     /// 获取故障转移队列（按 sort_index 排序）
     pub fn get_failover_queue(&self, app_type: &str) -> Result<Vec<FailoverQueueItem>, AppError> {
+        let key = self.provider_key(app_type);
         let conn = lock_conn!(self.conn);
 
         let mut stmt = conn
@@ -31,7 +32,7 @@ impl Database {
             .map_err(|e| AppError::Database(e.to_string()))?;
 
         let items = stmt
-            .query_map([app_type], |row| {
+            .query_map([&key], |row| {
                 Ok(FailoverQueueItem {
                     provider_id: row.get(0)?,
                     provider_name: row.get(1)?,
@@ -59,11 +60,12 @@ impl Database {
 
     /// 添加供应商到故障转移队列
     pub fn add_to_failover_queue(&self, app_type: &str, provider_id: &str) -> Result<(), AppError> {
+        let key = self.provider_key(app_type);
         let conn = lock_conn!(self.conn);
 
         conn.execute(
             "UPDATE providers SET in_failover_queue = 1 WHERE id = ?1 AND app_type = ?2",
-            rusqlite::params![provider_id, app_type],
+            rusqlite::params![provider_id, key],
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
 
@@ -76,18 +78,19 @@ impl Database {
         app_type: &str,
         provider_id: &str,
     ) -> Result<(), AppError> {
+        let key = self.provider_key(app_type);
         let conn = lock_conn!(self.conn);
 
         conn.execute(
             "UPDATE providers SET in_failover_queue = 0 WHERE id = ?1 AND app_type = ?2",
-            rusqlite::params![provider_id, app_type],
+            rusqlite::params![provider_id, key],
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
 
         // 清除该供应商的健康状态
         let _ = conn.execute(
             "DELETE FROM provider_health WHERE provider_id = ?1 AND app_type = ?2",
-            rusqlite::params![provider_id, app_type],
+            rusqlite::params![provider_id, key],
         );
 
         log::info!("已从故障转移队列移除供应商 {provider_id} ({app_type})");
@@ -97,11 +100,12 @@ impl Database {
 
     /// 清空故障转移队列
     pub fn clear_failover_queue(&self, app_type: &str) -> Result<(), AppError> {
+        let key = self.provider_key(app_type);
         let conn = lock_conn!(self.conn);
 
         conn.execute(
             "UPDATE providers SET in_failover_queue = 0 WHERE app_type = ?1",
-            [app_type],
+            [&key],
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
 
@@ -114,12 +118,13 @@ impl Database {
         app_type: &str,
         provider_id: &str,
     ) -> Result<bool, AppError> {
+        let key = self.provider_key(app_type);
         let conn = lock_conn!(self.conn);
 
         let in_queue: bool = conn
             .query_row(
                 "SELECT in_failover_queue FROM providers WHERE id = ?1 AND app_type = ?2",
-                rusqlite::params![provider_id, app_type],
+                rusqlite::params![provider_id, key],
                 |row| row.get(0),
             )
             .unwrap_or(false);
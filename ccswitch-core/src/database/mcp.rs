@@ -1,6 +1,7 @@
 //! MCP 服务器数据库操作模块
 
 use crate::app_config::McpApps;
+use crate::audit;
 use crate::database::{lock_conn, to_json_string, Database};
 use crate::error::AppError;
 use crate::mcp::McpServer;
@@ -17,7 +18,7 @@ impl Database {
                 r#"
                 SELECT id, name, server_config, description, homepage, docs, tags,
                        enabled_claude, enabled_codex, enabled_gemini, enabled_opencode,
-                       created_at, sort_index
+                       created_at, sort_index, enabled
                 FROM mcp_servers
                 ORDER BY sort_index ASC, created_at ASC
                 "#,
@@ -39,6 +40,7 @@ impl Database {
                 let enabled_opencode: bool = row.get::<_, i64>(10)? != 0;
                 let created_at: Option<i64> = row.get(11)?;
                 let sort_index: Option<usize> = row.get::<_, Option<i64>>(12)?.map(|v| v as usize);
+                let enabled: bool = row.get::<_, i64>(13)? != 0;
 
                 let server_config: serde_json::Value =
                     serde_json::from_str(&server_config_str).unwrap_or_default();
@@ -58,6 +60,7 @@ impl Database {
                             gemini: enabled_gemini,
                             opencode: enabled_opencode,
                         },
+                        enabled,
                         description,
                         homepage,
                         docs,
@@ -96,8 +99,8 @@ impl Database {
             INSERT OR REPLACE INTO mcp_servers
             (id, name, server_config, description, homepage, docs, tags,
              enabled_claude, enabled_codex, enabled_gemini, enabled_opencode,
-             created_at, sort_index)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+             created_at, sort_index, enabled)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
             rusqlite::params![
                 server.id,
@@ -113,10 +116,12 @@ impl Database {
                 server.apps.opencode as i64,
                 server.created_at,
                 server.sort_index.map(|v| v as i64),
+                server.enabled as i64,
             ],
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
 
+        audit::log_db_write("save_mcp_server", serde_json::json!({"id": server.id}));
         Ok(())
     }
 
@@ -127,6 +132,7 @@ impl Database {
         conn.execute("DELETE FROM mcp_servers WHERE id = ?", rusqlite::params![id])
             .map_err(|e| AppError::Database(e.to_string()))?;
 
+        audit::log_db_write("delete_mcp_server", serde_json::json!({"id": id}));
         Ok(())
     }
 
@@ -150,6 +156,21 @@ impl Database {
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
 
+        audit::log_db_write("update_mcp_server_apps", serde_json::json!({"id": id}));
+        Ok(())
+    }
+
+    /// 更新 MCP 服务器的全局启用状态
+    pub fn set_mcp_server_enabled(&self, id: &str, enabled: bool) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+
+        conn.execute(
+            "UPDATE mcp_servers SET enabled = ? WHERE id = ?",
+            rusqlite::params![enabled as i64, id],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        audit::log_db_write("set_mcp_server_enabled", serde_json::json!({"id": id, "enabled": enabled}));
         Ok(())
     }
 
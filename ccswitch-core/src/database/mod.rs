@@ -10,26 +10,56 @@
 mod failover;
 mod mcp;
 pub(crate) mod migration;
+mod model_pricing;
 mod prompt;
 mod provider_endpoints;
+mod provider_test_results;
 mod schema;
 mod skill;
 mod stream_check_db;
 mod usage_rollup;
 
 pub use failover::FailoverQueueItem;
+pub use model_pricing::ModelPricing;
+pub use provider_test_results::ProviderTestResult;
 
+use crate::audit;
 use crate::config::get_app_config_dir;
 use crate::error::AppError;
+use crate::profile::{active_profile, scoped_app_key};
 use crate::provider::Provider;
 use indexmap::IndexMap;
-use rusqlite::Connection;
+use rusqlite::{Connection, ErrorCode};
 use serde::Serialize;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
+use std::time::Duration;
 
 /// 当前 Schema 版本号
 #[allow(dead_code)]
-pub(crate) const SCHEMA_VERSION: i32 = 6;
+pub(crate) const SCHEMA_VERSION: i32 = 12;
+
+/// 数据库被其他进程锁定时的重试次数
+const BUSY_RETRIES: u32 = 3;
+
+/// 锁定重试的初始退避时间，之后每次翻倍
+const BUSY_RETRY_DELAY: Duration = Duration::from_millis(200);
+
+/// 错误是否表示数据库文件损坏
+fn is_corrupt(err: &rusqlite::Error) -> bool {
+    matches!(
+        err.sqlite_error_code(),
+        Some(ErrorCode::DatabaseCorrupt | ErrorCode::NotADatabase)
+    )
+}
+
+/// 错误是否表示数据库被其他连接锁定
+fn is_busy(err: &rusqlite::Error) -> bool {
+    matches!(
+        err.sqlite_error_code(),
+        Some(ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked)
+    )
+}
 
 /// 安全地序列化 JSON
 pub(crate) fn to_json_string<T: Serialize>(value: &T) -> Result<String, AppError> {
@@ -51,6 +81,8 @@ pub(crate) use lock_conn;
 /// 数据库连接封装
 pub struct Database {
     pub(crate) conn: Mutex<Connection>,
+    /// 供应商数据所属的 profile，None 为默认 profile
+    profile: Option<String>,
 }
 
 impl Database {
@@ -58,21 +90,33 @@ impl Database {
     ///
     /// 数据库文件位于 `~/.cc-switch/cc-switch.db`
     pub fn init() -> Result<Self, AppError> {
-        let db_path = get_app_config_dir().join("cc-switch.db");
+        Self::open(&get_app_config_dir().join("cc-switch.db"))
+    }
 
+    /// 打开指定路径的数据库文件（不存在时创建）
+    pub fn open(db_path: &Path) -> Result<Self, AppError> {
         // 确保父目录存在
         if let Some(parent) = db_path.parent() {
             std::fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
         }
 
-        let conn = Connection::open(&db_path).map_err(|e| AppError::Database(e.to_string()))?;
-
-        // 启用外键约束
-        conn.execute("PRAGMA foreign_keys = ON;", [])
-            .map_err(|e| AppError::Database(e.to_string()))?;
+        let conn = match Self::connect(db_path) {
+            Ok(conn) => conn,
+            Err(e) if is_corrupt(&e) => {
+                let quarantined = Self::quarantine(db_path)?;
+                log::warn!(
+                    "数据库文件已损坏（{}），已移至 {} 并创建新数据库",
+                    e,
+                    quarantined.display()
+                );
+                Self::connect(db_path).map_err(|e| Self::open_error(db_path, &e))?
+            }
+            Err(e) => return Err(Self::open_error(db_path, &e)),
+        };
 
         let db = Self {
             conn: Mutex::new(conn),
+            profile: active_profile(),
         };
         db.create_tables()?;
         db.apply_schema_migrations()?;
@@ -81,6 +125,57 @@ impl Database {
         Ok(db)
     }
 
+    /// 打开连接并确认文件可读，数据库被锁定时按指数退避重试
+    fn connect(db_path: &Path) -> Result<Connection, rusqlite::Error> {
+        let mut delay = BUSY_RETRY_DELAY;
+        let mut remaining = BUSY_RETRIES;
+        loop {
+            let result = Connection::open(db_path).and_then(|conn| {
+                conn.busy_timeout(BUSY_RETRY_DELAY)?;
+                // 读取文件头，损坏或非数据库文件会在此报错
+                conn.query_row("PRAGMA schema_version", [], |row| row.get::<_, i64>(0))?;
+                // 启用外键约束
+                conn.execute("PRAGMA foreign_keys = ON;", [])?;
+                Ok(conn)
+            });
+            match result {
+                Err(e) if remaining > 0 && is_busy(&e) => {
+                    log::debug!("数据库被锁定，{}ms 后重试", delay.as_millis());
+                    std::thread::sleep(delay);
+                    delay *= 2;
+                    remaining -= 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// 将损坏的数据库文件（及其日志文件）重命名为带时间戳的隔离文件，返回新路径
+    fn quarantine(db_path: &Path) -> Result<PathBuf, AppError> {
+        let stamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        let target = PathBuf::from(format!("{}.corrupt-{}", db_path.display(), stamp));
+        std::fs::rename(db_path, &target).map_err(|e| AppError::io(db_path, e))?;
+
+        for suffix in ["-journal", "-wal", "-shm"] {
+            let sidecar = PathBuf::from(format!("{}{}", db_path.display(), suffix));
+            if sidecar.exists() {
+                let dest = PathBuf::from(format!("{}{}", target.display(), suffix));
+                std::fs::rename(&sidecar, &dest).map_err(|e| AppError::io(&sidecar, e))?;
+            }
+        }
+        Ok(target)
+    }
+
+    /// 打开失败时给出包含恢复建议的错误
+    fn open_error(db_path: &Path, err: &rusqlite::Error) -> AppError {
+        let hint = if is_busy(err) {
+            "数据库正被其他 cc-switch 进程占用，请关闭其他进程后重试"
+        } else {
+            "请检查文件权限；如文件已损坏，可将其移走后重新运行以创建新数据库"
+        };
+        AppError::Database(format!("无法打开 {}: {}（{}）", db_path.display(), err, hint))
+    }
+
     /// 创建内存数据库（用于测试）
     pub fn memory() -> Result<Self, AppError> {
         let conn = Connection::open_in_memory().map_err(|e| AppError::Database(e.to_string()))?;
@@ -90,6 +185,7 @@ impl Database {
 
         let db = Self {
             conn: Mutex::new(conn),
+            profile: active_profile(),
         };
         db.create_tables()?;
         db.ensure_model_pricing_seeded()?;
@@ -97,16 +193,69 @@ impl Database {
         Ok(db)
     }
 
+    /// 指定供应商数据所属的 profile（None 为默认 profile）
+    pub fn with_profile(mut self, profile: Option<String>) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// 供应商数据所属的 profile（默认 profile 返回 None）
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    /// 当前 profile 下供应商相关表使用的 app_type 键
+    pub(crate) fn provider_key(&self, app_type: &str) -> String {
+        scoped_app_key(self.profile(), app_type)
+    }
+
+    /// 列出数据库中存有供应商的非默认 profile
+    pub fn list_provider_profiles(&self) -> Result<Vec<String>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare("SELECT DISTINCT app_type FROM providers WHERE instr(app_type, ':') > 0")
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let keys = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(|e| AppError::Database(e.to_string()))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut profiles: Vec<String> = keys
+            .iter()
+            .filter_map(|key| key.split_once(':').map(|(profile, _)| profile.to_string()))
+            .collect();
+        profiles.sort();
+        profiles.dedup();
+        Ok(profiles)
+    }
+
+    /// 删除指定 profile 下的全部供应商，返回删除的数量
+    pub fn delete_profile_providers(&self, profile: &str) -> Result<usize, AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "DELETE FROM provider_test_results WHERE substr(app_type, 1, length(?1) + 1) = ?1 || ':'",
+            [profile],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "DELETE FROM providers WHERE substr(app_type, 1, length(?1) + 1) = ?1 || ':'",
+            [profile],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+
     // ===== Provider DAO =====
 
     /// 获取所有供应商
     pub fn get_all_providers(&self, app_type: &str) -> Result<IndexMap<String, Provider>, AppError> {
+        let key = self.provider_key(app_type);
         let conn = lock_conn!(self.conn);
         let mut stmt = conn
             .prepare(
                 r#"
                 SELECT id, name, settings_config, website_url, category, created_at,
-                       sort_index, notes, meta, icon, icon_color, in_failover_queue
+                       sort_index, notes, meta, icon, icon_color, in_failover_queue, last_used_at
                 FROM providers
                 WHERE app_type = ?
                 ORDER BY sort_index ASC, created_at ASC
@@ -115,7 +264,7 @@ impl Database {
             .map_err(|e| AppError::Database(e.to_string()))?;
 
         let providers = stmt
-            .query_map([app_type], |row| {
+            .query_map([&key], |row| {
                 let id: String = row.get(0)?;
                 let name: String = row.get(1)?;
                 let settings_config_str: String = row.get(2)?;
@@ -128,6 +277,7 @@ impl Database {
                 let icon: Option<String> = row.get(9)?;
                 let icon_color: Option<String> = row.get(10)?;
                 let in_failover_queue: bool = row.get::<_, i64>(11)? != 0;
+                let last_used_at: Option<i64> = row.get(12)?;
 
                 Ok((
                     id.clone(),
@@ -145,6 +295,7 @@ impl Database {
                         icon,
                         icon_color,
                         in_failover_queue,
+                        last_used_at,
                     },
                 ))
             })
@@ -161,6 +312,7 @@ impl Database {
 
     /// 保存供应商
     pub fn save_provider(&self, app_type: &str, provider: &Provider) -> Result<(), AppError> {
+        let key = self.provider_key(app_type);
         let conn = lock_conn!(self.conn);
 
         let settings_config_str = to_json_string(&provider.settings_config)?;
@@ -174,12 +326,12 @@ impl Database {
             r#"
             INSERT OR REPLACE INTO providers
             (id, app_type, name, settings_config, website_url, category, created_at,
-             sort_index, notes, meta, icon, icon_color, in_failover_queue)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+             sort_index, notes, meta, icon, icon_color, in_failover_queue, last_used_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
             rusqlite::params![
                 provider.id,
-                app_type,
+                key,
                 provider.name,
                 settings_config_str,
                 provider.website_url,
@@ -191,34 +343,45 @@ impl Database {
                 provider.icon,
                 provider.icon_color,
                 provider.in_failover_queue as i64,
+                provider.last_used_at,
             ],
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
 
+        audit::log_db_write("save_provider", serde_json::json!({"app": app_type, "provider": provider.id}));
         Ok(())
     }
 
     /// 删除供应商
     pub fn delete_provider(&self, app_type: &str, id: &str) -> Result<(), AppError> {
+        let key = self.provider_key(app_type);
         let conn = lock_conn!(self.conn);
 
         conn.execute(
             "DELETE FROM providers WHERE id = ? AND app_type = ?",
-            rusqlite::params![id, app_type],
+            rusqlite::params![id, key],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        // 测试结果不使用外键：保存供应商的 INSERT OR REPLACE 会触发级联删除
+        conn.execute(
+            "DELETE FROM provider_test_results WHERE provider_id = ? AND app_type = ?",
+            rusqlite::params![id, key],
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
 
+        audit::log_db_write("delete_provider", serde_json::json!({"app": app_type, "provider": id}));
         Ok(())
     }
 
     /// 获取当前供应商 ID
     pub fn get_current_provider(&self, app_type: &str) -> Result<Option<String>, AppError> {
+        let key = self.provider_key(app_type);
         let conn = lock_conn!(self.conn);
 
         let result: Option<String> = conn
             .query_row(
                 "SELECT id FROM providers WHERE app_type = ? AND is_current = 1",
-                [app_type],
+                [&key],
                 |row| row.get(0),
             )
             .ok();
@@ -228,33 +391,68 @@ impl Database {
 
     /// 设置当前供应商
     pub fn set_current_provider(&self, app_type: &str, id: &str) -> Result<(), AppError> {
-        let conn = lock_conn!(self.conn);
+        let key = self.provider_key(app_type);
+        let mut conn = lock_conn!(self.conn);
+        // 使用保存点：任一步失败都不会留下“没有当前供应商”的中间状态，且可嵌套在事务中
+        let sp = conn.savepoint().map_err(|e| AppError::Database(e.to_string()))?;
 
         // 先清除所有 is_current
-        conn.execute(
+        sp.execute(
             "UPDATE providers SET is_current = 0 WHERE app_type = ?",
-            [app_type],
+            [&key],
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
 
         // 设置新的当前供应商
-        conn.execute(
+        sp.execute(
             "UPDATE providers SET is_current = 1 WHERE id = ? AND app_type = ?",
-            rusqlite::params![id, app_type],
+            rusqlite::params![id, key],
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
+        sp.commit().map_err(|e| AppError::Database(e.to_string()))?;
 
+        audit::log_db_write("set_current_provider", serde_json::json!({"app": app_type, "provider": id}));
+        Ok(())
+    }
+
+    /// 记录供应商最近一次被切换的时间（毫秒时间戳）
+    pub fn set_provider_last_used(&self, app_type: &str, id: &str, timestamp: i64) -> Result<(), AppError> {
+        let key = self.provider_key(app_type);
+        let conn = lock_conn!(self.conn);
+
+        conn.execute(
+            "UPDATE providers SET last_used_at = ? WHERE id = ? AND app_type = ?",
+            rusqlite::params![timestamp, id, key],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// 清除当前供应商标记
+    pub fn clear_current_provider(&self, app_type: &str) -> Result<(), AppError> {
+        let key = self.provider_key(app_type);
+        let conn = lock_conn!(self.conn);
+
+        conn.execute(
+            "UPDATE providers SET is_current = 0 WHERE app_type = ?",
+            [&key],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        audit::log_db_write("clear_current_provider", serde_json::json!({"app": app_type}));
         Ok(())
     }
 
     /// 获取供应商数量
     pub fn get_provider_count(&self, app_type: &str) -> Result<usize, AppError> {
+        let key = self.provider_key(app_type);
         let conn = lock_conn!(self.conn);
 
         let count: i64 = conn
             .query_row(
                 "SELECT COUNT(*) FROM providers WHERE app_type = ?",
-                [app_type],
+                [&key],
                 |row| row.get(0),
             )
             .map_err(|e| AppError::Database(e.to_string()))?;
@@ -289,6 +487,7 @@ impl Database {
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
 
+        audit::log_db_write("set_setting", serde_json::json!({"key": key}));
         Ok(())
     }
 
@@ -299,8 +498,115 @@ impl Database {
         conn.execute("DELETE FROM settings WHERE key = ?", [key])
             .map_err(|e| AppError::Database(e.to_string()))?;
 
+        audit::log_db_write("delete_setting", serde_json::json!({"key": key}));
         Ok(())
     }
+
+    /// 获取布尔设置（`true`/`false`，兼容 `1`/`0`）
+    pub fn get_setting_bool(&self, key: &str) -> Result<Option<bool>, AppError> {
+        self.get_setting(key)?
+            .map(|value| match value.trim() {
+                "true" | "1" => Ok(true),
+                "false" | "0" => Ok(false),
+                _ => Err(malformed_setting(key, &value, "布尔值")),
+            })
+            .transpose()
+    }
+
+    /// 设置布尔值
+    pub fn set_setting_bool(&self, key: &str, value: bool) -> Result<(), AppError> {
+        self.set_setting(key, if value { "true" } else { "false" })
+    }
+
+    /// 获取非负整数设置
+    pub fn get_setting_u64(&self, key: &str) -> Result<Option<u64>, AppError> {
+        self.get_setting_parsed(key, "非负整数")
+    }
+
+    /// 设置非负整数
+    pub fn set_setting_u64(&self, key: &str, value: u64) -> Result<(), AppError> {
+        self.set_setting(key, &value.to_string())
+    }
+
+    /// 获取浮点数设置
+    pub fn get_setting_f64(&self, key: &str) -> Result<Option<f64>, AppError> {
+        self.get_setting_parsed(key, "数字")
+    }
+
+    /// 设置浮点数（拒绝 NaN 与无穷大）
+    pub fn set_setting_f64(&self, key: &str, value: f64) -> Result<(), AppError> {
+        if !value.is_finite() {
+            return Err(AppError::InvalidInput(format!("设置 {} 的值必须是有限数字", key)));
+        }
+        self.set_setting(key, &value.to_string())
+    }
+
+    fn get_setting_parsed<T: std::str::FromStr>(
+        &self,
+        key: &str,
+        expected: &str,
+    ) -> Result<Option<T>, AppError> {
+        self.get_setting(key)?
+            .map(|value| {
+                value
+                    .trim()
+                    .parse()
+                    .map_err(|_| malformed_setting(key, &value, expected))
+            })
+            .transpose()
+    }
+
+    /// 获取所有设置（按键名排序）
+    pub fn get_all_settings(&self) -> Result<IndexMap<String, String>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare("SELECT key, value FROM settings ORDER BY key")
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut result = IndexMap::new();
+        for row in rows {
+            let (key, value) = row.map_err(|e| AppError::Database(e.to_string()))?;
+            result.insert(key, value);
+        }
+
+        Ok(result)
+    }
+
+    // ===== 事务 =====
+
+    /// 在同一事务中执行多个 DAO 操作，任一步失败则整体回滚
+    pub fn with_transaction<T>(
+        &self,
+        f: impl FnOnce(&Self) -> Result<T, AppError>,
+    ) -> Result<T, AppError> {
+        lock_conn!(self.conn)
+            .execute_batch("BEGIN")
+            .map_err(|e| AppError::Database(format!("开启事务失败: {e}")))?;
+
+        match f(self) {
+            Ok(value) => {
+                lock_conn!(self.conn)
+                    .execute_batch("COMMIT")
+                    .map_err(|e| AppError::Database(format!("提交事务失败: {e}")))?;
+                Ok(value)
+            }
+            Err(e) => {
+                if let Ok(conn) = self.conn.lock() {
+                    let _ = conn.execute_batch("ROLLBACK");
+                }
+                Err(e)
+            }
+        }
+    }
+}
+
+/// 设置值格式错误
+fn malformed_setting(key: &str, value: &str, expected: &str) -> AppError {
+    AppError::Config(format!("设置 {} 的值 {:?} 不是有效的{}", key, value, expected))
 }
 
 #[cfg(test)]
@@ -314,6 +620,29 @@ mod tests {
         assert!(db.get_all_providers("claude").unwrap().is_empty());
     }
 
+    #[test]
+    fn test_corrupt_database_is_quarantined_and_recreated() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cc-switch.db");
+        std::fs::write(&path, vec![0x5a; 4096]).unwrap();
+
+        let db = Database::open(&path).unwrap();
+        db.save_provider("claude", &Provider::new("p1", "P1", json!({}))).unwrap();
+        drop(db);
+
+        let quarantined: Vec<PathBuf> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .filter(|p| p.to_string_lossy().contains("cc-switch.db.corrupt-"))
+            .collect();
+        assert_eq!(quarantined.len(), 1);
+        assert_eq!(std::fs::read(&quarantined[0]).unwrap(), vec![0x5a; 4096]);
+
+        // 新数据库可正常使用
+        let db = Database::open(&path).unwrap();
+        assert!(db.get_all_providers("claude").unwrap().contains_key("p1"));
+    }
+
     #[test]
     fn test_provider_crud() {
         let db = Database::memory().unwrap();
@@ -363,4 +692,48 @@ mod tests {
         db.delete_setting("key1").unwrap();
         assert_eq!(db.get_setting("key1").unwrap(), None);
     }
+
+    #[test]
+    fn test_typed_settings() {
+        let db = Database::memory().unwrap();
+        assert_eq!(db.get_setting_bool("flag").unwrap(), None);
+
+        db.set_setting_bool("flag", true).unwrap();
+        assert_eq!(db.get_setting_bool("flag").unwrap(), Some(true));
+        db.set_setting_bool("flag", false).unwrap();
+        assert_eq!(db.get_setting("flag").unwrap().as_deref(), Some("false"));
+        assert_eq!(db.get_setting_bool("flag").unwrap(), Some(false));
+
+        db.set_setting_u64("count", 42).unwrap();
+        assert_eq!(db.get_setting_u64("count").unwrap(), Some(42));
+        db.set_setting_f64("limit", 12.5).unwrap();
+        assert_eq!(db.get_setting_f64("limit").unwrap(), Some(12.5));
+        assert!(db.set_setting_f64("limit", f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_malformed_typed_setting_is_error() {
+        let db = Database::memory().unwrap();
+        db.set_setting("flag", "maybe").unwrap();
+        db.set_setting("count", "-3").unwrap();
+
+        assert!(matches!(db.get_setting_bool("flag"), Err(AppError::Config(_))));
+        assert!(matches!(db.get_setting_u64("count"), Err(AppError::Config(_))));
+        assert!(matches!(db.get_setting_f64("flag"), Err(AppError::Config(_))));
+    }
+
+    #[test]
+    fn test_transaction_rolls_back_on_error() {
+        let db = Database::memory().unwrap();
+
+        let result: Result<(), AppError> = db.with_transaction(|db| {
+            db.save_provider("claude", &Provider::new("p1", "Provider 1", json!({})))?;
+            Err(AppError::InvalidInput("boom".to_string()))
+        });
+        assert!(result.is_err());
+        assert!(db.get_all_providers("claude").unwrap().is_empty());
+
+        db.with_transaction(|db| db.set_setting("key1", "value1")).unwrap();
+        assert_eq!(db.get_all_settings().unwrap().get("key1").map(String::as_str), Some("value1"));
+    }
 }
@@ -0,0 +1,100 @@
+//! Model Pricing DAO
+//!
+//! 读取 model_pricing 表中的模型定价（每百万 token 美元价格）
+
+use rusqlite::OptionalExtension;
+use serde::Serialize;
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+
+/// 模型定价
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelPricing {
+    pub model_id: String,
+    pub display_name: String,
+    pub input_cost_per_million: f64,
+    pub output_cost_per_million: f64,
+}
+
+impl ModelPricing {
+    /// 按 token 用量估算费用（美元）
+    pub fn estimate_cost(&self, input_tokens: u64, output_tokens: u64) -> f64 {
+        (input_tokens as f64 * self.input_cost_per_million
+            + output_tokens as f64 * self.output_cost_per_million)
+            / 1_000_000.0
+    }
+}
+
+impl Database {
+    /// 查找模型定价
+    ///
+    /// 先精确匹配，再匹配以已知模型 ID 开头的最长前缀（如带日期后缀的模型名）。
+    /// 前缀按字符逐一比较，模型 ID 中的 `_`/`%` 不作为通配符。
+    pub fn get_model_pricing(&self, model: &str) -> Result<Option<ModelPricing>, AppError> {
+        let conn = lock_conn!(self.conn);
+
+        let row = conn
+            .query_row(
+                "SELECT model_id, display_name, input_cost_per_million, output_cost_per_million
+                 FROM model_pricing
+                 WHERE model_id = ?1 OR substr(?1, 1, length(model_id)) = model_id
+                 ORDER BY model_id = ?1 DESC, length(model_id) DESC
+                 LIMIT 1",
+                [model],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, String>(3)?,
+                    ))
+                },
+            )
+            .optional()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(row.map(|(model_id, display_name, input, output)| ModelPricing {
+            model_id,
+            display_name,
+            input_cost_per_million: input.parse().unwrap_or(0.0),
+            output_cost_per_million: output.parse().unwrap_or(0.0),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_model_pricing() {
+        let db = Database::memory().unwrap();
+
+        let pricing = db.get_model_pricing("claude-opus-4-6-20260206").unwrap().unwrap();
+        assert_eq!(pricing.input_cost_per_million, 5.0);
+        assert_eq!(pricing.output_cost_per_million, 25.0);
+        assert!((pricing.estimate_cost(1_000_000, 100_000) - 7.5).abs() < 1e-9);
+
+        assert!(db.get_model_pricing("unknown-model").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_get_model_pricing_prefix_is_literal() {
+        let db = Database::memory().unwrap();
+        db.conn
+            .lock()
+            .unwrap()
+            .execute(
+                "INSERT INTO model_pricing (model_id, display_name, input_cost_per_million, output_cost_per_million)
+                 VALUES ('acme_%', 'Acme', '1', '2')",
+                [],
+            )
+            .unwrap();
+
+        assert!(db.get_model_pricing("acme_%-2026").unwrap().is_some());
+        assert!(db.get_model_pricing("acmeX-2026").unwrap().is_none());
+        assert!(db.get_model_pricing("ACME_%-2026").unwrap().is_none());
+    }
+}
@@ -1,6 +1,7 @@
 //! Prompt 数据库操作模块
 
-use crate::database::{lock_conn, Database};
+use crate::audit;
+use crate::database::{lock_conn, to_json_string, Database};
 use crate::error::AppError;
 use crate::prompt::Prompt;
 use indexmap::IndexMap;
@@ -14,7 +15,7 @@ impl Database {
         let mut stmt = conn
             .prepare(
                 r#"
-                SELECT id, name, content, description, enabled, created_at, updated_at
+                SELECT id, name, content, description, enabled, created_at, updated_at, variables
                 FROM prompts
                 WHERE app_type = ?
                 ORDER BY created_at ASC
@@ -31,6 +32,10 @@ impl Database {
                 let enabled: bool = row.get::<_, i64>(4)? != 0;
                 let created_at: Option<i64> = row.get(5)?;
                 let updated_at: Option<i64> = row.get(6)?;
+                let variables_str: Option<String> = row.get(7)?;
+                let variables: IndexMap<String, String> = variables_str
+                    .and_then(|s| serde_json::from_str(&s).ok())
+                    .unwrap_or_default();
 
                 Ok((
                     id.clone(),
@@ -40,6 +45,7 @@ impl Database {
                         content,
                         description,
                         enabled,
+                        variables,
                         created_at,
                         updated_at,
                     },
@@ -64,13 +70,14 @@ impl Database {
 
     /// 保存 Prompt
     pub fn save_prompt(&self, app_type: &str, prompt: &Prompt) -> Result<(), AppError> {
+        let variables_str = to_json_string(&prompt.variables)?;
         let conn = lock_conn!(self.conn);
 
         conn.execute(
             r#"
             INSERT OR REPLACE INTO prompts
-            (id, app_type, name, content, description, enabled, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            (id, app_type, name, content, description, enabled, created_at, updated_at, variables)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
             rusqlite::params![
                 prompt.id,
@@ -81,10 +88,12 @@ impl Database {
                 prompt.enabled as i64,
                 prompt.created_at,
                 prompt.updated_at,
+                variables_str,
             ],
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
 
+        audit::log_db_write("save_prompt", serde_json::json!({"app": app_type, "id": prompt.id}));
         Ok(())
     }
 
@@ -98,6 +107,7 @@ impl Database {
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
 
+        audit::log_db_write("delete_prompt", serde_json::json!({"app": app_type, "id": id}));
         Ok(())
     }
 
@@ -118,6 +128,7 @@ impl Database {
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
 
+        audit::log_db_write("update_prompt_enabled", serde_json::json!({"app": app_type, "id": id, "enabled": enabled}));
         Ok(())
     }
 
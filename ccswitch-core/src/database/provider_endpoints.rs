@@ -14,13 +14,14 @@ impl Database {
         app_type: &str,
         url: &str,
     ) -> Result<(), AppError> {
+        let key = self.provider_key(app_type);
         let conn = lock_conn!(self.conn);
         let now = chrono::Utc::now().timestamp();
 
         conn.execute(
             "INSERT INTO provider_endpoints (provider_id, app_type, url, added_at)
              VALUES (?1, ?2, ?3, ?4)",
-            rusqlite::params![provider_id, app_type, url, now],
+            rusqlite::params![provider_id, key, url, now],
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
 
@@ -33,6 +34,7 @@ impl Database {
         provider_id: &str,
         app_type: &str,
     ) -> Result<Vec<String>, AppError> {
+        let key = self.provider_key(app_type);
         let conn = lock_conn!(self.conn);
 
         let mut stmt = conn
@@ -44,7 +46,7 @@ impl Database {
             .map_err(|e| AppError::Database(e.to_string()))?;
 
         let urls = stmt
-            .query_map(rusqlite::params![provider_id, app_type], |row| {
+            .query_map(rusqlite::params![provider_id, key], |row| {
                 row.get(0)
             })
             .map_err(|e| AppError::Database(e.to_string()))?
@@ -61,12 +63,13 @@ impl Database {
         app_type: &str,
         url: &str,
     ) -> Result<(), AppError> {
+        let key = self.provider_key(app_type);
         let conn = lock_conn!(self.conn);
 
         conn.execute(
             "DELETE FROM provider_endpoints
              WHERE provider_id = ?1 AND app_type = ?2 AND url = ?3",
-            rusqlite::params![provider_id, app_type, url],
+            rusqlite::params![provider_id, key, url],
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
 
@@ -79,11 +82,12 @@ impl Database {
         provider_id: &str,
         app_type: &str,
     ) -> Result<(), AppError> {
+        let key = self.provider_key(app_type);
         let conn = lock_conn!(self.conn);
 
         conn.execute(
             "DELETE FROM provider_endpoints WHERE provider_id = ?1 AND app_type = ?2",
-            rusqlite::params![provider_id, app_type],
+            rusqlite::params![provider_id, key],
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
 
@@ -0,0 +1,136 @@
+//! 供应商测试结果 DAO
+//!
+//! 保存 `cc-switch test --save-result` 的测试结果，供 `list --detail` 显示最近一次状态。
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+
+/// 单次 API 测试结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProviderTestResult {
+    pub provider_id: String,
+    pub ok: bool,
+    /// 请求耗时，请求未完成时为 None
+    pub latency_ms: Option<u64>,
+    pub message: String,
+    /// 测试时间（毫秒时间戳）
+    pub tested_at: i64,
+}
+
+impl Database {
+    /// 保存一条测试结果
+    pub fn save_provider_test_result(
+        &self,
+        app_type: &str,
+        result: &ProviderTestResult,
+    ) -> Result<i64, AppError> {
+        let key = self.provider_key(app_type);
+        let conn = lock_conn!(self.conn);
+
+        conn.execute(
+            "INSERT INTO provider_test_results
+             (provider_id, app_type, ok, latency_ms, message, tested_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![
+                result.provider_id,
+                key,
+                result.ok,
+                result.latency_ms.map(|ms| ms as i64),
+                result.message,
+                result.tested_at,
+            ],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// 获取应用下每个供应商最近一次的测试结果（供应商 ID -> 结果）
+    pub fn get_latest_test_results(
+        &self,
+        app_type: &str,
+    ) -> Result<IndexMap<String, ProviderTestResult>, AppError> {
+        let key = self.provider_key(app_type);
+        let conn = lock_conn!(self.conn);
+
+        // 同一毫秒内的多条结果取最后插入的一条
+        let mut stmt = conn
+            .prepare(
+                "SELECT provider_id, ok, latency_ms, message, tested_at
+                 FROM provider_test_results r
+                 WHERE app_type = ?1 AND id = (
+                     SELECT id FROM provider_test_results
+                     WHERE app_type = r.app_type AND provider_id = r.provider_id
+                     ORDER BY tested_at DESC, id DESC LIMIT 1
+                 )
+                 ORDER BY provider_id",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let rows = stmt
+            .query_map([&key], |row| {
+                Ok(ProviderTestResult {
+                    provider_id: row.get(0)?,
+                    ok: row.get(1)?,
+                    latency_ms: row.get::<_, Option<i64>>(2)?.map(|ms| ms as u64),
+                    message: row.get(3)?,
+                    tested_at: row.get(4)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut results = IndexMap::new();
+        for row in rows {
+            let result = row.map_err(|e| AppError::Database(e.to_string()))?;
+            results.insert(result.provider_id.clone(), result);
+        }
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::Provider;
+    use serde_json::json;
+
+    fn result(id: &str, ok: bool, tested_at: i64) -> ProviderTestResult {
+        ProviderTestResult {
+            provider_id: id.to_string(),
+            ok,
+            latency_ms: ok.then_some(120),
+            message: if ok { String::new() } else { "HTTP 401".to_string() },
+            tested_at,
+        }
+    }
+
+    #[test]
+    fn test_latest_test_result_per_provider() -> Result<(), AppError> {
+        let db = Database::memory()?;
+        for id in ["p1", "p2", "p3"] {
+            db.save_provider("claude", &Provider::new(id, id, json!({})))?;
+        }
+
+        db.save_provider_test_result("claude", &result("p1", true, 1_000))?;
+        db.save_provider_test_result("claude", &result("p1", false, 2_000))?;
+        db.save_provider_test_result("claude", &result("p2", true, 1_500))?;
+        db.save_provider_test_result("claude", &result("p2", false, 500))?;
+
+        let latest = db.get_latest_test_results("claude")?;
+        assert_eq!(latest.len(), 2);
+        assert_eq!(latest["p1"], result("p1", false, 2_000));
+        assert_eq!(latest["p2"], result("p2", true, 1_500));
+        assert!(!latest.contains_key("p3"));
+        assert!(db.get_latest_test_results("codex")?.is_empty());
+
+        // 更新供应商不影响已保存的结果，删除供应商时一并删除
+        db.save_provider("claude", &Provider::new("p2", "renamed", json!({})))?;
+        assert!(db.get_latest_test_results("claude")?.contains_key("p2"));
+        db.delete_provider("claude", "p1")?;
+        assert!(!db.get_latest_test_results("claude")?.contains_key("p1"));
+        Ok(())
+    }
+}
@@ -32,6 +32,7 @@ impl Database {
                 icon_color TEXT,
                 in_failover_queue BOOLEAN NOT NULL DEFAULT 0,
                 is_current BOOLEAN NOT NULL DEFAULT 0,
+                last_used_at INTEGER,
                 PRIMARY KEY (id, app_type)
             )",
             [],
@@ -66,6 +67,7 @@ impl Database {
                 enabled_codex BOOLEAN NOT NULL DEFAULT 0,
                 enabled_gemini BOOLEAN NOT NULL DEFAULT 0,
                 enabled_opencode BOOLEAN NOT NULL DEFAULT 0,
+                enabled BOOLEAN NOT NULL DEFAULT 1,
                 created_at INTEGER,
                 sort_index INTEGER
             )",
@@ -84,6 +86,7 @@ impl Database {
                 enabled BOOLEAN NOT NULL DEFAULT 1,
                 created_at INTEGER,
                 updated_at INTEGER,
+                variables TEXT NOT NULL DEFAULT '{}',
                 PRIMARY KEY (id, app_type)
             )",
             [],
@@ -105,7 +108,9 @@ impl Database {
                 enabled_codex BOOLEAN NOT NULL DEFAULT 0,
                 enabled_gemini BOOLEAN NOT NULL DEFAULT 0,
                 enabled_opencode BOOLEAN NOT NULL DEFAULT 0,
-                installed_at INTEGER NOT NULL DEFAULT 0
+                installed_at INTEGER NOT NULL DEFAULT 0,
+                repo_commit TEXT,
+                repo_rev TEXT
             )",
             [],
         )
@@ -334,6 +339,26 @@ impl Database {
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
 
+        // 15. Provider Test Results 表
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS provider_test_results (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                provider_id TEXT NOT NULL,
+                app_type TEXT NOT NULL,
+                ok BOOLEAN NOT NULL,
+                latency_ms INTEGER,
+                message TEXT NOT NULL DEFAULT '',
+                tested_at INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        let _ = conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_provider_test_results_provider
+             ON provider_test_results(app_type, provider_id, tested_at DESC)",
+            [],
+        );
+
         // Indexes for providers
         let _ = conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_providers_app_type ON providers(app_type)",
@@ -418,6 +443,36 @@ impl Database {
                         Self::migrate_v5_to_v6(conn)?;
                         Self::set_user_version(conn, 6)?;
                     }
+                    6 => {
+                        log::info!("迁移数据库从 v6 到 v7（Prompt 模板变量）");
+                        Self::migrate_v6_to_v7(conn)?;
+                        Self::set_user_version(conn, 7)?;
+                    }
+                    7 => {
+                        log::info!("迁移数据库从 v7 到 v8（Skill 仓库提交记录）");
+                        Self::migrate_v7_to_v8(conn)?;
+                        Self::set_user_version(conn, 8)?;
+                    }
+                    8 => {
+                        log::info!("迁移数据库从 v8 到 v9（供应商最近使用时间）");
+                        Self::migrate_v8_to_v9(conn)?;
+                        Self::set_user_version(conn, 9)?;
+                    }
+                    9 => {
+                        log::info!("迁移数据库从 v9 到 v10（供应商测试结果）");
+                        Self::migrate_v9_to_v10(conn)?;
+                        Self::set_user_version(conn, 10)?;
+                    }
+                    10 => {
+                        log::info!("迁移数据库从 v10 到 v11（MCP 服务器全局启用状态）");
+                        Self::migrate_v10_to_v11(conn)?;
+                        Self::set_user_version(conn, 11)?;
+                    }
+                    11 => {
+                        log::info!("迁移数据库从 v11 到 v12（Skill 固定版本）");
+                        Self::migrate_v11_to_v12(conn)?;
+                        Self::set_user_version(conn, 12)?;
+                    }
                     _ => {
                         return Err(AppError::Database(format!(
                             "未知的数据库版本 {version}，无法迁移到 {SCHEMA_VERSION}"
@@ -633,6 +688,69 @@ impl Database {
         Ok(())
     }
 
+    /// v6 -> v7: 添加 Prompt 模板变量
+    fn migrate_v6_to_v7(conn: &Connection) -> Result<(), AppError> {
+        Self::add_column_if_missing(conn, "prompts", "variables", "TEXT NOT NULL DEFAULT '{}'")?;
+        log::info!("v6 -> v7 迁移完成：已添加 Prompt 模板变量");
+        Ok(())
+    }
+
+    /// v7 -> v8: 记录 Skill 安装时的仓库提交
+    fn migrate_v7_to_v8(conn: &Connection) -> Result<(), AppError> {
+        if Self::table_exists(conn, "skills")? {
+            Self::add_column_if_missing(conn, "skills", "repo_commit", "TEXT")?;
+        }
+        log::info!("v7 -> v8 迁移完成：已添加 Skill 仓库提交记录");
+        Ok(())
+    }
+
+    /// v8 -> v9: 记录供应商最近一次被切换的时间
+    fn migrate_v8_to_v9(conn: &Connection) -> Result<(), AppError> {
+        Self::add_column_if_missing(conn, "providers", "last_used_at", "INTEGER")?;
+        log::info!("v8 -> v9 迁移完成：已添加供应商最近使用时间");
+        Ok(())
+    }
+
+    /// v9 -> v10: 保存供应商 API 测试结果
+    fn migrate_v9_to_v10(conn: &Connection) -> Result<(), AppError> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS provider_test_results (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                provider_id TEXT NOT NULL,
+                app_type TEXT NOT NULL,
+                ok BOOLEAN NOT NULL,
+                latency_ms INTEGER,
+                message TEXT NOT NULL DEFAULT '',
+                tested_at INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(format!("创建 provider_test_results 表失败: {e}")))?;
+        let _ = conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_provider_test_results_provider
+             ON provider_test_results(app_type, provider_id, tested_at DESC)",
+            [],
+        );
+        log::info!("v9 -> v10 迁移完成：已添加供应商测试结果表");
+        Ok(())
+    }
+
+    /// v10 -> v11: MCP 服务器全局启用状态
+    fn migrate_v10_to_v11(conn: &Connection) -> Result<(), AppError> {
+        Self::add_column_if_missing(conn, "mcp_servers", "enabled", "BOOLEAN NOT NULL DEFAULT 1")?;
+        log::info!("v10 -> v11 迁移完成：已添加 MCP 服务器全局启用状态");
+        Ok(())
+    }
+
+    /// v11 -> v12: 记录 Skill 固定的提交或标签
+    fn migrate_v11_to_v12(conn: &Connection) -> Result<(), AppError> {
+        if Self::table_exists(conn, "skills")? {
+            Self::add_column_if_missing(conn, "skills", "repo_rev", "TEXT")?;
+        }
+        log::info!("v11 -> v12 迁移完成：已添加 Skill 固定版本");
+        Ok(())
+    }
+
     /// 插入默认模型定价数据
     fn seed_model_pricing(conn: &Connection) -> Result<(), AppError> {
         let pricing_data: &[(&str, &str, &str, &str, &str, &str)] = &[
@@ -1,6 +1,7 @@
 //! Skill 数据库操作模块
 
 use crate::app_config::McpApps;
+use crate::audit;
 use crate::database::{lock_conn, Database};
 use crate::error::AppError;
 use crate::skill::{Skill, SkillRepo};
@@ -17,7 +18,7 @@ impl Database {
                 r#"
                 SELECT id, name, description, directory, repo_owner, repo_name, repo_branch,
                        readme_url, enabled_claude, enabled_codex, enabled_gemini, enabled_opencode,
-                       installed_at
+                       installed_at, repo_commit, repo_rev
                 FROM skills
                 ORDER BY installed_at ASC
                 "#,
@@ -39,6 +40,8 @@ impl Database {
                 let enabled_gemini: bool = row.get::<_, i64>(10)? != 0;
                 let enabled_opencode: bool = row.get::<_, i64>(11)? != 0;
                 let installed_at: Option<i64> = row.get(12)?;
+                let repo_commit: Option<String> = row.get(13)?;
+                let repo_rev: Option<String> = row.get(14)?;
 
                 Ok((
                     id.clone(),
@@ -50,6 +53,8 @@ impl Database {
                         repo_owner,
                         repo_name,
                         repo_branch,
+                        repo_commit,
+                        repo_rev,
                         readme_url,
                         apps: McpApps {
                             claude: enabled_claude,
@@ -87,8 +92,8 @@ impl Database {
             INSERT OR REPLACE INTO skills
             (id, name, description, directory, repo_owner, repo_name, repo_branch,
              readme_url, enabled_claude, enabled_codex, enabled_gemini, enabled_opencode,
-             installed_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+             installed_at, repo_commit, repo_rev)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
             rusqlite::params![
                 skill.id,
@@ -104,10 +109,13 @@ impl Database {
                 skill.apps.gemini as i64,
                 skill.apps.opencode as i64,
                 skill.installed_at,
+                skill.repo_commit,
+                skill.repo_rev,
             ],
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
 
+        audit::log_db_write("save_skill", serde_json::json!({"id": skill.id}));
         Ok(())
     }
 
@@ -118,6 +126,7 @@ impl Database {
         conn.execute("DELETE FROM skills WHERE id = ?", rusqlite::params![id])
             .map_err(|e| AppError::Database(e.to_string()))?;
 
+        audit::log_db_write("delete_skill", serde_json::json!({"id": id}));
         Ok(())
     }
 
@@ -141,6 +150,7 @@ impl Database {
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
 
+        audit::log_db_write("update_skill_apps", serde_json::json!({"id": id}));
         Ok(())
     }
 
@@ -0,0 +1,132 @@
+//! JSON 差异比较模块
+//!
+//! 逐字段比较两个 JSON 值，路径使用 JSON Pointer 格式（如 `/env/ANTHROPIC_MODEL`）。
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// 差异类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffKind {
+    /// 仅右侧存在
+    Added,
+    /// 仅左侧存在
+    Removed,
+    /// 两侧值不同
+    Changed,
+}
+
+/// 单个字段的差异
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct JsonDiff {
+    pub path: String,
+    pub kind: DiffKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub left: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub right: Option<Value>,
+}
+
+/// 比较两个 JSON 值，返回所有叶子级差异
+pub fn diff_json(left: &Value, right: &Value) -> Vec<JsonDiff> {
+    let mut diffs = Vec::new();
+    diff_at("", left, right, &mut diffs);
+    diffs
+}
+
+fn diff_at(path: &str, left: &Value, right: &Value, diffs: &mut Vec<JsonDiff>) {
+    match (left, right) {
+        (Value::Object(l), Value::Object(r)) => {
+            for (key, lv) in l {
+                let child = child_path(path, key);
+                match r.get(key) {
+                    Some(rv) => diff_at(&child, lv, rv, diffs),
+                    None => diffs.push(JsonDiff {
+                        path: child,
+                        kind: DiffKind::Removed,
+                        left: Some(lv.clone()),
+                        right: None,
+                    }),
+                }
+            }
+            for (key, rv) in r {
+                if !l.contains_key(key) {
+                    diffs.push(JsonDiff {
+                        path: child_path(path, key),
+                        kind: DiffKind::Added,
+                        left: None,
+                        right: Some(rv.clone()),
+                    });
+                }
+            }
+        }
+        (Value::Array(l), Value::Array(r)) => {
+            for i in 0..l.len().max(r.len()) {
+                let child = child_path(path, &i.to_string());
+                match (l.get(i), r.get(i)) {
+                    (Some(lv), Some(rv)) => diff_at(&child, lv, rv, diffs),
+                    (Some(lv), None) => diffs.push(JsonDiff {
+                        path: child,
+                        kind: DiffKind::Removed,
+                        left: Some(lv.clone()),
+                        right: None,
+                    }),
+                    (None, Some(rv)) => diffs.push(JsonDiff {
+                        path: child,
+                        kind: DiffKind::Added,
+                        left: None,
+                        right: Some(rv.clone()),
+                    }),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ if left != right => diffs.push(JsonDiff {
+            path: if path.is_empty() { "/".to_string() } else { path.to_string() },
+            kind: DiffKind::Changed,
+            left: Some(left.clone()),
+            right: Some(right.clone()),
+        }),
+        _ => {}
+    }
+}
+
+/// 拼接 JSON Pointer 路径，转义 `~` 与 `/`
+fn child_path(parent: &str, key: &str) -> String {
+    format!("{}/{}", parent, key.replace('~', "~0").replace('/', "~1"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_json_reports_leaf_changes() {
+        let left = json!({
+            "env": { "MODEL": "a", "KEY": "k", "a/b": 1 },
+            "list": [1, 2],
+            "same": true
+        });
+        let right = json!({
+            "env": { "MODEL": "b", "KEY": "k", "NEW": "x" },
+            "list": [1],
+            "same": true
+        });
+
+        let diffs = diff_json(&left, &right);
+        let summary: Vec<(&str, DiffKind)> =
+            diffs.iter().map(|d| (d.path.as_str(), d.kind)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("/env/MODEL", DiffKind::Changed),
+                ("/env/a~1b", DiffKind::Removed),
+                ("/env/NEW", DiffKind::Added),
+                ("/list/1", DiffKind::Removed),
+            ]
+        );
+        assert!(diff_json(&left, &left).is_empty());
+    }
+}
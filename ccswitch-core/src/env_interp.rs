@@ -0,0 +1,131 @@
+//! 环境变量插值
+//!
+//! 供应商配置中的字符串可写 `${NAME}` 或 `${NAME:-默认值}`，写入 live 配置时从进程环境解析，
+//! 数据库中仍保留占位符。需通过 `--resolve-env` 或设置 `resolve_env` 显式开启。
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde_json::Value;
+
+use crate::error::AppError;
+use crate::settings;
+
+/// 命令行 `--resolve-env` 开关
+static RESOLVE_ENV: AtomicBool = AtomicBool::new(false);
+
+/// 本进程启用环境变量插值（`--resolve-env`）
+pub fn set_resolve_env(enabled: bool) {
+    RESOLVE_ENV.store(enabled, Ordering::Relaxed);
+}
+
+/// 是否启用环境变量插值（命令行开关或 `resolve_env` 设置）
+pub fn resolve_env_enabled() -> bool {
+    RESOLVE_ENV.load(Ordering::Relaxed) || settings::resolve_env()
+}
+
+/// 解析 JSON 中所有字符串里的 `${NAME}` 引用
+///
+/// 未设置且没有默认值的变量会汇总为一个错误返回。
+pub fn resolve_env_refs(value: &Value) -> Result<Value, AppError> {
+    resolve_env_refs_with(value, |name| std::env::var(name).ok())
+}
+
+/// 使用指定的查找函数解析 `${NAME}` 引用
+pub fn resolve_env_refs_with(
+    value: &Value,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<Value, AppError> {
+    let mut missing = Vec::new();
+    let resolved = resolve_value(value, &lookup, &mut missing);
+    if missing.is_empty() {
+        Ok(resolved)
+    } else {
+        Err(AppError::InvalidInput(format!(
+            "供应商配置引用的环境变量未设置: {}",
+            missing.join(", ")
+        )))
+    }
+}
+
+fn resolve_value(value: &Value, lookup: &impl Fn(&str) -> Option<String>, missing: &mut Vec<String>) -> Value {
+    match value {
+        Value::String(s) => Value::String(resolve_str(s, lookup, missing)),
+        Value::Array(items) => Value::Array(items.iter().map(|v| resolve_value(v, lookup, missing)).collect()),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), resolve_value(v, lookup, missing)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+fn resolve_str(s: &str, lookup: &impl Fn(&str) -> Option<String>, missing: &mut Vec<String>) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find('}') else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+
+        let expr = &after[..end];
+        let (name, default) = match expr.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (expr, None),
+        };
+        if !is_env_name(name) {
+            // 不是合法变量名时原样保留
+            out.push_str(&rest[start..start + 2 + end + 1]);
+        } else {
+            match lookup(name).or_else(|| default.map(str::to_string)) {
+                Some(v) => out.push_str(&v),
+                None => {
+                    if !missing.iter().any(|m| m == name) {
+                        missing.push(name.to_string());
+                    }
+                    out.push_str(&rest[start..start + 2 + end + 1]);
+                }
+            }
+        }
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn is_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c == '_' || c.is_ascii_alphabetic())
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_resolve_env_refs() {
+        let lookup = |name: &str| (name == "MY_KEY").then(|| "sk-123".to_string());
+        let config = json!({
+            "env": {
+                "ANTHROPIC_AUTH_TOKEN": "${MY_KEY}",
+                "ANTHROPIC_BASE_URL": "https://${HOST:-api.example.com}/v1",
+                "LITERAL": "${not a var} $HOME",
+            },
+            "list": ["Bearer ${MY_KEY}"],
+        });
+
+        let resolved = resolve_env_refs_with(&config, lookup).unwrap();
+        assert_eq!(resolved["env"]["ANTHROPIC_AUTH_TOKEN"], "sk-123");
+        assert_eq!(resolved["env"]["ANTHROPIC_BASE_URL"], "https://api.example.com/v1");
+        assert_eq!(resolved["env"]["LITERAL"], "${not a var} $HOME");
+        assert_eq!(resolved["list"][0], "Bearer sk-123");
+
+        let err = resolve_env_refs_with(&json!({"a": "${NOPE}", "b": "${NOPE2}"}), lookup).unwrap_err();
+        assert!(err.to_string().contains("NOPE, NOPE2"));
+    }
+}
@@ -92,9 +92,21 @@ pub enum AppError {
     #[error("供应商不存在: {0}")]
     ProviderNotFound(String),
 
-    /// HTTP 请求错误
+    /// HTTP 请求错误（服务端返回非成功状态）
     #[error("HTTP 请求失败: {0}")]
     Http(String),
+
+    /// 网络错误（连接失败、超时等，沿用 HTTP 请求失败的文案）
+    #[error("HTTP 请求失败: {0}")]
+    Network(String),
+
+    /// 对象不存在（MCP 服务器、Prompt、Skill 等，沿用无效输入的文案）
+    #[error("无效输入: {0}")]
+    NotFound(String),
+
+    /// 配置校验失败（沿用无效输入的文案）
+    #[error("无效输入: {0}")]
+    Validation(String),
 }
 
 impl AppError {
@@ -161,9 +173,30 @@ impl From<serde_json::Error> for AppError {
 
 impl From<reqwest::Error> for AppError {
     fn from(err: reqwest::Error) -> Self {
-        Self::Http(err.to_string())
+        Self::Network(err.to_string())
     }
 }
 
 /// Result 类型别名
 pub type Result<T> = std::result::Result<T, AppError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_structured_variants_keep_display_text() {
+        assert_eq!(
+            AppError::NotFound("Prompt 'x' 不存在".into()).to_string(),
+            AppError::InvalidInput("Prompt 'x' 不存在".into()).to_string()
+        );
+        assert_eq!(
+            AppError::Validation("缺少 apiKey".into()).to_string(),
+            AppError::InvalidInput("缺少 apiKey".into()).to_string()
+        );
+        assert_eq!(
+            AppError::Network("timeout".into()).to_string(),
+            AppError::Http("timeout".into()).to_string()
+        );
+    }
+}
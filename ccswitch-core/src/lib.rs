@@ -10,6 +10,7 @@
 //! ├── lib.rs           - 公共 API 导出
 //! ├── config.rs        - 配置文件路径和读写
 //! ├── error.rs         - 统一错误类型
+//! ├── diff.rs          - JSON 差异比较
 //! ├── provider.rs      - 供应商数据结构
 //! ├── mcp.rs           - MCP 服务器数据结构
 //! ├── app_config.rs    - 应用类型定义
@@ -53,11 +54,15 @@
 //! ```
 
 pub mod app_config;
+pub mod audit;
 pub mod config;
 pub mod database;
+pub mod diff;
+pub mod env_interp;
 pub mod error;
 pub mod mcp;
 pub mod openclaw_config;
+pub mod profile;
 pub mod prompt;
 pub mod provider;
 pub mod services;
@@ -65,8 +70,11 @@ pub mod settings;
 pub mod skill;
 pub mod store;
 
+#[cfg(any(test, feature = "test-hooks"))]
+pub mod test_support;
+
 // 公共类型导出
-pub use app_config::{AppType, McpApps, SkillApps};
+pub use app_config::{AppConfigPaths, AppType, McpApps, SkillApps};
 pub use config::{
     get_app_config_dir, get_app_config_path, get_claude_config_dir, get_claude_mcp_path,
     get_claude_settings_path, get_codex_config_dir, get_codex_config_path, get_codex_auth_path,
@@ -74,7 +82,8 @@ pub use config::{
     get_openclaw_config_dir, get_openclaw_config_path, get_openclaw_providers_path,
     get_home_dir, get_database_path, read_json_file, write_json_file, write_text_file,
 };
-pub use database::Database;
+pub use database::{Database, ModelPricing, ProviderTestResult};
+pub use diff::{diff_json, DiffKind, JsonDiff};
 pub use error::AppError;
 pub use mcp::{McpServer, McpStdioConfig};
 pub use openclaw_config::{
@@ -96,11 +105,13 @@ pub use openclaw_config::{
 };
 pub use prompt::Prompt;
 pub use provider::{Provider, ProviderManager, ProviderMeta};
-pub use skill::{Skill, SkillRepo};
+pub use skill::{Skill, SkillIndexEntry, SkillRepo};
 pub use services::{
-    ConfigService, EnvCheckerService, FailoverService, McpService, PromptService, ProxyService, ProviderService,
-    SkillService, SpeedtestService, StreamCheckService, UsageStatsService, WebDavSyncService,
+    BackupService, ConfigService, EnvCheckerService, FailoverService, LiveBackupService, McpService, ProfileService, PromptService, ProxyService,
+    ProviderService, SkillService, SpeedtestService, StreamCheckService, UsageStatsService, WebDavSyncService,
 };
+pub use services::backup::{FullBackup, RestoreReport};
+pub use services::mcp::{McpCheckResult, McpScope};
 pub use services::failover::FailoverQueueItem;
 pub use services::stream_check::{HealthStatus, HealthCheckResult, StreamCheckConfig};
 pub use services::usage_stats::{
@@ -2,9 +2,11 @@
 //!
 //! 定义 MCP (Model Context Protocol) 服务器的数据结构。
 
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 use crate::app_config::McpApps;
+use crate::error::AppError;
 
 /// MCP 服务器配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +20,9 @@ pub struct McpServer {
     /// 应用启用状态
     #[serde(default)]
     pub apps: McpApps,
+    /// 全局启用状态，禁用时不会同步到任何应用
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
     /// 描述
     pub description: Option<String>,
     /// 主页链接
@@ -41,6 +46,7 @@ impl McpServer {
             name: name.into(),
             server_config,
             apps: McpApps::default(),
+            enabled: true,
             description: None,
             homepage: None,
             docs: None,
@@ -76,17 +82,26 @@ impl McpServer {
     /// 获取启用的应用列表字符串
     pub fn enabled_apps_str(&self) -> String {
         let apps = self.apps.enabled_apps();
-        if apps.is_empty() {
+        let apps = if apps.is_empty() {
             "无".to_string()
         } else {
             apps.iter()
                 .map(|a| a.display_name())
                 .collect::<Vec<_>>()
                 .join(", ")
+        };
+        if self.enabled {
+            apps
+        } else {
+            format!("{} (已全局禁用)", apps)
         }
     }
 }
 
+fn default_enabled() -> bool {
+    true
+}
+
 /// MCP 服务器的 stdio 配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct McpStdioConfig {
@@ -122,6 +137,27 @@ impl McpStdioConfig {
         self
     }
 
+    /// 解析 `KEY=VALUE` 形式的环境变量列表
+    ///
+    /// 缺少 `=` 或键为空的条目会返回错误；值允许为空或包含 `=`。
+    pub fn parse_env(entries: Vec<String>) -> Result<IndexMap<String, String>, AppError> {
+        let mut env = IndexMap::new();
+        for entry in entries {
+            let Some((key, value)) = entry.split_once('=') else {
+                return Err(AppError::InvalidInput(format!(
+                    "无效的环境变量格式（应为 KEY=VALUE）: {}",
+                    entry
+                )));
+            };
+            let key = key.trim();
+            if key.is_empty() {
+                return Err(AppError::InvalidInput(format!("环境变量名不能为空: {}", entry)));
+            }
+            env.insert(key.to_string(), value.to_string());
+        }
+        Ok(env)
+    }
+
     /// 转换为 JSON Value
     pub fn to_json(&self) -> serde_json::Value {
         serde_json::json!({
@@ -155,4 +191,29 @@ mod tests {
         assert_eq!(config.args.len(), 2);
         assert_eq!(config.env.get("API_KEY"), Some(&"test-key".to_string()));
     }
+
+    #[test]
+    fn test_parse_env() {
+        let env = McpStdioConfig::parse_env(vec![
+            "API_KEY=abc".to_string(),
+            " LOG_LEVEL =debug".to_string(),
+            "QUERY=a=b".to_string(),
+            "EMPTY=".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(env["API_KEY"], "abc");
+        assert_eq!(env["LOG_LEVEL"], "debug");
+        assert_eq!(env["QUERY"], "a=b");
+        assert_eq!(env["EMPTY"], "");
+        assert_eq!(env.keys().next().map(String::as_str), Some("API_KEY"));
+    }
+
+    #[test]
+    fn test_parse_env_rejects_malformed_entries() {
+        let missing_eq = McpStdioConfig::parse_env(vec!["API_KEY".to_string()]);
+        assert!(matches!(missing_eq, Err(AppError::InvalidInput(_))));
+
+        let empty_key = McpStdioConfig::parse_env(vec!["=value".to_string()]);
+        assert!(matches!(empty_key, Err(AppError::InvalidInput(_))));
+    }
 }
@@ -0,0 +1,59 @@
+//! Profile（隔离的供应商集合）
+//!
+//! 非默认 profile 通过为 `app_type` 键加前缀（如 `work:claude`）实现隔离，无需修改 Schema。
+
+use std::sync::RwLock;
+
+use crate::error::AppError;
+
+/// 默认 profile 名称（沿用不带前缀的键）
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// 当前进程激活的 profile，None 表示默认 profile
+static ACTIVE_PROFILE: RwLock<Option<String>> = RwLock::new(None);
+
+/// 设置激活的 profile（`default` 等同于 None）
+pub fn set_active_profile(name: Option<&str>) {
+    let name = name.filter(|n| *n != DEFAULT_PROFILE).map(str::to_string);
+    if let Ok(mut guard) = ACTIVE_PROFILE.write() {
+        *guard = name;
+    }
+}
+
+/// 当前激活的 profile（默认 profile 返回 None）
+pub fn active_profile() -> Option<String> {
+    ACTIVE_PROFILE.read().ok().and_then(|guard| guard.clone())
+}
+
+/// 校验 profile 名称：仅允许字母、数字、`-` 与 `_`
+pub fn validate_profile_name(name: &str) -> Result<(), AppError> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(AppError::InvalidInput(format!(
+            "无效的 profile 名称 '{}'：仅允许字母、数字、- 与 _",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// 计算 profile 下的 app_type 键
+pub fn scoped_app_key(profile: Option<&str>, app_type: &str) -> String {
+    match profile {
+        Some(profile) => format!("{}:{}", profile, app_type),
+        None => app_type.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scoped_app_key_and_validation() {
+        assert_eq!(scoped_app_key(None, "claude"), "claude");
+        assert_eq!(scoped_app_key(Some("work"), "claude"), "work:claude");
+        assert!(validate_profile_name("work_2-a").is_ok());
+        assert!(validate_profile_name("a:b").is_err());
+        assert!(validate_profile_name("").is_err());
+    }
+}
@@ -2,9 +2,11 @@
 //!
 //! 定义 Prompt 的数据结构，用于管理各应用的系统提示词。
 
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 use crate::app_config::AppType;
+use crate::error::AppError;
 
 /// Prompt 配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +21,9 @@ pub struct Prompt {
     pub description: Option<String>,
     /// 是否启用
     pub enabled: bool,
+    /// 模板变量默认值，对应内容中的 `{{var}}` 占位符
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub variables: IndexMap<String, String>,
     /// 创建时间 (Unix 时间戳)
     pub created_at: Option<i64>,
     /// 更新时间 (Unix 时间戳)
@@ -35,6 +40,7 @@ impl Prompt {
             content: content.into(),
             description: None,
             enabled: false,
+            variables: IndexMap::new(),
             created_at: Some(now),
             updated_at: Some(now),
         }
@@ -51,6 +57,83 @@ impl Prompt {
         self.enabled = enabled;
         self
     }
+
+    /// 内容中出现的占位符名称（去重，按出现顺序）
+    pub fn placeholders(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for name in placeholder_spans(&self.content).into_iter().filter_map(|(_, _, n)| n) {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    /// 替换 `{{var}}` 占位符
+    ///
+    /// `overrides` 优先于 Prompt 自带的默认值；仍有未提供的变量时返回错误并列出名称。
+    /// `\{{` 原样输出为 `{{`，用于保留 `${{ github.sha }}` 之类的文本。
+    pub fn render(&self, overrides: &IndexMap<String, String>) -> Result<String, AppError> {
+        let lookup = |name: &str| overrides.get(name).or_else(|| self.variables.get(name));
+
+        let missing: Vec<String> = self
+            .placeholders()
+            .into_iter()
+            .filter(|name| lookup(name).is_none())
+            .collect();
+        if !missing.is_empty() {
+            return Err(AppError::InvalidInput(format!(
+                "Prompt '{}' 缺少变量: {}（如需原样保留 {{{{，请写作 \\{{{{）",
+                self.id,
+                missing.join(", ")
+            )));
+        }
+
+        let mut rendered = String::with_capacity(self.content.len());
+        let mut last = 0;
+        for (start, end, name) in placeholder_spans(&self.content) {
+            rendered.push_str(&self.content[last..start]);
+            match name {
+                Some(name) => rendered.push_str(lookup(&name).map(String::as_str).unwrap_or_default()),
+                None => rendered.push_str("{{"),
+            }
+            last = end;
+        }
+        rendered.push_str(&self.content[last..]);
+        Ok(rendered)
+    }
+}
+
+/// 查找内容中的 `{{ name }}` 占位符，返回 (起始位置, 结束位置, 变量名)
+///
+/// 转义的 `\{{` 变量名为 `None`，渲染时替换为字面量 `{{`。
+fn placeholder_spans(content: &str) -> Vec<(usize, usize, Option<String>)> {
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    while let Some(open) = content[offset..].find("{{") {
+        let start = offset + open;
+        if content[..start].ends_with('\\') {
+            spans.push((start - 1, start + 2, None));
+            offset = start + 2;
+            continue;
+        }
+        let Some(close) = content[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + close + 2;
+        let name = content[start + 2..end - 2].trim();
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+        if valid {
+            spans.push((start, end, Some(name.to_string())));
+            offset = end;
+        } else {
+            offset = start + 2;
+        }
+    }
+    spans
 }
 
 /// 获取应用的 Prompt 文件路径
@@ -102,4 +185,38 @@ mod tests {
         assert_eq!(prompt.description, Some("A test prompt".to_string()));
         assert!(prompt.enabled);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_prompt_render_substitutes_variables() {
+        let mut prompt = Prompt::new("t", "T", "项目 {{project}} 使用 {{ model }}，{{project}} 优先");
+        prompt.variables.insert("model".to_string(), "sonnet".to_string());
+
+        let mut vars = IndexMap::new();
+        vars.insert("project".to_string(), "cc-switch".to_string());
+        assert_eq!(
+            prompt.render(&vars).unwrap(),
+            "项目 cc-switch 使用 sonnet，cc-switch 优先"
+        );
+
+        // 命令行变量覆盖默认值
+        vars.insert("model".to_string(), "opus".to_string());
+        assert!(prompt.render(&vars).unwrap().contains("使用 opus"));
+    }
+
+    #[test]
+    fn test_prompt_render_reports_missing_variables() {
+        let prompt = Prompt::new("t", "T", "{{a}} {{b}} {{a}} {{ not a var }}");
+        let err = prompt.render(&IndexMap::new()).unwrap_err().to_string();
+        assert!(err.contains("a, b"), "{err}");
+    }
+
+    #[test]
+    fn test_prompt_render_keeps_escaped_braces() {
+        let prompt = Prompt::new("t", "T", "sha: $\\{{ github.sha }}, 项目 {{project}}");
+        assert_eq!(prompt.placeholders(), vec!["project"]);
+
+        let mut vars = IndexMap::new();
+        vars.insert("project".to_string(), "cc-switch".to_string());
+        assert_eq!(prompt.render(&vars).unwrap(), "sha: ${{ github.sha }}, 项目 cc-switch");
+    }
+}
@@ -49,6 +49,10 @@ pub struct Provider {
     #[serde(default)]
     #[serde(rename = "inFailoverQueue")]
     pub in_failover_queue: bool,
+    /// 最近一次切换到该供应商的时间（毫秒时间戳）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "lastUsedAt")]
+    pub last_used_at: Option<i64>,
 }
 
 impl Provider {
@@ -67,6 +71,7 @@ impl Provider {
             icon: None,
             icon_color: None,
             in_failover_queue: false,
+            last_used_at: None,
         }
     }
 
@@ -90,6 +95,7 @@ impl Provider {
             icon: None,
             icon_color: None,
             in_failover_queue: false,
+            last_used_at: None,
         }
     }
 
@@ -0,0 +1,234 @@
+//! 完整备份服务模块
+//!
+//! 将供应商、MCP 服务器、Prompts、Skills 与设置导出为单个 JSON 文档，并支持事务性恢复。
+//! Skills 只备份数据库记录，不包含已安装的目录内容。
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::app_config::AppType;
+use crate::database::SCHEMA_VERSION;
+use crate::error::AppError;
+use crate::mcp::McpServer;
+use crate::prompt::Prompt;
+use crate::provider::Provider;
+use crate::skill::Skill;
+use crate::store::AppState;
+
+/// 完整备份（`cc-switch backup` / `cc-switch restore`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FullBackup {
+    /// 导出时的数据库 Schema 版本
+    pub version: i32,
+    pub created_at: String,
+    /// 应用 -> 供应商 ID -> 供应商
+    pub providers: IndexMap<String, IndexMap<String, Provider>>,
+    /// 应用 -> 当前供应商 ID
+    #[serde(default)]
+    pub current_providers: IndexMap<String, String>,
+    #[serde(default)]
+    pub mcp_servers: IndexMap<String, McpServer>,
+    /// 应用 -> Prompt ID -> Prompt
+    #[serde(default)]
+    pub prompts: IndexMap<String, IndexMap<String, Prompt>>,
+    #[serde(default)]
+    pub skills: IndexMap<String, Skill>,
+    #[serde(default)]
+    pub settings: IndexMap<String, String>,
+}
+
+/// 恢复结果统计
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RestoreReport {
+    pub added: usize,
+    pub overwritten: usize,
+    pub skipped: usize,
+}
+
+impl RestoreReport {
+    fn record(&mut self, exists: bool, overwrite: bool) -> bool {
+        match (exists, overwrite) {
+            (false, _) => {
+                self.added += 1;
+                true
+            }
+            (true, true) => {
+                self.overwritten += 1;
+                true
+            }
+            (true, false) => {
+                self.skipped += 1;
+                false
+            }
+        }
+    }
+}
+
+/// 完整备份服务
+pub struct BackupService;
+
+impl BackupService {
+    /// 导出数据库中的全部数据
+    pub fn export(state: &AppState) -> Result<FullBackup, AppError> {
+        let mut providers = IndexMap::new();
+        let mut current_providers = IndexMap::new();
+        let mut prompts = IndexMap::new();
+
+        for app_type in AppType::all() {
+            let app = app_type.as_str();
+            providers.insert(app.to_string(), state.db.get_all_providers(app)?);
+            if let Some(current) = state.db.get_current_provider(app)? {
+                current_providers.insert(app.to_string(), current);
+            }
+            prompts.insert(app.to_string(), state.db.get_all_prompts(app)?);
+        }
+
+        Ok(FullBackup {
+            version: SCHEMA_VERSION,
+            created_at: chrono::Local::now().to_rfc3339(),
+            providers,
+            current_providers,
+            mcp_servers: state.db.get_all_mcp_servers()?,
+            prompts,
+            skills: state.db.get_all_skills()?,
+            settings: state.db.get_all_settings()?,
+        })
+    }
+
+    /// 在单个事务中恢复备份，ID 冲突时按 `overwrite` 覆盖或跳过
+    ///
+    /// 只写入数据库，live 配置需由调用方随后对当前供应商执行 `ProviderService::switch` 同步。
+    pub fn restore(
+        state: &AppState,
+        backup: &FullBackup,
+        overwrite: bool,
+    ) -> Result<RestoreReport, AppError> {
+        if backup.version > SCHEMA_VERSION {
+            return Err(AppError::InvalidInput(format!(
+                "备份版本过新（{}），当前仅支持 {}，请升级后再恢复",
+                backup.version, SCHEMA_VERSION
+            )));
+        }
+
+        for app in backup.providers.keys().chain(backup.prompts.keys()) {
+            app.parse::<AppType>()?;
+        }
+
+        state.db.with_transaction(|db| {
+            let mut report = RestoreReport::default();
+
+            for (app, providers) in &backup.providers {
+                let existing = db.get_all_providers(app)?;
+                for (id, provider) in providers {
+                    if report.record(existing.contains_key(id), overwrite) {
+                        db.save_provider(app, provider)?;
+                    }
+                }
+            }
+            for (app, id) in &backup.current_providers {
+                let exists = db.get_all_providers(app)?.contains_key(id);
+                if exists && (overwrite || db.get_current_provider(app)?.is_none()) {
+                    db.set_current_provider(app, id)?;
+                }
+            }
+
+            for (id, server) in &backup.mcp_servers {
+                if report.record(db.get_mcp_server(id)?.is_some(), overwrite) {
+                    db.save_mcp_server(server)?;
+                }
+            }
+
+            for (app, prompts) in &backup.prompts {
+                for (id, prompt) in prompts {
+                    if report.record(db.get_prompt(app, id)?.is_some(), overwrite) {
+                        db.save_prompt(app, prompt)?;
+                    }
+                }
+            }
+
+            for (id, skill) in &backup.skills {
+                if report.record(db.get_skill(id)?.is_some(), overwrite) {
+                    db.save_skill(skill)?;
+                }
+            }
+
+            for (key, value) in &backup.settings {
+                if report.record(db.get_setting(key)?.is_some(), overwrite) {
+                    db.set_setting(key, value)?;
+                }
+            }
+
+            Ok(report)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn seeded_state() -> AppState {
+        let state = AppState::memory().unwrap();
+        state
+            .db
+            .save_provider("claude", &Provider::new("p1", "P1", json!({"env": {"ANTHROPIC_AUTH_TOKEN": "sk-1"}})))
+            .unwrap();
+        state
+            .db
+            .save_provider("codex", &Provider::new("x1", "X1", json!({"auth": {}})))
+            .unwrap();
+        state.db.set_current_provider("claude", "p1").unwrap();
+
+        let mut server = McpServer::new("fs", "fs", json!({"command": "npx"}));
+        server.apps.claude = true;
+        state.db.save_mcp_server(&server).unwrap();
+        state
+            .db
+            .save_prompt("claude", &Prompt::new("rules", "Rules", "be nice"))
+            .unwrap();
+        state.db.set_setting("default_app", "codex").unwrap();
+        state
+    }
+
+    #[test]
+    fn test_backup_round_trip() {
+        let source = seeded_state();
+        let json = serde_json::to_string(&BackupService::export(&source).unwrap()).unwrap();
+        let backup: FullBackup = serde_json::from_str(&json).unwrap();
+        assert_eq!(backup.version, SCHEMA_VERSION);
+
+        let target = AppState::memory().unwrap();
+        let report = BackupService::restore(&target, &backup, false).unwrap();
+        assert_eq!(report, RestoreReport { added: 5, overwritten: 0, skipped: 0 });
+
+        let claude = target.db.get_all_providers("claude").unwrap();
+        assert_eq!(claude["p1"].settings_config, json!({"env": {"ANTHROPIC_AUTH_TOKEN": "sk-1"}}));
+        assert!(target.db.get_all_providers("codex").unwrap().contains_key("x1"));
+        assert_eq!(target.db.get_current_provider("claude").unwrap().as_deref(), Some("p1"));
+        let server = target.db.get_mcp_server("fs").unwrap().unwrap();
+        assert!(server.apps.claude && !server.apps.codex);
+        assert!(target.db.get_prompt("claude", "rules").unwrap().is_some());
+        assert_eq!(target.db.get_setting("default_app").unwrap().as_deref(), Some("codex"));
+
+        // 再次恢复：默认跳过，overwrite 时覆盖
+        let report = BackupService::restore(&target, &backup, false).unwrap();
+        assert_eq!(report.skipped, 5);
+        let report = BackupService::restore(&target, &backup, true).unwrap();
+        assert_eq!(report.overwritten, 5);
+    }
+
+    #[test]
+    fn test_restore_rejects_newer_version_and_unknown_app() {
+        let state = AppState::memory().unwrap();
+        let mut backup = BackupService::export(&seeded_state()).unwrap();
+
+        backup.version = SCHEMA_VERSION + 1;
+        assert!(BackupService::restore(&state, &backup, false).is_err());
+
+        backup.version = SCHEMA_VERSION;
+        backup.providers.insert("unknown".to_string(), IndexMap::new());
+        assert!(BackupService::restore(&state, &backup, false).is_err());
+        assert!(state.db.get_all_providers("claude").unwrap().is_empty());
+    }
+}
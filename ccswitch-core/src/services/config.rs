@@ -4,12 +4,9 @@
 
 use std::path::PathBuf;
 
+pub use crate::app_config::AppConfigPaths;
 use crate::app_config::AppType;
-use crate::config::{
-    get_app_config_dir, get_claude_config_dir, get_claude_mcp_path, get_claude_settings_path,
-    get_codex_auth_path, get_codex_config_dir, get_codex_config_path, get_gemini_config_dir,
-    get_gemini_settings_path, get_openclaw_config_dir, get_opencode_config_dir,
-};
+use crate::config::get_app_config_dir;
 use crate::error::AppError;
 
 /// 导出格式
@@ -54,19 +51,6 @@ pub struct ConfigPaths {
     pub settings_path: PathBuf,
 }
 
-/// 应用特定的配置路径
-#[derive(Debug, Clone)]
-pub struct AppConfigPaths {
-    /// 配置目录
-    pub config_dir: PathBuf,
-    /// 主配置文件
-    pub settings_path: PathBuf,
-    /// MCP 配置文件（如有）
-    pub mcp_path: Option<PathBuf>,
-    /// 认证文件（如有）
-    pub auth_path: Option<PathBuf>,
-}
-
 /// 配置服务
 pub struct ConfigService;
 
@@ -83,38 +67,7 @@ impl ConfigService {
 
     /// 获取指定应用的配置路径
     pub fn get_app_paths(app_type: AppType) -> AppConfigPaths {
-        match app_type {
-            AppType::Claude => AppConfigPaths {
-                config_dir: get_claude_config_dir(),
-                settings_path: get_claude_settings_path(),
-                mcp_path: Some(get_claude_mcp_path()),
-                auth_path: None,
-            },
-            AppType::Codex => AppConfigPaths {
-                config_dir: get_codex_config_dir(),
-                settings_path: get_codex_config_path(),
-                mcp_path: None,
-                auth_path: Some(get_codex_auth_path()),
-            },
-            AppType::Gemini => AppConfigPaths {
-                config_dir: get_gemini_config_dir(),
-                settings_path: get_gemini_settings_path(),
-                mcp_path: None,
-                auth_path: None,
-            },
-            AppType::OpenCode => AppConfigPaths {
-                config_dir: get_opencode_config_dir(),
-                settings_path: get_opencode_config_dir().join("config.toml"),
-                mcp_path: None,
-                auth_path: None,
-            },
-            AppType::OpenClaw => AppConfigPaths {
-                config_dir: get_openclaw_config_dir(),
-                settings_path: get_openclaw_config_dir().join("openclaw.json"),
-                mcp_path: None,
-                auth_path: None,
-            },
-        }
+        app_type.config_paths()
     }
 
     /// 检查应用是否已安装/配置
@@ -2,16 +2,27 @@
 //!
 //! 检测可能与 AI CLI 工具冲突的环境变量。
 
+use std::ffi::OsStr;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::app_config::AppType;
-use crate::config::get_home_dir;
+use crate::config::{get_app_config_dir, get_home_dir};
 use crate::error::AppError;
 
 /// 环境检测服务
 pub struct EnvCheckerService;
 
+/// Shell 配置备份目录，跟随 `--config-dir`/`CC_SWITCH_HOME` 等配置目录设置
+fn backup_root() -> PathBuf {
+    get_app_config_dir().join("backups").join("env")
+}
+
+/// 旧版本使用的备份目录，其中的备份仍可列出和恢复
+fn legacy_backup_root() -> PathBuf {
+    get_home_dir().join(".cc-switch-backups")
+}
+
 /// 环境变量检测结果
 #[derive(Debug, Clone)]
 pub struct EnvCheckResult {
@@ -29,6 +40,23 @@ pub struct EnvConflict {
     pub description: String,
 }
 
+/// `fix_conflicts` 的执行结果
+#[derive(Debug, Clone)]
+pub struct EnvFixReport {
+    /// 本次备份目录
+    pub backup_dir: PathBuf,
+    /// 每个应用被修改的配置文件
+    pub modified: Vec<(AppType, Vec<String>)>,
+}
+
+/// CLI 工具检测结果
+#[derive(Debug, Clone, Default)]
+pub struct ToolStatus {
+    pub installed: bool,
+    pub version: Option<String>,
+    pub path: Option<PathBuf>,
+}
+
 /// 环境变量来源
 #[derive(Debug, Clone, PartialEq)]
 pub enum EnvSource {
@@ -90,6 +118,41 @@ fn get_shell_config_files() -> Vec<PathBuf> {
     ]
 }
 
+/// 在 PATH 各目录中查找可执行文件（Windows 下按 PATHEXT 补全扩展名）
+pub(crate) fn find_in_path(cmd: &str, path_var: &OsStr) -> Option<PathBuf> {
+    let extensions: Vec<String> = if cfg!(windows) {
+        std::env::var("PATHEXT")
+            .unwrap_or_else(|_| ".EXE;.CMD;.BAT;.COM".to_string())
+            .split(';')
+            .filter(|ext| !ext.is_empty())
+            .map(|ext| ext.to_string())
+            .chain(std::iter::once(String::new()))
+            .collect()
+    } else {
+        vec![String::new()]
+    };
+
+    std::env::split_paths(path_var).find_map(|dir| {
+        extensions
+            .iter()
+            .map(|ext| dir.join(format!("{}{}", cmd, ext)))
+            .find(|candidate| is_executable(candidate))
+    })
+}
+
+#[cfg(unix)]
+pub(crate) fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path)
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+pub(crate) fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
 impl EnvCheckerService {
     /// 检查指定应用的环境变量冲突
     pub fn check(app: AppType) -> Result<EnvCheckResult, AppError> {
@@ -162,6 +225,48 @@ impl EnvCheckerService {
         vars
     }
 
+    /// 各应用对应的 CLI 命令名
+    pub fn tool_command(app: AppType) -> &'static str {
+        match app {
+            AppType::Claude => "claude",
+            AppType::Codex => "codex",
+            AppType::Gemini => "gemini",
+            AppType::OpenCode => "opencode",
+            AppType::OpenClaw => "openclaw",
+        }
+    }
+
+    /// 检测 CLI 工具是否安装及其版本
+    pub fn detect_tool(cmd: &str) -> ToolStatus {
+        Self::detect_tool_in(cmd, std::env::var_os("PATH").as_deref())
+    }
+
+    /// 在给定的 PATH 中检测 CLI 工具
+    fn detect_tool_in(cmd: &str, path_var: Option<&OsStr>) -> ToolStatus {
+        let Some(path) = path_var.and_then(|p| find_in_path(cmd, p)) else {
+            return ToolStatus::default();
+        };
+
+        let version = std::process::Command::new(&path)
+            .arg("--version")
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| {
+                String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .map(str::trim)
+                    .find(|line| !line.is_empty())
+                    .map(str::to_string)
+            });
+
+        ToolStatus {
+            installed: true,
+            version,
+            path: Some(path),
+        }
+    }
+
     /// 检查内容中是否包含环境变量设置
     fn check_env_in_content(content: &str, key: &str) -> bool {
         // 检查 export KEY= 或 KEY= 模式
@@ -191,8 +296,7 @@ impl EnvCheckerService {
 
     /// 备份 Shell 配置文件
     pub fn backup_shell_configs() -> Result<PathBuf, AppError> {
-        let home = get_home_dir();
-        let backup_dir = home.join(".cc-switch-backups");
+        let backup_dir = backup_root();
 
         // 创建备份目录
         fs::create_dir_all(&backup_dir).map_err(|e| {
@@ -284,33 +388,81 @@ impl EnvCheckerService {
         Ok(modified_files)
     }
 
-    /// 列出所有备份
-    pub fn list_backups() -> Result<Vec<PathBuf>, AppError> {
-        let home = get_home_dir();
-        let backup_dir = home.join(".cc-switch-backups");
+    /// 先备份 Shell 配置，再移除指定应用的冲突环境变量
+    ///
+    /// 备份失败时不做任何修改。
+    pub fn fix_conflicts(apps: &[AppType]) -> Result<EnvFixReport, AppError> {
+        let backup_dir = Self::backup_shell_configs()?;
 
-        if !backup_dir.exists() {
-            return Ok(Vec::new());
+        let mut modified = Vec::new();
+        for app in apps {
+            let files = Self::remove_env_from_shell_configs(*app)?;
+            modified.push((*app, files));
         }
 
+        Ok(EnvFixReport { backup_dir, modified })
+    }
+
+    /// 列出所有备份（含旧版本目录中的备份）
+    pub fn list_backups() -> Result<Vec<PathBuf>, AppError> {
         let mut backups: Vec<PathBuf> = Vec::new();
-        for entry in fs::read_dir(&backup_dir).map_err(|e| {
-            AppError::Message(format!("无法读取备份目录: {}", e))
-        })? {
-            let entry = entry.map_err(|e| {
-                AppError::Message(format!("无法读取目录项: {}", e))
-            })?;
-            let path: PathBuf = entry.path();
-            if path.is_dir() {
-                backups.push(path);
+        for backup_dir in [backup_root(), legacy_backup_root()] {
+            if !backup_dir.exists() {
+                continue;
+            }
+            for entry in fs::read_dir(&backup_dir).map_err(|e| {
+                AppError::Message(format!("无法读取备份目录: {}", e))
+            })? {
+                let entry = entry.map_err(|e| {
+                    AppError::Message(format!("无法读取目录项: {}", e))
+                })?;
+                let path: PathBuf = entry.path();
+                if path.is_dir() {
+                    backups.push(path);
+                }
             }
         }
 
-        // 按时间倒序排序
-        backups.sort_by(|a: &PathBuf, b: &PathBuf| b.cmp(a));
+        // 按时间倒序排序（目录名含时间戳）
+        backups.sort_by(|a: &PathBuf, b: &PathBuf| b.file_name().cmp(&a.file_name()));
         Ok(backups)
     }
 
+    /// 选择要恢复的备份
+    ///
+    /// 未指定时使用最新备份；纯数字按 [`list_backups`](Self::list_backups) 的序号（从 1 开始，1 为最新）选择，
+    /// 否则按备份目录名或完整路径匹配。
+    pub fn resolve_backup(selector: Option<&str>) -> Result<PathBuf, AppError> {
+        let backups = Self::list_backups()?;
+        if backups.is_empty() {
+            return Err(AppError::Config("没有可用的备份".to_string()));
+        }
+
+        let Some(selector) = selector.map(str::trim).filter(|s| !s.is_empty()) else {
+            return Ok(backups[0].clone());
+        };
+
+        if let Ok(index) = selector.parse::<usize>() {
+            return index
+                .checked_sub(1)
+                .and_then(|i| backups.get(i))
+                .cloned()
+                .ok_or_else(|| {
+                    AppError::InvalidInput(format!(
+                        "备份序号 {} 超出范围（共 {} 个备份）",
+                        index,
+                        backups.len()
+                    ))
+                });
+        }
+
+        let wanted = PathBuf::from(selector);
+        backups
+            .into_iter()
+            .find(|b| *b == wanted || b.file_name().is_some_and(|n| n == selector))
+            .ok_or_else(|| AppError::InvalidInput(format!("未找到备份: {}", selector)))
+    }
+
     /// 恢复备份
     pub fn restore_backup(backup_path: &PathBuf) -> Result<Vec<String>, AppError> {
         if !backup_path.exists() {
@@ -379,6 +531,8 @@ impl EnvCheckerService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::isolated_home;
+    use serial_test::serial;
 
     #[test]
     fn test_mask_value() {
@@ -401,4 +555,101 @@ export PATH="/usr/bin:$PATH"
         ));
         assert!(!EnvCheckerService::check_env_in_content(content, "OPENAI_API_KEY"));
     }
+
+    #[test]
+    #[serial]
+    fn test_fix_conflicts_backs_up_then_removes() {
+        let home = isolated_home();
+        let bashrc = home.path().join(".bashrc");
+        let original = "export PATH=\"/usr/bin:$PATH\"\nexport ANTHROPIC_API_KEY=\"sk-xxx\"\nANTHROPIC_BASE_URL=https://example.com\nexport OPENAI_API_KEY=sk-openai\n";
+        fs::write(&bashrc, original).unwrap();
+
+        let report = EnvCheckerService::fix_conflicts(&[AppType::Claude]).unwrap();
+
+        assert_eq!(fs::read_to_string(report.backup_dir.join(".bashrc")).unwrap(), original);
+        assert!(report.backup_dir.starts_with(home.path().join(".cc-switch/backups/env")));
+        assert_eq!(report.modified, vec![(AppType::Claude, vec![".bashrc".to_string()])]);
+
+        let content = fs::read_to_string(&bashrc).unwrap();
+        assert!(!content.contains("ANTHROPIC_"));
+        assert!(content.contains("export PATH="));
+        assert!(content.contains("OPENAI_API_KEY"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_resolve_backup_by_index_and_name() {
+        let home = isolated_home();
+        // 新目录与旧版本目录中的备份合并按时间排序
+        let root = home.path().join(".cc-switch/backups/env");
+        let legacy = home.path().join(".cc-switch-backups");
+        let latest = root.join("env_backup_20250102_090000");
+        let older = legacy.join("env_backup_20250101_090000");
+        for dir in [&latest, &older] {
+            fs::create_dir_all(dir).unwrap();
+        }
+
+        assert_eq!(EnvCheckerService::resolve_backup(None).unwrap(), latest);
+        assert_eq!(EnvCheckerService::resolve_backup(Some("1")).unwrap(), latest);
+        assert_eq!(EnvCheckerService::resolve_backup(Some("2")).unwrap(), older);
+        assert_eq!(
+            EnvCheckerService::resolve_backup(Some("env_backup_20250101_090000")).unwrap(),
+            older
+        );
+        assert_eq!(
+            EnvCheckerService::resolve_backup(Some(older.to_str().unwrap())).unwrap(),
+            older
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_resolve_backup_rejects_out_of_range_index() {
+        let home = isolated_home();
+        assert!(EnvCheckerService::resolve_backup(None).is_err());
+
+        fs::create_dir_all(home.path().join(".cc-switch-backups/env_backup_20250101_090000"))
+            .unwrap();
+        for selector in ["0", "2", "env_backup_19990101_000000"] {
+            let err = EnvCheckerService::resolve_backup(Some(selector)).unwrap_err();
+            assert!(matches!(err, AppError::InvalidInput(_)), "{}", selector);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_backups_follow_cc_switch_home() {
+        let home = isolated_home();
+        let data = tempfile::tempdir().unwrap();
+        std::env::remove_var("CCSWITCH_CONFIG_DIR");
+        std::env::set_var("CC_SWITCH_HOME", data.path());
+        fs::write(home.path().join(".bashrc"), "export PATH=\"/usr/bin:$PATH\"\n").unwrap();
+
+        let backup = EnvCheckerService::backup_shell_configs().unwrap();
+        assert!(backup.starts_with(data.path().join("backups/env")), "{}", backup.display());
+        assert_eq!(EnvCheckerService::list_backups().unwrap(), vec![backup]);
+
+        std::env::remove_var("CC_SWITCH_HOME");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_detect_tool_on_temp_path() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let bin = dir.path().join("fake-cli");
+        fs::write(&bin, "#!/bin/sh\necho 'fake-cli 1.2.3'\necho 'extra line'\n").unwrap();
+        fs::set_permissions(&bin, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let path_var = std::env::join_paths([dir.path()]).unwrap();
+        let status = EnvCheckerService::detect_tool_in("fake-cli", Some(&path_var));
+        assert!(status.installed);
+        assert_eq!(status.path.as_deref(), Some(bin.as_path()));
+        assert_eq!(status.version.as_deref(), Some("fake-cli 1.2.3"));
+
+        let missing = EnvCheckerService::detect_tool_in("not-there", Some(&path_var));
+        assert!(!missing.installed);
+        assert!(missing.version.is_none());
+    }
 }
@@ -0,0 +1,217 @@
+//! live 配置备份服务模块
+//!
+//! 覆盖各应用的 live 配置前自动备份，支持按保留数量清理与回滚。
+
+use std::fs;
+use std::path::PathBuf;
+
+use crate::app_config::AppType;
+use crate::config::{atomic_write, get_app_config_dir};
+use crate::error::AppError;
+use crate::settings;
+
+/// 默认保留的备份数量
+pub const DEFAULT_LIVE_BACKUP_RETENTION: usize = 5;
+
+/// live 配置备份服务
+pub struct LiveBackupService;
+
+impl LiveBackupService {
+    /// 应用的 live 配置文件（累加模式应用不备份）
+    pub fn live_files(app_type: AppType) -> Vec<PathBuf> {
+        if app_type.is_additive_mode() {
+            return Vec::new();
+        }
+        let paths = app_type.config_paths();
+        std::iter::once(paths.settings_path).chain(paths.auth_path).collect()
+    }
+
+    /// 应用的备份目录
+    pub fn backup_root(app_type: AppType) -> PathBuf {
+        get_app_config_dir().join("live-backups").join(app_type.as_str())
+    }
+
+    /// 备份当前 live 配置，并按设置的保留数量清理旧备份
+    ///
+    /// 保留数量设为 0 时按 1 处理，始终保留刚创建的备份。没有任何 live 文件时返回 `Ok(None)`。
+    pub fn backup(app_type: AppType) -> Result<Option<PathBuf>, AppError> {
+        let files: Vec<PathBuf> = Self::live_files(app_type)
+            .into_iter()
+            .filter(|p| p.is_file())
+            .collect();
+        if files.is_empty() {
+            return Ok(None);
+        }
+
+        let root = Self::backup_root(app_type);
+        let stamp = chrono::Local::now().format("%Y%m%d_%H%M%S_%6f").to_string();
+        let mut dir = root.join(&stamp);
+        let mut n = 2;
+        while dir.exists() {
+            dir = root.join(format!("{}-{}", stamp, n));
+            n += 1;
+        }
+        fs::create_dir_all(&dir).map_err(|e| AppError::io(&dir, e))?;
+
+        for file in &files {
+            let Some(name) = file.file_name() else { continue };
+            let dest = dir.join(name);
+            fs::copy(file, &dest).map_err(|e| AppError::io(&dest, e))?;
+        }
+
+        Self::prune(app_type, settings::live_backup_retention().max(1))?;
+        Ok(Some(dir))
+    }
+
+    /// 列出备份（最新在前）
+    pub fn list(app_type: AppType) -> Result<Vec<PathBuf>, AppError> {
+        let root = Self::backup_root(app_type);
+        if !root.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut backups: Vec<PathBuf> = fs::read_dir(&root)
+            .map_err(|e| AppError::io(&root, e))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.is_dir())
+            .collect();
+        backups.sort();
+        backups.reverse();
+        Ok(backups)
+    }
+
+    /// 仅保留最近 `keep` 个备份，返回删除的数量
+    pub fn prune(app_type: AppType, keep: usize) -> Result<usize, AppError> {
+        let backups = Self::list(app_type)?;
+        let mut removed = 0;
+        for dir in backups.iter().skip(keep) {
+            fs::remove_dir_all(dir).map_err(|e| AppError::io(dir, e))?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    /// 从备份恢复 live 配置
+    ///
+    /// `index` 为 1 起的序号（1 为最新），默认最新。恢复前会先备份当前配置。
+    pub fn restore(app_type: AppType, index: Option<usize>) -> Result<PathBuf, AppError> {
+        let backups = Self::list(app_type)?;
+        let index = index.unwrap_or(1);
+        let backup = index
+            .checked_sub(1)
+            .and_then(|i| backups.get(i))
+            .cloned()
+            .ok_or_else(|| {
+                AppError::InvalidInput(format!(
+                    "{} 没有序号为 {} 的备份（共 {} 个）",
+                    app_type.display_name(),
+                    index,
+                    backups.len()
+                ))
+            })?;
+
+        // 先读出备份内容：备份当前配置时的清理可能删除所选备份
+        let mut restored = Vec::new();
+        for target in Self::live_files(app_type) {
+            let Some(name) = target.file_name() else { continue };
+            let source = backup.join(name);
+            if source.is_file() {
+                let data = fs::read(&source).map_err(|e| AppError::io(&source, e))?;
+                restored.push((target, data));
+            }
+        }
+
+        Self::backup(app_type)?;
+        for (target, data) in &restored {
+            atomic_write(target, data)?;
+        }
+
+        Ok(backup)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::Provider;
+    use crate::services::ProviderService;
+    use crate::store::AppState;
+    use crate::test_support::isolated_home;
+    use serde_json::json;
+    use serial_test::serial;
+
+    fn claude_provider(id: &str, token: &str) -> Provider {
+        Provider::new(id, id, json!({"env": {"ANTHROPIC_AUTH_TOKEN": token}}))
+    }
+
+    #[test]
+    #[serial]
+    fn test_switch_backs_up_live_config() {
+        let home = isolated_home();
+        let settings_path = home.path().join(".claude").join("settings.json");
+        fs::create_dir_all(settings_path.parent().unwrap()).unwrap();
+        fs::write(&settings_path, r#"{"manual": true}"#).unwrap();
+
+        let state = AppState::memory().unwrap();
+        ProviderService::add(&state, AppType::Claude, claude_provider("p1", "sk-1")).unwrap();
+        ProviderService::switch(&state, AppType::Claude, "p1").unwrap();
+
+        // 最早的备份保存着手工修改的内容
+        let backups = LiveBackupService::list(AppType::Claude).unwrap();
+        assert!(!backups.is_empty());
+        let oldest = backups.len();
+        assert_eq!(
+            fs::read_to_string(backups[oldest - 1].join("settings.json")).unwrap(),
+            r#"{"manual": true}"#
+        );
+
+        LiveBackupService::restore(AppType::Claude, Some(oldest)).unwrap();
+        assert_eq!(fs::read_to_string(&settings_path).unwrap(), r#"{"manual": true}"#);
+    }
+
+    #[test]
+    #[serial]
+    fn test_backups_are_pruned_to_retention() {
+        let home = isolated_home();
+        settings::update_settings(|s| s.live_backup_retention = Some(2)).unwrap();
+        let settings_path = home.path().join(".claude").join("settings.json");
+        fs::create_dir_all(settings_path.parent().unwrap()).unwrap();
+
+        for i in 0..4 {
+            fs::write(&settings_path, format!(r#"{{"n": {}}}"#, i)).unwrap();
+            LiveBackupService::backup(AppType::Claude).unwrap();
+        }
+
+        let backups = LiveBackupService::list(AppType::Claude).unwrap();
+        assert_eq!(backups.len(), 2);
+        assert_eq!(
+            fs::read_to_string(backups[0].join("settings.json")).unwrap(),
+            r#"{"n": 3}"#
+        );
+        assert_eq!(
+            fs::read_to_string(backups[1].join("settings.json")).unwrap(),
+            r#"{"n": 2}"#
+        );
+        assert!(LiveBackupService::restore(AppType::Claude, Some(3)).is_err());
+
+        // 恢复最旧的备份时，即使它在备份当前配置后被清理，内容也能写回
+        LiveBackupService::restore(AppType::Claude, Some(2)).unwrap();
+        assert_eq!(fs::read_to_string(&settings_path).unwrap(), r#"{"n": 2}"#);
+    }
+
+    #[test]
+    #[serial]
+    fn test_zero_retention_keeps_new_backup() {
+        let home = isolated_home();
+        settings::update_settings(|s| s.live_backup_retention = Some(0)).unwrap();
+        let settings_path = home.path().join(".claude").join("settings.json");
+        fs::create_dir_all(settings_path.parent().unwrap()).unwrap();
+
+        for i in 0..2 {
+            fs::write(&settings_path, format!(r#"{{"n": {}}}"#, i)).unwrap();
+            let dir = LiveBackupService::backup(AppType::Claude).unwrap().unwrap();
+            assert!(dir.join("settings.json").is_file());
+        }
+        assert_eq!(LiveBackupService::list(AppType::Claude).unwrap().len(), 1);
+    }
+}
@@ -3,21 +3,64 @@
 //! 提供 MCP 服务器的业务逻辑，包括配置同步到各应用。
 
 use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsStr;
 use std::fs;
+use std::path::{Path, PathBuf};
 
-use crate::app_config::AppType;
-use crate::config::{
-    get_claude_mcp_path, get_codex_config_dir, get_gemini_config_dir, get_opencode_config_dir,
-    read_json_file, write_json_file, write_text_file,
-};
+use crate::app_config::{AppType, McpApps};
+use crate::config::{read_json_file, write_json_file, write_text_file};
 use crate::error::AppError;
 use crate::mcp::McpServer;
+use crate::services::env_checker::{find_in_path, is_executable};
+use crate::settings;
 use crate::store::AppState;
 
 /// MCP 服务器服务
 pub struct McpService;
 
+/// 可移植的 MCP 服务器包（`mcp export` / `mcp import <file>`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpBundle {
+    pub version: u32,
+    pub servers: Vec<McpServer>,
+}
+
+/// 导入 MCP 服务器包的结果
+#[derive(Debug, Clone, Default)]
+pub struct McpImportReport {
+    pub added: Vec<String>,
+    pub overwritten: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+/// 单个 MCP 服务器的校验结果（`mcp validate`）
+#[derive(Debug, Clone, Serialize)]
+pub struct McpCheckResult {
+    pub id: String,
+    pub transport: String,
+    pub problems: Vec<String>,
+}
+
+impl McpCheckResult {
+    /// 是否通过校验
+    pub fn passed(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// MCP 配置写入范围
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum McpScope {
+    /// 用户级：主目录下各应用的全局配置
+    #[default]
+    User,
+    /// 项目级：当前工作目录下的 `.mcp.json` 等项目文件
+    Project,
+}
+
 impl McpService {
     /// 列出所有 MCP 服务器
     pub fn list(state: &AppState) -> Result<IndexMap<String, McpServer>, AppError> {
@@ -91,18 +134,139 @@ impl McpService {
         app: AppType,
         enable: bool,
     ) -> Result<(), AppError> {
+        Self::toggle_apps(state, id, &[app], enable).map(|_| ())
+    }
+
+    /// 全局启用/禁用 MCP 服务器，不改变各应用的启用状态
+    ///
+    /// 禁用后该服务器不会写入任何应用的配置，重新启用时按原有应用状态同步。
+    pub fn set_enabled(state: &AppState, id: &str, enabled: bool) -> Result<McpServer, AppError> {
+        let mut server = state
+            .db
+            .get_mcp_server(id)?
+            .ok_or_else(|| AppError::NotFound(format!("MCP 服务器 '{}' 不存在", id)))?;
+
+        server.enabled = enabled;
+        state.db.set_mcp_server_enabled(id, enabled)?;
+        Self::sync_to_apps(state, &server)?;
+
+        Ok(server)
+    }
+
+    /// 为多个应用启用/禁用 MCP 服务器，每个应用只同步一次
+    ///
+    /// 不支持 MCP 的应用会被忽略，返回实际处理的应用列表。
+    pub fn toggle_apps(
+        state: &AppState,
+        id: &str,
+        apps: &[AppType],
+        enable: bool,
+    ) -> Result<Vec<AppType>, AppError> {
         let mut server = state
             .db
             .get_mcp_server(id)?
-            .ok_or_else(|| AppError::InvalidInput(format!("MCP 服务器 '{}' 不存在", id)))?;
+            .ok_or_else(|| AppError::NotFound(format!("MCP 服务器 '{}' 不存在", id)))?;
 
-        server.apps.set_enabled_for(&app, enable);
+        let mut targets: Vec<AppType> = Vec::new();
+        for app in apps {
+            if Self::config_path(app).is_some() && !targets.contains(app) {
+                targets.push(*app);
+            }
+        }
+
+        for app in &targets {
+            server.apps.set_enabled_for(app, enable);
+        }
         state.db.update_mcp_server_apps(id, &server.apps)?;
 
-        // 同步到该应用
-        Self::sync_to_app(state, &app)?;
+        // 同步到涉及的应用
+        for app in &targets {
+            Self::sync_to_app(state, app, McpScope::User)?;
+        }
 
-        Ok(())
+        Ok(targets)
+    }
+
+    /// 按范围启用/禁用 MCP 服务器
+    ///
+    /// 用户级等同于 [`Self::toggle_apps`]；项目级作用于当前工作目录，见 [`Self::toggle_apps_in_project`]。
+    pub fn toggle_apps_in_scope(
+        state: &AppState,
+        id: &str,
+        apps: &[AppType],
+        enable: bool,
+        scope: McpScope,
+    ) -> Result<Vec<AppType>, AppError> {
+        match scope {
+            McpScope::User => Self::toggle_apps(state, id, apps, enable),
+            McpScope::Project => Self::toggle_apps_in_project(state, id, apps, enable, &current_dir()?),
+        }
+    }
+
+    /// 在 `project_dir` 的项目文件中启用/禁用 MCP 服务器
+    ///
+    /// 只增删该服务器，不修改数据库中的启用状态，也不触碰全局配置。
+    pub fn toggle_apps_in_project(
+        state: &AppState,
+        id: &str,
+        apps: &[AppType],
+        enable: bool,
+        project_dir: &Path,
+    ) -> Result<Vec<AppType>, AppError> {
+        let server = state
+            .db
+            .get_mcp_server(id)?
+            .ok_or_else(|| AppError::NotFound(format!("MCP 服务器 '{}' 不存在", id)))?;
+        if enable && !server.enabled {
+            return Err(AppError::InvalidInput(format!(
+                "MCP 服务器 '{}' 已全局禁用，请先执行 cc-switch mcp toggle {} --global --enable",
+                id, id
+            )));
+        }
+
+        let mut targets: Vec<AppType> = Vec::new();
+        for app in apps {
+            if targets.contains(app) {
+                continue;
+            }
+            let Some(path) = Self::project_config_path(app, project_dir) else {
+                continue;
+            };
+            let mut servers = IndexMap::new();
+            let mut remove = HashSet::new();
+            if enable {
+                servers.insert(id.to_string(), server.server_config.clone());
+            } else {
+                remove.insert(id.to_string());
+            }
+            Self::write_mcp_file(app, &path, &servers, &remove)?;
+            targets.push(*app);
+        }
+
+        Ok(targets)
+    }
+
+    /// 复制 MCP 服务器配置到新 ID，新服务器默认不启用任何应用
+    pub fn duplicate(state: &AppState, id: &str, new_id: &str) -> Result<McpServer, AppError> {
+        let source = state
+            .db
+            .get_mcp_server(id)?
+            .ok_or_else(|| AppError::NotFound(format!("MCP 服务器 '{}' 不存在", id)))?;
+
+        let new_id = new_id.trim();
+        if new_id.is_empty() {
+            return Err(AppError::InvalidInput("新的 MCP 服务器 ID 不能为空".to_string()));
+        }
+
+        let mut server = source.clone();
+        server.id = new_id.to_string();
+        if source.name == source.id {
+            server.name = new_id.to_string();
+        }
+        server.apps = McpApps::default();
+
+        Self::add(state, server.clone())?;
+        Ok(server)
     }
 
     /// 从应用导入 MCP 服务器配置
@@ -126,138 +290,333 @@ impl McpService {
         Ok(imported)
     }
 
+    /// 从应用导入 MCP 服务器，并为来源应用及 `also` 中的应用启用、同步
+    pub fn import_and_enable(state: &AppState, app: AppType, also: &[AppType]) -> Result<Vec<String>, AppError> {
+        let imported = Self::import_from_app(state, app)?;
+        let apps: Vec<AppType> = std::iter::once(app).chain(also.iter().copied()).collect();
+        for id in &imported {
+            Self::toggle_apps(state, id, &apps, true)?;
+        }
+        Ok(imported)
+    }
+
+    /// 从原始 JSON 解析 MCP 服务器
+    ///
+    /// 支持单个服务器对象（需提供 `id`）或 `{"mcpServers": {...}}` 映射，
+    /// 所有配置均先校验，任一无效则整体报错。
+    pub fn parse_servers_json(value: &Value, id: Option<&str>) -> Result<Vec<McpServer>, AppError> {
+        if let Some(map) = value.get("mcpServers") {
+            if id.is_some() {
+                return Err(AppError::McpValidation(
+                    "mcpServers 映射已包含服务器 ID，不能再单独指定 ID".to_string(),
+                ));
+            }
+            let map = map.as_object().ok_or_else(|| {
+                AppError::McpValidation("mcpServers 必须是对象".to_string())
+            })?;
+            if map.is_empty() {
+                return Err(AppError::McpValidation("mcpServers 为空".to_string()));
+            }
+            return map
+                .iter()
+                .map(|(id, config)| {
+                    let config = Self::normalize_server_config(config)
+                        .map_err(|e| AppError::McpValidation(format!("{}: {}", id, e)))?;
+                    Ok(McpServer::new(id, id, config))
+                })
+                .collect();
+        }
+
+        let id = id.ok_or_else(|| {
+            AppError::McpValidation("单个服务器配置需要指定服务器 ID".to_string())
+        })?;
+        Ok(vec![McpServer::new(id, id, Self::normalize_server_config(value)?)])
+    }
+
+    /// 校验单个服务器配置，并根据 `command` / `url` 推断传输方式
+    pub fn normalize_server_config(config: &Value) -> Result<Value, AppError> {
+        let obj = config
+            .as_object()
+            .ok_or_else(|| AppError::McpValidation("服务器配置必须是 JSON 对象".to_string()))?;
+        let non_empty = |key: &str| {
+            obj.get(key)
+                .and_then(|v| v.as_str())
+                .is_some_and(|s| !s.trim().is_empty())
+        };
+
+        let transport = match obj.get("type").and_then(|v| v.as_str()) {
+            Some(t) => t.to_string(),
+            None if non_empty("command") => "stdio".to_string(),
+            None if non_empty("url") => "http".to_string(),
+            None => {
+                return Err(AppError::McpValidation(
+                    "服务器配置需要 command（stdio）或 url（http/sse）".to_string(),
+                ))
+            }
+        };
+
+        let mut normalized = obj.clone();
+        match transport.as_str() {
+            "stdio" => {
+                if !non_empty("command") {
+                    return Err(AppError::McpValidation("stdio 服务器缺少 command".to_string()));
+                }
+                if let Some(args) = obj.get("args") {
+                    let valid = args
+                        .as_array()
+                        .is_some_and(|a| a.iter().all(|v| v.is_string()));
+                    if !valid {
+                        return Err(AppError::McpValidation("args 必须是字符串数组".to_string()));
+                    }
+                }
+                if let Some(env) = obj.get("env") {
+                    let valid = env
+                        .as_object()
+                        .is_some_and(|e| e.values().all(|v| v.is_string()));
+                    if !valid {
+                        return Err(AppError::McpValidation("env 必须是字符串映射".to_string()));
+                    }
+                }
+            }
+            "http" | "sse" | "streamable-http" => {
+                let url = obj.get("url").and_then(|v| v.as_str()).unwrap_or("");
+                if !(url.starts_with("http://") || url.starts_with("https://")) {
+                    return Err(AppError::McpValidation(format!(
+                        "{} 服务器需要 http(s) url",
+                        transport
+                    )));
+                }
+                normalized.insert("type".to_string(), json!(transport));
+            }
+            other => {
+                return Err(AppError::McpValidation(format!("不支持的传输方式: {}", other)));
+            }
+        }
+
+        Ok(Value::Object(normalized))
+    }
+
+    /// 检查所有已保存的 MCP 服务器是否存在明显问题
+    pub fn validate_all(state: &AppState) -> Result<Vec<McpCheckResult>, AppError> {
+        let path_var = std::env::var_os("PATH").unwrap_or_default();
+        Ok(Self::check_servers(&Self::list(state)?, &path_var))
+    }
+
+    /// 逐个检查服务器：stdio 命令可在 PATH 中找到、env 值非空、
+    /// http url 可解析且使用 https（本机地址除外）、ID 不区分大小写时不重复
+    fn check_servers(servers: &IndexMap<String, McpServer>, path_var: &OsStr) -> Vec<McpCheckResult> {
+        let mut seen: HashMap<String, &str> = HashMap::new();
+
+        servers
+            .values()
+            .map(|server| {
+                let mut problems = Vec::new();
+                let config = &server.server_config;
+                let transport = match Self::normalize_server_config(config) {
+                    Ok(normalized) => normalized
+                        .get("type")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("stdio")
+                        .to_string(),
+                    Err(e) => {
+                        problems.push(e.to_string());
+                        "unknown".to_string()
+                    }
+                };
+
+                if transport == "stdio" {
+                    let command = config.get("command").and_then(|v| v.as_str()).unwrap_or("").trim();
+                    if !command.is_empty() && !command_resolvable(command, path_var) {
+                        problems.push(format!("命令 '{}' 不在 PATH 中", command));
+                    }
+                    if let Some(env) = config.get("env").and_then(|v| v.as_object()) {
+                        for (key, value) in env {
+                            if value.as_str().is_some_and(|v| v.trim().is_empty()) {
+                                problems.push(format!("环境变量 {} 的值为空", key));
+                            }
+                        }
+                    }
+                } else if transport != "unknown" {
+                    let url = config.get("url").and_then(|v| v.as_str()).unwrap_or("");
+                    match url::Url::parse(url) {
+                        Ok(parsed) if parsed.scheme() == "https" => {}
+                        Ok(parsed) if is_loopback_host(parsed.host_str()) => {}
+                        Ok(_) => problems.push(format!("url 未使用 https: {}", url)),
+                        Err(e) => problems.push(format!("url 无法解析: {} ({})", url, e)),
+                    }
+                }
+
+                match seen.get(&server.id.to_lowercase()) {
+                    Some(other) => problems.push(format!("ID 与 '{}' 重复（不区分大小写）", other)),
+                    None => {
+                        seen.insert(server.id.to_lowercase(), &server.id);
+                    }
+                }
+
+                McpCheckResult {
+                    id: server.id.clone(),
+                    transport,
+                    problems,
+                }
+            })
+            .collect()
+    }
+
+    /// 导出所有 MCP 服务器（包含各应用启用状态）
+    pub fn export_bundle(state: &AppState) -> Result<McpBundle, AppError> {
+        Ok(McpBundle {
+            version: 1,
+            servers: Self::list(state)?.into_values().collect(),
+        })
+    }
+
+    /// 导入 MCP 服务器包，ID 冲突时按 `overwrite` 覆盖或跳过，最后同步到所有应用
+    pub fn import_bundle(
+        state: &AppState,
+        bundle: McpBundle,
+        overwrite: bool,
+    ) -> Result<McpImportReport, AppError> {
+        let mut report = McpImportReport::default();
+
+        for server in bundle.servers {
+            let id = server.id.clone();
+            if state.db.get_mcp_server(&id)?.is_some() {
+                if overwrite {
+                    Self::update(state, server)?;
+                    report.overwritten.push(id);
+                } else {
+                    report.skipped.push(id);
+                }
+            } else {
+                Self::add(state, server)?;
+                report.added.push(id);
+            }
+        }
+
+        Self::sync_all(state)?;
+        Ok(report)
+    }
+
     /// 同步所有 MCP 服务器到所有应用
     pub fn sync_all(state: &AppState) -> Result<(), AppError> {
         for app in AppType::all() {
-            Self::sync_to_app(state, app)?;
+            Self::sync_to_app(state, app, McpScope::User)?;
         }
         Ok(())
     }
 
     /// 同步 MCP 服务器到指定应用
-    pub fn sync_to_app(state: &AppState, app: &AppType) -> Result<(), AppError> {
+    ///
+    /// 将数据库中为该应用启用的服务器写入 `scope` 对应的配置文件。文件中由用户直接添加、
+    /// 不归 cc-switch 管理的服务器保持不变；只移除数据库中已有或上次同步写入的条目。
+    pub fn sync_to_app(state: &AppState, app: &AppType, scope: McpScope) -> Result<(), AppError> {
+        let Some(path) = Self::scoped_config_path(app, scope)? else {
+            // OpenClaw 不支持 MCP，跳过
+            return Ok(());
+        };
         let servers = state.db.get_all_mcp_servers()?;
 
-        // 筛选出为该应用启用的服务器
+        // 筛选出为该应用启用的服务器，全局禁用的服务器一律跳过
         let enabled_servers: IndexMap<String, Value> = servers
             .iter()
-            .filter(|(_, s)| s.apps.is_enabled_for(app))
+            .filter(|(_, s)| s.enabled && s.apps.is_enabled_for(app))
             .map(|(id, s)| (id.clone(), s.server_config.clone()))
             .collect();
 
-        match app {
-            AppType::Claude => Self::write_claude_mcp(&enabled_servers)?,
-            AppType::Codex => Self::write_codex_mcp(&enabled_servers)?,
-            AppType::Gemini => Self::write_gemini_mcp(&enabled_servers)?,
-            AppType::OpenCode => Self::write_opencode_mcp(&enabled_servers)?,
-            AppType::OpenClaw => {
-                // OpenClaw 不支持 MCP，跳过
-            }
-        }
+        let remove: HashSet<String> = servers
+            .keys()
+            .cloned()
+            .chain(settings::mcp_managed_ids(app))
+            .filter(|id| !enabled_servers.contains_key(id))
+            .collect();
 
+        Self::write_mcp_file(app, &path, &enabled_servers, &remove)?;
+        if scope == McpScope::User {
+            settings::set_mcp_managed_ids(app, enabled_servers.keys().cloned().collect())?;
+        }
         Ok(())
     }
 
     /// 同步单个服务器到其启用的应用
     fn sync_to_apps(state: &AppState, server: &McpServer) -> Result<(), AppError> {
         for app in server.apps.enabled_apps() {
-            Self::sync_to_app(state, &app)?;
+            Self::sync_to_app(state, &app, McpScope::User)?;
         }
         Ok(())
     }
 
-    // ===== 配置文件读取 =====
+    /// 获取应用的 MCP 配置文件路径（不支持 MCP 的应用返回 None）
+    pub fn config_path(app: &AppType) -> Option<PathBuf> {
+        app.config_paths().mcp_path
+    }
 
-    /// 读取应用的 MCP 配置
-    fn read_app_mcp_config(app: &AppType) -> Result<IndexMap<String, Value>, AppError> {
-        match app {
-            AppType::Claude => Self::read_claude_mcp(),
-            AppType::Codex => Self::read_codex_mcp(),
-            AppType::Gemini => Self::read_gemini_mcp(),
-            AppType::OpenCode => Self::read_opencode_mcp(),
-            AppType::OpenClaw => Ok(IndexMap::new()), // OpenClaw 不支持 MCP
+    /// 获取指定范围的 MCP 配置文件路径（项目级相对当前工作目录）
+    pub fn scoped_config_path(app: &AppType, scope: McpScope) -> Result<Option<PathBuf>, AppError> {
+        match scope {
+            McpScope::User => Ok(Self::config_path(app)),
+            McpScope::Project => Ok(Self::project_config_path(app, &current_dir()?)),
         }
     }
 
-    /// 读取 Claude MCP 配置
-    fn read_claude_mcp() -> Result<IndexMap<String, Value>, AppError> {
-        let path = get_claude_mcp_path();
-        if !path.exists() {
-            return Ok(IndexMap::new());
+    /// 获取 `project_dir` 下的项目级 MCP 配置文件路径
+    pub fn project_config_path(app: &AppType, project_dir: &Path) -> Option<PathBuf> {
+        match app {
+            AppType::Claude => Some(project_dir.join(".mcp.json")),
+            AppType::Codex => Some(project_dir.join(".codex").join("config.toml")),
+            AppType::Gemini => Some(project_dir.join(".gemini").join("settings.json")),
+            AppType::OpenCode => Some(project_dir.join("opencode.json")),
+            AppType::OpenClaw => None,
         }
+    }
 
-        let config: Value = read_json_file(&path)?;
-        let servers = config
-            .get("mcpServers")
-            .and_then(|v| v.as_object())
-            .map(|obj| {
-                obj.iter()
-                    .map(|(k, v)| (k.clone(), v.clone()))
-                    .collect::<IndexMap<_, _>>()
+    /// 查找当前已写入该服务器配置的应用及其配置文件
+    pub fn synced_files(id: &str) -> Vec<(AppType, PathBuf)> {
+        AppType::all()
+            .iter()
+            .filter_map(|app| {
+                let path = Self::config_path(app)?;
+                let servers = Self::read_app_mcp_config(app).ok()?;
+                servers.contains_key(id).then_some((*app, path))
             })
-            .unwrap_or_default();
-
-        Ok(servers)
+            .collect()
     }
 
-    /// 读取 Codex MCP 配置
-    fn read_codex_mcp() -> Result<IndexMap<String, Value>, AppError> {
-        let path = get_codex_config_dir().join("config.toml");
-        if !path.exists() {
-            return Ok(IndexMap::new());
-        }
-
-        let content = fs::read_to_string(&path).map_err(|e| AppError::io(&path, e))?;
-        let config: toml::Value = content
-            .parse()
-            .map_err(|e| AppError::toml(&path, e))?;
-
-        let servers = config
-            .get("mcp_servers")
-            .and_then(|v| v.as_table())
-            .map(|table| {
-                table
-                    .iter()
-                    .map(|(k, v)| {
-                        let json_value = toml_to_json(v);
-                        (k.clone(), json_value)
-                    })
-                    .collect::<IndexMap<_, _>>()
-            })
-            .unwrap_or_default();
+    // ===== 配置文件读取 =====
 
-        Ok(servers)
+    /// 读取应用的 MCP 配置
+    fn read_app_mcp_config(app: &AppType) -> Result<IndexMap<String, Value>, AppError> {
+        match Self::config_path(app) {
+            Some(path) => Self::read_mcp_file(app, &path),
+            None => Ok(IndexMap::new()), // OpenClaw 不支持 MCP
+        }
     }
 
-    /// 读取 Gemini MCP 配置
-    fn read_gemini_mcp() -> Result<IndexMap<String, Value>, AppError> {
-        let path = get_gemini_config_dir().join("settings.json");
+    /// 读取 MCP 配置文件（Codex 为 TOML 的 `mcp_servers`，其余为 JSON 的 `mcpServers`）
+    fn read_mcp_file(app: &AppType, path: &Path) -> Result<IndexMap<String, Value>, AppError> {
         if !path.exists() {
             return Ok(IndexMap::new());
         }
 
-        let config: Value = read_json_file(&path)?;
-        let servers = config
-            .get("mcpServers")
-            .and_then(|v| v.as_object())
-            .map(|obj| {
-                obj.iter()
-                    .map(|(k, v)| (k.clone(), v.clone()))
-                    .collect::<IndexMap<_, _>>()
-            })
-            .unwrap_or_default();
-
-        Ok(servers)
-    }
-
-    /// 读取 OpenCode MCP 配置
-    fn read_opencode_mcp() -> Result<IndexMap<String, Value>, AppError> {
-        let path = get_opencode_config_dir().join("opencode.json");
-        if !path.exists() {
-            return Ok(IndexMap::new());
+        if *app == AppType::Codex {
+            let content = fs::read_to_string(path).map_err(|e| AppError::io(path, e))?;
+            let config: toml::Value = content
+                .parse()
+                .map_err(|e| AppError::toml(path, e))?;
+
+            let servers = config
+                .get("mcp_servers")
+                .and_then(|v| v.as_table())
+                .map(|table| {
+                    table
+                        .iter()
+                        .map(|(k, v)| (k.clone(), toml_to_json(v)))
+                        .collect::<IndexMap<_, _>>()
+                })
+                .unwrap_or_default();
+            return Ok(servers);
         }
 
-        let config: Value = read_json_file(&path)?;
+        let config: Value = read_json_file(path)?;
         let servers = config
             .get("mcpServers")
             .and_then(|v| v.as_object())
@@ -273,99 +632,87 @@ impl McpService {
 
     // ===== 配置文件写入 =====
 
-    /// 写入 Claude MCP 配置
-    fn write_claude_mcp(servers: &IndexMap<String, Value>) -> Result<(), AppError> {
-        let path = get_claude_mcp_path();
-
-        // 读取现有配置或创建新配置
-        let mut config: Value = if path.exists() {
-            read_json_file(&path).unwrap_or_else(|_| json!({}))
-        } else {
-            json!({})
-        };
-
-        // 更新 mcpServers
-        let mcp_servers: serde_json::Map<String, Value> = servers
-            .iter()
-            .map(|(k, v)| (k.clone(), v.clone()))
-            .collect();
-
-        config["mcpServers"] = Value::Object(mcp_servers);
-
-        write_json_file(&path, &config)
-    }
-
-    /// 写入 Codex MCP 配置
-    fn write_codex_mcp(servers: &IndexMap<String, Value>) -> Result<(), AppError> {
-        let path = get_codex_config_dir().join("config.toml");
+    /// 写入 MCP 配置文件：新增或更新 `servers`，删除 `remove` 中的条目，
+    /// 保留文件中的其他服务器与配置项
+    fn write_mcp_file(
+        app: &AppType,
+        path: &Path,
+        servers: &IndexMap<String, Value>,
+        remove: &HashSet<String>,
+    ) -> Result<(), AppError> {
+        if *app == AppType::Codex {
+            // 读取现有配置或创建新配置
+            let mut config: toml::Value = if path.exists() {
+                let content = fs::read_to_string(path).map_err(|e| AppError::io(path, e))?;
+                content.parse().unwrap_or_else(|_| toml::Value::Table(toml::map::Map::new()))
+            } else {
+                toml::Value::Table(toml::map::Map::new())
+            };
+
+            // 转换为 TOML 格式
+            let mut mcp_table = config
+                .get("mcp_servers")
+                .and_then(|v| v.as_table())
+                .cloned()
+                .unwrap_or_default();
+            mcp_table.retain(|id, _| !remove.contains(id));
+            for (id, server_config) in servers {
+                mcp_table.insert(id.clone(), json_to_toml(server_config));
+            }
 
-        // 读取现有配置或创建新配置
-        let mut config: toml::Value = if path.exists() {
-            let content = fs::read_to_string(&path).map_err(|e| AppError::io(&path, e))?;
-            content.parse().unwrap_or_else(|_| toml::Value::Table(toml::map::Map::new()))
-        } else {
-            toml::Value::Table(toml::map::Map::new())
-        };
+            if let toml::Value::Table(ref mut table) = config {
+                table.insert("mcp_servers".to_string(), toml::Value::Table(mcp_table));
+            }
 
-        // 转换为 TOML 格式
-        let mut mcp_table = toml::map::Map::new();
-        for (id, server_config) in servers {
-            mcp_table.insert(id.clone(), json_to_toml(server_config));
-        }
+            let toml_str = toml::to_string_pretty(&config)
+                .map_err(|e| AppError::Config(format!("TOML 序列化失败: {}", e)))?;
 
-        if let toml::Value::Table(ref mut table) = config {
-            table.insert("mcp_servers".to_string(), toml::Value::Table(mcp_table));
+            return write_text_file(path, &toml_str);
         }
 
-        let toml_str = toml::to_string_pretty(&config)
-            .map_err(|e| AppError::Config(format!("TOML 序列化失败: {}", e)))?;
-
-        write_text_file(&path, &toml_str)
-    }
-
-    /// 写入 Gemini MCP 配置
-    fn write_gemini_mcp(servers: &IndexMap<String, Value>) -> Result<(), AppError> {
-        let path = get_gemini_config_dir().join("settings.json");
-
         // 读取现有配置或创建新配置
         let mut config: Value = if path.exists() {
-            read_json_file(&path).unwrap_or_else(|_| json!({}))
+            read_json_file(path).unwrap_or_else(|_| json!({}))
         } else {
             json!({})
         };
 
         // 更新 mcpServers
-        let mcp_servers: serde_json::Map<String, Value> = servers
-            .iter()
-            .map(|(k, v)| (k.clone(), v.clone()))
-            .collect();
+        let mut mcp_servers = config
+            .get("mcpServers")
+            .and_then(|v| v.as_object())
+            .cloned()
+            .unwrap_or_default();
+        mcp_servers.retain(|id, _| !remove.contains(id));
+        for (id, server_config) in servers {
+            mcp_servers.insert(id.clone(), server_config.clone());
+        }
 
         config["mcpServers"] = Value::Object(mcp_servers);
 
-        write_json_file(&path, &config)
+        write_json_file(path, &config)
     }
+}
 
-    /// 写入 OpenCode MCP 配置
-    fn write_opencode_mcp(servers: &IndexMap<String, Value>) -> Result<(), AppError> {
-        let path = get_opencode_config_dir().join("opencode.json");
-
-        // 读取现有配置或创建新配置
-        let mut config: Value = if path.exists() {
-            read_json_file(&path).unwrap_or_else(|_| json!({}))
-        } else {
-            json!({})
-        };
-
-        // 更新 mcpServers
-        let mcp_servers: serde_json::Map<String, Value> = servers
-            .iter()
-            .map(|(k, v)| (k.clone(), v.clone()))
-            .collect();
-
-        config["mcpServers"] = Value::Object(mcp_servers);
+/// 当前工作目录
+fn current_dir() -> Result<PathBuf, AppError> {
+    std::env::current_dir().map_err(|e| AppError::IoContext {
+        context: "无法获取当前工作目录".to_string(),
+        source: e,
+    })
+}
 
-        write_json_file(&path, &config)
+/// stdio 命令是否可执行：含路径分隔符时检查文件本身，否则在 PATH 中查找
+fn command_resolvable(command: &str, path_var: &OsStr) -> bool {
+    if command.contains('/') || command.contains('\\') {
+        return is_executable(Path::new(command));
     }
+    find_in_path(command, path_var).is_some()
+}
+
+/// 是否为本机回环地址
+fn is_loopback_host(host: Option<&str>) -> bool {
+    matches!(host, Some("localhost" | "127.0.0.1" | "[::1]" | "::1"))
 }
 
 /// 将 TOML 值转换为 JSON 值
@@ -420,7 +767,10 @@ fn json_to_toml(value: &Value) -> toml::Value {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::get_claude_mcp_path;
+    use crate::test_support::isolated_home;
     use serde_json::json;
+    use serial_test::serial;
 
     #[test]
     fn test_mcp_service_add_and_list() {
@@ -434,6 +784,183 @@ mod tests {
         assert!(servers.contains_key("test-server"));
     }
 
+    #[test]
+    fn test_check_servers_flags_problems() {
+        let bin = tempfile::tempdir().unwrap();
+        let mut servers = IndexMap::new();
+        for server in [
+            McpServer::new("missing", "missing", json!({"command": "no-such-mcp-binary"})),
+            McpServer::new("remote", "remote", json!({"type": "http", "url": "https://mcp.example.com/mcp"})),
+            McpServer::new("plain", "plain", json!({"type": "sse", "url": "http://mcp.example.com/sse"})),
+            McpServer::new("Remote", "Remote", json!({"type": "http", "url": "http://localhost:3000"})),
+        ] {
+            servers.insert(server.id.clone(), server);
+        }
+
+        let results = McpService::check_servers(&servers, bin.path().as_os_str());
+        let by_id = |id: &str| results.iter().find(|r| r.id == id).unwrap();
+
+        assert!(!by_id("missing").passed());
+        assert!(by_id("missing").problems[0].contains("no-such-mcp-binary"));
+        assert!(by_id("remote").passed(), "{:?}", by_id("remote").problems);
+        assert_eq!(by_id("remote").transport, "http");
+        assert!(by_id("plain").problems[0].contains("https"));
+        // 本机地址允许 http，但 ID 与 remote 仅大小写不同
+        assert_eq!(by_id("Remote").problems.len(), 1);
+        assert!(by_id("Remote").problems[0].contains("重复"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_servers_resolves_command_and_env() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let bin = tempfile::tempdir().unwrap();
+        let exe = bin.path().join("my-mcp");
+        fs::write(&exe, "#!/bin/sh\n").unwrap();
+        fs::set_permissions(&exe, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut servers = IndexMap::new();
+        let ok = McpServer::new("ok", "ok", json!({"command": "my-mcp", "env": {"TOKEN": "x"}}));
+        let empty_env = McpServer::new("env", "env", json!({"command": "my-mcp", "env": {"TOKEN": " "}}));
+        servers.insert(ok.id.clone(), ok);
+        servers.insert(empty_env.id.clone(), empty_env);
+
+        let results = McpService::check_servers(&servers, bin.path().as_os_str());
+        assert!(results[0].passed(), "{:?}", results[0].problems);
+        assert_eq!(results[1].problems, vec!["环境变量 TOKEN 的值为空".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_servers_json() {
+        let map = json!({
+            "mcpServers": {
+                "fs": { "command": "npx", "args": ["-y", "@mcp/fs"] },
+                "remote": { "url": "https://mcp.example.com/mcp" }
+            }
+        });
+        let servers = McpService::parse_servers_json(&map, None).unwrap();
+        assert_eq!(servers.len(), 2);
+        assert_eq!(servers[0].server_config.get("type"), None);
+        assert_eq!(servers[1].server_config["type"], "http");
+        assert!(McpService::parse_servers_json(&map, Some("x")).is_err());
+
+        let single = json!({ "type": "sse", "url": "http://localhost:3000/sse" });
+        let servers = McpService::parse_servers_json(&single, Some("local")).unwrap();
+        assert_eq!(servers[0].id, "local");
+        assert!(McpService::parse_servers_json(&single, None).is_err());
+
+        for bad in [
+            json!({}),
+            json!({ "command": "" }),
+            json!({ "command": "npx", "args": "-y" }),
+            json!({ "url": "ftp://example.com" }),
+            json!({ "type": "ws", "url": "https://example.com" }),
+        ] {
+            assert!(matches!(
+                McpService::parse_servers_json(&bad, Some("bad")),
+                Err(AppError::McpValidation(_))
+            ));
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_project_scope_leaves_global_untouched() {
+        let home = isolated_home();
+        let project = tempfile::tempdir().unwrap();
+
+        let state = AppState::memory().unwrap();
+        McpService::add(&state, McpServer::new("fs", "fs", json!({"command": "npx"}))).unwrap();
+        let toggled = McpService::toggle_apps_in_project(
+            &state,
+            "fs",
+            &[AppType::Claude, AppType::Codex],
+            true,
+            project.path(),
+        )
+        .unwrap();
+        assert_eq!(toggled, vec![AppType::Claude, AppType::Codex]);
+
+        let claude: Value = read_json_file(&project.path().join(".mcp.json")).unwrap();
+        assert_eq!(claude["mcpServers"]["fs"]["command"], "npx");
+        let codex = fs::read_to_string(project.path().join(".codex").join("config.toml")).unwrap();
+        assert!(codex.contains("[mcp_servers.fs]"));
+
+        // 全局配置与数据库启用状态均未改变
+        assert!(!get_claude_mcp_path().exists());
+        assert!(!home.path().join(".codex").join("config.toml").exists());
+        assert!(!McpService::get(&state, "fs").unwrap().unwrap().apps.claude);
+
+        // 禁用时从项目文件移除
+        McpService::toggle_apps_in_project(&state, "fs", &[AppType::Claude], false, project.path()).unwrap();
+        let claude: Value = read_json_file(&project.path().join(".mcp.json")).unwrap();
+        assert_eq!(claude["mcpServers"], json!({}));
+    }
+
+    #[test]
+    #[serial]
+    fn test_sync_preserves_hand_added_servers() {
+        let home = isolated_home();
+        write_json_file(
+            &get_claude_mcp_path(),
+            &json!({"mcpServers": {"mine": {"command": "my-mcp"}}, "theme": "dark"}),
+        )
+        .unwrap();
+        let codex_path = home.path().join(".codex").join("config.toml");
+        fs::create_dir_all(codex_path.parent().unwrap()).unwrap();
+        fs::write(&codex_path, "[mcp_servers.mine]\ncommand = \"my-mcp\"\n").unwrap();
+
+        let state = AppState::memory().unwrap();
+        McpService::add(&state, McpServer::new("fs", "fs", json!({"command": "npx"}))).unwrap();
+        McpService::toggle_apps(&state, "fs", &[AppType::Claude, AppType::Codex], true).unwrap();
+
+        let claude: Value = read_json_file(&get_claude_mcp_path()).unwrap();
+        assert_eq!(claude["mcpServers"]["mine"]["command"], "my-mcp");
+        assert_eq!(claude["mcpServers"]["fs"]["command"], "npx");
+        assert_eq!(claude["theme"], "dark");
+        let codex = fs::read_to_string(&codex_path).unwrap();
+        assert!(codex.contains("[mcp_servers.mine]") && codex.contains("[mcp_servers.fs]"));
+        assert_eq!(settings::mcp_managed_ids(&AppType::Claude), ["fs"]);
+
+        // 删除 cc-switch 管理的服务器后只移除该条目
+        McpService::remove(&state, "fs").unwrap();
+        let claude: Value = read_json_file(&get_claude_mcp_path()).unwrap();
+        assert_eq!(claude["mcpServers"], json!({"mine": {"command": "my-mcp"}}));
+        let codex = fs::read_to_string(&codex_path).unwrap();
+        assert!(codex.contains("[mcp_servers.mine]") && !codex.contains("[mcp_servers.fs]"));
+        assert!(settings::mcp_managed_ids(&AppType::Claude).is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_import_and_enable_from_claude() {
+        let home = isolated_home();
+        write_json_file(
+            &get_claude_mcp_path(),
+            &json!({"mcpServers": {
+                "fs": {"command": "npx", "args": ["-y", "@modelcontextprotocol/server-filesystem"]},
+                "docs": {"type": "http", "url": "https://mcp.example.com"}
+            }}),
+        )
+        .unwrap();
+
+        let state = AppState::memory().unwrap();
+        let mut imported = McpService::import_and_enable(&state, AppType::Claude, &[AppType::Codex]).unwrap();
+        imported.sort();
+        assert_eq!(imported, ["docs", "fs"]);
+
+        for id in ["fs", "docs"] {
+            let server = McpService::get(&state, id).unwrap().unwrap();
+            assert!(server.apps.claude && server.apps.codex && !server.apps.gemini, "{}", id);
+        }
+        let codex = fs::read_to_string(home.path().join(".codex").join("config.toml")).unwrap();
+        assert!(codex.contains("[mcp_servers.fs]") && codex.contains("[mcp_servers.docs]"));
+
+        // 再次导入时已存在的服务器被跳过
+        assert!(McpService::import_and_enable(&state, AppType::Claude, &[]).unwrap().is_empty());
+    }
+
     #[test]
     fn test_mcp_service_toggle() {
         let state = AppState::memory().unwrap();
@@ -447,4 +974,116 @@ mod tests {
         assert!(server.apps.claude);
         assert!(!server.apps.codex);
     }
+
+    #[test]
+    #[serial]
+    fn test_globally_disabled_server_is_never_synced() {
+        let _home = isolated_home();
+        let state = AppState::memory().unwrap();
+
+        let mut server = McpServer::new("off", "off", json!({"command": "npx"}));
+        server.apps.claude = true;
+        server.enabled = false;
+        McpService::add(&state, server).unwrap();
+        let mut on = McpServer::new("on", "on", json!({"command": "uvx"}));
+        on.apps.claude = true;
+        McpService::add(&state, on).unwrap();
+
+        McpService::sync_all(&state).unwrap();
+        let synced = McpService::read_app_mcp_config(&AppType::Claude).unwrap();
+        assert!(synced.contains_key("on") && !synced.contains_key("off"));
+
+        // 应用标记仍保留，重新全局启用后恢复同步
+        McpService::set_enabled(&state, "off", true).unwrap();
+        assert!(McpService::read_app_mcp_config(&AppType::Claude).unwrap().contains_key("off"));
+
+        McpService::set_enabled(&state, "off", false).unwrap();
+        let server = McpService::get(&state, "off").unwrap().unwrap();
+        assert!(!server.enabled && server.apps.claude);
+        assert!(!McpService::read_app_mcp_config(&AppType::Claude).unwrap().contains_key("off"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_mcp_toggle_all_apps() {
+        let _home = isolated_home();
+        let state = AppState::memory().unwrap();
+        McpService::add(&state, McpServer::new("srv", "srv", json!({"command": "npx"}))).unwrap();
+
+        let toggled = McpService::toggle_apps(&state, "srv", AppType::all(), true).unwrap();
+        assert!(!toggled.contains(&AppType::OpenClaw));
+
+        let server = McpService::get(&state, "srv").unwrap().unwrap();
+        assert!(server.apps.claude && server.apps.codex && server.apps.gemini && server.apps.opencode);
+
+        McpService::toggle_apps(&state, "srv", AppType::all(), false).unwrap();
+        let server = McpService::get(&state, "srv").unwrap().unwrap();
+        assert!(server.apps.enabled_apps().is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_mcp_duplicate_is_independent() {
+        let _home = isolated_home();
+        let state = AppState::memory().unwrap();
+        McpService::add(
+            &state,
+            McpServer::new("srv", "srv", json!({"command": "npx", "args": ["-y", "pkg"]})),
+        )
+        .unwrap();
+        McpService::toggle(&state, "srv", AppType::Claude, true).unwrap();
+
+        let copy = McpService::duplicate(&state, "srv", "srv-copy").unwrap();
+        assert_eq!(copy.id, "srv-copy");
+        assert_eq!(copy.name, "srv-copy");
+        assert!(copy.apps.enabled_apps().is_empty());
+
+        let mut edited = McpService::get(&state, "srv-copy").unwrap().unwrap();
+        edited.server_config["args"] = json!(["changed"]);
+        McpService::update(&state, edited).unwrap();
+
+        let original = McpService::get(&state, "srv").unwrap().unwrap();
+        assert_eq!(original.server_config["args"], json!(["-y", "pkg"]));
+        assert!(original.apps.claude);
+        assert!(McpService::duplicate(&state, "srv", "srv-copy").is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_mcp_bundle_round_trip() {
+        let _home = isolated_home();
+        let state = AppState::memory().unwrap();
+
+        let mut fs_server = McpServer::new("fs", "Filesystem", json!({"command": "npx", "args": ["fs"]}));
+        fs_server.apps.claude = true;
+        fs_server.apps.gemini = true;
+        let mut git = McpServer::new("git", "Git", json!({"command": "uvx", "env": {"A": "1"}}));
+        git.apps.codex = true;
+        McpService::add(&state, fs_server).unwrap();
+        McpService::add(&state, git).unwrap();
+
+        let exported = serde_json::to_string(&McpService::export_bundle(&state).unwrap()).unwrap();
+        let before = McpService::list(&state).unwrap();
+
+        for id in ["fs", "git"] {
+            state.db.delete_mcp_server(id).unwrap();
+        }
+        assert!(McpService::list(&state).unwrap().is_empty());
+
+        let bundle: McpBundle = serde_json::from_str(&exported).unwrap();
+        let report = McpService::import_bundle(&state, bundle.clone(), false).unwrap();
+        assert_eq!(report.added, vec!["fs", "git"]);
+
+        let after = McpService::list(&state).unwrap();
+        for id in ["fs", "git"] {
+            assert_eq!(after[id].server_config, before[id].server_config);
+            assert_eq!(after[id].apps, before[id].apps);
+        }
+
+        // 再次导入：默认跳过，--overwrite 时覆盖
+        let report = McpService::import_bundle(&state, bundle.clone(), false).unwrap();
+        assert_eq!(report.skipped, vec!["fs", "git"]);
+        let report = McpService::import_bundle(&state, bundle, true).unwrap();
+        assert_eq!(report.overwritten, vec!["fs", "git"]);
+    }
 }
@@ -2,11 +2,14 @@
 //!
 //! 提供业务逻辑服务，包括供应商管理、配置同步、MCP 服务器管理、Prompt 管理等。
 
+pub mod backup;
 pub mod config;
 pub mod env_checker;
 pub mod failover;
 pub mod global_proxy;
+pub mod live_backup;
 pub mod mcp;
+pub mod profile;
 pub mod prompt;
 pub mod provider;
 pub mod skill;
@@ -15,11 +18,14 @@ pub mod stream_check;
 pub mod usage_stats;
 pub mod webdav_sync;
 
+pub use backup::BackupService;
 pub use config::ConfigService;
 pub use env_checker::EnvCheckerService;
 pub use failover::FailoverService;
 pub use global_proxy::ProxyService;
+pub use live_backup::LiveBackupService;
 pub use mcp::McpService;
+pub use profile::ProfileService;
 pub use prompt::PromptService;
 pub use provider::ProviderService;
 pub use skill::SkillService;
@@ -0,0 +1,132 @@
+//! Profile 管理服务模块
+//!
+//! 已创建的 profile 名称记录在数据库设置 `profiles` 中（JSON 数组）。
+
+use crate::error::AppError;
+use crate::profile::{validate_profile_name, DEFAULT_PROFILE};
+use crate::store::AppState;
+
+/// 记录已创建 profile 的设置键
+pub const PROFILES_SETTING_KEY: &str = "profiles";
+
+/// Profile 管理服务
+pub struct ProfileService;
+
+impl ProfileService {
+    /// 列出全部 profile（默认 profile 在首位）
+    pub fn list(state: &AppState) -> Result<Vec<String>, AppError> {
+        let mut profiles = Self::registered(state)?;
+        profiles.extend(state.db.list_provider_profiles()?);
+        profiles.sort();
+        profiles.dedup();
+        profiles.insert(0, DEFAULT_PROFILE.to_string());
+        Ok(profiles)
+    }
+
+    /// profile 是否存在
+    pub fn exists(state: &AppState, name: &str) -> Result<bool, AppError> {
+        Ok(Self::list(state)?.iter().any(|p| p == name))
+    }
+
+    /// 创建 profile
+    pub fn create(state: &AppState, name: &str) -> Result<(), AppError> {
+        validate_profile_name(name)?;
+        if Self::exists(state, name)? {
+            return Err(AppError::InvalidInput(format!("profile '{}' 已存在", name)));
+        }
+
+        let mut profiles = Self::registered(state)?;
+        profiles.push(name.to_string());
+        Self::save_registered(state, &profiles)
+    }
+
+    /// 删除 profile 及其全部供应商，返回删除的供应商数量
+    ///
+    /// 默认 profile 与当前激活的 profile 不可删除。
+    pub fn delete(state: &AppState, name: &str) -> Result<usize, AppError> {
+        if name == DEFAULT_PROFILE {
+            return Err(AppError::InvalidInput("默认 profile 不可删除".to_string()));
+        }
+        if state.db.profile() == Some(name) {
+            return Err(AppError::InvalidInput(format!(
+                "profile '{}' 正在使用中，请切换到其他 profile 后再删除",
+                name
+            )));
+        }
+        if !Self::exists(state, name)? {
+            return Err(AppError::NotFound(format!("profile '{}' 不存在", name)));
+        }
+
+        let removed = state.db.delete_profile_providers(name)?;
+        let profiles: Vec<String> = Self::registered(state)?
+            .into_iter()
+            .filter(|p| p != name)
+            .collect();
+        Self::save_registered(state, &profiles)?;
+        Ok(removed)
+    }
+
+    fn registered(state: &AppState) -> Result<Vec<String>, AppError> {
+        match state.db.get_setting(PROFILES_SETTING_KEY)? {
+            Some(raw) => serde_json::from_str(&raw)
+                .map_err(|e| AppError::Config(format!("profile 列表解析失败: {}", e))),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    fn save_registered(state: &AppState, profiles: &[String]) -> Result<(), AppError> {
+        let raw = serde_json::to_string(profiles)
+            .map_err(|e| AppError::Config(format!("JSON serialization failed: {e}")))?;
+        state.db.set_setting(PROFILES_SETTING_KEY, &raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_config::AppType;
+    use crate::database::Database;
+    use crate::provider::Provider;
+    use crate::services::ProviderService;
+    use crate::test_support::isolated_home;
+    use serde_json::json;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn test_profile_providers_are_isolated_from_default() {
+        let home = isolated_home();
+        let db_path = home.path().join("profiles.db");
+        let default_state = AppState::with_database(Database::open(&db_path).unwrap());
+        let work_state = AppState::with_database(
+            Database::open(&db_path).unwrap().with_profile(Some("work".to_string())),
+        );
+
+        ProfileService::create(&default_state, "work").unwrap();
+        ProviderService::add(
+            &work_state,
+            AppType::Claude,
+            Provider::new("w1", "Work", json!({"env": {"ANTHROPIC_AUTH_TOKEN": "sk-w"}})),
+        )
+        .unwrap();
+
+        assert!(ProviderService::list(&default_state, AppType::Claude).unwrap().is_empty());
+        assert!(ProviderService::list(&work_state, AppType::Claude).unwrap().contains_key("w1"));
+        assert_eq!(ProfileService::list(&default_state).unwrap(), vec!["default", "work"]);
+
+        assert!(ProfileService::delete(&work_state, "work").is_err());
+        assert_eq!(ProfileService::delete(&default_state, "work").unwrap(), 1);
+        assert!(ProviderService::list(&work_state, AppType::Claude).unwrap().is_empty());
+        assert_eq!(ProfileService::list(&default_state).unwrap(), vec!["default"]);
+    }
+
+    #[test]
+    fn test_create_rejects_invalid_and_duplicate_names() {
+        let state = AppState::memory().unwrap();
+        assert!(ProfileService::create(&state, "a:b").is_err());
+        assert!(ProfileService::create(&state, "default").is_err());
+        ProfileService::create(&state, "work").unwrap();
+        assert!(ProfileService::create(&state, "work").is_err());
+        assert!(ProfileService::delete(&state, "default").is_err());
+    }
+}
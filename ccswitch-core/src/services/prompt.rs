@@ -14,7 +14,46 @@ use crate::store::AppState;
 /// Prompt 服务
 pub struct PromptService;
 
+/// 当前生效 Prompt 的设置键
+fn active_key(app: AppType) -> String {
+    format!("prompt_active_{}", app.as_str())
+}
+
+/// 启用 Prompt 前原 live 文件内容的设置键
+fn backup_key(app: AppType) -> String {
+    format!("prompt_backup_{}", app.as_str())
+}
+
 impl PromptService {
+    /// 获取应用当前生效的 Prompt ID
+    pub fn active(state: &AppState, app: AppType) -> Result<Option<String>, AppError> {
+        state.db.get_setting(&active_key(app))
+    }
+
+    /// 记录应用当前生效的 Prompt
+    fn set_active(state: &AppState, app: AppType, id: Option<&str>) -> Result<(), AppError> {
+        match id {
+            Some(id) => state.db.set_setting(&active_key(app), id),
+            None => state.db.delete_setting(&active_key(app)),
+        }
+    }
+
+    /// 首次启用 Prompt 时备份 live 文件中原有的内容，禁用后可恢复
+    fn backup_live_if_unmanaged(state: &AppState, app: AppType) -> Result<(), AppError> {
+        if Self::active(state, app)?.is_some() {
+            return Ok(());
+        }
+        let path = get_prompt_path(&app);
+        if !path.exists() {
+            return Ok(());
+        }
+        let content = fs::read_to_string(&path).map_err(|e| AppError::io(&path, e))?;
+        if !content.trim().is_empty() {
+            state.db.set_setting(&backup_key(app), &content)?;
+        }
+        Ok(())
+    }
+
     /// 列出指定应用的所有 Prompts
     pub fn list(state: &AppState, app: AppType) -> Result<IndexMap<String, Prompt>, AppError> {
         state.db.get_all_prompts(app.as_str())
@@ -68,7 +107,7 @@ impl PromptService {
         // 检查是否存在
         let prompt = state.db.get_prompt(app.as_str(), id)?;
         if prompt.is_none() {
-            return Err(AppError::InvalidInput(format!("Prompt '{}' 不存在", id)));
+            return Err(AppError::NotFound(format!("Prompt '{}' 不存在", id)));
         }
 
         let was_enabled = prompt.unwrap().enabled;
@@ -85,9 +124,27 @@ impl PromptService {
 
     /// 启用 Prompt（同时禁用其他 Prompt）
     pub fn enable(state: &AppState, app: AppType, id: &str) -> Result<(), AppError> {
-        // 检查是否存在
-        if state.db.get_prompt(app.as_str(), id)?.is_none() {
-            return Err(AppError::InvalidInput(format!("Prompt '{}' 不存在", id)));
+        Self::enable_with_vars(state, app, id, &IndexMap::new())
+    }
+
+    /// 启用 Prompt 并设置模板变量
+    ///
+    /// 传入的变量会保存为该 Prompt 的默认值，后续同步时沿用。
+    pub fn enable_with_vars(
+        state: &AppState,
+        app: AppType,
+        id: &str,
+        vars: &IndexMap<String, String>,
+    ) -> Result<(), AppError> {
+        let Some(mut prompt) = state.db.get_prompt(app.as_str(), id)? else {
+            return Err(AppError::NotFound(format!("Prompt '{}' 不存在", id)));
+        };
+
+        // 先校验变量是否齐全，避免写入未替换的内容
+        prompt.render(vars)?;
+        if !vars.is_empty() {
+            prompt.variables.extend(vars.clone());
+            state.db.save_prompt(app.as_str(), &prompt)?;
         }
 
         // 禁用所有其他 Prompt
@@ -98,6 +155,8 @@ impl PromptService {
             }
         }
 
+        Self::backup_live_if_unmanaged(state, app)?;
+
         // 启用指定的 Prompt
         state.db.update_prompt_enabled(app.as_str(), id, true)?;
 
@@ -107,22 +166,39 @@ impl PromptService {
         Ok(())
     }
 
+    /// 预览替换变量后的 Prompt 内容（不写入文件）
+    pub fn render(
+        state: &AppState,
+        app: AppType,
+        id: &str,
+        vars: &IndexMap<String, String>,
+    ) -> Result<String, AppError> {
+        let prompt = state
+            .db
+            .get_prompt(app.as_str(), id)?
+            .ok_or_else(|| AppError::NotFound(format!("Prompt '{}' 不存在", id)))?;
+        prompt.render(vars)
+    }
+
     /// 禁用 Prompt
     pub fn disable(state: &AppState, app: AppType, id: &str) -> Result<(), AppError> {
         // 检查是否存在
         if state.db.get_prompt(app.as_str(), id)?.is_none() {
-            return Err(AppError::InvalidInput(format!("Prompt '{}' 不存在", id)));
+            return Err(AppError::NotFound(format!("Prompt '{}' 不存在", id)));
         }
 
         state.db.update_prompt_enabled(app.as_str(), id, false)?;
 
-        // 同步到应用（清空 Prompt 文件）
+        // 同步到应用（恢复原有内容或清空 Prompt 文件）
         Self::sync_to_app(state, app)?;
 
         Ok(())
     }
 
     /// 从应用导入 Prompt
+    ///
+    /// 将应用现有的 Prompt 文件（如 `~/.claude/CLAUDE.md`）登记为名为 `imported-<app>` 的 Prompt 并设为生效。
+    /// 文件不存在、为空或内容已由某个 Prompt 管理时返回 `Ok(None)`。
     pub fn import_from_app(state: &AppState, app: AppType) -> Result<Option<String>, AppError> {
         let path = get_prompt_path(&app);
 
@@ -136,19 +212,21 @@ impl PromptService {
             return Ok(None);
         }
 
-        // 生成 ID
-        let id = format!("imported-{}", chrono::Utc::now().timestamp());
-        let name = format!("从 {} 导入", app.display_name());
+        let prompts = state.db.get_all_prompts(app.as_str())?;
+        if prompts.values().any(|p| p.content == content) {
+            return Ok(None);
+        }
 
-        let prompt = Prompt::new(&id, &name, content).with_enabled(true);
+        let id = format!("imported-{}", app.as_str());
+        let prompt = Prompt::new(&id, &id, content).with_enabled(true);
 
         // 禁用其他 Prompt
-        let prompts = state.db.get_all_prompts(app.as_str())?;
-        for (prompt_id, _) in prompts {
-            state.db.update_prompt_enabled(app.as_str(), &prompt_id, false)?;
+        for prompt_id in prompts.keys() {
+            state.db.update_prompt_enabled(app.as_str(), prompt_id, false)?;
         }
 
         state.db.save_prompt(app.as_str(), &prompt)?;
+        Self::set_active(state, app, Some(&id))?;
 
         Ok(Some(id))
     }
@@ -162,14 +240,20 @@ impl PromptService {
 
         match enabled_prompt {
             Some(prompt) => {
-                // 写入 Prompt 内容
-                write_text_file(&path, &prompt.content)?;
+                // 写入替换变量后的 Prompt 内容
+                let content = prompt.render(&IndexMap::new())?;
+                write_text_file(&path, &content)?;
+                Self::set_active(state, app, Some(&prompt.id))?;
             }
             None => {
-                // 如果没有启用的 Prompt，清空文件（如果存在）
-                if path.exists() {
+                // 没有启用的 Prompt：恢复启用前的内容，否则清空文件（如果存在）
+                if let Some(backup) = state.db.get_setting(&backup_key(app))? {
+                    write_text_file(&path, &backup)?;
+                    state.db.delete_setting(&backup_key(app))?;
+                } else if path.exists() {
                     write_text_file(&path, "")?;
                 }
+                Self::set_active(state, app, None)?;
             }
         }
 
@@ -188,6 +272,44 @@ impl PromptService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::isolated_home;
+    use serial_test::serial;
+
+    #[test]
+    fn test_missing_prompt_is_not_found() {
+        let state = AppState::memory().unwrap();
+        assert!(matches!(
+            PromptService::remove(&state, AppType::Claude, "missing"),
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    #[serial]
+    fn test_import_from_app_adopts_existing_claude_md() {
+        let home = isolated_home();
+        let claude_md = home.path().join(".claude").join("CLAUDE.md");
+        fs::create_dir_all(claude_md.parent().unwrap()).unwrap();
+        fs::write(&claude_md, "# 项目规范\n").unwrap();
+
+        let state = AppState::memory().unwrap();
+        state
+            .db
+            .save_prompt("claude", &Prompt::new("old", "Old", "old").with_enabled(true))
+            .unwrap();
+
+        let id = PromptService::import_from_app(&state, AppType::Claude).unwrap();
+        assert_eq!(id.as_deref(), Some("imported-claude"));
+        let prompt = PromptService::get(&state, AppType::Claude, "imported-claude").unwrap().unwrap();
+        assert_eq!((prompt.name.as_str(), prompt.content.as_str()), ("imported-claude", "# 项目规范\n"));
+        assert!(prompt.enabled);
+        assert!(!PromptService::get(&state, AppType::Claude, "old").unwrap().unwrap().enabled);
+        assert_eq!(PromptService::active(&state, AppType::Claude).unwrap().as_deref(), Some("imported-claude"));
+
+        // 再次导入相同内容、缺失文件的应用均跳过
+        assert!(PromptService::import_from_app(&state, AppType::Claude).unwrap().is_none());
+        assert!(PromptService::import_from_app(&state, AppType::Gemini).unwrap().is_none());
+    }
 
     #[test]
     fn test_prompt_service_add_and_list() {
@@ -224,4 +346,52 @@ mod tests {
         assert!(!prompts.get("prompt1").unwrap().enabled);
         assert!(prompts.get("prompt2").unwrap().enabled);
     }
+
+    #[test]
+    #[serial]
+    fn test_prompt_enable_disable_round_trip() {
+        let _home = isolated_home();
+        let state = AppState::memory().unwrap();
+        let path = get_prompt_path(&AppType::Claude);
+        write_text_file(&path, "# 原有内容").unwrap();
+
+        PromptService::add(&state, AppType::Claude, Prompt::new("p1", "P1", "# P1")).unwrap();
+
+        PromptService::enable(&state, AppType::Claude, "p1").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "# P1");
+        assert_eq!(
+            PromptService::active(&state, AppType::Claude).unwrap().as_deref(),
+            Some("p1")
+        );
+
+        PromptService::disable(&state, AppType::Claude, "p1").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "# 原有内容");
+        assert!(PromptService::active(&state, AppType::Claude).unwrap().is_none());
+    }
+
+    #[test]
+    #[serial]
+    fn test_prompt_enable_with_vars_writes_rendered_content() {
+        let _home = isolated_home();
+        let state = AppState::memory().unwrap();
+        PromptService::add(
+            &state,
+            AppType::Claude,
+            Prompt::new("tpl", "Template", "# {{project}}"),
+        )
+        .unwrap();
+
+        assert!(PromptService::enable(&state, AppType::Claude, "tpl").is_err());
+        assert!(!get_prompt_path(&AppType::Claude).exists());
+
+        let mut vars = IndexMap::new();
+        vars.insert("project".to_string(), "demo".to_string());
+        PromptService::enable_with_vars(&state, AppType::Claude, "tpl", &vars).unwrap();
+
+        let path = get_prompt_path(&AppType::Claude);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "# demo");
+        // 变量已保存，重新同步仍可渲染
+        PromptService::sync_to_app(&state, AppType::Claude).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "# demo");
+    }
 }
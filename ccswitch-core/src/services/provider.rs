@@ -3,29 +3,364 @@
 //! 处理供应商的 CRUD 操作、切换和配置管理。
 
 use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 use indexmap::IndexMap;
+use serde::Serialize;
 use serde_json::Value;
 
 use crate::app_config::AppType;
-use crate::config::{
-    get_claude_settings_path, get_codex_auth_path, get_codex_config_path,
-    get_gemini_settings_path, read_json_file, write_json_file, write_text_file,
-};
+use crate::audit;
+use crate::config::{read_json_file, sanitize_name, write_text_file};
+use crate::diff::{diff_json, JsonDiff};
+use crate::env_interp;
 use crate::error::AppError;
 use crate::provider::Provider;
+use crate::services::LiveBackupService;
 use crate::settings;
 use crate::store::AppState;
 
 /// 供应商业务逻辑服务
 pub struct ProviderService;
 
+/// 两个供应商的单项对比
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldComparison {
+    pub field: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+impl FieldComparison {
+    /// 两侧是否不同
+    pub fn differs(&self) -> bool {
+        self.left != self.right
+    }
+}
+
+/// 供应商对比结果
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderComparison {
+    pub left: String,
+    pub right: String,
+    pub fields: Vec<FieldComparison>,
+    pub settings_diff: Vec<JsonDiff>,
+    /// 按两侧归类的同一份差异（`only_in_a`/`only_in_b`/`changed`）
+    #[serde(flatten)]
+    pub diff: ProviderDiff,
+}
+
+/// 两个供应商规范化配置的差异，键为 JSON Pointer 路径
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProviderDiff {
+    pub only_in_a: IndexMap<String, Value>,
+    pub only_in_b: IndexMap<String, Value>,
+    pub changed: IndexMap<String, ChangedValue>,
+}
+
+/// 两侧取值不同的字段
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangedValue {
+    pub a: Value,
+    pub b: Value,
+}
+
+impl ProviderDiff {
+    /// 按两侧归类逐项差异
+    pub fn from_json_diffs(diffs: &[JsonDiff]) -> Self {
+        let mut result = Self::default();
+        for d in diffs {
+            match (d.left.clone(), d.right.clone()) {
+                (Some(a), Some(b)) => {
+                    result.changed.insert(d.path.clone(), ChangedValue { a, b });
+                }
+                (Some(a), None) => {
+                    result.only_in_a.insert(d.path.clone(), a);
+                }
+                (None, Some(b)) => {
+                    result.only_in_b.insert(d.path.clone(), b);
+                }
+                (None, None) => {}
+            }
+        }
+        result
+    }
+
+    /// 配置是否完全相同
+    pub fn is_empty(&self) -> bool {
+        self.only_in_a.is_empty() && self.only_in_b.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Claude 配置中的 `apiKeyHelper` 命令
+fn claude_api_key_helper(provider: &Provider) -> Option<&str> {
+    provider
+        .settings_config
+        .get("apiKeyHelper")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+/// 从 Codex 的 `auth` 字段读取 API Key（JSON 对象或 `[openai] api_key` 形式的 TOML）
+fn codex_auth_api_key(provider: &Provider) -> Option<String> {
+    let auth = provider.settings_config.get("auth")?;
+    if let Some(key) = auth.get("OPENAI_API_KEY").and_then(|v| v.as_str()) {
+        return Some(key.to_string());
+    }
+    let table: toml::Table = toml::from_str(auth.as_str()?).ok()?;
+    table
+        .get("openai")
+        .and_then(|t| t.get("api_key"))
+        .or_else(|| table.get("OPENAI_API_KEY"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+}
+
+/// 通过 shell 运行 `apiKeyHelper`，返回其输出的 token
+pub fn run_api_key_helper(command: &str) -> Result<String, AppError> {
+    #[cfg(windows)]
+    let output = std::process::Command::new("cmd").args(["/C", command]).output();
+    #[cfg(not(windows))]
+    let output = std::process::Command::new("sh").args(["-c", command]).output();
+
+    let output =
+        output.map_err(|e| AppError::Config(format!("执行 apiKeyHelper 失败: {}", e)))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AppError::Config(format!(
+            "apiKeyHelper 执行失败: {}",
+            stderr.trim()
+        )));
+    }
+
+    let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if token.is_empty() {
+        return Err(AppError::Config("apiKeyHelper 未输出任何内容".to_string()));
+    }
+    Ok(token)
+}
+
+/// 备注中保留的测试记录条数
+pub const MAX_TEST_NOTE_LINES: usize = 10;
+
+/// 生成测试记录行，如 `[2025-01-02] OK 340ms`
+pub fn format_test_note(date: chrono::NaiveDate, status: &str, latency_ms: u64) -> String {
+    format!("[{}] {} {}ms", date.format("%Y-%m-%d"), status, latency_ms)
+}
+
+/// 是否为 [`format_test_note`] 生成的测试记录行
+fn is_test_note_line(line: &str) -> bool {
+    let Some(rest) = line.strip_prefix('[') else {
+        return false;
+    };
+    let Some((date, status)) = rest.split_once("] ") else {
+        return false;
+    };
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok()
+        && ["OK ", "WARN ", "FAIL "].iter().any(|s| status.starts_with(s))
+        && status.ends_with("ms")
+}
+
+/// 追加测试记录，超出 `max_records` 时丢弃最早的记录，其他备注内容保持不变
+pub fn append_test_note(notes: Option<&str>, line: &str, max_records: usize) -> String {
+    let mut lines: Vec<&str> = notes.unwrap_or_default().lines().collect();
+    lines.push(line);
+
+    let records = lines.iter().filter(|l| is_test_note_line(l)).count();
+    let mut to_drop = records.saturating_sub(max_records);
+    lines.retain(|l| {
+        if to_drop > 0 && is_test_note_line(l) {
+            to_drop -= 1;
+            return false;
+        }
+        true
+    });
+
+    lines.join("\n")
+}
+
+/// 递归按键名排序 JSON 对象
+fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(entries.into_iter().map(|(k, v)| (k, sort_keys(v))).collect())
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(sort_keys).collect()),
+        other => other,
+    }
+}
+
+/// 记录上一个当前供应商的设置键（随 Profile 隔离）
+fn previous_provider_key(state: &AppState, app_type: AppType) -> String {
+    format!("previous_provider_{}", state.db.provider_key(app_type.as_str()))
+}
+
+/// 按 JSON Pointer（RFC 6901）写入值
+///
+/// 中间缺失的对象键会自动创建；数组只能修改已有下标，末级可用 `-` 追加。
+pub fn set_json_pointer(target: &mut Value, pointer: &str, value: Value) -> Result<(), AppError> {
+    let Some(rest) = pointer.strip_prefix('/') else {
+        return Err(AppError::InvalidInput(format!(
+            "无效的 JSON Pointer: \"{}\"（需以 / 开头）",
+            pointer
+        )));
+    };
+    let tokens: Vec<String> = rest
+        .split('/')
+        .map(|t| t.replace("~1", "/").replace("~0", "~"))
+        .collect();
+    let invalid = |reason: &str| AppError::InvalidInput(format!("JSON Pointer \"{}\" {}", pointer, reason));
+
+    let (last, parents) = tokens.split_last().expect("split 至少返回一个片段");
+    let mut node = target;
+    for token in parents {
+        node = match node {
+            Value::Object(map) => map
+                .entry(token.clone())
+                .or_insert_with(|| Value::Object(Default::default())),
+            Value::Array(items) => token
+                .parse::<usize>()
+                .ok()
+                .and_then(|i| items.get_mut(i))
+                .ok_or_else(|| invalid(&format!("中的数组下标 {} 不存在", token)))?,
+            _ => return Err(invalid(&format!("无法进入非对象/数组节点 {}", token))),
+        };
+    }
+
+    match node {
+        Value::Object(map) => {
+            map.insert(last.clone(), value);
+        }
+        Value::Array(items) if last == "-" => items.push(value),
+        Value::Array(items) => {
+            let slot = last
+                .parse::<usize>()
+                .ok()
+                .and_then(|i| items.get_mut(i))
+                .ok_or_else(|| invalid(&format!("中的数组下标 {} 不存在", last)))?;
+            *slot = value;
+        }
+        _ => return Err(invalid(&format!("无法在非对象/数组节点上设置 {}", last))),
+    }
+    Ok(())
+}
+
+/// 单个应用切换前的状态
+#[derive(Debug)]
+struct AppSwitchState {
+    app_type: AppType,
+    db_current: Option<String>,
+    settings_current: Option<String>,
+    /// live 文件原内容，`None` 表示文件原本不存在
+    files: Vec<(PathBuf, Option<Vec<u8>>)>,
+}
+
+/// 多应用切换快照
+///
+/// 切换前记录 live 文件与当前供应商指针，任一应用失败时可整体恢复。
+#[derive(Debug)]
+pub struct SwitchSnapshot {
+    apps: Vec<AppSwitchState>,
+}
+
+impl SwitchSnapshot {
+    /// 记录指定应用的当前状态
+    pub fn capture(state: &AppState, app_types: &[AppType]) -> Result<Self, AppError> {
+        let mut apps = Vec::new();
+        for app_type in app_types {
+            let mut files = Vec::new();
+            for path in ProviderService::live_config_paths(app_type) {
+                let content = if path.exists() {
+                    Some(fs::read(&path).map_err(|e| AppError::io(&path, e))?)
+                } else {
+                    None
+                };
+                files.push((path, content));
+            }
+            apps.push(AppSwitchState {
+                app_type: *app_type,
+                db_current: state.db.get_current_provider(app_type.as_str())?,
+                settings_current: settings::get_current_provider(app_type),
+                files,
+            });
+        }
+        Ok(Self { apps })
+    }
+
+    /// 恢复到记录时的状态
+    pub fn restore(&self, state: &AppState) -> Result<(), AppError> {
+        for app in &self.apps {
+            for (path, content) in &app.files {
+                match content {
+                    Some(bytes) => crate::config::atomic_write(path, bytes)?,
+                    None if path.exists() => {
+                        fs::remove_file(path).map_err(|e| AppError::io(path, e))?
+                    }
+                    None => {}
+                }
+            }
+
+            match &app.db_current {
+                Some(id) => state.db.set_current_provider(app.app_type.as_str(), id)?,
+                None => state.db.clear_current_provider(app.app_type.as_str())?,
+            }
+            if !app.app_type.is_additive_mode() {
+                settings::set_current_provider(&app.app_type, app.settings_current.as_deref())?;
+            }
+        }
+        Ok(())
+    }
+}
+
 impl ProviderService {
     /// 列出指定应用类型的所有供应商
     pub fn list(state: &AppState, app_type: AppType) -> Result<IndexMap<String, Provider>, AppError> {
         state.db.get_all_providers(app_type.as_str())
     }
 
+    /// 列出指定分类的供应商
+    pub fn list_by_category(
+        state: &AppState,
+        app_type: AppType,
+        category: &str,
+    ) -> Result<IndexMap<String, Provider>, AppError> {
+        let mut providers = Self::list(state, app_type)?;
+        providers.retain(|_, p| p.category.as_deref() == Some(category));
+        Ok(providers)
+    }
+
+    /// 为新供应商生成 ID
+    ///
+    /// 默认使用 `<名称>-<时间戳>`；`deterministic` 时仅由名称生成，
+    /// 与已有 ID 冲突则依次追加 `-2`、`-3`…，保证重复导入得到相同的 ID。
+    pub fn generate_id(
+        state: &AppState,
+        app_type: AppType,
+        name: &str,
+        deterministic: bool,
+    ) -> Result<String, AppError> {
+        let base = sanitize_name(name.trim());
+        let base = if base.is_empty() { "provider".to_string() } else { base };
+
+        if !deterministic {
+            return Ok(format!("{}-{}", base, chrono::Utc::now().timestamp()));
+        }
+
+        let existing = state.db.get_all_providers(app_type.as_str())?;
+        if !existing.contains_key(&base) {
+            return Ok(base);
+        }
+        let id = (2..)
+            .map(|n| format!("{}-{}", base, n))
+            .find(|id| !existing.contains_key(id))
+            .expect("unbounded range always yields a free id");
+        Ok(id)
+    }
+
     /// 获取当前供应商 ID
     ///
     /// 优先从本地 settings 读取，fallback 到数据库
@@ -111,6 +446,56 @@ impl ProviderService {
         Ok(true)
     }
 
+    /// 更新供应商备注（仅修改数据库，不同步 live 配置）
+    pub fn update_notes(
+        state: &AppState,
+        app_type: AppType,
+        id: &str,
+        notes: Option<String>,
+    ) -> Result<(), AppError> {
+        let mut provider = state
+            .db
+            .get_all_providers(app_type.as_str())?
+            .shift_remove(id)
+            .ok_or_else(|| AppError::ProviderNotFound(id.to_string()))?;
+        provider.notes = notes.filter(|n| !n.trim().is_empty());
+        state.db.save_provider(app_type.as_str(), &provider)
+    }
+
+    /// 在备注末尾追加一条测试记录，只保留最近 [`MAX_TEST_NOTE_LINES`] 条
+    pub fn record_test_note(
+        state: &AppState,
+        app_type: AppType,
+        id: &str,
+        line: &str,
+    ) -> Result<(), AppError> {
+        let provider = state
+            .db
+            .get_all_providers(app_type.as_str())?
+            .shift_remove(id)
+            .ok_or_else(|| AppError::ProviderNotFound(id.to_string()))?;
+        let notes = append_test_note(provider.notes.as_deref(), line, MAX_TEST_NOTE_LINES);
+        Self::update_notes(state, app_type, id, Some(notes))
+    }
+
+    /// 按 JSON Pointer 修改供应商的 `settings_config`，校验通过后保存并同步 live 配置
+    pub fn set_field(
+        state: &AppState,
+        app_type: AppType,
+        id: &str,
+        pointer: &str,
+        value: Value,
+    ) -> Result<Provider, AppError> {
+        let mut provider = state
+            .db
+            .get_all_providers(app_type.as_str())?
+            .shift_remove(id)
+            .ok_or_else(|| AppError::ProviderNotFound(id.to_string()))?;
+        set_json_pointer(&mut provider.settings_config, pointer, value)?;
+        Self::update(state, app_type, provider.clone())?;
+        Ok(provider)
+    }
+
     /// 删除供应商
     pub fn delete(state: &AppState, app_type: AppType, id: &str) -> Result<(), AppError> {
         // 累加模式可以随时删除
@@ -131,25 +516,127 @@ impl ProviderService {
     }
 
     /// 切换到指定供应商
+    ///
+    /// 切换前会校验供应商配置，校验失败时保持当前供应商不变。
     pub fn switch(state: &AppState, app_type: AppType, id: &str) -> Result<(), AppError> {
+        Self::switch_with_options(state, app_type, id, false)
+    }
+
+    /// 切换到指定供应商，`force` 为 true 时跳过配置校验
+    pub fn switch_with_options(
+        state: &AppState,
+        app_type: AppType,
+        id: &str,
+        force: bool,
+    ) -> Result<(), AppError> {
         // 验证供应商存在
         let providers = state.db.get_all_providers(app_type.as_str())?;
         let provider = providers
             .get(id)
             .ok_or_else(|| AppError::ProviderNotFound(id.to_string()))?;
 
-        // 更新本地设置
-        settings::set_current_provider(&app_type, Some(id))?;
+        if !force {
+            Self::validate_provider_settings(&app_type, provider).map_err(|e| {
+                AppError::Message(format!(
+                    "供应商 \"{}\" 配置校验失败，已取消切换: {}",
+                    provider.name, e
+                ))
+            })?;
+        }
 
-        // 更新数据库
-        state.db.set_current_provider(app_type.as_str(), id)?;
+        // 环境变量缺失时在修改当前供应商之前失败
+        if env_interp::resolve_env_enabled() {
+            env_interp::resolve_env_refs(&provider.settings_config)?;
+        }
+
+        let replaced = Self::current(state, app_type)?;
+
+        // 更新本地设置与数据库；数据库写入失败时恢复本地设置，避免两处记录不一致
+        let previous = settings::get_current_provider(&app_type);
+        settings::set_current_provider(&app_type, Some(id))?;
+        if let Err(e) = state.db.set_current_provider(app_type.as_str(), id) {
+            settings::set_current_provider(&app_type, previous.as_deref()).map_err(|restore_err| {
+                AppError::Message(format!("更新当前供应商失败 ({})，且恢复本地设置失败: {}", e, restore_err))
+            })?;
+            return Err(e);
+        }
 
         // 同步到 live 配置
         Self::write_live_snapshot(&app_type, provider)?;
 
+        // 记录最近使用时间
+        state
+            .db
+            .set_provider_last_used(app_type.as_str(), id, chrono::Utc::now().timestamp_millis())?;
+
+        // 记录被替换的供应商，供 `use --previous` 切回
+        if !replaced.is_empty() && replaced != id {
+            state.db.set_setting(&previous_provider_key(state, app_type), &replaced)?;
+        }
+
+        Ok(())
+    }
+
+    /// 上一个当前供应商的 ID（`use --previous`）
+    pub fn previous(state: &AppState, app_type: AppType) -> Result<Option<String>, AppError> {
+        state.db.get_setting(&previous_provider_key(state, app_type))
+    }
+
+    /// 切换回上一个当前供应商，返回切换到的供应商
+    pub fn switch_to_previous(state: &AppState, app_type: AppType, force: bool) -> Result<Provider, AppError> {
+        let id = Self::previous(state, app_type)?.ok_or_else(|| {
+            AppError::NotFound(format!("{} 没有可切回的上一个供应商", app_type.display_name()))
+        })?;
+        let provider = state
+            .db
+            .get_all_providers(app_type.as_str())?
+            .shift_remove(&id)
+            .ok_or_else(|| AppError::ProviderNotFound(id.clone()))?;
+        Self::switch_with_options(state, app_type, &id, force)?;
+        Ok(provider)
+    }
+
+    /// 原子地切换多个应用
+    ///
+    /// 切换前为所有应用记录快照，任一应用失败时恢复全部 live 文件和当前供应商。
+    pub fn switch_all_atomic(
+        state: &AppState,
+        targets: &[(AppType, String)],
+        force: bool,
+    ) -> Result<(), AppError> {
+        let app_types: Vec<AppType> = targets.iter().map(|(app, _)| *app).collect();
+        let snapshot = SwitchSnapshot::capture(state, &app_types)?;
+
+        for (app_type, id) in targets {
+            if let Err(e) = Self::switch_with_options(state, *app_type, id, force) {
+                snapshot.restore(state).map_err(|restore_err| {
+                    AppError::Message(format!(
+                        "{} 切换失败 ({})，且回滚失败: {}",
+                        app_type.display_name(),
+                        e,
+                        restore_err
+                    ))
+                })?;
+                return Err(AppError::Message(format!(
+                    "{} 切换失败，已回滚全部更改: {}",
+                    app_type.display_name(),
+                    e
+                )));
+            }
+        }
+
         Ok(())
     }
 
+    /// 应用切换时会写入的 live 配置文件
+    pub fn live_config_paths(app_type: &AppType) -> Vec<PathBuf> {
+        if *app_type == AppType::OpenCode {
+            return Vec::new();
+        }
+        let paths = app_type.config_paths();
+        std::iter::once(paths.settings_path).chain(paths.auth_path).collect()
+    }
+
     /// 按名称或 ID 查找供应商
     pub fn find(
         state: &AppState,
@@ -181,13 +668,79 @@ impl ProviderService {
         Ok(None)
     }
 
+    /// 查找所有匹配的供应商
+    ///
+    /// ID 完全一致或名称完全一致（忽略大小写）时只返回这些结果，否则返回所有名称前缀匹配，
+    /// 调用方据此判断是否有歧义。
+    pub fn find_all(
+        state: &AppState,
+        app_type: AppType,
+        name_or_id: &str,
+    ) -> Result<Vec<Provider>, AppError> {
+        let providers = state.db.get_all_providers(app_type.as_str())?;
+
+        if let Some(provider) = providers.get(name_or_id) {
+            return Ok(vec![provider.clone()]);
+        }
+
+        let name_lower = name_or_id.to_lowercase();
+        let exact: Vec<Provider> = providers
+            .values()
+            .filter(|p| p.name.to_lowercase() == name_lower)
+            .cloned()
+            .collect();
+        if !exact.is_empty() {
+            return Ok(exact);
+        }
+
+        Ok(providers
+            .into_values()
+            .filter(|p| p.name.to_lowercase().starts_with(&name_lower))
+            .collect())
+    }
+
+    /// 模糊查找供应商，返回得分最高的一组候选（保持列表顺序）
+    ///
+    /// 依次按 ID/名称完全一致、前缀、子串、子序列、一个字符以内的拼写错误打分，
+    /// 返回多个候选时由调用方让用户消歧。
+    pub fn fuzzy_find(
+        state: &AppState,
+        app_type: AppType,
+        query: &str,
+    ) -> Result<Vec<Provider>, AppError> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let providers = state.db.get_all_providers(app_type.as_str())?;
+        let scored: Vec<(u8, Provider)> = providers
+            .into_values()
+            .filter_map(|p| {
+                let score = fuzzy_score(&query, &p.name.to_lowercase())
+                    .max(fuzzy_score(&query, &p.id.to_lowercase()))?;
+                Some((score, p))
+            })
+            .collect();
+
+        let Some(best) = scored.iter().map(|(score, _)| *score).max() else {
+            return Ok(Vec::new());
+        };
+        Ok(scored
+            .into_iter()
+            .filter(|(score, _)| *score == best)
+            .map(|(_, p)| p)
+            .collect())
+    }
+
     /// 验证供应商配置
     fn validate_provider_settings(app_type: &AppType, provider: &Provider) -> Result<(), AppError> {
         match app_type {
             AppType::Claude => Self::validate_claude_settings(provider),
             AppType::Codex => Self::validate_codex_settings(provider),
             AppType::Gemini => Self::validate_gemini_settings(provider),
-            AppType::OpenCode | AppType::OpenClaw => Ok(()), // OpenCode/OpenClaw 验证较宽松
+            AppType::OpenCode => Self::validate_opencode_settings(provider),
+            AppType::OpenClaw => Ok(()), // OpenClaw 验证较宽松
         }
     }
 
@@ -207,9 +760,10 @@ impl ProviderService {
             .map(|s| !s.is_empty())
             .unwrap_or(false);
 
-        if !has_api_key && !has_auth_token {
-            return Err(AppError::InvalidInput(
-                "Claude 供应商需要配置 ANTHROPIC_API_KEY 或 ANTHROPIC_AUTH_TOKEN".to_string(),
+        if !has_api_key && !has_auth_token && claude_api_key_helper(provider).is_none() {
+            return Err(AppError::Validation(
+                "Claude 供应商需要配置 ANTHROPIC_API_KEY、ANTHROPIC_AUTH_TOKEN 或 apiKeyHelper"
+                    .to_string(),
             ));
         }
 
@@ -235,7 +789,7 @@ impl ProviderService {
         let has_auth_file = !auth.is_empty();
 
         if !has_auth_in_config && !has_auth_file {
-            return Err(AppError::InvalidInput(
+            return Err(AppError::Validation(
                 "Codex 供应商需要配置 auth 认证信息".to_string(),
             ));
         }
@@ -253,7 +807,7 @@ impl ProviderService {
             .unwrap_or(false);
 
         if !has_api_key {
-            return Err(AppError::InvalidInput(
+            return Err(AppError::Validation(
                 "Gemini 供应商需要配置 apiKey".to_string(),
             ));
         }
@@ -261,8 +815,60 @@ impl ProviderService {
         Ok(())
     }
 
+    fn validate_opencode_settings(provider: &Provider) -> Result<(), AppError> {
+        // OpenCode 供应商条目形如 {"npm", "options": {"baseURL", "apiKey"}, "models"}，
+        // 也可能是带 provider 映射的完整 opencode.json 片段
+        let config = &provider.settings_config;
+        let non_empty = |v: Option<&Value>| {
+            v.and_then(|v| v.as_str()).map(|s| !s.is_empty()).unwrap_or(false)
+        };
+
+        let has_section = ["provider", "model", "models", "options"]
+            .iter()
+            .any(|key| config.get(key).is_some());
+        if !has_section {
+            return Err(AppError::Validation(
+                "OpenCode 供应商需要配置 provider、model 或 options".to_string(),
+            ));
+        }
+
+        let has_api_key = non_empty(config.get("options").and_then(|o| o.get("apiKey")))
+            || non_empty(config.get("apiKey"))
+            || config
+                .get("provider")
+                .and_then(|v| v.as_object())
+                .map(|providers| {
+                    providers
+                        .values()
+                        .any(|p| non_empty(p.get("options").and_then(|o| o.get("apiKey"))))
+                })
+                .unwrap_or(false);
+        if !has_api_key {
+            return Err(AppError::Validation(
+                "OpenCode 供应商需要在 options 中配置 apiKey".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
     /// 将供应商配置写入 live 文件
+    ///
+    /// 启用 `--resolve-env` 时先解析 `${ENV_VAR}` 引用，变量缺失则不写入任何文件。
     fn write_live_snapshot(app_type: &AppType, provider: &Provider) -> Result<(), AppError> {
+        let resolved;
+        let provider = if env_interp::resolve_env_enabled() {
+            resolved = Provider {
+                settings_config: env_interp::resolve_env_refs(&provider.settings_config)?,
+                ..provider.clone()
+            };
+            &resolved
+        } else {
+            provider
+        };
+
+        LiveBackupService::backup(*app_type)?;
+
         match app_type {
             AppType::Claude => Self::write_claude_live(provider),
             AppType::Codex => Self::write_codex_live(provider),
@@ -272,30 +878,48 @@ impl ProviderService {
         }
     }
 
+    /// 写入单个 live 配置文件并记录审计日志
+    fn write_live_file(app_type: AppType, provider: &Provider, path: &Path, content: &str) -> Result<(), AppError> {
+        write_text_file(path, content)?;
+        audit::log_event(
+            "live_write",
+            serde_json::json!({
+                "app": app_type.as_str(),
+                "provider": provider.id,
+                "path": path.display().to_string(),
+                "bytes": content.len(),
+            }),
+        );
+        Ok(())
+    }
+
     fn write_claude_live(provider: &Provider) -> Result<(), AppError> {
-        let path = get_claude_settings_path();
-        write_json_file(&path, &provider.settings_config)
+        let content = serde_json::to_string_pretty(&provider.settings_config)?;
+        let path = AppType::Claude.config_paths().settings_path;
+        Self::write_live_file(AppType::Claude, provider, &path, &content)
     }
 
     fn write_codex_live(provider: &Provider) -> Result<(), AppError> {
+        let paths = AppType::Codex.config_paths();
+
         // 写入 config.toml
         if let Some(config) = provider.settings_config.get("config").and_then(|v| v.as_str()) {
-            let path = get_codex_config_path();
-            write_text_file(&path, config)?;
+            Self::write_live_file(AppType::Codex, provider, &paths.settings_path, config)?;
         }
 
         // 写入 auth.toml
-        if let Some(auth) = provider.settings_config.get("auth").and_then(|v| v.as_str()) {
-            let path = get_codex_auth_path();
-            write_text_file(&path, auth)?;
+        let auth = provider.settings_config.get("auth").and_then(|v| v.as_str());
+        if let (Some(auth), Some(auth_path)) = (auth, &paths.auth_path) {
+            Self::write_live_file(AppType::Codex, provider, auth_path, auth)?;
         }
 
         Ok(())
     }
 
     fn write_gemini_live(provider: &Provider) -> Result<(), AppError> {
-        let path = get_gemini_settings_path();
-        write_json_file(&path, &provider.settings_config)
+        let content = serde_json::to_string_pretty(&provider.settings_config)?;
+        let path = AppType::Gemini.config_paths().settings_path;
+        Self::write_live_file(AppType::Gemini, provider, &path, &content)
     }
 
     fn write_opencode_live(_provider: &Provider) -> Result<(), AppError> {
@@ -357,14 +981,26 @@ impl ProviderService {
         // 写入配置
         set_openclaw_provider(&provider.id, json!(openclaw_config))?;
 
+        let path = crate::openclaw_config::get_openclaw_config_path();
+        audit::log_event(
+            "live_write",
+            json!({
+                "app": AppType::OpenClaw.as_str(),
+                "provider": provider.id,
+                "path": path.display().to_string(),
+                "bytes": fs::metadata(&path).map(|m| m.len()).unwrap_or(0),
+            }),
+        );
+
         Ok(())
     }
 
     /// 从 live 配置读取设置
     pub fn read_live_settings(app_type: AppType) -> Result<Value, AppError> {
+        let paths = app_type.config_paths();
         match app_type {
-            AppType::Claude => {
-                let path = get_claude_settings_path();
+            AppType::Claude | AppType::Gemini => {
+                let path = paths.settings_path;
                 if path.exists() {
                     read_json_file(&path)
                 } else {
@@ -372,20 +1008,18 @@ impl ProviderService {
                 }
             }
             AppType::Codex => {
-                let config_path = get_codex_config_path();
-                let auth_path = get_codex_auth_path();
-
-                let config = if config_path.exists() {
-                    std::fs::read_to_string(&config_path)
-                        .map_err(|e| AppError::io(&config_path, e))?
-                } else {
-                    String::new()
+                let read_optional = |path: &PathBuf| -> Result<String, AppError> {
+                    if path.exists() {
+                        std::fs::read_to_string(path).map_err(|e| AppError::io(path, e))
+                    } else {
+                        Ok(String::new())
+                    }
                 };
 
-                let auth = if auth_path.exists() {
-                    std::fs::read_to_string(&auth_path).map_err(|e| AppError::io(&auth_path, e))?
-                } else {
-                    String::new()
+                let config = read_optional(&paths.settings_path)?;
+                let auth = match &paths.auth_path {
+                    Some(path) => read_optional(path)?,
+                    None => String::new(),
                 };
 
                 Ok(serde_json::json!({
@@ -393,14 +1027,6 @@ impl ProviderService {
                     "auth": auth
                 }))
             }
-            AppType::Gemini => {
-                let path = get_gemini_settings_path();
-                if path.exists() {
-                    read_json_file(&path)
-                } else {
-                    Ok(Value::Object(serde_json::Map::new()))
-                }
-            }
             AppType::OpenCode | AppType::OpenClaw => {
                 // OpenCode/OpenClaw 使用累加模式
                 Ok(Value::Object(serde_json::Map::new()))
@@ -408,7 +1034,99 @@ impl ProviderService {
         }
     }
 
-    /// 提取凭据信息
+    /// 检查当前供应商与 live 配置是否一致
+    ///
+    /// 累加模式应用或未设置当前供应商时返回 `Ok(None)`。
+    pub fn live_matches_current(state: &AppState, app_type: AppType) -> Result<Option<bool>, AppError> {
+        if app_type.is_additive_mode() {
+            return Ok(None);
+        }
+        let Some(provider) = Self::current_provider(state, app_type)? else {
+            return Ok(None);
+        };
+
+        let live = Self::read_live_settings(app_type)?;
+        let matches = match app_type {
+            // Codex 的 live 配置由 config/auth 两个文本文件组成
+            AppType::Codex => ["config", "auth"].iter().all(|key| {
+                let text = |v: &Value| {
+                    v.get(*key)
+                        .and_then(|s| s.as_str())
+                        .unwrap_or_default()
+                        .trim()
+                        .to_string()
+                };
+                text(&live) == text(&provider.settings_config)
+            }),
+            _ => live == provider.settings_config,
+        };
+        Ok(Some(matches))
+    }
+
+    /// 将应用现有的 live 配置导入为供应商并设为当前
+    ///
+    /// 供应商名称为 `Imported (<应用>)`，重复导入时更新同一供应商。
+    /// 应用没有现有配置时返回 `Ok(None)`。
+    pub fn import_from_live(state: &AppState, app_type: AppType) -> Result<Option<Provider>, AppError> {
+        if app_type.is_additive_mode() {
+            return Ok(None);
+        }
+
+        let live = Self::read_live_settings(app_type)?;
+        let is_empty = match &live {
+            Value::Object(obj) => obj.values().all(|v| match v {
+                Value::String(s) => s.trim().is_empty(),
+                Value::Null => true,
+                _ => false,
+            }),
+            _ => true,
+        };
+        if is_empty {
+            return Ok(None);
+        }
+
+        let id = format!("imported-{}", app_type.as_str());
+        let name = format!("Imported ({})", app_type.display_name());
+
+        let existing = state.db.get_all_providers(app_type.as_str())?.get(&id).cloned();
+        let provider = match existing {
+            Some(mut p) => {
+                p.settings_config = live;
+                Self::validate_provider_settings(&app_type, &p)?;
+                state.db.save_provider(app_type.as_str(), &p)?;
+                p
+            }
+            None => {
+                let p = Provider::new(id, name, live);
+                Self::add(state, app_type, p.clone())?;
+                p
+            }
+        };
+
+        // live 配置即为来源，只需更新当前指针，无需回写
+        state.db.set_current_provider(app_type.as_str(), &provider.id)?;
+        settings::set_current_provider(&app_type, Some(&provider.id))?;
+
+        Ok(Some(provider))
+    }
+
+    /// 解析发起请求所用的凭据：未配置静态密钥时运行 Claude 的 `apiKeyHelper` 获取
+    ///
+    /// 仅用于测试、测速等确实需要调用 API 的场景，展示类场景请使用 [`Self::extract_credentials`]。
+    pub fn resolve_credentials(
+        provider: &Provider,
+        app_type: &AppType,
+    ) -> Result<(String, String), AppError> {
+        let (mut api_key, base_url) = Self::extract_credentials(provider, app_type)?;
+        if api_key.is_empty() && *app_type == AppType::Claude {
+            if let Some(helper) = claude_api_key_helper(provider) {
+                api_key = run_api_key_helper(helper)?;
+            }
+        }
+        Ok((api_key, base_url))
+    }
+
+    /// 提取配置中的静态凭据信息（不执行 `apiKeyHelper`）
     pub fn extract_credentials(
         provider: &Provider,
         app_type: &AppType,
@@ -456,6 +1174,11 @@ impl ProviderService {
                     }
                 }
 
+                // add 生成的配置将密钥保存在 auth 中
+                if api_key.is_empty() {
+                    api_key = codex_auth_api_key(provider).unwrap_or_default();
+                }
+
                 Ok((api_key, base_url))
             }
             AppType::Gemini => {
@@ -478,19 +1201,290 @@ impl ProviderService {
             AppType::OpenCode | AppType::OpenClaw => Ok((String::new(), String::new())),
         }
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use serde_json::json;
 
-    #[test]
-    fn test_list_providers() {
-        let state = AppState::memory().unwrap();
+    /// 将供应商从一个应用移动到另一个应用，返回移动后的供应商与未能迁移的字段说明
+    ///
+    /// 仅迁移 API Key 与 Base URL；源应用中正在使用的供应商需先切换走。
+    pub fn move_provider(
+        state: &AppState,
+        id: &str,
+        from: AppType,
+        to: AppType,
+    ) -> Result<(Provider, Vec<String>), AppError> {
+        let provider = state
+            .db
+            .get_all_providers(from.as_str())?
+            .shift_remove(id)
+            .ok_or_else(|| AppError::ProviderNotFound(id.to_string()))?;
 
-        let provider = Provider::new(
-            "p1",
+        if !from.is_additive_mode() && Self::current(state, from)? == id {
+            return Err(AppError::Message(
+                "无法移动当前正在使用的供应商，请先切换到其他供应商".to_string(),
+            ));
+        }
+        if state.db.get_all_providers(to.as_str())?.contains_key(id) {
+            return Err(AppError::InvalidInput(format!(
+                "{} 已存在 ID 为 {} 的供应商",
+                to.display_name(),
+                id
+            )));
+        }
+
+        let (settings_config, warnings) = Self::convert_settings(&provider, from, to)?;
+        let moved = Provider {
+            settings_config,
+            ..provider
+        };
+        // 删除与添加放在同一事务中，任一步失败都不会留下重复或丢失的供应商；
+        // 先删除再添加，使 live 配置只在数据库写入都成功后才会更新
+        state.db.with_transaction(|_| {
+            Self::delete(state, from, id)?;
+            Self::add(state, to, moved.clone())
+        })?;
+
+        Ok((moved, warnings))
+    }
+
+    /// 将 `settings_config` 转换为目标应用的结构
+    ///
+    /// 目前支持 Claude、Codex 与 Gemini 之间的转换，无法映射的字段以说明形式返回。
+    pub fn convert_settings(
+        provider: &Provider,
+        from: AppType,
+        to: AppType,
+    ) -> Result<(Value, Vec<String>), AppError> {
+        let supported = |app: AppType| matches!(app, AppType::Claude | AppType::Codex | AppType::Gemini);
+        if from == to || !supported(from) || !supported(to) {
+            return Err(AppError::InvalidInput(format!(
+                "不支持从 {} 移动到 {}（仅支持 Claude、Codex、Gemini 之间互相移动）",
+                from.display_name(),
+                to.display_name()
+            )));
+        }
+        if from == AppType::Claude && claude_api_key_helper(provider).is_some() {
+            return Err(AppError::Validation(
+                "使用 apiKeyHelper 的 Claude 供应商无法迁移到其他应用".to_string(),
+            ));
+        }
+
+        let config = &provider.settings_config;
+        let (api_key, _) = Self::extract_credentials(provider, &from)?;
+        let mut warnings = Vec::new();
+        let base_url = match from {
+            AppType::Claude => {
+                let mapped = ["ANTHROPIC_AUTH_TOKEN", "ANTHROPIC_API_KEY", "ANTHROPIC_BASE_URL"];
+                if let Some(env) = config.get("env").and_then(|v| v.as_object()) {
+                    warnings.extend(
+                        env.keys()
+                            .filter(|k| !mapped.contains(&k.as_str()))
+                            .map(|k| format!("env.{}", k)),
+                    );
+                }
+                if let Some(obj) = config.as_object() {
+                    warnings.extend(obj.keys().filter(|k| *k != "env").cloned());
+                }
+                provider.get_base_url()
+            }
+            AppType::Codex => {
+                warnings.push("config（TOML 中的模型、wire_api 等设置）".to_string());
+                provider.get_base_url()
+            }
+            _ => {
+                if let Some(obj) = config.as_object() {
+                    warnings.extend(obj.keys().filter(|k| !matches!(k.as_str(), "apiKey" | "baseUrl")).cloned());
+                }
+                config.get("baseUrl").and_then(|v| v.as_str()).map(str::to_string)
+            }
+        };
+
+        let converted = match to {
+            AppType::Claude => {
+                let mut env = serde_json::Map::new();
+                env.insert("ANTHROPIC_AUTH_TOKEN".to_string(), Value::String(api_key));
+                if let Some(url) = base_url {
+                    env.insert("ANTHROPIC_BASE_URL".to_string(), Value::String(url));
+                }
+                serde_json::json!({ "env": env })
+            }
+            AppType::Codex => {
+                let mut config = "model_provider = \"openai\"\n\n[model_providers.openai]\nname = \"OpenAI\"\n".to_string();
+                if let Some(url) = base_url {
+                    config.push_str(&format!("base_url = \"{}\"\n", url));
+                }
+                config.push_str("wire_api = \"responses\"\n");
+                serde_json::json!({
+                    "config": config,
+                    "auth": format!("[openai]\napi_key = \"{}\"\n", api_key),
+                })
+            }
+            _ => {
+                let mut obj = serde_json::Map::new();
+                obj.insert("apiKey".to_string(), Value::String(api_key));
+                if let Some(url) = base_url {
+                    obj.insert("baseUrl".to_string(), Value::String(url));
+                }
+                Value::Object(obj)
+            }
+        };
+
+        Ok((converted, warnings))
+    }
+
+    /// 逐项对比两个供应商，并给出规范化后 `settings_config` 的 JSON 差异
+    pub fn compare(left: &Provider, right: &Provider, app_type: &AppType) -> ProviderComparison {
+        let key_presence = |p: &Provider| {
+            let present = claude_api_key_helper(p).is_some()
+                || Self::extract_credentials(p, app_type).is_ok_and(|(key, _)| !key.is_empty());
+            Some(if present { "已配置" } else { "未配置" }.to_string())
+        };
+        let field = |name: &str, l: Option<String>, r: Option<String>| FieldComparison {
+            field: name.to_string(),
+            left: l,
+            right: r,
+        };
+
+        let settings_diff = diff_json(
+            &Self::normalized_settings(left, app_type),
+            &Self::normalized_settings(right, app_type),
+        );
+        ProviderComparison {
+            left: left.name.clone(),
+            right: right.name.clone(),
+            fields: vec![
+                field("base_url", left.get_base_url(), right.get_base_url()),
+                field("model", left.get_model(), right.get_model()),
+                field("api_key", key_presence(left), key_presence(right)),
+                field("category", left.category.clone(), right.category.clone()),
+            ],
+            diff: ProviderDiff::from_json_diffs(&settings_diff),
+            settings_diff,
+        }
+    }
+
+    /// 规范化 `settings_config` 以便比较
+    ///
+    /// 对象键按字母排序；Codex 的 `config`/`auth` 文本解析为 JSON 结构，无法解析时保留原文。
+    pub fn normalized_settings(provider: &Provider, app_type: &AppType) -> Value {
+        let mut config = provider.settings_config.clone();
+        if *app_type == AppType::Codex {
+            if let Some(obj) = config.as_object_mut() {
+                for value in obj.values_mut() {
+                    let Some(text) = value.as_str() else { continue };
+                    let parsed = toml::from_str::<toml::Value>(text)
+                        .ok()
+                        .and_then(|v| serde_json::to_value(v).ok())
+                        .or_else(|| serde_json::from_str::<Value>(text).ok());
+                    if let Some(parsed) = parsed {
+                        *value = parsed;
+                    }
+                }
+            }
+        }
+        sort_keys(config)
+    }
+
+    /// 移除供应商配置中的凭据字段（用于分享/导出）
+    ///
+    /// 仅删除密钥类字段，Base URL 与模型等配置保持不变。
+    pub fn redact_credentials(provider: &mut Provider, app_type: &AppType) {
+        let config = &mut provider.settings_config;
+        match app_type {
+            AppType::Claude => {
+                if let Some(env) = config.get_mut("env").and_then(|v| v.as_object_mut()) {
+                    env.remove("ANTHROPIC_AUTH_TOKEN");
+                    env.remove("ANTHROPIC_API_KEY");
+                }
+            }
+            AppType::Codex => {
+                if let Some(obj) = config.as_object_mut() {
+                    obj.remove("auth");
+                    if let Some(text) = obj.get("config").and_then(|v| v.as_str()) {
+                        let kept: Vec<&str> = text
+                            .lines()
+                            .filter(|line| {
+                                let line = line.trim_start();
+                                !(line.starts_with("api_key") || line.starts_with("access_token"))
+                            })
+                            .collect();
+                        let mut redacted = kept.join("\n");
+                        if text.ends_with('\n') {
+                            redacted.push('\n');
+                        }
+                        obj.insert("config".to_string(), Value::String(redacted));
+                    }
+                }
+            }
+            AppType::Gemini => {
+                if let Some(obj) = config.as_object_mut() {
+                    obj.remove("apiKey");
+                }
+            }
+            AppType::OpenCode | AppType::OpenClaw => {
+                if let Some(options) = config.get_mut("options").and_then(|v| v.as_object_mut()) {
+                    options.remove("apiKey");
+                }
+                if let Some(obj) = config.as_object_mut() {
+                    obj.remove("apiKey");
+                }
+            }
+        }
+    }
+}
+
+/// 模糊匹配得分（越高越好），不匹配返回 None；参数均为小写
+fn fuzzy_score(query: &str, candidate: &str) -> Option<u8> {
+    if candidate == query {
+        Some(100)
+    } else if candidate.starts_with(query) {
+        Some(80)
+    } else if candidate.contains(query) {
+        Some(60)
+    } else if is_subsequence(query, candidate) {
+        Some(40)
+    } else if query.chars().count() >= 3 && within_one_edit(query, candidate) {
+        Some(20)
+    } else {
+        None
+    }
+}
+
+/// `query` 的字符是否按顺序出现在 `candidate` 中
+fn is_subsequence(query: &str, candidate: &str) -> bool {
+    let mut chars = candidate.chars();
+    query.chars().all(|q| chars.any(|c| c == q))
+}
+
+/// 两个字符串的编辑距离是否不超过 1
+fn within_one_edit(a: &str, b: &str) -> bool {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (short, long) = if a.len() <= b.len() { (&a, &b) } else { (&b, &a) };
+    if long.len() - short.len() > 1 {
+        return false;
+    }
+
+    let prefix = short.iter().zip(long.iter()).take_while(|(x, y)| x == y).count();
+    if short.len() == long.len() {
+        short[prefix + 1..] == long[prefix + 1..]
+    } else {
+        short[prefix..] == long[prefix + 1..]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{get_claude_settings_path, get_codex_config_path, write_json_file};
+    use crate::test_support::isolated_home;
+    use serial_test::serial;
+    use serde_json::json;
+
+    #[test]
+    fn test_list_providers() {
+        let state = AppState::memory().unwrap();
+
+        let provider = Provider::new(
+            "p1",
             "Provider 1",
             json!({
                 "env": {
@@ -531,4 +1525,762 @@ mod tests {
         let found = ProviderService::find(&state, AppType::Claude, "My Test").unwrap();
         assert!(found.is_some());
     }
+
+    #[test]
+    fn test_find_all_exact_match_wins_over_prefix() {
+        let state = AppState::memory().unwrap();
+        for (id, name) in [("a", "OpenAI"), ("b", "OpenAI-backup"), ("c", "Other")] {
+            state
+                .db
+                .save_provider("claude", &Provider::new(id, name, json!({})))
+                .unwrap();
+        }
+
+        let ids = |query: &str| -> Vec<String> {
+            ProviderService::find_all(&state, AppType::Claude, query)
+                .unwrap()
+                .into_iter()
+                .map(|p| p.id)
+                .collect()
+        };
+
+        assert_eq!(ids("openai"), vec!["a"]);
+        assert_eq!(ids("OpenAI-b"), vec!["b"]);
+        assert_eq!(ids("c"), vec!["c"]);
+        // 仅前缀匹配时返回全部候选
+        assert_eq!(ids("Open"), vec!["a", "b"]);
+        assert!(ids("none").is_empty());
+    }
+
+    #[test]
+    fn test_fuzzy_find_substring_and_typo() {
+        let state = AppState::memory().unwrap();
+        for (id, name) in [("yunwu", "Yunwu API"), ("openai", "OpenAI"), ("router", "OpenRouter")] {
+            state
+                .db
+                .save_provider("claude", &Provider::new(id, name, json!({})))
+                .unwrap();
+        }
+
+        let names = |query: &str| -> Vec<String> {
+            ProviderService::fuzzy_find(&state, AppType::Claude, query)
+                .unwrap()
+                .into_iter()
+                .map(|p| p.name)
+                .collect()
+        };
+
+        assert_eq!(names("wu"), vec!["Yunwu API"]);
+        assert_eq!(names("ywa"), vec!["Yunwu API"]);
+        assert_eq!(names("opanai"), vec!["OpenAI"]);
+        // 完全一致优先于前缀
+        assert_eq!(names("openai"), vec!["OpenAI"]);
+        assert!(names("zzz").is_empty());
+    }
+
+    #[test]
+    fn test_fuzzy_find_lists_ambiguous_candidates() {
+        let state = AppState::memory().unwrap();
+        for (id, name) in [("openai", "OpenAI"), ("router", "OpenRouter"), ("yunwu", "Yunwu")] {
+            state
+                .db
+                .save_provider("claude", &Provider::new(id, name, json!({})))
+                .unwrap();
+        }
+
+        let found = ProviderService::fuzzy_find(&state, AppType::Claude, "open").unwrap();
+        let names: Vec<&str> = found.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["OpenAI", "OpenRouter"]);
+    }
+
+    #[test]
+    fn test_generate_deterministic_ids() {
+        let state = AppState::memory().unwrap();
+
+        let id = ProviderService::generate_id(&state, AppType::Claude, "Work", true).unwrap();
+        assert_eq!(id, "work");
+        state
+            .db
+            .save_provider("claude", &Provider::new(id, "Work", json!({})))
+            .unwrap();
+
+        let id = ProviderService::generate_id(&state, AppType::Claude, "Work", true).unwrap();
+        assert_eq!(id, "work-2");
+        // 不同应用互不影响
+        let id = ProviderService::generate_id(&state, AppType::Codex, "Work", true).unwrap();
+        assert_eq!(id, "work");
+    }
+
+    #[test]
+    #[serial]
+    fn test_switch_all_atomic_rolls_back_on_failure() {
+        let _home = isolated_home();
+        let state = AppState::memory().unwrap();
+
+        let claude_old = Provider::new(
+            "old",
+            "Old",
+            json!({ "env": { "ANTHROPIC_AUTH_TOKEN": "sk-old" } }),
+        );
+        let claude_new = Provider::new(
+            "new",
+            "New",
+            json!({ "env": { "ANTHROPIC_AUTH_TOKEN": "sk-new" } }),
+        );
+        // 缺少 auth/config，校验失败
+        let codex_broken = Provider::new("new", "New", json!({}));
+        state.db.save_provider("claude", &claude_old).unwrap();
+        state.db.save_provider("claude", &claude_new).unwrap();
+        state.db.save_provider("codex", &codex_broken).unwrap();
+        ProviderService::switch(&state, AppType::Claude, "old").unwrap();
+
+        let settings_path = get_claude_settings_path();
+        let before = fs::read_to_string(&settings_path).unwrap();
+
+        let targets = vec![
+            (AppType::Claude, "new".to_string()),
+            (AppType::Codex, "new".to_string()),
+        ];
+        let err = ProviderService::switch_all_atomic(&state, &targets, false).unwrap_err();
+        assert!(err.to_string().contains("已回滚"));
+
+        assert_eq!(fs::read_to_string(&settings_path).unwrap(), before);
+        assert_eq!(
+            state.db.get_current_provider("claude").unwrap().as_deref(),
+            Some("old")
+        );
+        assert_eq!(ProviderService::current(&state, AppType::Claude).unwrap(), "old");
+        assert!(state.db.get_current_provider("codex").unwrap().is_none());
+        assert!(!get_codex_config_path().exists());
+    }
+
+    #[test]
+    fn test_compare_highlights_model_only() {
+        let config = |model: &str| {
+            json!({
+                "env": {
+                    "ANTHROPIC_AUTH_TOKEN": "sk-same",
+                    "ANTHROPIC_BASE_URL": "https://relay.example.com",
+                    "ANTHROPIC_MODEL": model
+                }
+            })
+        };
+        let a = Provider::new("a", "A", config("claude-sonnet-4"));
+        let b = Provider::new("b", "B", config("claude-opus-4"));
+
+        let cmp = ProviderService::compare(&a, &b, &AppType::Claude);
+        let differing: Vec<&str> = cmp
+            .fields
+            .iter()
+            .filter(|f| f.differs())
+            .map(|f| f.field.as_str())
+            .collect();
+        assert_eq!(differing, ["model"]);
+        assert_eq!(cmp.settings_diff.len(), 1);
+        assert_eq!(cmp.settings_diff[0].path, "/env/ANTHROPIC_MODEL");
+        assert_eq!(cmp.settings_diff[0].right, Some(json!("claude-opus-4")));
+    }
+
+    #[test]
+    fn test_claude_api_key_helper_auth() {
+        let provider = Provider::new(
+            "helper",
+            "Helper",
+            json!({
+                "apiKeyHelper": "echo sk-from-helper",
+                "env": { "ANTHROPIC_BASE_URL": "https://relay.example.com" }
+            }),
+        );
+        assert!(ProviderService::validate_provider_settings(&AppType::Claude, &provider).is_ok());
+
+        // 展示用的静态凭据不执行 helper
+        let (key, _) = ProviderService::extract_credentials(&provider, &AppType::Claude).unwrap();
+        assert_eq!(key, "");
+
+        let (key, url) = ProviderService::resolve_credentials(&provider, &AppType::Claude).unwrap();
+        assert_eq!(key, "sk-from-helper");
+        assert_eq!(url, "https://relay.example.com");
+
+        let missing = Provider::new("none", "None", json!({ "env": {} }));
+        assert!(matches!(
+            ProviderService::validate_provider_settings(&AppType::Claude, &missing),
+            Err(AppError::Validation(_))
+        ));
+        assert!(run_api_key_helper("exit 3").is_err());
+    }
+
+    #[test]
+    fn test_extract_credentials_reads_codex_auth() {
+        let toml_auth = Provider::new(
+            "c1",
+            "C1",
+            json!({
+                "config": "model = \"gpt-4\"\n\n[model_providers.openai]\nbase_url = \"https://relay.example.com/v1\"\n",
+                "auth": "[openai]\napi_key = \"sk-codex\"\n"
+            }),
+        );
+        let (key, url) = ProviderService::extract_credentials(&toml_auth, &AppType::Codex).unwrap();
+        assert_eq!(key, "sk-codex");
+        assert_eq!(url, "https://relay.example.com/v1");
+
+        let json_auth = Provider::new(
+            "c2",
+            "C2",
+            json!({ "config": "", "auth": { "OPENAI_API_KEY": "sk-json" } }),
+        );
+        let (key, _) = ProviderService::extract_credentials(&json_auth, &AppType::Codex).unwrap();
+        assert_eq!(key, "sk-json");
+    }
+
+    #[test]
+    #[serial]
+    fn test_import_from_live_keeps_api_key_helper_settings() {
+        let home = isolated_home();
+        let settings = json!({
+            "apiKeyHelper": "~/bin/get-claude-token.sh",
+            "permissions": { "allow": ["Read"], "deny": ["Bash(rm:*)"] }
+        });
+        let path = home.path().join(".claude").join("settings.json");
+        write_json_file(&path, &settings).unwrap();
+
+        let state = AppState::memory().unwrap();
+        let provider = ProviderService::import_from_live(&state, AppType::Claude)
+            .unwrap()
+            .unwrap();
+        assert_eq!(provider.settings_config, settings);
+        assert_eq!(state.db.get_all_providers("claude").unwrap()[&provider.id].settings_config, settings);
+
+        // 切换回该供应商时原样写回 live 配置
+        ProviderService::switch(&state, AppType::Claude, &provider.id).unwrap();
+        let live: Value = read_json_file(&path).unwrap();
+        assert_eq!(live, settings);
+    }
+
+    #[test]
+    fn test_validate_opencode_settings() {
+        let valid = Provider::new(
+            "oc",
+            "OC",
+            json!({
+                "npm": "@ai-sdk/openai-compatible",
+                "options": { "baseURL": "https://api.example.com/v1", "apiKey": "sk-oc" },
+                "models": { "gpt-4o": {} }
+            }),
+        );
+        assert!(ProviderService::validate_provider_settings(&AppType::OpenCode, &valid).is_ok());
+
+        let nested = Provider::new(
+            "nested",
+            "Nested",
+            json!({ "provider": { "relay": { "options": { "apiKey": "sk-relay" } } } }),
+        );
+        assert!(ProviderService::validate_provider_settings(&AppType::OpenCode, &nested).is_ok());
+
+        let empty = Provider::new("empty", "Empty", json!({}));
+        assert!(matches!(
+            ProviderService::validate_provider_settings(&AppType::OpenCode, &empty),
+            Err(AppError::Validation(_))
+        ));
+
+        let no_key = Provider::new("nokey", "NoKey", json!({ "options": { "apiKey": "" } }));
+        assert!(matches!(
+            ProviderService::validate_provider_settings(&AppType::OpenCode, &no_key),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_append_test_note_caps_records() {
+        let date = chrono::NaiveDate::from_ymd_opt(2025, 1, 2).unwrap();
+        let line = format_test_note(date, "OK", 340);
+        assert_eq!(line, "[2025-01-02] OK 340ms");
+
+        let mut notes = "主力线路".to_string();
+        for ms in 0..3 {
+            notes = append_test_note(Some(&notes), &format_test_note(date, "OK", ms), 2);
+        }
+        assert_eq!(notes, "主力线路\n[2025-01-02] OK 1ms\n[2025-01-02] OK 2ms");
+    }
+
+    #[test]
+    fn test_record_test_note_appends_line() {
+        let state = AppState::memory().unwrap();
+        let mut provider = Provider::new("p1", "P1", json!({}));
+        provider.notes = Some("备用".to_string());
+        state.db.save_provider("claude", &provider).unwrap();
+
+        ProviderService::record_test_note(&state, AppType::Claude, "p1", "[2025-01-02] OK 340ms")
+            .unwrap();
+
+        let stored = state.db.get_all_providers("claude").unwrap()["p1"].clone();
+        assert_eq!(stored.notes.as_deref(), Some("备用\n[2025-01-02] OK 340ms"));
+    }
+
+    #[test]
+    fn test_redact_credentials_keeps_base_url_and_model() {
+        let mut provider = Provider::new(
+            "p1",
+            "Provider 1",
+            json!({
+                "env": {
+                    "ANTHROPIC_AUTH_TOKEN": "sk-secret",
+                    "ANTHROPIC_BASE_URL": "https://api.example.com",
+                    "ANTHROPIC_MODEL": "claude-sonnet"
+                }
+            }),
+        );
+
+        ProviderService::redact_credentials(&mut provider, &AppType::Claude);
+
+        let env = &provider.settings_config["env"];
+        assert!(env.get("ANTHROPIC_AUTH_TOKEN").is_none());
+        assert_eq!(env["ANTHROPIC_BASE_URL"], "https://api.example.com");
+        assert_eq!(env["ANTHROPIC_MODEL"], "claude-sonnet");
+
+        let mut codex = Provider::new(
+            "c1",
+            "Codex 1",
+            json!({
+                "config": "model = \"gpt-5\"\napi_key = \"sk-secret\"\nbase_url = \"https://api.example.com/v1\"\n",
+                "auth": "[openai]\napi_key = \"sk-secret\"\n"
+            }),
+        );
+
+        ProviderService::redact_credentials(&mut codex, &AppType::Codex);
+
+        assert!(codex.settings_config.get("auth").is_none());
+        let config = codex.settings_config["config"].as_str().unwrap();
+        assert!(!config.contains("sk-secret"));
+        assert!(config.contains("base_url = \"https://api.example.com/v1\""));
+        assert!(config.contains("model = \"gpt-5\""));
+    }
+
+    #[test]
+    fn test_switch_refuses_invalid_provider() {
+        let state = AppState::memory().unwrap();
+
+        let valid = Provider::new(
+            "valid",
+            "Valid",
+            json!({ "env": { "ANTHROPIC_AUTH_TOKEN": "sk-valid" } }),
+        );
+        let keyless = Provider::new(
+            "keyless",
+            "Keyless",
+            json!({ "env": { "ANTHROPIC_BASE_URL": "https://api.example.com" } }),
+        );
+        state.db.save_provider("claude", &valid).unwrap();
+        state.db.save_provider("claude", &keyless).unwrap();
+        state.db.set_current_provider("claude", "valid").unwrap();
+
+        let result = ProviderService::switch(&state, AppType::Claude, "keyless");
+        assert!(result.is_err());
+        assert_eq!(
+            state.db.get_current_provider("claude").unwrap().as_deref(),
+            Some("valid")
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_import_from_live_creates_current_provider() {
+        let home = isolated_home();
+        let settings_path = home.path().join(".claude").join("settings.json");
+        write_json_file(
+            &settings_path,
+            &json!({
+                "env": {
+                    "ANTHROPIC_AUTH_TOKEN": "sk-live",
+                    "ANTHROPIC_BASE_URL": "https://live.example.com"
+                }
+            }),
+        )
+        .unwrap();
+
+        let state = AppState::memory().unwrap();
+        let imported = ProviderService::import_from_live(&state, AppType::Claude)
+            .unwrap()
+            .unwrap();
+        assert_eq!(imported.name, "Imported (Claude Code)");
+        assert_eq!(ProviderService::current(&state, AppType::Claude).unwrap(), imported.id);
+        assert_eq!(imported.get_base_url().as_deref(), Some("https://live.example.com"));
+        let stored = &state.db.get_all_providers("claude").unwrap()[&imported.id];
+        assert_eq!(stored.settings_config, read_json_file::<Value>(&settings_path).unwrap());
+
+        // 没有现有配置的应用会被跳过
+        assert!(ProviderService::import_from_live(&state, AppType::Gemini)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    #[serial]
+    fn test_live_matches_current_detects_drift() {
+        let home = isolated_home();
+        let state = AppState::memory().unwrap();
+        assert_eq!(ProviderService::live_matches_current(&state, AppType::Claude).unwrap(), None);
+
+        let provider = Provider::new("p1", "P1", json!({"env": {"ANTHROPIC_AUTH_TOKEN": "sk-1"}}));
+        ProviderService::add(&state, AppType::Claude, provider).unwrap();
+        ProviderService::switch(&state, AppType::Claude, "p1").unwrap();
+        assert_eq!(
+            ProviderService::live_matches_current(&state, AppType::Claude).unwrap(),
+            Some(true)
+        );
+
+        let settings_path = home.path().join(".claude").join("settings.json");
+        write_json_file(&settings_path, &json!({"env": {"ANTHROPIC_AUTH_TOKEN": "sk-other"}})).unwrap();
+        assert_eq!(
+            ProviderService::live_matches_current(&state, AppType::Claude).unwrap(),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn test_list_by_category() {
+        let state = AppState::memory().unwrap();
+        for (id, category) in [("p1", Some("official")), ("p2", Some("relay")), ("p3", None)] {
+            let mut provider = Provider::new(id, id, json!({"env": {"ANTHROPIC_AUTH_TOKEN": "sk"}}));
+            provider.category = category.map(str::to_string);
+            state.db.save_provider("claude", &provider).unwrap();
+        }
+
+        let relay = ProviderService::list_by_category(&state, AppType::Claude, "relay").unwrap();
+        assert_eq!(relay.keys().collect::<Vec<_>>(), vec!["p2"]);
+        assert!(ProviderService::list_by_category(&state, AppType::Claude, "none")
+            .unwrap()
+            .is_empty());
+
+        // 通过 update 设置分类后出现在筛选结果中
+        let mut p3 = state.db.get_all_providers("claude").unwrap().shift_remove("p3").unwrap();
+        p3.category = Some("relay".to_string());
+        ProviderService::update(&state, AppType::Claude, p3).unwrap();
+        let relay = ProviderService::list_by_category(&state, AppType::Claude, "relay").unwrap();
+        assert_eq!(relay.keys().collect::<Vec<_>>(), vec!["p2", "p3"]);
+    }
+
+    #[test]
+    fn test_set_field_writes_nested_env_key() {
+        let state = AppState::memory().unwrap();
+        let provider = Provider::new("p1", "P1", json!({"env": {"ANTHROPIC_AUTH_TOKEN": "sk-1"}}));
+        state.db.save_provider("claude", &provider).unwrap();
+
+        ProviderService::set_field(
+            &state,
+            AppType::Claude,
+            "p1",
+            "/env/ANTHROPIC_CUSTOM_HEADERS",
+            json!("x-team: a"),
+        )
+        .unwrap();
+        ProviderService::set_field(&state, AppType::Claude, "p1", "/permissions/allow", json!(["Bash"])).unwrap();
+        ProviderService::set_field(&state, AppType::Claude, "p1", "/permissions/allow/-", json!("Read")).unwrap();
+
+        let saved = &state.db.get_all_providers("claude").unwrap()["p1"];
+        assert_eq!(saved.settings_config["env"]["ANTHROPIC_CUSTOM_HEADERS"], "x-team: a");
+        assert_eq!(saved.settings_config["env"]["ANTHROPIC_AUTH_TOKEN"], "sk-1");
+        assert_eq!(saved.settings_config["permissions"]["allow"], json!(["Bash", "Read"]));
+
+        // 修改后的配置仍需通过校验
+        let err = ProviderService::set_field(&state, AppType::Claude, "p1", "/env", json!({}));
+        assert!(err.is_err());
+        assert_eq!(
+            state.db.get_all_providers("claude").unwrap()["p1"].settings_config["env"]["ANTHROPIC_AUTH_TOKEN"],
+            "sk-1"
+        );
+    }
+
+    #[test]
+    fn test_set_json_pointer_rejects_invalid_pointer() {
+        let mut value = json!({"env": {"KEY": "v"}, "list": [1]});
+        assert!(matches!(
+            set_json_pointer(&mut value, "env/KEY", json!(1)),
+            Err(AppError::InvalidInput(_))
+        ));
+        assert!(set_json_pointer(&mut value, "/env/KEY/inner", json!(1)).is_err());
+        assert!(set_json_pointer(&mut value, "/list/5", json!(1)).is_err());
+
+        set_json_pointer(&mut value, "/a~1b/c~0d", json!(true)).unwrap();
+        assert_eq!(value["a/b"]["c~d"], true);
+    }
+
+    #[test]
+    #[serial]
+    fn test_switch_rolls_back_settings_when_db_write_fails() {
+        let _home = isolated_home();
+        let state = AppState::memory().unwrap();
+        for id in ["p1", "p2"] {
+            let provider = Provider::new(id, id, json!({"env": {"ANTHROPIC_AUTH_TOKEN": "sk"}}));
+            state.db.save_provider("claude", &provider).unwrap();
+        }
+        ProviderService::switch(&state, AppType::Claude, "p1").unwrap();
+
+        // 注入数据库写入失败：将 p2 设为当前时中止
+        state
+            .db
+            .conn
+            .lock()
+            .unwrap()
+            .execute_batch(
+                "CREATE TRIGGER fail_switch BEFORE UPDATE OF is_current ON providers \
+                 WHEN NEW.id = 'p2' AND NEW.is_current = 1 \
+                 BEGIN SELECT RAISE(ABORT, 'injected failure'); END;",
+            )
+            .unwrap();
+
+        assert!(ProviderService::switch(&state, AppType::Claude, "p2").is_err());
+        assert_eq!(settings::get_current_provider(&AppType::Claude).as_deref(), Some("p1"));
+        assert_eq!(state.db.get_current_provider("claude").unwrap().as_deref(), Some("p1"));
+        assert_eq!(ProviderService::current(&state, AppType::Claude).unwrap(), "p1");
+    }
+
+    #[test]
+    fn test_compare_claude_providers_differing_only_in_base_url() {
+        let config = |url: &str| {
+            json!({"env": {
+                "ANTHROPIC_AUTH_TOKEN": "sk-same",
+                "ANTHROPIC_BASE_URL": url,
+                "ANTHROPIC_MODEL": "claude-sonnet-4",
+            }})
+        };
+        let a = Provider::new("a", "A", config("https://a.example.com"));
+        let b = Provider::new("b", "B", config("https://b.example.com"));
+
+        let diff = ProviderService::compare(&a, &b, &AppType::Claude).diff;
+        assert!(diff.only_in_a.is_empty() && diff.only_in_b.is_empty());
+        assert_eq!(diff.changed.len(), 1);
+        let changed = &diff.changed["/env/ANTHROPIC_BASE_URL"];
+        assert_eq!((changed.a.as_str(), changed.b.as_str()), (Some("https://a.example.com"), Some("https://b.example.com")));
+        assert!(ProviderService::compare(&a, &a, &AppType::Claude).diff.is_empty());
+    }
+
+    #[test]
+    fn test_compare_codex_uses_parsed_toml() {
+        let a = Provider::new("a", "A", json!({"config": "model = \"gpt-5\"\nbase_url = \"https://x\"\n"}));
+        let b = Provider::new("b", "B", json!({"config": "base_url = \"https://x\"\n\nmodel = \"gpt-5\"\n"}));
+        assert!(ProviderService::compare(&a, &b, &AppType::Codex).settings_diff.is_empty());
+
+        let c = Provider::new("c", "C", json!({"config": "model = \"o3\"\n"}));
+        let diff = ProviderService::compare(&a, &c, &AppType::Codex).settings_diff;
+        let paths: Vec<&str> = diff.iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(paths, ["/config/base_url", "/config/model"]);
+        assert_eq!(diff[0].right, None);
+    }
+
+    #[test]
+    fn test_compare_codex_reports_key_from_auth() {
+        let with_key = Provider::new(
+            "a",
+            "A",
+            json!({"config": "model = \"gpt-5\"\n", "auth": "[openai]\napi_key = \"sk-a\"\n"}),
+        );
+        let without_key = Provider::new("b", "B", json!({"config": "model = \"gpt-5\"\n"}));
+        let comparison = ProviderService::compare(&with_key, &without_key, &AppType::Codex);
+        let api_key = comparison.fields.iter().find(|f| f.field == "api_key").unwrap();
+        assert_eq!(api_key.left.as_deref(), Some("已配置"));
+        assert_eq!(api_key.right.as_deref(), Some("未配置"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_resolve_env_in_claude_live_settings() {
+        let home = isolated_home();
+        let state = AppState::memory().unwrap();
+        let provider = Provider::new("p1", "p1", json!({"env": {"ANTHROPIC_AUTH_TOKEN": "${MY_KEY}"}}));
+        state.db.save_provider("claude", &provider).unwrap();
+
+        env_interp::set_resolve_env(true);
+        std::env::set_var("MY_KEY", "sk-from-env");
+        let result = ProviderService::switch(&state, AppType::Claude, "p1");
+        std::env::remove_var("MY_KEY");
+        env_interp::set_resolve_env(false);
+        result.unwrap();
+
+        let live: Value = read_json_file(&home.path().join(".claude").join("settings.json")).unwrap();
+        assert_eq!(live["env"]["ANTHROPIC_AUTH_TOKEN"], "sk-from-env");
+        // 数据库中保留占位符
+        let stored = &state.db.get_all_providers("claude").unwrap()["p1"];
+        assert_eq!(stored.settings_config["env"]["ANTHROPIC_AUTH_TOKEN"], "${MY_KEY}");
+    }
+
+    #[test]
+    #[serial]
+    fn test_resolve_env_missing_var_fails_before_writing() {
+        let home = isolated_home();
+        let state = AppState::memory().unwrap();
+        let provider = Provider::new("p1", "p1", json!({"env": {"ANTHROPIC_AUTH_TOKEN": "${CC_SWITCH_TEST_MISSING}"}}));
+        state.db.save_provider("claude", &provider).unwrap();
+
+        env_interp::set_resolve_env(true);
+        let result = ProviderService::switch(&state, AppType::Claude, "p1");
+        env_interp::set_resolve_env(false);
+
+        let err = result.unwrap_err();
+        assert!(err.to_string().contains("CC_SWITCH_TEST_MISSING"));
+        assert!(state.db.get_current_provider("claude").unwrap().is_none());
+        assert!(!home.path().join(".claude").join("settings.json").exists());
+    }
+
+    #[test]
+    #[serial]
+    fn test_switch_to_previous_toggles_back() {
+        let _home = isolated_home();
+        let state = AppState::memory().unwrap();
+        for id in ["a", "b"] {
+            let provider = Provider::new(id, id, json!({"env": {"ANTHROPIC_AUTH_TOKEN": "sk"}}));
+            state.db.save_provider("claude", &provider).unwrap();
+        }
+
+        ProviderService::switch(&state, AppType::Claude, "a").unwrap();
+        assert!(ProviderService::previous(&state, AppType::Claude).unwrap().is_none());
+        ProviderService::switch(&state, AppType::Claude, "b").unwrap();
+
+        let provider = ProviderService::switch_to_previous(&state, AppType::Claude, false).unwrap();
+        assert_eq!(provider.id, "a");
+        assert_eq!(ProviderService::current(&state, AppType::Claude).unwrap(), "a");
+
+        // 再次切回上一个即在两者间来回切换
+        ProviderService::switch_to_previous(&state, AppType::Claude, false).unwrap();
+        assert_eq!(ProviderService::current(&state, AppType::Claude).unwrap(), "b");
+    }
+
+    #[test]
+    #[serial]
+    fn test_switch_to_previous_without_history_fails() {
+        let _home = isolated_home();
+        let state = AppState::memory().unwrap();
+        let provider = Provider::new("a", "a", json!({"env": {"ANTHROPIC_AUTH_TOKEN": "sk"}}));
+        state.db.save_provider("claude", &provider).unwrap();
+        ProviderService::switch(&state, AppType::Claude, "a").unwrap();
+
+        let err = ProviderService::switch_to_previous(&state, AppType::Claude, false).unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+        assert_eq!(ProviderService::current(&state, AppType::Claude).unwrap(), "a");
+    }
+
+    #[test]
+    #[serial]
+    fn test_switch_records_last_used_at() {
+        let _home = isolated_home();
+        let state = AppState::memory().unwrap();
+        for id in ["p1", "p2"] {
+            let provider = Provider::new(id, id, json!({"env": {"ANTHROPIC_AUTH_TOKEN": "sk"}}));
+            state.db.save_provider("claude", &provider).unwrap();
+        }
+        assert!(state.db.get_all_providers("claude").unwrap()["p1"].last_used_at.is_none());
+
+        let before = chrono::Utc::now().timestamp_millis();
+        ProviderService::switch(&state, AppType::Claude, "p1").unwrap();
+        let providers = state.db.get_all_providers("claude").unwrap();
+        assert!(providers["p1"].last_used_at.is_some_and(|t| t >= before));
+        assert!(providers["p2"].last_used_at.is_none());
+
+        // 保存供应商时保留最近使用时间
+        ProviderService::update(&state, AppType::Claude, providers["p1"].clone()).unwrap();
+        assert_eq!(
+            state.db.get_all_providers("claude").unwrap()["p1"].last_used_at,
+            providers["p1"].last_used_at
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_switch_logs_live_write_event() {
+        let home = isolated_home();
+        crate::audit::capture::install();
+        let state = AppState::memory().unwrap();
+        let provider = Provider::new("p1", "P1", json!({"env": {"ANTHROPIC_AUTH_TOKEN": "sk"}}));
+        state.db.save_provider("claude", &provider).unwrap();
+
+        ProviderService::switch(&state, AppType::Claude, "p1").unwrap();
+
+        let path = home.path().join(".claude").join("settings.json");
+        let events = crate::audit::capture::events();
+        let write = events
+            .iter()
+            .find(|e| e["event"] == "live_write" && e["path"] == path.display().to_string())
+            .expect("切换应记录 live 配置写入事件");
+        assert_eq!(write["app"], "claude");
+        assert_eq!(write["provider"], "p1");
+        assert_eq!(write["bytes"].as_u64(), Some(fs::metadata(&path).unwrap().len()));
+        assert!(events
+            .iter()
+            .any(|e| e["event"] == "db_write" && e["op"] == "set_current_provider" && e["provider"] == "p1"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_move_claude_provider_to_gemini() {
+        let _home = isolated_home();
+        let state = AppState::memory().unwrap();
+        let current = Provider::new("main", "Main", json!({"env": {"ANTHROPIC_AUTH_TOKEN": "sk-main"}}));
+        ProviderService::add(&state, AppType::Claude, current).unwrap();
+        let relay = Provider::new(
+            "relay",
+            "Relay",
+            json!({"env": {
+                "ANTHROPIC_AUTH_TOKEN": "sk-relay",
+                "ANTHROPIC_BASE_URL": "https://relay.example.com",
+                "ANTHROPIC_MODEL": "claude-sonnet-4"
+            }}),
+        );
+        ProviderService::add(&state, AppType::Claude, relay).unwrap();
+
+        // 当前供应商不能被移走
+        assert!(ProviderService::move_provider(&state, "main", AppType::Claude, AppType::Gemini).is_err());
+
+        let (moved, warnings) =
+            ProviderService::move_provider(&state, "relay", AppType::Claude, AppType::Gemini).unwrap();
+        assert_eq!(moved.name, "Relay");
+        assert_eq!(warnings, ["env.ANTHROPIC_MODEL"]);
+
+        let gemini = state.db.get_all_providers("gemini").unwrap();
+        assert_eq!(
+            gemini["relay"].settings_config,
+            json!({"apiKey": "sk-relay", "baseUrl": "https://relay.example.com"})
+        );
+        assert!(!state.db.get_all_providers("claude").unwrap().contains_key("relay"));
+
+        // 转换回 Claude 结构，密钥与地址保持不变
+        let (back, warnings) =
+            ProviderService::convert_settings(&gemini["relay"], AppType::Gemini, AppType::Claude).unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(
+            back,
+            json!({"env": {"ANTHROPIC_AUTH_TOKEN": "sk-relay", "ANTHROPIC_BASE_URL": "https://relay.example.com"}})
+        );
+        assert!(ProviderService::move_provider(&state, "relay", AppType::Claude, AppType::OpenCode).is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_move_provider_rolls_back_when_a_step_fails() {
+        let _home = isolated_home();
+        let state = AppState::memory().unwrap();
+        ProviderService::add(&state, AppType::Claude, Provider::new("main", "Main", json!({"env": {"ANTHROPIC_AUTH_TOKEN": "sk-main"}}))).unwrap();
+        ProviderService::add(&state, AppType::Claude, Provider::new("relay", "Relay", json!({"env": {"ANTHROPIC_AUTH_TOKEN": "sk-relay"}}))).unwrap();
+
+        let exec = |sql: &str| state.db.conn.lock().unwrap().execute_batch(sql).unwrap();
+        let assert_unmoved = || {
+            assert!(state.db.get_all_providers("claude").unwrap().contains_key("relay"));
+            assert!(state.db.get_all_providers("gemini").unwrap().is_empty());
+        };
+
+        // 写入目标应用失败：源供应商保留
+        exec("CREATE TRIGGER fail_move BEFORE INSERT ON providers WHEN NEW.app_type = 'gemini' \
+              BEGIN SELECT RAISE(ABORT, 'injected failure'); END;");
+        assert!(ProviderService::move_provider(&state, "relay", AppType::Claude, AppType::Gemini).is_err());
+        assert_unmoved();
+
+        // 删除源供应商失败：不会在目标应用留下副本
+        exec("DROP TRIGGER fail_move; \
+              CREATE TRIGGER fail_move BEFORE DELETE ON providers \
+              BEGIN SELECT RAISE(ABORT, 'injected failure'); END;");
+        assert!(ProviderService::move_provider(&state, "relay", AppType::Claude, AppType::Gemini).is_err());
+        assert_unmoved();
+
+        exec("DROP TRIGGER fail_move;");
+        ProviderService::move_provider(&state, "relay", AppType::Claude, AppType::Gemini).unwrap();
+        assert!(state.db.get_all_providers("gemini").unwrap().contains_key("relay"));
+    }
 }
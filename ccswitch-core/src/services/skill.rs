@@ -3,22 +3,115 @@
 //! 提供 Skill 的业务逻辑，包括安装、同步到各应用等。
 
 use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use crate::app_config::AppType;
-use crate::config::{
-    get_app_config_dir, get_claude_config_dir, get_codex_config_dir, get_gemini_config_dir,
-    get_opencode_config_dir,
-};
+use crate::config::{get_app_config_dir, read_json_file, sanitize_name, write_json_file};
 use crate::error::AppError;
-use crate::skill::{Skill, SkillRepo};
+use crate::skill::{Skill, SkillIndexEntry, SkillRepo};
 use crate::store::AppState;
 
 /// Skill 服务
 pub struct SkillService;
 
+/// Skill 更新结果
+#[derive(Debug, Clone)]
+pub struct SkillUpdate {
+    /// 更新后的 Skill
+    pub skill: Skill,
+    /// 更新前的提交
+    pub previous_commit: Option<String>,
+    /// 是否拉取了新提交（false 表示已是最新）
+    pub updated: bool,
+}
+
+/// Skill 索引本地缓存有效期（秒）
+const SKILL_INDEX_TTL_SECS: i64 = 24 * 60 * 60;
+
+/// Skill 索引本地缓存
+#[derive(Debug, Serialize, Deserialize)]
+struct SkillIndexCache {
+    url: String,
+    fetched_at: i64,
+    entries: Vec<SkillIndexEntry>,
+}
+
+/// 索引文件格式：条目数组或 `{ "skills": [...] }`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SkillIndexManifest {
+    List(Vec<SkillIndexEntry>),
+    Wrapped { skills: Vec<SkillIndexEntry> },
+}
+
+/// Skill 目录中必须包含的清单文件
+pub const SKILL_MANIFEST: &str = "SKILL.md";
+
+/// 判断安装来源是否为本地目录
+fn is_local_source(source: &str) -> bool {
+    source.starts_with("./")
+        || source.starts_with("../")
+        || source.starts_with('/')
+        || Path::new(source).is_dir()
+}
+
+/// 读取 SKILL.md 头部 front matter 中的字段
+fn manifest_field(manifest: &str, key: &str) -> Option<String> {
+    let mut lines = manifest.lines();
+    if lines.next()?.trim() != "---" {
+        return None;
+    }
+    lines
+        .take_while(|line| line.trim() != "---")
+        .filter_map(|line| line.split_once(':'))
+        .find(|(k, _)| k.trim() == key)
+        .map(|(_, v)| v.trim().trim_matches(|c| c == '"' || c == '\'').to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// 递归复制目录（跳过 .git 与符号链接）
+///
+/// 仓库内容不可信，符号链接可能指向仓库外的文件，因此不跟随也不复制。
+fn copy_dir(from: &Path, to: &Path) -> Result<(), AppError> {
+    fs::create_dir_all(to).map_err(|e| AppError::io(to, e))?;
+    for entry in fs::read_dir(from).map_err(|e| AppError::io(from, e))? {
+        let entry = entry.map_err(|e| AppError::io(from, e))?;
+        let path = entry.path();
+        let target = to.join(entry.file_name());
+        let file_type = entry.file_type().map_err(|e| AppError::io(&path, e))?;
+        if file_type.is_symlink() {
+            continue;
+        }
+        if file_type.is_dir() {
+            if entry.file_name() == ".git" {
+                continue;
+            }
+            copy_dir(&path, &target)?;
+        } else {
+            fs::copy(&path, &target).map_err(|e| AppError::io(&path, e))?;
+        }
+    }
+    Ok(())
+}
+
+/// 判断是否需要更新
+///
+/// 未记录本地提交时总是更新；支持缩写提交号的前缀比较。
+pub fn needs_update(installed: Option<&str>, latest: &str) -> bool {
+    let Some(installed) = installed.map(str::trim).filter(|c| !c.is_empty()) else {
+        return true;
+    };
+    let latest = latest.trim();
+    let len = installed.len().min(latest.len());
+    if len < 7 {
+        return !installed.eq_ignore_ascii_case(latest);
+    }
+    !installed[..len].eq_ignore_ascii_case(&latest[..len])
+}
+
 impl SkillService {
     /// 获取 Skills 存储目录
     pub fn get_skills_dir() -> PathBuf {
@@ -27,16 +120,11 @@ impl SkillService {
 
     /// 获取应用的 Skills 目录
     pub fn get_app_skills_dir(app: &AppType) -> PathBuf {
-        match app {
-            AppType::Claude => get_claude_config_dir().join("skills"),
-            AppType::Codex => get_codex_config_dir().join("skills"),
-            AppType::Gemini => get_gemini_config_dir().join("skills"),
-            AppType::OpenCode => get_opencode_config_dir().join("skills"),
-            AppType::OpenClaw => {
-                // OpenClaw 不支持 Skills，返回空路径
-                PathBuf::new()
-            }
+        if !app.supports_skills() {
+            // OpenClaw 不支持 Skills，返回空路径
+            return PathBuf::new();
         }
+        app.config_paths().config_dir.join("skills")
     }
 
     /// 列出所有 Skills
@@ -49,11 +137,93 @@ impl SkillService {
         state.db.get_skill(id)
     }
 
-    /// 从 GitHub 仓库安装 Skill
+    /// 安装 Skill
+    ///
+    /// `source` 为 `owner/name` 时从 GitHub 安装；以 `./`、`../`、`/` 开头
+    /// 或指向已存在的目录时从本地目录安装。`rev` 指定时检出该提交或标签并固定。
     pub fn install(
+        state: &AppState,
+        source: &str,
+        branch: Option<String>,
+        rev: Option<String>,
+    ) -> Result<Skill, AppError> {
+        if is_local_source(source) {
+            if rev.is_some() {
+                return Err(AppError::InvalidInput(
+                    "从本地目录安装时不支持 --rev".to_string(),
+                ));
+            }
+            return Self::install_from_path(state, Path::new(source));
+        }
+        Self::install_from_repo(state, source, branch, rev)
+    }
+
+    /// 从本地目录安装 Skill（复制到托管目录）
+    pub fn install_from_path(state: &AppState, source: &Path) -> Result<Skill, AppError> {
+        if !source.is_dir() {
+            return Err(AppError::InvalidInput(format!(
+                "目录不存在: {}",
+                source.display()
+            )));
+        }
+
+        let manifest_path = source.join(SKILL_MANIFEST);
+        if !manifest_path.is_file() {
+            return Err(AppError::InvalidInput(format!(
+                "目录中缺少 {}，不是有效的 Skill: {}",
+                SKILL_MANIFEST,
+                source.display()
+            )));
+        }
+        let manifest =
+            fs::read_to_string(&manifest_path).map_err(|e| AppError::io(&manifest_path, e))?;
+
+        let source = source.canonicalize().map_err(|e| AppError::io(source, e))?;
+        let dir_name = source
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let skill_id = sanitize_name(&dir_name);
+        if skill_id.is_empty() {
+            return Err(AppError::InvalidInput(format!(
+                "无法从目录名生成 Skill ID: {}",
+                source.display()
+            )));
+        }
+
+        if state.db.get_skill(&skill_id)?.is_some() {
+            return Err(AppError::InvalidInput(format!(
+                "Skill '{}' 已安装",
+                skill_id
+            )));
+        }
+
+        let skill_dir = Self::get_skills_dir().join(&skill_id);
+        if skill_dir.exists() {
+            return Err(AppError::InvalidInput(format!(
+                "目标目录已存在: {}",
+                skill_dir.display()
+            )));
+        }
+        copy_dir(&source, &skill_dir)?;
+
+        let name = manifest_field(&manifest, "name").unwrap_or(dir_name);
+        let mut skill = Skill::new(&skill_id, name, skill_dir.to_string_lossy());
+        if let Some(description) = manifest_field(&manifest, "description") {
+            skill = skill.with_description(description);
+        }
+
+        state.db.save_skill(&skill)?;
+
+        Ok(skill)
+    }
+
+    /// 从 GitHub 仓库安装 Skill
+    fn install_from_repo(
         state: &AppState,
         repo: &str,
         branch: Option<String>,
+        rev: Option<String>,
     ) -> Result<Skill, AppError> {
         // 解析仓库格式 owner/name
         let parts: Vec<&str> = repo.split('/').collect();
@@ -85,10 +255,105 @@ impl SkillService {
         // 克隆仓库
         let skill_dir = skills_dir.join(&skill_id);
         let repo_url = format!("https://github.com/{}/{}.git", owner, name);
+        match &rev {
+            Some(rev) => Self::clone_repo_at_rev(&repo_url, rev, &skill_dir)?,
+            None => Self::clone_repo(&repo_url, &branch, &skill_dir)?,
+        }
 
+        // 创建 Skill 记录
+        let skill = Skill::new(&skill_id, name, skill_dir.to_string_lossy())
+            .with_repo(owner, name, Some(branch))
+            .with_commit(Self::head_commit(&skill_dir))
+            .with_rev(rev);
+
+        state.db.save_skill(&skill)?;
+
+        Ok(skill)
+    }
+
+    /// 从记录的仓库和分支更新 Skill
+    ///
+    /// 远端提交与本地一致时不做任何改动；否则重新克隆替换本地文件，
+    /// 并重新同步到已启用的应用。已固定版本的 Skill 需传入 `unpin` 解除固定后才会更新。
+    pub fn update(state: &AppState, id: &str, unpin: bool) -> Result<SkillUpdate, AppError> {
+        let mut skill = state
+            .db
+            .get_skill(id)?
+            .ok_or_else(|| AppError::NotFound(format!("Skill '{}' 不存在", id)))?;
+
+        let unpinned = match &skill.repo_rev {
+            Some(rev) if !unpin => {
+                return Err(AppError::InvalidInput(format!(
+                    "Skill '{}' 已固定在 {}，如需跟随分支更新请添加 --unpin",
+                    id, rev
+                )));
+            }
+            Some(_) => {
+                skill.repo_rev = None;
+                true
+            }
+            None => false,
+        };
+
+        let Some(repo_url) = skill.repo_url() else {
+            return Err(AppError::InvalidInput(format!(
+                "Skill '{}' 未记录来源仓库，无法更新",
+                id
+            )));
+        };
+        let repo_url = format!("{}.git", repo_url);
+        let branch = skill.repo_branch.clone().unwrap_or_else(|| "main".to_string());
+        let skill_dir = PathBuf::from(&skill.directory);
+
+        let previous_commit = skill
+            .repo_commit
+            .clone()
+            .or_else(|| Self::head_commit(&skill_dir));
+        let latest = Self::remote_commit(&repo_url, &branch)?;
+
+        if !needs_update(previous_commit.as_deref(), &latest) {
+            if skill.repo_commit.is_none() || unpinned {
+                skill.repo_commit = previous_commit.clone();
+                state.db.save_skill(&skill)?;
+            }
+            return Ok(SkillUpdate {
+                skill,
+                previous_commit,
+                updated: false,
+            });
+        }
+
+        // 先克隆到临时目录，成功后再替换，避免更新失败丢失现有文件
+        let staging_dir = skill_dir.with_file_name(format!(".{}.update", skill.id));
+        if staging_dir.exists() {
+            fs::remove_dir_all(&staging_dir).map_err(|e| AppError::io(&staging_dir, e))?;
+        }
+        Self::clone_repo(&repo_url, &branch, &staging_dir)?;
+
+        if skill_dir.exists() {
+            fs::remove_dir_all(&skill_dir).map_err(|e| AppError::io(&skill_dir, e))?;
+        }
+        fs::rename(&staging_dir, &skill_dir).map_err(|e| AppError::io(&skill_dir, e))?;
+
+        skill.repo_commit = Self::head_commit(&skill_dir).or(Some(latest));
+        state.db.save_skill(&skill)?;
+
+        for app in skill.apps.enabled_apps() {
+            Self::create_app_symlink(&app, &skill)?;
+        }
+
+        Ok(SkillUpdate {
+            skill,
+            previous_commit,
+            updated: true,
+        })
+    }
+
+    /// 浅克隆仓库指定分支
+    fn clone_repo(repo_url: &str, branch: &str, dest: &Path) -> Result<(), AppError> {
         let output = Command::new("git")
-            .args(["clone", "--depth", "1", "--branch", &branch, &repo_url])
-            .arg(&skill_dir)
+            .args(["clone", "--depth", "1", "--branch", branch, repo_url])
+            .arg(dest)
             .output()
             .map_err(|e| AppError::Config(format!("执行 git clone 失败: {}", e)))?;
 
@@ -97,13 +362,77 @@ impl SkillService {
             return Err(AppError::Config(format!("克隆仓库失败: {}", stderr)));
         }
 
-        // 创建 Skill 记录
-        let skill = Skill::new(&skill_id, name, skill_dir.to_string_lossy())
-            .with_repo(owner, name, Some(branch));
+        Ok(())
+    }
 
-        state.db.save_skill(&skill)?;
+    /// 克隆仓库并检出指定的提交或标签
+    fn clone_repo_at_rev(repo_url: &str, rev: &str, dest: &Path) -> Result<(), AppError> {
+        // 以 `-` 开头会被 git checkout 当作选项解析
+        if rev.is_empty() || rev.starts_with('-') {
+            return Err(AppError::InvalidInput(format!("无效的提交或标签: '{}'", rev)));
+        }
 
-        Ok(skill)
+        let output = Command::new("git")
+            .args(["clone", "--quiet", "--no-checkout", repo_url])
+            .arg(dest)
+            .output()
+            .map_err(|e| AppError::Config(format!("执行 git clone 失败: {}", e)))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(AppError::Config(format!("克隆仓库失败: {}", stderr)));
+        }
+
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dest)
+            .args(["checkout", "--quiet", "--detach", rev])
+            .output()
+            .map_err(|e| AppError::Config(format!("执行 git checkout 失败: {}", e)))?;
+
+        if !output.status.success() {
+            let _ = fs::remove_dir_all(dest);
+            return Err(AppError::InvalidInput(format!(
+                "仓库中不存在提交或标签 '{}'",
+                rev
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// 获取本地仓库当前提交
+    fn head_commit(dir: &Path) -> Option<String> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["rev-parse", "HEAD"])
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .filter(|c| !c.is_empty())
+    }
+
+    /// 获取远端分支最新提交
+    fn remote_commit(repo_url: &str, branch: &str) -> Result<String, AppError> {
+        let output = Command::new("git")
+            .args(["ls-remote", repo_url, &format!("refs/heads/{}", branch)])
+            .output()
+            .map_err(|e| AppError::Config(format!("执行 git ls-remote 失败: {}", e)))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(AppError::Config(format!("查询远端仓库失败: {}", stderr)));
+        }
+
+        String::from_utf8_lossy(&output.stdout)
+            .split_whitespace()
+            .next()
+            .map(str::to_string)
+            .ok_or_else(|| AppError::Config(format!("远端仓库不存在分支 '{}'", branch)))
     }
 
     /// 卸载 Skill
@@ -111,7 +440,7 @@ impl SkillService {
         let skill = state
             .db
             .get_skill(id)?
-            .ok_or_else(|| AppError::InvalidInput(format!("Skill '{}' 不存在", id)))?;
+            .ok_or_else(|| AppError::NotFound(format!("Skill '{}' 不存在", id)))?;
 
         // 删除本地目录
         let skill_dir = PathBuf::from(&skill.directory);
@@ -132,22 +461,49 @@ impl SkillService {
 
     /// 切换 Skill 的应用启用状态
     pub fn toggle(state: &AppState, id: &str, app: AppType, enable: bool) -> Result<(), AppError> {
+        Self::toggle_apps(state, id, &[app], enable).map(|_| ())
+    }
+
+    /// 支持 Skills 的应用（OpenClaw 不支持）
+    pub fn supported_apps() -> Vec<AppType> {
+        AppType::all()
+            .iter()
+            .copied()
+            .filter(|app| *app != AppType::OpenClaw)
+            .collect()
+    }
+
+    /// 一次性切换多个应用的启用状态，每个应用只同步一次
+    ///
+    /// 不支持 Skills 的应用会被忽略，返回实际处理的应用。
+    pub fn toggle_apps(
+        state: &AppState,
+        id: &str,
+        apps: &[AppType],
+        enable: bool,
+    ) -> Result<Vec<AppType>, AppError> {
         let mut skill = state
             .db
             .get_skill(id)?
-            .ok_or_else(|| AppError::InvalidInput(format!("Skill '{}' 不存在", id)))?;
+            .ok_or_else(|| AppError::NotFound(format!("Skill '{}' 不存在", id)))?;
 
-        skill.apps.set_enabled_for(&app, enable);
+        let supported = Self::supported_apps();
+        let apps: Vec<AppType> = apps.iter().copied().filter(|a| supported.contains(a)).collect();
+        for app in &apps {
+            skill.apps.set_enabled_for(app, enable);
+        }
         state.db.update_skill_apps(id, &skill.apps)?;
 
         // 同步到应用
-        if enable {
-            Self::create_app_symlink(&app, &skill)?;
-        } else {
-            Self::remove_app_symlink(&app, id)?;
+        for app in &apps {
+            if enable {
+                Self::create_app_symlink(app, &skill)?;
+            } else {
+                Self::remove_app_symlink(app, id)?;
+            }
         }
 
-        Ok(())
+        Ok(apps)
     }
 
     /// 同步所有 Skills 到所有应用
@@ -246,6 +602,108 @@ impl SkillService {
         Ok(found)
     }
 
+    // ===== Skill 索引 =====
+
+    /// 获取 Skill 索引地址（未配置时为 None）
+    pub fn index_url(state: &AppState) -> Result<Option<String>, AppError> {
+        state.db.get_setting("skill_index_url")
+    }
+
+    /// 设置 Skill 索引地址，`None` 清除
+    pub fn set_index_url(state: &AppState, url: Option<&str>) -> Result<(), AppError> {
+        match url {
+            Some(url) => {
+                url::Url::parse(url)
+                    .map_err(|e| AppError::InvalidInput(format!("无效的索引地址: {}", e)))?;
+                state.db.set_setting("skill_index_url", url)
+            }
+            None => state.db.delete_setting("skill_index_url"),
+        }
+    }
+
+    /// 在 Skill 索引中搜索
+    ///
+    /// 索引缓存在本地，超过有效期或 `refresh` 时重新下载。
+    pub async fn search(
+        state: &AppState,
+        query: &str,
+        refresh: bool,
+    ) -> Result<Vec<SkillIndexEntry>, AppError> {
+        let url = Self::index_url(state)?.ok_or_else(|| {
+            AppError::InvalidInput(
+                "未配置 Skill 索引，请先执行 cc-switch skill index <URL> 设置 JSON 索引清单地址".to_string(),
+            )
+        })?;
+        let entries = match Self::cached_index(&url, refresh) {
+            Some(entries) => entries,
+            None => {
+                let entries = Self::fetch_index(&url).await?;
+                Self::save_index_cache(&url, &entries)?;
+                entries
+            }
+        };
+
+        Ok(entries.into_iter().filter(|e| e.matches(query)).collect())
+    }
+
+    /// 索引缓存文件路径
+    fn index_cache_path() -> PathBuf {
+        get_app_config_dir().join("cache").join("skill-index.json")
+    }
+
+    /// 读取未过期的索引缓存
+    fn cached_index(url: &str, refresh: bool) -> Option<Vec<SkillIndexEntry>> {
+        if refresh {
+            return None;
+        }
+        let path = Self::index_cache_path();
+        if !path.exists() {
+            return None;
+        }
+        let cache: SkillIndexCache = read_json_file(&path).ok()?;
+        let age = chrono::Utc::now().timestamp() - cache.fetched_at;
+        (cache.url == url && (0..SKILL_INDEX_TTL_SECS).contains(&age)).then_some(cache.entries)
+    }
+
+    /// 写入索引缓存
+    fn save_index_cache(url: &str, entries: &[SkillIndexEntry]) -> Result<(), AppError> {
+        let cache = SkillIndexCache {
+            url: url.to_string(),
+            fetched_at: chrono::Utc::now().timestamp(),
+            entries: entries.to_vec(),
+        };
+        write_json_file(&Self::index_cache_path(), &cache)
+    }
+
+    /// 下载索引
+    async fn fetch_index(url: &str) -> Result<Vec<SkillIndexEntry>, AppError> {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(15))
+            .build()
+            .map_err(|e| AppError::Config(format!("客户端构建失败: {}", e)))?;
+
+        let response = client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| AppError::Config(format!("下载 Skill 索引失败: {}", e)))?;
+        if !response.status().is_success() {
+            return Err(AppError::Config(format!(
+                "下载 Skill 索引失败: HTTP {}（请检查 cc-switch skill index 设置的地址）",
+                response.status()
+            )));
+        }
+
+        let manifest: SkillIndexManifest = response
+            .json()
+            .await
+            .map_err(|e| AppError::Config(format!("解析 Skill 索引失败: {}", e)))?;
+        Ok(match manifest {
+            SkillIndexManifest::List(entries) => entries,
+            SkillIndexManifest::Wrapped { skills } => skills,
+        })
+    }
+
     // ===== Skill Repo 管理 =====
 
     /// 列出所有 Skill 仓库
@@ -267,6 +725,25 @@ impl SkillService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::isolated_home;
+    use serial_test::serial;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_dir_skips_symlinks() {
+        let src = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        std::fs::write(outside.path().join("secret"), "s").unwrap();
+        std::fs::write(src.path().join("SKILL.md"), "# skill").unwrap();
+        std::os::unix::fs::symlink(outside.path().join("secret"), src.path().join("link")).unwrap();
+        std::os::unix::fs::symlink(outside.path(), src.path().join("dir-link")).unwrap();
+
+        let dest = tempfile::tempdir().unwrap();
+        copy_dir(src.path(), &dest.path().join("skill")).unwrap();
+        assert!(dest.path().join("skill/SKILL.md").exists());
+        assert!(!dest.path().join("skill/link").exists());
+        assert!(!dest.path().join("skill/dir-link").exists());
+    }
 
     #[test]
     fn test_skill_service_list() {
@@ -274,4 +751,183 @@ mod tests {
         let skills = SkillService::list(&state).unwrap();
         assert!(skills.is_empty());
     }
+
+    #[test]
+    fn test_needs_update_compares_commits() {
+        let full = "4f2c9d1e8a7b6c5d4e3f2a1b0c9d8e7f6a5b4c3d";
+
+        assert!(needs_update(None, full));
+        assert!(needs_update(Some(""), full));
+        assert!(!needs_update(Some(full), full));
+        // 缩写提交号与完整提交号视为相同
+        assert!(!needs_update(Some("4F2C9D1"), full));
+        assert!(needs_update(Some("0000000000"), full));
+        assert!(needs_update(Some("4f2c"), full));
+    }
+
+    #[test]
+    #[serial]
+    fn test_install_from_local_path() {
+        let home = isolated_home();
+        let state = AppState::memory().unwrap();
+
+        let source = home.path().join("dev").join("my-skill");
+        fs::create_dir_all(source.join("scripts")).unwrap();
+        fs::write(
+            source.join(SKILL_MANIFEST),
+            "---\nname: My Skill\ndescription: Local test skill\n---\n# Usage\n",
+        )
+        .unwrap();
+        fs::write(source.join("scripts").join("run.sh"), "echo hi").unwrap();
+
+        let skill = SkillService::install(&state, source.to_str().unwrap(), None, None).unwrap();
+        assert_eq!(skill.id, "my-skill");
+        assert_eq!(skill.name, "My Skill");
+        assert_eq!(skill.description.as_deref(), Some("Local test skill"));
+        assert!(skill.repo_url().is_none());
+
+        let installed = PathBuf::from(&skill.directory);
+        assert!(installed.starts_with(SkillService::get_skills_dir()));
+        assert!(installed.join("scripts").join("run.sh").is_file());
+
+        let skills = SkillService::list(&state).unwrap();
+        assert!(skills.contains_key("my-skill"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_toggle_apps_enables_every_supported_app() {
+        let home = isolated_home();
+        let state = AppState::memory().unwrap();
+
+        let source = home.path().join("dev").join("my-skill");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join(SKILL_MANIFEST), "---\nname: My Skill\n---\n").unwrap();
+        SkillService::install(&state, source.to_str().unwrap(), None, None).unwrap();
+
+        let applied = SkillService::toggle_apps(&state, "my-skill", AppType::all(), true).unwrap();
+        assert_eq!(applied, SkillService::supported_apps());
+
+        let skill = SkillService::get(&state, "my-skill").unwrap().unwrap();
+        assert_eq!(skill.apps.enabled_apps(), SkillService::supported_apps());
+        for app in &applied {
+            assert!(SkillService::get_app_skills_dir(app).join("my-skill").exists());
+        }
+
+        SkillService::toggle_apps(&state, "my-skill", &[AppType::Codex], false).unwrap();
+        let skill = SkillService::get(&state, "my-skill").unwrap().unwrap();
+        assert!(skill.apps.claude && !skill.apps.codex);
+    }
+
+    #[test]
+    #[serial]
+    fn test_install_from_path_requires_manifest() {
+        let home = isolated_home();
+        let state = AppState::memory().unwrap();
+
+        let source = home.path().join("not-a-skill");
+        fs::create_dir_all(&source).unwrap();
+
+        assert!(SkillService::install_from_path(&state, &source).is_err());
+        assert!(SkillService::list(&state).unwrap().is_empty());
+    }
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    #[test]
+    #[serial]
+    fn test_pinned_rev_is_checked_out_and_blocks_update() {
+        let home = isolated_home();
+        let state = AppState::memory().unwrap();
+
+        // 本地仓库：v1 标签之后还有一个提交
+        let origin = home.path().join("origin");
+        fs::create_dir_all(&origin).unwrap();
+        git(&origin, &["init", "--quiet"]);
+        fs::write(origin.join(SKILL_MANIFEST), "---\nname: v1\n---\n").unwrap();
+        git(&origin, &["add", "."]);
+        git(&origin, &["commit", "--quiet", "-m", "v1"]);
+        git(&origin, &["tag", "v1"]);
+        let v1 = git(&origin, &["rev-parse", "HEAD"]);
+        fs::write(origin.join(SKILL_MANIFEST), "---\nname: v2\n---\n").unwrap();
+        git(&origin, &["commit", "--quiet", "-am", "v2"]);
+
+        let dest = home.path().join("pinned");
+        SkillService::clone_repo_at_rev(origin.to_str().unwrap(), "v1", &dest).unwrap();
+        assert_eq!(SkillService::head_commit(&dest).as_deref(), Some(v1.as_str()));
+        assert!(fs::read_to_string(dest.join(SKILL_MANIFEST)).unwrap().contains("v1"));
+
+        let missing = home.path().join("missing");
+        assert!(SkillService::clone_repo_at_rev(origin.to_str().unwrap(), "v9", &missing).is_err());
+        let err = SkillService::clone_repo_at_rev(origin.to_str().unwrap(), "--orphan=x", &missing).unwrap_err();
+        assert!(matches!(err, AppError::InvalidInput(_)));
+        assert!(!missing.exists());
+
+        // 固定版本记录在 Skill 上，未解除固定时拒绝更新
+        let skill = Skill::new("acme-tools", "tools", dest.to_string_lossy())
+            .with_repo("acme", "tools", Some("main".to_string()))
+            .with_commit(Some(v1.clone()))
+            .with_rev(Some("v1".to_string()));
+        state.db.save_skill(&skill).unwrap();
+        let saved = SkillService::get(&state, "acme-tools").unwrap().unwrap();
+        assert_eq!(saved.repo_rev.as_deref(), Some("v1"));
+
+        let err = SkillService::update(&state, "acme-tools", false).unwrap_err();
+        assert!(err.to_string().contains("--unpin"));
+        assert_eq!(SkillService::get(&state, "acme-tools").unwrap().unwrap().repo_rev.as_deref(), Some("v1"));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_search_filters_cached_index() {
+        let _home = isolated_home();
+        let state = AppState::memory().unwrap();
+
+        let entries = vec![
+            SkillIndexEntry {
+                repo: "acme/pdf-tools".to_string(),
+                name: "PDF Tools".to_string(),
+                description: Some("Fill and merge PDF forms".to_string()),
+                tags: vec!["documents".to_string()],
+                branch: None,
+            },
+            SkillIndexEntry {
+                repo: "acme/web-testing".to_string(),
+                name: "Web Testing".to_string(),
+                description: Some("Browser automation with Playwright".to_string()),
+                tags: vec!["testing".to_string()],
+                branch: None,
+            },
+        ];
+        // 未配置索引时给出可操作的提示
+        assert!(SkillService::index_url(&state).unwrap().is_none());
+        let err = SkillService::search(&state, "pdf", false).await.unwrap_err();
+        assert!(matches!(err, AppError::InvalidInput(_)) && err.to_string().contains("skill index"));
+
+        // 预置缓存，模拟已下载的索引
+        let url = "https://example.com/skills-index.json";
+        SkillService::set_index_url(&state, Some(url)).unwrap();
+        SkillService::save_index_cache(url, &entries).unwrap();
+
+        let found = SkillService::search(&state, "pdf", false).await.unwrap();
+        assert_eq!(found, vec![entries[0].clone()]);
+
+        let found = SkillService::search(&state, "PLAYWRIGHT", false).await.unwrap();
+        assert_eq!(found[0].repo, "acme/web-testing");
+
+        let found = SkillService::search(&state, "documents", false).await.unwrap();
+        assert_eq!(found.len(), 1);
+
+        assert!(SkillService::search(&state, "nothing", false).await.unwrap().is_empty());
+    }
 }
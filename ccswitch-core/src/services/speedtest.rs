@@ -4,6 +4,8 @@
 
 use std::time::{Duration, Instant};
 
+use crate::services::global_proxy::ProxyService;
+
 /// 测速服务
 pub struct SpeedtestService;
 
@@ -29,6 +31,7 @@ impl SpeedtestService {
         urls: Option<Vec<String>>,
         timeout_secs: u64,
         proxy: Option<&str>,
+        no_proxy: &[String],
     ) -> Vec<SpeedtestResult> {
         let urls = urls.unwrap_or_else(|| {
             DEFAULT_TARGETS.iter().map(|s| s.to_string()).collect()
@@ -39,20 +42,25 @@ impl SpeedtestService {
         // 并发测试所有端点
         let futures: Vec<_> = urls
             .iter()
-            .map(|url| Self::test_single(url, timeout, proxy))
+            .map(|url| Self::test_single(url, timeout, proxy, no_proxy))
             .collect();
 
         futures::future::join_all(futures).await
     }
 
     /// 测试单个端点
-    async fn test_single(url: &str, timeout: Duration, proxy: Option<&str>) -> SpeedtestResult {
+    async fn test_single(
+        url: &str,
+        timeout: Duration,
+        proxy: Option<&str>,
+        no_proxy: &[String],
+    ) -> SpeedtestResult {
         let client_builder = reqwest::Client::builder()
             .timeout(timeout)
             .connect_timeout(Duration::from_secs(5));
 
         let client_builder = if let Some(proxy_url) = proxy {
-            match reqwest::Proxy::all(proxy_url) {
+            match ProxyService::build_proxy(proxy_url, no_proxy) {
                 Ok(p) => client_builder.proxy(p),
                 Err(e) => {
                     return SpeedtestResult {
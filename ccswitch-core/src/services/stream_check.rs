@@ -117,7 +117,7 @@ impl StreamCheckService {
         use crate::services::ProviderService;
 
         // 提取凭据
-        let (api_key, base_url) = ProviderService::extract_credentials(provider, app_type)?;
+        let (api_key, base_url) = ProviderService::resolve_credentials(provider, app_type)?;
 
         if api_key.is_empty() {
             return Err(AppError::Message("API Key 未配置".to_string()));
@@ -188,4 +188,26 @@ mod tests {
         assert_eq!(config.interval_seconds, 300);
         assert_eq!(config.retry_count, 3);
     }
+
+    #[tokio::test]
+    async fn test_unreachable_endpoint_is_network_error() {
+        let provider = Provider::new(
+            "p1",
+            "P1",
+            serde_json::json!({"env": {
+                "ANTHROPIC_AUTH_TOKEN": "sk-test",
+                "ANTHROPIC_BASE_URL": "http://127.0.0.1:1"
+            }}),
+        );
+        let config = StreamCheckConfig {
+            timeout_seconds: 2,
+            ..StreamCheckConfig::default()
+        };
+
+        let result = StreamCheckService::do_health_check(&AppType::Claude, &provider, &config).await;
+        let Err(err) = result else {
+            panic!("不可达地址不应检查成功");
+        };
+        assert!(matches!(err, AppError::Network(_)), "{:?}", err);
+    }
 }
\ No newline at end of file
@@ -255,11 +255,8 @@ impl UsageStatsService {
         let daily_limit_key = format!("limit_daily_{}", provider_id);
         let monthly_limit_key = format!("limit_monthly_{}", provider_id);
 
-        let daily_limit: Option<f64> = state.db.get_setting(&daily_limit_key)?
-            .and_then(|s| s.parse().ok());
-
-        let monthly_limit: Option<f64> = state.db.get_setting(&monthly_limit_key)?
-            .and_then(|s| s.parse().ok());
+        let daily_limit = state.db.get_setting_f64(&daily_limit_key)?;
+        let monthly_limit = state.db.get_setting_f64(&monthly_limit_key)?;
 
         // 获取今日使用量
         let today = Utc::now().format("%Y-%m-%d").to_string();
@@ -296,7 +293,7 @@ impl UsageStatsService {
         limit: f64,
     ) -> Result<(), AppError> {
         let key = format!("limit_daily_{}", provider_id);
-        state.db.set_setting(&key, &limit.to_string())
+        state.db.set_setting_f64(&key, limit)
     }
 
     /// 设置月限额
@@ -306,7 +303,7 @@ impl UsageStatsService {
         limit: f64,
     ) -> Result<(), AppError> {
         let key = format!("limit_monthly_{}", provider_id);
-        state.db.set_setting(&key, &limit.to_string())
+        state.db.set_setting_f64(&key, limit)
     }
 
     /// 清除使用记录
@@ -3,6 +3,7 @@
 //! 管理设备级别的本地设置，不随云同步。
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::sync::OnceLock;
 use std::sync::RwLock;
@@ -10,6 +11,7 @@ use std::sync::RwLock;
 use crate::app_config::AppType;
 use crate::config::{get_app_config_dir, read_json_file, write_json_file};
 use crate::error::AppError;
+use crate::profile::active_profile;
 
 /// 全局设置缓存
 static SETTINGS_CACHE: OnceLock<RwLock<Option<AppSettings>>> = OnceLock::new();
@@ -88,6 +90,26 @@ pub struct AppSettings {
     /// 输出格式 (table, json, yaml)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub output_format: Option<String>,
+
+    /// 新增/导入供应商时仅由名称生成 ID（不带时间戳）
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deterministic_ids: bool,
+
+    /// 切换时保留的 live 配置备份数量（默认 5，至少保留 1 个）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub live_backup_retention: Option<usize>,
+
+    /// 交互菜单空闲超时秒数，超时无输入自动退出（默认关闭）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interactive_idle_timeout: Option<u64>,
+
+    /// 写入 live 配置时解析 `${ENV_VAR}` 引用（等同于始终传入 --resolve-env）
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub resolve_env: bool,
+
+    /// 各应用由 cc-switch 写入的 MCP 服务器 ID，同步时只增删这些条目
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub mcp_managed_ids: BTreeMap<String, Vec<String>>,
 }
 
 impl AppSettings {
@@ -183,8 +205,79 @@ where
     Ok(())
 }
 
+/// 从文件重新读取设置并刷新缓存（其他进程修改 settings.json 后使用）
+pub fn reload_settings() -> Result<AppSettings, AppError> {
+    let settings = AppSettings::load()?;
+    let mut write_guard = settings_cache().write().map_err(|e| AppError::Lock(e.to_string()))?;
+    *write_guard = Some(settings.clone());
+    Ok(settings)
+}
+
+/// 清空设置缓存（测试切换配置目录后使用）
+#[cfg(any(test, feature = "test-hooks"))]
+pub fn reset_settings_cache() {
+    if let Ok(mut guard) = settings_cache().write() {
+        *guard = None;
+    }
+}
+
+/// 是否启用确定性 ID（便捷函数）
+pub fn deterministic_ids() -> bool {
+    get_settings().map(|s| s.deterministic_ids).unwrap_or(false)
+}
+
+/// 是否解析供应商配置中的环境变量引用（便捷函数）
+pub fn resolve_env() -> bool {
+    get_settings().map(|s| s.resolve_env).unwrap_or(false)
+}
+
+/// live 配置备份保留数量（便捷函数）
+pub fn live_backup_retention() -> usize {
+    get_settings()
+        .ok()
+        .and_then(|s| s.live_backup_retention)
+        .unwrap_or(crate::services::live_backup::DEFAULT_LIVE_BACKUP_RETENTION)
+}
+
+/// 交互菜单空闲超时（便捷函数，未设置或为 0 时关闭）
+pub fn interactive_idle_timeout() -> Option<std::time::Duration> {
+    get_settings()
+        .ok()
+        .and_then(|s| s.interactive_idle_timeout)
+        .filter(|secs| *secs > 0)
+        .map(std::time::Duration::from_secs)
+}
+
+/// 上次同步写入指定应用的 MCP 服务器 ID（便捷函数）
+pub fn mcp_managed_ids(app_type: &AppType) -> Vec<String> {
+    get_settings()
+        .ok()
+        .and_then(|s| s.mcp_managed_ids.get(app_type.as_str()).cloned())
+        .unwrap_or_default()
+}
+
+/// 记录写入指定应用的 MCP 服务器 ID（便捷函数，未变化时不写文件）
+pub fn set_mcp_managed_ids(app_type: &AppType, mut ids: Vec<String>) -> Result<(), AppError> {
+    ids.sort();
+    if mcp_managed_ids(app_type) == ids {
+        return Ok(());
+    }
+    update_settings(|settings| {
+        if ids.is_empty() {
+            settings.mcp_managed_ids.remove(app_type.as_str());
+        } else {
+            settings.mcp_managed_ids.insert(app_type.as_str().to_string(), ids);
+        }
+    })
+}
+
 /// 获取指定应用的当前供应商（便捷函数）
+///
+/// 设备级当前供应商只属于默认 profile，其他 profile 以数据库为准。
 pub fn get_current_provider(app_type: &AppType) -> Option<String> {
+    if active_profile().is_some() {
+        return None;
+    }
     get_settings()
         .ok()
         .and_then(|s| s.get_current_provider(app_type).map(|s| s.to_string()))
@@ -192,6 +285,9 @@ pub fn get_current_provider(app_type: &AppType) -> Option<String> {
 
 /// 设置指定应用的当前供应商（便捷函数）
 pub fn set_current_provider(app_type: &AppType, id: Option<&str>) -> Result<(), AppError> {
+    if active_profile().is_some() {
+        return Ok(());
+    }
     update_settings(|settings| {
         settings.set_current_provider(app_type, id);
     })
@@ -200,6 +296,8 @@ pub fn set_current_provider(app_type: &AppType, id: Option<&str>) -> Result<(),
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::isolated_home;
+    use serial_test::serial;
 
     #[test]
     fn test_app_settings_default() {
@@ -209,6 +307,22 @@ mod tests {
         // So color_output is false from Default but true when loading from file
     }
 
+    #[test]
+    #[serial]
+    fn test_reload_settings_sees_external_changes() {
+        let _home = isolated_home();
+        assert!(get_settings().unwrap().current_provider_claude.is_none());
+
+        // 模拟另一个进程直接改写 settings.json
+        let mut external = AppSettings::default();
+        external.set_current_provider(&AppType::Claude, Some("p1"));
+        external.save().unwrap();
+        assert!(get_settings().unwrap().current_provider_claude.is_none());
+
+        reload_settings().unwrap();
+        assert_eq!(get_settings().unwrap().current_provider_claude.as_deref(), Some("p1"));
+    }
+
     #[test]
     fn test_current_provider() {
         let mut settings = AppSettings::default();
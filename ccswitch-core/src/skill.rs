@@ -23,6 +23,12 @@ pub struct Skill {
     pub repo_name: Option<String>,
     /// GitHub 仓库分支
     pub repo_branch: Option<String>,
+    /// 安装时的仓库提交
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo_commit: Option<String>,
+    /// 固定的提交或标签（`--rev`），设置后更新时不跟随分支
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo_rev: Option<String>,
     /// README URL
     pub readme_url: Option<String>,
     /// 应用启用状态
@@ -43,6 +49,8 @@ impl Skill {
             repo_owner: None,
             repo_name: None,
             repo_branch: None,
+            repo_commit: None,
+            repo_rev: None,
             readme_url: None,
             apps: McpApps::default(),
             installed_at: Some(chrono::Utc::now().timestamp()),
@@ -68,6 +76,18 @@ impl Skill {
         self
     }
 
+    /// 设置仓库提交
+    pub fn with_commit(mut self, commit: Option<String>) -> Self {
+        self.repo_commit = commit;
+        self
+    }
+
+    /// 设置固定的提交或标签
+    pub fn with_rev(mut self, rev: Option<String>) -> Self {
+        self.repo_rev = rev;
+        self
+    }
+
     /// 获取 GitHub 仓库 URL
     pub fn repo_url(&self) -> Option<String> {
         match (&self.repo_owner, &self.repo_name) {
@@ -133,6 +153,39 @@ impl SkillRepo {
     }
 }
 
+/// Skill 索引条目
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillIndexEntry {
+    /// GitHub 仓库 (owner/name)
+    pub repo: String,
+    /// 显示名称
+    pub name: String,
+    /// 描述
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// 标签
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// 分支
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+}
+
+impl SkillIndexEntry {
+    /// 名称、仓库、描述或标签是否包含查询词（不区分大小写）
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return true;
+        }
+        let contains = |s: &str| s.to_lowercase().contains(&query);
+        contains(&self.name)
+            || contains(&self.repo)
+            || self.description.as_deref().is_some_and(contains)
+            || self.tags.iter().any(|t| contains(t))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
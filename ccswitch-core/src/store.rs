@@ -29,6 +29,7 @@ impl AppState {
     pub fn with_database(db: Database) -> Self {
         Self { db: Arc::new(db) }
     }
+
 }
 
 #[cfg(test)]
@@ -44,4 +45,5 @@ mod tests {
             .unwrap()
             .is_empty());
     }
+
 }
@@ -0,0 +1,41 @@
+//! 测试辅助模块
+//!
+//! 将所有配置路径重定向到临时目录，避免测试读写真实用户配置。
+//! 使用方需配合 `#[serial]`，因为环境变量是进程级共享的。
+
+use tempfile::TempDir;
+
+use crate::settings;
+
+const PATH_OVERRIDES: &[&str] = &[
+    "CC_SWITCH_HOME",
+    "CCSWITCH_CLAUDE_CONFIG_DIR",
+    "CCSWITCH_CLAUDE_MCP_PATH",
+    "CCSWITCH_CODEX_CONFIG_DIR",
+    "CCSWITCH_GEMINI_CONFIG_DIR",
+    "CCSWITCH_OPENCODE_CONFIG_DIR",
+    "CCSWITCH_OPENCLAW_CONFIG_DIR",
+];
+
+/// 临时主目录，离开作用域时自动清理
+pub struct TestHome {
+    pub dir: TempDir,
+}
+
+impl TestHome {
+    pub fn path(&self) -> &std::path::Path {
+        self.dir.path()
+    }
+}
+
+/// 创建隔离的主目录并重置设置缓存
+pub fn isolated_home() -> TestHome {
+    let dir = tempfile::tempdir().expect("create temp home");
+    std::env::set_var("CCSWITCH_HOME", dir.path());
+    std::env::set_var("CCSWITCH_CONFIG_DIR", dir.path().join(".cc-switch"));
+    for key in PATH_OVERRIDES {
+        std::env::remove_var(key);
+    }
+    settings::reset_settings_cache();
+    TestHome { dir }
+}